@@ -0,0 +1,241 @@
+use crate::cli::utils::resolve_project_name;
+use crate::client::DaemonClient;
+use clap::Args;
+use colored::*;
+use nix::sys::termios::{tcgetattr, tcsetattr, InputFlags, LocalFlags, SetArg, Termios};
+use proto::{attach_request, attach_response, AttachRequest, AttachTarget};
+use std::io::{IsTerminal, Read, Write};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+#[derive(Debug, Args)]
+pub struct AttachCommand {
+    /// Process name or ID
+    name: String,
+
+    /// Project name (optional, helps disambiguate)
+    #[arg(short, long)]
+    project: Option<String>,
+
+    /// Key that detaches and leaves the process running, as ctrl-<key>
+    #[arg(long, value_name = "KEY", default_value = "ctrl-]", value_parser = parse_detach_key)]
+    detach_key: u8,
+}
+
+/// Parse a detach key such as `ctrl-]` or `ctrl-x` into the byte it sends
+fn parse_detach_key(value: &str) -> Result<u8, String> {
+    let key = value
+        .to_ascii_lowercase()
+        .strip_prefix("ctrl-")
+        .and_then(|key| {
+            let mut chars = key.chars();
+            chars.next().filter(|_| chars.next().is_none())
+        })
+        .ok_or_else(|| format!("Detach key must look like ctrl-<key>, got '{value}'"))?;
+    match key {
+        'a'..='z' | '@' | '[' | '\\' | ']' | '^' | '_' => Ok(key.to_ascii_uppercase() as u8 & 0x1f),
+        _ => Err(format!("Unsupported detach key: {value}")),
+    }
+}
+
+/// Puts the local terminal into raw input mode and restores it when dropped
+///
+/// Output processing stays enabled so the process's "\n" still moves to the
+/// start of the next line.
+struct RawInputMode {
+    original: Termios,
+}
+
+impl RawInputMode {
+    fn enable() -> nix::Result<Self> {
+        let stdin = std::io::stdin();
+        let original = tcgetattr(&stdin)?;
+        let mut raw = original.clone();
+        raw.local_flags
+            .remove(LocalFlags::ICANON | LocalFlags::ECHO | LocalFlags::ISIG | LocalFlags::IEXTEN);
+        raw.input_flags
+            .remove(InputFlags::ICRNL | InputFlags::IXON | InputFlags::BRKINT);
+        tcsetattr(&stdin, SetArg::TCSANOW, &raw)?;
+        Ok(Self { original })
+    }
+}
+
+impl Drop for RawInputMode {
+    fn drop(&mut self) {
+        let _ = tcsetattr(std::io::stdin(), SetArg::TCSANOW, &self.original);
+    }
+}
+
+/// Local input, as read by the input reader
+enum InputEvent {
+    Keys(Vec<u8>),
+    /// The detach key was pressed
+    Detach,
+}
+
+/// Read local input on a plain thread, ending at EOF or the detach key
+///
+/// The channel closes at EOF without a detach, so output keeps streaming
+/// when stdin is not a terminal. A thread rather than tokio's stdin keeps a
+/// pending read from holding up the runtime once the session ends.
+fn spawn_input_reader(detach_key: u8) -> mpsc::Receiver<InputEvent> {
+    let (tx, rx) = mpsc::channel(16);
+    std::thread::spawn(move || {
+        let mut stdin = std::io::stdin();
+        let mut buffer = [0u8; 1024];
+        loop {
+            let n = match stdin.read(&mut buffer) {
+                Ok(0) | Err(_) => return,
+                Ok(n) => n,
+            };
+            let input = &buffer[..n];
+            let detach_at = input.iter().position(|byte| *byte == detach_key);
+            let input = &input[..detach_at.unwrap_or(n)];
+            if !input.is_empty() && tx.blocking_send(InputEvent::Keys(input.to_vec())).is_err() {
+                return;
+            }
+            if detach_at.is_some() {
+                let _ = tx.blocking_send(InputEvent::Detach);
+                return;
+            }
+        }
+    });
+    rx
+}
+
+fn describe_detach_key(key: u8) -> String {
+    format!("Ctrl-{}", (key | 0x40) as char)
+}
+
+impl AttachCommand {
+    pub async fn execute(self, mut client: DaemonClient) -> Result<(), Box<dyn std::error::Error>> {
+        let (request_tx, request_rx) = mpsc::channel(16);
+        request_tx
+            .send(AttachRequest {
+                request: Some(attach_request::Request::Target(AttachTarget {
+                    name: self.name.clone(),
+                    project: resolve_project_name(self.project)?,
+                })),
+            })
+            .await?;
+
+        let mut responses = match client.inner().attach(ReceiverStream::new(request_rx)).await {
+            Ok(response) => response.into_inner(),
+            Err(e) => {
+                eprintln!(
+                    "{} Failed to attach to '{}': {}",
+                    "✗".red(),
+                    self.name,
+                    e.message()
+                );
+                return Err(e.into());
+            }
+        };
+
+        let process = match responses.message().await? {
+            Some(proto::AttachResponse {
+                response: Some(attach_response::Response::Attached(process)),
+            }) => process,
+            _ => return Err("Daemon did not confirm the attach".into()),
+        };
+
+        let pty = process.pty_cols.is_some();
+        let detach = describe_detach_key(self.detach_key);
+        let hint = if pty {
+            format!("press {detach} to detach")
+        } else if process.stdin {
+            format!("input is sent line by line; press {detach} and Enter to detach")
+        } else {
+            "read-only, the process was started without --stdin or --pty; press Ctrl-C to detach"
+                .to_string()
+        };
+        eprintln!(
+            "{} Attached to '{}' ({})",
+            "✓".green(),
+            self.name,
+            hint.dimmed()
+        );
+
+        // Forward keystrokes as typed, including Ctrl-C, to processes on a terminal
+        let raw_mode = if pty && std::io::stdin().is_terminal() {
+            Some(RawInputMode::enable()?)
+        } else {
+            None
+        };
+        let mut input = Some(spawn_input_reader(self.detach_key));
+        let mut stdout = std::io::stdout();
+
+        let exited = loop {
+            tokio::select! {
+                response = responses.message() => match response? {
+                    Some(proto::AttachResponse { response: Some(attach_response::Response::Output(output)) }) => {
+                        stdout.write_all(&output)?;
+                        stdout.flush()?;
+                    }
+                    Some(proto::AttachResponse { response: Some(attach_response::Response::Exited(process)) }) => {
+                        break Some(process);
+                    }
+                    Some(_) => {}
+                    None => break None,
+                },
+                event = async { input.as_mut()?.recv().await }, if input.is_some() => match event {
+                    Some(InputEvent::Keys(keys)) => {
+                        if pty || process.stdin {
+                            let request = AttachRequest {
+                                request: Some(attach_request::Request::Input(keys)),
+                            };
+                            if request_tx.send(request).await.is_err() {
+                                break None;
+                            }
+                        }
+                    }
+                    Some(InputEvent::Detach) => break None,
+                    // Stdin reached EOF: keep streaming until the process exits or Ctrl-C
+                    None => input = None,
+                },
+            }
+        };
+        drop(raw_mode);
+
+        match exited {
+            Some(process) => {
+                let exit_code = process
+                    .exit_code
+                    .map(|code| code.to_string())
+                    .unwrap_or_else(|| "unknown".to_string());
+                eprintln!(
+                    "\r\n{} Process '{}' exited (code {})",
+                    "●".yellow(),
+                    self.name,
+                    exit_code
+                );
+            }
+            None => {
+                eprintln!(
+                    "\r\n{} Detached from '{}', the process keeps running",
+                    "✓".green(),
+                    self.name
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detach_keys_parse_to_control_bytes() {
+        assert_eq!(parse_detach_key("ctrl-]"), Ok(0x1d));
+        assert_eq!(parse_detach_key("Ctrl-Q"), Ok(0x11));
+        assert_eq!(parse_detach_key("ctrl-a"), Ok(0x01));
+        assert!(parse_detach_key("]").is_err());
+        assert!(parse_detach_key("ctrl-ab").is_err());
+        assert!(parse_detach_key("ctrl-1").is_err());
+        assert_eq!(describe_detach_key(0x1d), "Ctrl-]");
+        assert_eq!(describe_detach_key(0x11), "Ctrl-Q");
+    }
+}
@@ -4,6 +4,7 @@ use tonic::Request;
 
 use crate::cli::utils::resolve_project_name;
 use crate::client::DaemonClient;
+use crate::common::labels::parse_selector_arg;
 use proto::CleanProjectRequest;
 
 /// Clean up processes and logs for projects
@@ -25,6 +26,10 @@ pub struct CleanCommand {
     #[arg(short = 'f', long)]
     force: bool,
 
+    /// Only clean processes whose labels match, e.g. `role=frontend` (repeatable)
+    #[arg(short = 'l', long = "selector", value_name = "SELECTOR", value_parser = parse_selector_arg)]
+    selectors: Vec<String>,
+
     /// Verbose output (set from global flag)
     #[arg(skip)]
     pub verbose: bool,
@@ -46,6 +51,7 @@ impl CleanCommand {
             project: resolved_project,
             all_projects: self.all_projects,
             force: self.force,
+            label_selector: self.selectors.clone(),
         });
 
         let response = client.inner().clean_project(request).await?;
@@ -1,6 +1,7 @@
 //! Daemon management commands
 
 use crate::common::config::Config;
+use crate::daemon::process::persistence::ProcessStore;
 use clap::{Parser, Subcommand};
 use std::time::Duration;
 use sysinfo::{Pid, System};
@@ -15,6 +16,20 @@ fn command_identifies_mcproc_daemon(name: &str, command: &[String]) -> bool {
     executable_is_mcproc && command.iter().any(|argument| argument == "--daemon")
 }
 
+/// How long to wait for the daemon to stop its processes before killing it
+///
+/// Processes are stopped in parallel, so the wait is extended to cover the
+/// longest per-process stop timeout in the persisted registry.
+fn shutdown_timeout_ms(config: &Config) -> u64 {
+    ProcessStore::new(&config.paths.data_dir)
+        .load()
+        .iter()
+        .filter(|process| process.was_active())
+        .filter_map(|process| process.stop_policy.timeout)
+        .map(|timeout| timeout.as_millis() as u64 + 5000)
+        .fold(config.daemon.daemon_shutdown_timeout_ms, u64::max)
+}
+
 fn pid_is_mcproc_daemon(pid: i32) -> bool {
     if pid <= 0 {
         return false;
@@ -120,8 +135,9 @@ impl DaemonCommand {
                 )?;
 
                 // Wait for daemon to stop
+                let shutdown_timeout_ms = shutdown_timeout_ms(&config);
                 let max_wait_iterations =
-                    config.daemon.daemon_shutdown_timeout_ms / config.daemon.stop_check_interval_ms;
+                    shutdown_timeout_ms / config.daemon.stop_check_interval_ms;
                 let mut elapsed = 0;
                 for _ in 0..max_wait_iterations {
                     tokio::time::sleep(Duration::from_millis(config.daemon.stop_check_interval_ms))
@@ -138,7 +154,7 @@ impl DaemonCommand {
                         println!(
                             "Waiting for daemon to stop gracefully... ({}/{}s)",
                             elapsed / 1000,
-                            config.daemon.daemon_shutdown_timeout_ms / 1000
+                            shutdown_timeout_ms / 1000
                         );
                     }
                 }
@@ -185,8 +201,9 @@ impl DaemonCommand {
                     )?;
 
                     // Wait for stop
-                    let max_wait_iterations = config.daemon.daemon_shutdown_timeout_ms
-                        / config.daemon.stop_check_interval_ms;
+                    let shutdown_timeout_ms = shutdown_timeout_ms(&config);
+                    let max_wait_iterations =
+                        shutdown_timeout_ms / config.daemon.stop_check_interval_ms;
                     let mut stopped = false;
                     let mut elapsed = 0;
                     for _ in 0..max_wait_iterations {
@@ -206,7 +223,7 @@ impl DaemonCommand {
                             println!(
                                 "Waiting for daemon to stop gracefully... ({}/{}s)",
                                 elapsed / 1000,
-                                config.daemon.daemon_shutdown_timeout_ms / 1000
+                                shutdown_timeout_ms / 1000
                             );
                         }
                     }
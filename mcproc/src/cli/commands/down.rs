@@ -0,0 +1,79 @@
+use crate::cli::manifest::load_manifest;
+use crate::cli::utils::registered_process_names;
+use crate::client::DaemonClient;
+use clap::Args;
+use colored::*;
+use proto::StopProcessRequest;
+use std::path::PathBuf;
+use tonic::Request;
+
+/// Stop the processes declared in mcproc.toml
+#[derive(Debug, Args)]
+pub struct DownCommand {
+    /// Path to the manifest (defaults to the nearest mcproc.toml)
+    #[arg(short, long)]
+    file: Option<PathBuf>,
+
+    /// Project name (defaults to the manifest's project or directory name)
+    #[arg(short, long)]
+    project: Option<String>,
+
+    /// Force stop (SIGKILL)
+    #[arg(long)]
+    force: bool,
+}
+
+impl DownCommand {
+    pub async fn execute(self, mut client: DaemonClient) -> Result<(), Box<dyn std::error::Error>> {
+        let manifest = load_manifest(self.file.as_deref())?;
+        let project = manifest.project_name(self.project)?;
+
+        let config = crate::common::config::Config::load()?;
+        let registered = registered_process_names(&mut client, &project).await?;
+
+        // Stop dependents before the processes they depend on
+        let mut order = manifest.manifest.start_order()?;
+        order.reverse();
+        for name in order {
+            if !registered.contains(name) {
+                println!("{} Process '{}' is not running", "-".dimmed(), name);
+                continue;
+            }
+            let mut request = Request::new(StopProcessRequest {
+                name: name.to_string(),
+                force: Some(self.force),
+                project: project.clone(),
+                ..Default::default()
+            });
+            request.set_timeout(crate::cli::utils::stop_deadline(
+                crate::cli::utils::stop_timeout_ms(
+                    manifest.manifest.processes[name].stop_timeout,
+                    config.process.restart.process_stop_timeout_ms,
+                ),
+            ));
+
+            match client.inner().stop_process(request).await {
+                Ok(response) if response.get_ref().success => {
+                    println!("{} Process '{}' stopped", "✓".green(), name);
+                }
+                Ok(response) => println!(
+                    "{} Failed to stop process '{}': {}",
+                    "✗".red(),
+                    name,
+                    response
+                        .into_inner()
+                        .message
+                        .unwrap_or_else(|| "Unknown error".to_string())
+                ),
+                Err(e) => println!(
+                    "{} Failed to stop process '{}': {}",
+                    "✗".red(),
+                    name,
+                    e.message()
+                ),
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -40,6 +40,10 @@ pub struct GrepCommand {
     /// Show logs from the last duration (e.g., "1h", "30m", "2d")
     #[arg(long)]
     last: Option<String>,
+
+    /// Only search one run: "current", "previous" or a run ID (prefix) from `mcproc history`
+    #[arg(long)]
+    run: Option<String>,
 }
 
 impl GrepCommand {
@@ -57,6 +61,7 @@ impl GrepCommand {
             since: self.since,
             until: self.until,
             last: self.last,
+            run: self.run,
         };
 
         match client.inner().grep_logs(request).await {
@@ -0,0 +1,137 @@
+use crate::client::DaemonClient;
+use crate::common::history::run_duration;
+use crate::common::status::format_status;
+use crate::common::timestamp::format_timestamp_short;
+use crate::common::validation::validate_process_name;
+use clap::Args;
+use proto::ListRunsRequest;
+use std::time::Duration;
+use tabled::{Table, Tabled};
+
+#[derive(Debug, Args)]
+pub struct HistoryCommand {
+    /// Only show runs of this process
+    name: Option<String>,
+
+    /// Project name (defaults to directory name when a process name is given)
+    #[arg(short, long)]
+    project: Option<String>,
+
+    /// Number of most recent runs to show
+    #[arg(short = 'n', long, default_value = "20", value_parser = clap::value_parser!(u32).range(1..))]
+    limit: u32,
+}
+
+#[derive(Tabled)]
+struct RunRow {
+    #[tabled(rename = "RUN")]
+    id: String,
+
+    #[tabled(rename = "PROJECT")]
+    project: String,
+
+    #[tabled(rename = "NAME")]
+    name: String,
+
+    #[tabled(rename = "STARTED")]
+    started: String,
+
+    #[tabled(rename = "DURATION")]
+    duration: String,
+
+    #[tabled(rename = "STATUS")]
+    status: String,
+
+    #[tabled(rename = "EXIT")]
+    exit_code: String,
+
+    #[tabled(rename = "STARTED BY")]
+    started_by: String,
+
+    #[tabled(rename = "PORTS")]
+    ports: String,
+}
+
+impl HistoryCommand {
+    pub async fn execute(self, mut client: DaemonClient) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(name) = &self.name {
+            validate_process_name(name)?;
+        }
+        let project = match (&self.name, self.project) {
+            (Some(_), project) => Some(crate::cli::utils::resolve_project_name(project)?),
+            (None, project) => project,
+        };
+
+        let request = ListRunsRequest {
+            name: self.name,
+            project,
+            limit: Some(self.limit),
+        };
+        let runs = client.inner().list_runs(request).await?.into_inner().runs;
+
+        if runs.is_empty() {
+            println!("No runs recorded");
+            return Ok(());
+        }
+
+        let rows: Vec<RunRow> = runs
+            .into_iter()
+            .map(|run| RunRow {
+                id: run.id.chars().take(8).collect(),
+                started: format_timestamp_short(run.start_time.as_ref()),
+                duration: run_duration(&run)
+                    .map(format_run_duration)
+                    .unwrap_or_else(|| "-".to_string()),
+                status: match run
+                    .exit_reason
+                    .as_ref()
+                    .filter(|_| run.exit_code != Some(0))
+                {
+                    Some(reason) => format!("{} ({})", format_status(run.status), reason),
+                    None => format_status(run.status),
+                },
+                exit_code: run
+                    .exit_code
+                    .map(|code| code.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                started_by: run.started_by.unwrap_or_else(|| "-".to_string()),
+                ports: if run.ports.is_empty() {
+                    "-".to_string()
+                } else {
+                    run.ports
+                        .iter()
+                        .map(|port| port.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                },
+                project: run.project,
+                name: run.name,
+            })
+            .collect();
+
+        println!("{}", Table::new(rows));
+        Ok(())
+    }
+}
+
+/// Format a run duration compactly, e.g. "4.2s", "3m07s" or "2h05m"
+fn format_run_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..=59 => format!("{:.1}s", duration.as_secs_f64()),
+        60..=3599 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_are_formatted_compactly() {
+        assert_eq!(format_run_duration(Duration::from_millis(4_240)), "4.2s");
+        assert_eq!(format_run_duration(Duration::from_secs(187)), "3m07s");
+        assert_eq!(format_run_duration(Duration::from_secs(7_500)), "2h05m");
+    }
+}
@@ -1,5 +1,6 @@
 use crate::cli::utils::resolve_project_name;
 use crate::client::DaemonClient;
+use crate::common::labels::parse_selector_arg;
 use chrono;
 use clap::Args;
 use colored::*;
@@ -44,6 +45,14 @@ pub struct LogsCommand {
     #[arg(short, long)]
     project: Option<String>,
 
+    /// Only show one run: "current", "previous" or a run ID (prefix) from `mcproc history`
+    #[arg(long)]
+    run: Option<String>,
+
+    /// Only show processes whose labels match, e.g. `role=frontend` (repeatable)
+    #[arg(short = 'l', long = "selector", value_name = "SELECTOR", value_parser = parse_selector_arg)]
+    selectors: Vec<String>,
+
     /// Show process output colors only (disable mcproc colors)
     #[arg(long)]
     raw_color: bool,
@@ -150,6 +159,8 @@ impl LogsCommand {
         let shutdown_flag_clone = shutdown_flag.clone();
         let tail = self.tail;
         let follow = self.follow;
+        let run = self.run.clone();
+        let label_selector = self.selectors.clone();
         tasks.spawn(async move {
             let request = proto::GetLogsRequest {
                 process_names,
@@ -157,6 +168,8 @@ impl LogsCommand {
                 follow: Some(follow),
                 project,
                 include_events: Some(false),
+                run,
+                label_selector,
             };
 
             // Start single gRPC stream
@@ -42,7 +42,11 @@ async fn serve_mcp(
     use crate::common::validation::validate_project_name;
     use crate::common::version::VERSION;
     use mcp_rs::{ServerBuilder, StdioTransport};
-    use tools::{GrepTool, LogsTool, PsTool, RestartTool, StartTool, StatusTool, StopTool};
+    use tools::{
+        DownTool, GrepTool, LogsTool, LookupPortTool, MetricsTool, PsTool, RestartTool,
+        RunHistoryTool, RunTaskTool, SendInputTool, SignalTool, StartTool, StatusTool, StopTool,
+        UpTool,
+    };
     use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
     // Validate and set default project as environment variable if provided
@@ -68,10 +72,18 @@ async fn serve_mcp(
         .add_tool(Arc::new(StartTool::new(client.clone())))
         .add_tool(Arc::new(StopTool::new(client.clone())))
         .add_tool(Arc::new(RestartTool::new(client.clone())))
+        .add_tool(Arc::new(RunTaskTool::new(client.clone())))
+        .add_tool(Arc::new(SignalTool::new(client.clone())))
+        .add_tool(Arc::new(SendInputTool::new(client.clone())))
         .add_tool(Arc::new(PsTool::new(client.clone())))
         .add_tool(Arc::new(LogsTool::new(client.clone())))
         .add_tool(Arc::new(StatusTool::new(client.clone())))
+        .add_tool(Arc::new(MetricsTool::new(client.clone())))
+        .add_tool(Arc::new(RunHistoryTool::new(client.clone())))
         .add_tool(Arc::new(GrepTool::new(client.clone())))
+        .add_tool(Arc::new(LookupPortTool::new(client.clone())))
+        .add_tool(Arc::new(UpTool::new(client.clone())))
+        .add_tool(Arc::new(DownTool::new(client.clone())))
         .build(transport)
         .await?;
 
@@ -52,6 +52,10 @@ impl McpTestHarness {
         }
     }
 
+    pub fn root(&self) -> &std::path::Path {
+        &self.fixture.root
+    }
+
     pub fn context() -> ToolContext {
         ToolContext::new(Arc::new(QueuedNotificationSender::new()), None, None)
     }
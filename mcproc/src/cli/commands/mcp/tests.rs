@@ -1,5 +1,8 @@
 use super::test_support::McpTestHarness;
-use super::tools::{GrepTool, LogsTool, PsTool, RestartTool, StartTool, StatusTool, StopTool};
+use super::tools::{
+    DownTool, GrepTool, LogsTool, LookupPortTool, MetricsTool, PsTool, RestartTool, RunHistoryTool,
+    RunTaskTool, SendInputTool, SignalTool, StartTool, StatusTool, StopTool, UpTool,
+};
 use mcp_rs::{Error as McpError, ToolHandler};
 use serde_json::{json, Value};
 
@@ -37,18 +40,35 @@ async fn all_tools_publish_nonempty_object_schemas_with_declared_required_fields
 
     let tools: Vec<(Box<dyn ToolHandler>, &[&str])> = vec![
         (Box::new(StartTool::new(harness.client.clone())), &["name"]),
-        (Box::new(StopTool::new(harness.client.clone())), &["name"]),
+        (Box::new(StopTool::new(harness.client.clone())), &[]),
+        (Box::new(RestartTool::new(harness.client.clone())), &[]),
         (
-            Box::new(RestartTool::new(harness.client.clone())),
+            Box::new(RunTaskTool::new(harness.client.clone())),
             &["name"],
         ),
+        (
+            Box::new(SignalTool::new(harness.client.clone())),
+            &["name", "signal"],
+        ),
+        (
+            Box::new(SendInputTool::new(harness.client.clone())),
+            &["name", "input"],
+        ),
         (Box::new(PsTool::new(harness.client.clone())), &[]),
         (Box::new(StatusTool::new(harness.client.clone())), &["name"]),
-        (Box::new(LogsTool::new(harness.client.clone())), &["name"]),
+        (Box::new(MetricsTool::new(harness.client.clone())), &[]),
+        (Box::new(RunHistoryTool::new(harness.client.clone())), &[]),
+        (Box::new(LogsTool::new(harness.client.clone())), &[]),
         (
             Box::new(GrepTool::new(harness.client.clone())),
             &["pattern", "name"],
         ),
+        (
+            Box::new(LookupPortTool::new(harness.client.clone())),
+            &["port"],
+        ),
+        (Box::new(UpTool::new(harness.client.clone())), &[]),
+        (Box::new(DownTool::new(harness.client.clone())), &[]),
     ];
 
     for (tool, expected_required) in tools {
@@ -113,6 +133,50 @@ async fn start_returns_process_identity_and_rejects_missing_command() {
     harness.cleanup().await;
 }
 
+#[cfg(unix)]
+#[tokio::test]
+async fn start_reports_resource_limits_and_rejects_invalid_sizes() {
+    let harness = McpTestHarness::new().await;
+    let started = StartTool::new(harness.client.clone())
+        .handle(
+            Some(json!({
+                "name": "limited",
+                "cmd": "sleep 30",
+                "project": PROJECT,
+                "limits": { "memory": "1G", "open_files": 128 },
+            })),
+            McpTestHarness::context(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(started["limits"], "memory 1G, open files 128");
+
+    let status = StatusTool::new(harness.client.clone())
+        .handle(
+            Some(json!({ "name": "limited", "project": PROJECT })),
+            McpTestHarness::context(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(status["limits"], "memory 1G, open files 128");
+
+    let error = StartTool::new(harness.client.clone())
+        .handle(
+            Some(json!({
+                "name": "unlimited",
+                "cmd": "sleep 30",
+                "project": PROJECT,
+                "limits": { "memory": "lots" },
+            })),
+            McpTestHarness::context(),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(error, McpError::InvalidParams(_)));
+    assert!(error.to_string().contains("Invalid size"));
+    harness.cleanup().await;
+}
+
 #[cfg(unix)]
 #[tokio::test]
 async fn stop_reports_success_for_existing_process_and_false_for_missing_process() {
@@ -141,6 +205,152 @@ async fn stop_reports_success_for_existing_process_and_false_for_missing_process
     harness.cleanup().await;
 }
 
+#[cfg(unix)]
+#[tokio::test]
+async fn signal_pauses_and_resumes_process_and_rejects_unknown_signals() {
+    let harness = McpTestHarness::new().await;
+    start_process(&harness, "signal-target", "sleep 30").await;
+    let tool = SignalTool::new(harness.client.clone());
+    let send = |signal: &str| {
+        tool.handle(
+            Some(json!({ "name": "signal-target", "signal": signal, "project": PROJECT })),
+            McpTestHarness::context(),
+        )
+    };
+
+    let paused = send("STOP").await.unwrap();
+    assert_eq!(paused["signal"], "SIGSTOP");
+    assert_eq!(paused["target"], "group");
+    assert_eq!(paused["status"], "Paused");
+
+    let resumed = send("SIGCONT").await.unwrap();
+    assert_eq!(resumed["status"], "Running");
+
+    assert!(matches!(
+        send("SIGNOPE").await,
+        Err(McpError::InvalidParams(_))
+    ));
+    assert!(tool
+        .handle(
+            Some(json!({ "name": "not-present", "signal": "HUP", "project": PROJECT })),
+            McpTestHarness::context(),
+        )
+        .await
+        .is_err());
+
+    // Leave the process paused; stopping it must still shut it down
+    send("SIGSTOP").await.unwrap();
+    harness.cleanup().await;
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn send_input_reaches_stdin_only_when_enabled() {
+    let harness = McpTestHarness::new().await;
+    let marker = harness.root().join("stdin-line");
+    StartTool::new(harness.client.clone())
+        .handle(
+            Some(json!({
+                "name": "stdin-target",
+                "cmd": format!("read line && echo \"$line\" > {} && sleep 30", marker.display()),
+                "project": PROJECT,
+                "stdin": true,
+            })),
+            McpTestHarness::context(),
+        )
+        .await
+        .unwrap();
+    start_process(&harness, "no-stdin-target", "sleep 30").await;
+    let tool = SendInputTool::new(harness.client.clone());
+
+    let sent = tool
+        .handle(
+            Some(json!({ "name": "stdin-target", "input": "hello", "project": PROJECT })),
+            McpTestHarness::context(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(sent["bytes"], 6);
+    let mut received = String::new();
+    for _ in 0..50 {
+        received = std::fs::read_to_string(&marker).unwrap_or_default();
+        if !received.is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    assert_eq!(received, "hello\n");
+
+    let rejected = tool
+        .handle(
+            Some(json!({ "name": "no-stdin-target", "input": "hello", "project": PROJECT })),
+            McpTestHarness::context(),
+        )
+        .await;
+    assert!(matches!(rejected, Err(McpError::Internal(message)) if message.contains("stdin")));
+
+    harness.cleanup().await;
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn project_up_is_idempotent_and_project_down_stops_manifest_processes() {
+    let harness = McpTestHarness::new().await;
+    let manifest = harness.root().join("mcproc.toml");
+    std::fs::write(
+        &manifest,
+        "[processes.web]\ncmd = \"sleep 30\"\n\n[processes.worker]\ncmd = \"sleep 30\"\n",
+    )
+    .unwrap();
+    let params = json!({ "file": manifest, "project": PROJECT });
+    let up = UpTool::new(harness.client.clone());
+
+    let first = up
+        .handle(Some(params.clone()), McpTestHarness::context())
+        .await
+        .unwrap();
+    let actions = first["processes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|process| process["action"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(actions, vec!["started", "started"]);
+
+    let second = up
+        .handle(Some(params.clone()), McpTestHarness::context())
+        .await
+        .unwrap();
+    assert!(second["processes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|process| process["action"] == "unchanged"));
+
+    let down = DownTool::new(harness.client.clone())
+        .handle(Some(params.clone()), McpTestHarness::context())
+        .await
+        .unwrap();
+    assert!(down["processes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|process| process["success"] == true));
+
+    let again = DownTool::new(harness.client.clone())
+        .handle(Some(params), McpTestHarness::context())
+        .await
+        .unwrap();
+    assert!(again["processes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|process| {
+            process["success"] == true && process["message"] == "Process is not running"
+        }));
+    harness.cleanup().await;
+}
+
 #[cfg(unix)]
 #[tokio::test]
 async fn restart_replaces_the_running_process_pid() {
@@ -161,6 +371,217 @@ async fn restart_replaces_the_running_process_pid() {
     harness.cleanup().await;
 }
 
+#[cfg(unix)]
+#[tokio::test]
+async fn run_task_returns_exit_code_tail_and_success_status() {
+    let harness = McpTestHarness::new().await;
+    let tool = RunTaskTool::new(harness.client.clone());
+    let run = |name: &str, command: &str, extra: Value| {
+        let mut params = json!({ "name": name, "cmd": command, "project": PROJECT });
+        params
+            .as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        tool.handle(Some(params), McpTestHarness::context())
+    };
+
+    let succeeded = run(
+        "migrate",
+        "echo one; echo two; echo three",
+        json!({ "tail_lines": 2 }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(succeeded["status"], "Succeeded");
+    assert_eq!(succeeded["succeeded"], true);
+    assert_eq!(succeeded["exit_code"], 0);
+    assert_eq!(succeeded["tail"], json!(["two", "three"]));
+
+    let failed = run("lint", "echo warning >&2; exit 3", json!({}))
+        .await
+        .unwrap();
+    assert_eq!(failed["status"], "Failed");
+    assert_eq!(failed["exit_code"], 3);
+    assert_eq!(failed["tail"], json!(["warning"]));
+
+    let accepted = run(
+        "lint",
+        "echo linted; exit 3",
+        json!({ "success_exit_codes": [0, 3] }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(accepted["status"], "Succeeded");
+
+    let timed_out = run("hang", "sleep 30", json!({ "timeout_secs": 1 }))
+        .await
+        .unwrap();
+    assert_eq!(timed_out["status"], "Failed");
+    assert_eq!(timed_out["timed_out"], true);
+
+    harness.cleanup().await;
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn lookup_port_reports_unmanaged_holders_without_stopping_them() {
+    let harness = McpTestHarness::new().await;
+    let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let tool = LookupPortTool::new(harness.client.clone());
+
+    let response = tool
+        .handle(
+            Some(json!({ "port": port, "kill_holder": true })),
+            McpTestHarness::context(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response["in_use"], true);
+    let holder = &response["holders"][0];
+    assert_eq!(holder["pid"], std::process::id());
+    assert_eq!(holder["managed"], false);
+    assert_eq!(holder["protocol"], "tcp");
+    assert_eq!(holder["address"], "127.0.0.1");
+    assert_eq!(response["stopped"], json!([]));
+
+    drop(listener);
+    let response = tool
+        .handle(Some(json!({ "port": port })), McpTestHarness::context())
+        .await
+        .unwrap();
+    assert_eq!(response["in_use"], false);
+    assert!(tool
+        .handle(Some(json!({ "port": 0 })), McpTestHarness::context())
+        .await
+        .is_err());
+    harness.cleanup().await;
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn labels_select_processes_to_list_and_stop() {
+    let harness = McpTestHarness::new().await;
+    for (name, role) in [
+        ("web", "frontend"),
+        ("admin", "frontend"),
+        ("jobs", "worker"),
+    ] {
+        StartTool::new(harness.client.clone())
+            .handle(
+                Some(json!({
+                    "name": name,
+                    "cmd": "sleep 30",
+                    "project": PROJECT,
+                    "labels": { "role": role },
+                    "description": format!("{role} for issue 42"),
+                })),
+                McpTestHarness::context(),
+            )
+            .await
+            .unwrap();
+    }
+
+    let frontend = PsTool::new(harness.client.clone())
+        .handle(
+            Some(json!({ "labels": "role=frontend" })),
+            McpTestHarness::context(),
+        )
+        .await
+        .unwrap();
+    let mut names = frontend["processes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|process| {
+            assert_eq!(process["labels"]["role"], "frontend");
+            assert_eq!(process["description"], "frontend for issue 42");
+            process["name"].as_str().unwrap().to_string()
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, ["admin", "web"]);
+
+    let stopped = StopTool::new(harness.client.clone())
+        .handle(
+            Some(json!({ "labels": "role=frontend", "project": PROJECT })),
+            McpTestHarness::context(),
+        )
+        .await
+        .unwrap();
+    let stopped = stopped["processes"].as_array().unwrap();
+    assert_eq!(stopped.len(), 2);
+    assert_eq!(stopped[0]["name"], "admin");
+    assert_eq!(stopped[1]["name"], "web");
+    assert!(stopped.iter().all(|result| result["success"] == true));
+
+    let remaining = PsTool::new(harness.client.clone())
+        .handle(Some(json!({})), McpTestHarness::context())
+        .await
+        .unwrap();
+    let remaining = remaining["processes"].as_array().unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0]["name"], "jobs");
+
+    let none = StopTool::new(harness.client.clone())
+        .handle(
+            Some(json!({ "labels": "role=db", "project": PROJECT })),
+            McpTestHarness::context(),
+        )
+        .await;
+    assert!(matches!(none, Err(McpError::InvalidParams(_))));
+    harness.cleanup().await;
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn patterns_restart_and_stop_several_processes() {
+    let harness = McpTestHarness::new().await;
+    for name in ["worker-1", "worker-2", "web"] {
+        start_process(&harness, name, "sleep 30").await;
+    }
+
+    let restarted = RestartTool::new(harness.client.clone())
+        .handle(
+            Some(json!({ "name": "worker-*", "project": PROJECT, "parallelism": 2 })),
+            McpTestHarness::context(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(restarted["success"], true);
+    let mut names = restarted["processes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|process| {
+            assert_eq!(process["status"], "Running");
+            process["name"].as_str().unwrap().to_string()
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, ["worker-1", "worker-2"]);
+
+    let stopped = StopTool::new(harness.client.clone())
+        .handle(
+            Some(json!({ "name": format!("{PROJECT}/*"), "status": "running" })),
+            McpTestHarness::context(),
+        )
+        .await
+        .unwrap();
+    let stopped = stopped["processes"].as_array().unwrap();
+    assert_eq!(stopped.len(), 3);
+    assert!(stopped.iter().all(|result| result["success"] == true));
+
+    let invalid = StopTool::new(harness.client.clone())
+        .handle(
+            Some(json!({ "name": "worker-*", "status": "sleeping" })),
+            McpTestHarness::context(),
+        )
+        .await;
+    assert!(matches!(invalid, Err(McpError::InvalidParams(_))));
+    harness.cleanup().await;
+}
+
 #[cfg(unix)]
 #[tokio::test]
 async fn ps_lists_the_started_process() {
@@ -196,6 +617,133 @@ async fn status_returns_the_started_process_name_and_state() {
     harness.cleanup().await;
 }
 
+#[cfg(unix)]
+#[tokio::test]
+async fn metrics_report_running_processes_and_reject_unknown_names() {
+    let harness = McpTestHarness::new().await;
+    start_process(&harness, "metrics-target", "sleep 30").await;
+
+    let tool = MetricsTool::new(harness.client.clone());
+    let response = tool
+        .handle(
+            Some(json!({ "name": "metrics-target", "project": PROJECT })),
+            McpTestHarness::context(),
+        )
+        .await
+        .unwrap();
+    let processes = response["processes"].as_array().unwrap();
+    assert_eq!(processes.len(), 1);
+    assert_eq!(processes[0]["name"], "metrics-target");
+    assert!(processes[0]["history"]["rss_bytes"].is_array());
+
+    let error = tool
+        .handle(
+            Some(json!({ "name": "not-present", "project": PROJECT })),
+            McpTestHarness::context(),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(error, McpError::InvalidParams(_)));
+    harness.cleanup().await;
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn run_history_lists_replaced_runs_newest_first() {
+    let harness = McpTestHarness::new().await;
+    let started = start_process(&harness, "history-target", "sleep 30").await;
+    RestartTool::new(harness.client.clone())
+        .handle(
+            Some(json!({ "name": "history-target", "project": PROJECT })),
+            McpTestHarness::context(),
+        )
+        .await
+        .unwrap();
+
+    let response = RunHistoryTool::new(harness.client.clone())
+        .handle(
+            Some(json!({ "name": "history-target", "project": PROJECT })),
+            McpTestHarness::context(),
+        )
+        .await
+        .unwrap();
+    let runs = response["runs"].as_array().unwrap();
+    assert_eq!(runs.len(), 2, "{runs:?}");
+    assert_eq!(runs[0]["status"], "Running");
+    assert_eq!(runs[0]["started_by"], "mcp");
+    assert!(runs[0]["exit_time"].is_null());
+    assert_eq!(runs[1]["id"], started["id"]);
+    assert_eq!(runs[1]["status"], "Stopped");
+    assert!(runs[1]["duration_ms"].is_u64());
+    harness.cleanup().await;
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn logs_and_grep_select_the_run_before_a_restart() {
+    let harness = McpTestHarness::new().await;
+    let started = start_process(&harness, "runs-target", "echo \"output of $$\"; sleep 30").await;
+    let log_file = started["log_file"].as_str().unwrap();
+    let first_output = format!("output of {}", started["pid"]);
+    harness.wait_for_log(log_file, &[&first_output]).await;
+    let restarted = RestartTool::new(harness.client.clone())
+        .handle(
+            Some(json!({ "name": "runs-target", "project": PROJECT })),
+            McpTestHarness::context(),
+        )
+        .await
+        .unwrap();
+    let second_output = format!("output of {}", restarted["pid"]);
+    harness.wait_for_log(log_file, &[&second_output]).await;
+
+    let logs = |run: &str| {
+        let tool = LogsTool::new(harness.client.clone());
+        let params = json!({ "name": "runs-target", "project": PROJECT, "run": run });
+        async move {
+            let logs = tool
+                .handle(Some(params), McpTestHarness::context())
+                .await
+                .unwrap();
+            serde_json::to_string(&logs).unwrap()
+        }
+    };
+    let previous = logs("previous").await;
+    assert!(previous.contains(&format!("run {}", started["id"].as_str().unwrap())));
+    assert!(previous.contains(&first_output));
+    assert!(!previous.contains(&second_output));
+    let current = logs("current").await;
+    assert!(current.contains(&second_output));
+    assert!(!current.contains(&first_output));
+
+    let grep = GrepTool::new(harness.client.clone())
+        .handle(
+            Some(json!({
+                "name": "runs-target",
+                "pattern": "output of",
+                "context": 0,
+                "project": PROJECT,
+                "run": &started["id"].as_str().unwrap()[..8],
+            })),
+            McpTestHarness::context(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(grep["total_matches"], 1);
+    assert!(serde_json::to_string(&grep)
+        .unwrap()
+        .contains(&first_output));
+
+    let error = LogsTool::new(harness.client.clone())
+        .handle(
+            Some(json!({ "name": "runs-target", "project": PROJECT, "run": "no-such-run" })),
+            McpTestHarness::context(),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(error, McpError::InvalidParams(_)));
+    harness.cleanup().await;
+}
+
 #[cfg(unix)]
 #[tokio::test]
 async fn logs_and_grep_return_context_and_strip_ansi_sequences() {
@@ -0,0 +1,114 @@
+//! Project down tool implementation
+
+use crate::cli::utils::load_mcp_manifest;
+use crate::client::DaemonClient;
+use async_trait::async_trait;
+use mcp_rs::{Error as McpError, Result as McpResult, ToolHandler, ToolInfo};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use tonic::Request;
+
+pub struct DownTool {
+    client: DaemonClient,
+}
+
+impl DownTool {
+    pub fn new(client: DaemonClient) -> Self {
+        Self { client }
+    }
+}
+
+#[derive(Deserialize)]
+struct DownParams {
+    file: Option<PathBuf>,
+    project: Option<String>,
+}
+
+#[async_trait]
+impl ToolHandler for DownTool {
+    fn tool_info(&self) -> ToolInfo {
+        ToolInfo {
+            name: "stop_project".to_string(),
+            description: "Gracefully stop every process declared in the project's mcproc.toml manifest. Processes that are not running are reported as such. Log files are kept; use this to shut down a project started with start_project.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "file": { "type": "string", "description": "Path to the manifest. Defaults to the nearest mcproc.toml in the current directory or its parents." },
+                    "project": { "type": "string", "description": "Project name. Defaults to the manifest's 'project' key, then the manifest directory name." }
+                }
+            }),
+        }
+    }
+
+    async fn handle(
+        &self,
+        params: Option<Value>,
+        _context: mcp_rs::ToolContext,
+    ) -> McpResult<Value> {
+        let params: DownParams = serde_json::from_value(params.unwrap_or_else(|| json!({})))
+            .map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+        let (manifest, project) = load_mcp_manifest(params.file.as_deref(), params.project)?;
+
+        let config =
+            crate::common::config::Config::load().map_err(|e| McpError::Internal(e.to_string()))?;
+
+        let mut client = self.client.clone();
+        let registered = crate::cli::utils::registered_process_names(&mut client, &project)
+            .await
+            .map_err(|e| McpError::Internal(e.message().to_string()))?;
+        let mut processes = Vec::new();
+        // Stop dependents before the processes they depend on
+        let mut order = manifest
+            .manifest
+            .start_order()
+            .map_err(McpError::InvalidParams)?;
+        order.reverse();
+        for name in order {
+            if !registered.contains(name) {
+                processes.push(json!({
+                    "name": name,
+                    "success": true,
+                    "message": "Process is not running",
+                }));
+                continue;
+            }
+            let mut request = Request::new(proto::StopProcessRequest {
+                name: name.to_string(),
+                force: None,
+                project: project.clone(),
+                ..Default::default()
+            });
+            request.set_timeout(crate::cli::utils::stop_deadline(
+                crate::cli::utils::stop_timeout_ms(
+                    manifest.manifest.processes[name].stop_timeout,
+                    config.process.restart.process_stop_timeout_ms,
+                ),
+            ));
+
+            let result = match client.inner().stop_process(request).await {
+                Ok(response) => {
+                    let response = response.into_inner();
+                    json!({
+                        "name": name,
+                        "success": response.success,
+                        "message": response.message,
+                    })
+                }
+                Err(e) => json!({
+                    "name": name,
+                    "success": false,
+                    "message": e.message(),
+                }),
+            };
+            processes.push(result);
+        }
+
+        Ok(json!({
+            "project": project,
+            "manifest": manifest.path.display().to_string(),
+            "processes": processes,
+        }))
+    }
+}
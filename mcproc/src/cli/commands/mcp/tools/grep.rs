@@ -30,6 +30,7 @@ struct GrepParams {
     since: Option<String>,
     until: Option<String>,
     last: Option<String>,
+    run: Option<String>,
 }
 
 #[async_trait]
@@ -37,7 +38,7 @@ impl ToolHandler for GrepTool {
     fn tool_info(&self) -> ToolInfo {
         ToolInfo {
             name: "search_process_logs".to_string(),
-            description: "Search through process logs using regex patterns to find specific errors, events, or messages. Returns matching lines with surrounding context to help understand what happened. Perfect for debugging issues like 'find all error messages' or 'show when the server started'. Searches through the entire log history, not just recent entries.".to_string(),
+            description: "Search through process logs using regex patterns to find specific errors, events, or messages. Returns matching lines with surrounding context to help understand what happened. Perfect for debugging issues like 'find all error messages' or 'show when the server started'. Searches through the entire log history, not just recent entries, unless a run is selected.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
@@ -49,7 +50,8 @@ impl ToolHandler for GrepTool {
                     "after": { "type": "integer", "description": "Override context - number of lines to show after each match" },
                     "since": { "type": "string", "description": "Only search logs after this time. Format: 'YYYY-MM-DD HH:MM' or just 'HH:MM' for today" },
                     "until": { "type": "string", "description": "Only search logs before this time. Format: 'YYYY-MM-DD HH:MM' or just 'HH:MM' for today" },
-                    "last": { "type": "string", "description": "Only search recent logs. Examples: '1h' (last hour), '30m' (last 30 minutes), '2d' (last 2 days)" }
+                    "last": { "type": "string", "description": "Only search recent logs. Examples: '1h' (last hour), '30m' (last 30 minutes), '2d' (last 2 days)" },
+                    "run": { "type": "string", "description": "Only search the output of one run: 'current' (latest run), 'previous' (the run before it, e.g. before a restart or crash) or a run ID or ID prefix from get_run_history" }
                 },
                 "required": ["pattern", "name"]
             }),
@@ -80,6 +82,7 @@ impl ToolHandler for GrepTool {
             since: params.since,
             until: params.until,
             last: params.last,
+            run: params.run.clone(),
         };
 
         let mut client = self.client.clone();
@@ -162,7 +165,11 @@ impl ToolHandler for GrepTool {
                 }))
             }
             Err(e) => {
-                if e.code() == tonic::Code::NotFound {
+                if e.code() == tonic::Code::InvalidArgument
+                    || (e.code() == tonic::Code::NotFound && params.run.is_some())
+                {
+                    Err(McpError::InvalidParams(e.message().to_string()))
+                } else if e.code() == tonic::Code::NotFound {
                     Err(McpError::InvalidParams(format!(
                         "Log file for process \"{}\" not found",
                         params.name
@@ -0,0 +1,101 @@
+//! Run history tool implementation
+
+use crate::cli::utils::resolve_mcp_project_name;
+use crate::client::DaemonClient;
+use crate::common::history::run_duration;
+use crate::common::status::format_status;
+use crate::common::timestamp::format_timestamp_rfc3339;
+use async_trait::async_trait;
+use mcp_rs::{Error as McpError, Result as McpResult, ToolHandler, ToolInfo};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+pub struct RunHistoryTool {
+    client: DaemonClient,
+}
+
+impl RunHistoryTool {
+    pub fn new(client: DaemonClient) -> Self {
+        Self { client }
+    }
+}
+
+#[derive(Deserialize)]
+struct RunHistoryParams {
+    name: Option<String>,
+    project: Option<String>,
+    limit: Option<u32>,
+}
+
+#[async_trait]
+impl ToolHandler for RunHistoryTool {
+    fn tool_info(&self) -> ToolInfo {
+        ToolInfo {
+            name: "get_run_history".to_string(),
+            description: "Get the past and current runs of processes, most recent first, including runs of processes that have since been stopped and removed. Each run reports its command, working directory, start and exit time, duration, status (running/stopped/failed/succeeded/crash-looping), exit code and reason, ports it used and who started it (cli, mcp, manifest, restart policy, file watch, watchdog, health check, schedule or daemon restart). Use this to answer questions like whether a process crashed the last few times it ran, or why it keeps being restarted.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string", "description": "Name of the process whose runs to list. Omit to list the runs of every process in the project" },
+                    "project": { "type": "string", "description": "Optional project name to scope the history. Useful when multiple projects have processes with the same name." },
+                    "limit": { "type": "integer", "minimum": 1, "description": "Number of most recent runs to return (default: 20)" }
+                }
+            }),
+        }
+    }
+
+    async fn handle(
+        &self,
+        params: Option<Value>,
+        _context: mcp_rs::ToolContext,
+    ) -> McpResult<Value> {
+        let params: RunHistoryParams = serde_json::from_value(params.unwrap_or_else(|| json!({})))
+            .map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+        // Determine project name if not provided
+        let project = resolve_mcp_project_name(params.project)?;
+
+        let request = proto::ListRunsRequest {
+            name: params.name,
+            project: Some(project),
+            limit: params.limit,
+        };
+        let mut client = self.client.clone();
+        let runs = client
+            .inner()
+            .list_runs(request)
+            .await
+            .map_err(|e| match e.code() {
+                tonic::Code::InvalidArgument => McpError::InvalidParams(e.message().to_string()),
+                _ => McpError::Internal(e.message().to_string()),
+            })?
+            .into_inner()
+            .runs;
+
+        let runs: Vec<Value> = runs
+            .iter()
+            .map(|run| {
+                json!({
+                    "id": run.id,
+                    "project": run.project,
+                    "name": run.name,
+                    "cmd": run.cmd,
+                    "args": run.args,
+                    "cwd": run.cwd,
+                    "toolchain": run.toolchain,
+                    "started_by": run.started_by,
+                    "start_time": run.start_time.as_ref().map(format_timestamp_rfc3339),
+                    "exit_time": run.exit_time.as_ref().map(format_timestamp_rfc3339),
+                    "duration_ms": run_duration(run)
+                        .map(|duration| u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)),
+                    "status": format_status(run.status),
+                    "exit_code": run.exit_code,
+                    "exit_reason": run.exit_reason,
+                    "ports": run.ports,
+                })
+            })
+            .collect();
+
+        Ok(json!({ "runs": runs }))
+    }
+}
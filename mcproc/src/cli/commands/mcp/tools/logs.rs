@@ -22,9 +22,11 @@ impl LogsTool {
 
 #[derive(Deserialize)]
 struct LogsParams {
-    name: String,
+    name: Option<String>,
     tail: Option<u32>,
     project: Option<String>,
+    run: Option<String>,
+    labels: Option<String>,
 }
 
 #[async_trait]
@@ -32,15 +34,16 @@ impl ToolHandler for LogsTool {
     fn tool_info(&self) -> ToolInfo {
         ToolInfo {
             name: "get_process_logs".to_string(),
-            description: "Retrieve console output and logs from a process. Returns the most recent log entries including stdout, stderr, and any output from the process. Useful for debugging issues, checking server status, or monitoring process behavior. Logs are persisted even after process stops.".to_string(),
+            description: "Retrieve console output and logs from a process. Returns the most recent log entries including stdout, stderr, and any output from the process. Useful for debugging issues, checking server status, or monitoring process behavior. Logs are persisted even after process stops. Each run of the process starts with a header line naming its run ID; select a run to see only its output, e.g. the run before the last restart. Instead of a name, a label selector returns the logs of every matching process, each line prefixed with its process name.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
-                    "name": { "type": "string", "description": "Name of the process to get logs from" },
+                    "name": { "type": "string", "description": "Name of the process to get logs from. Required unless labels is given" },
                     "tail": { "type": "integer", "description": "Number of most recent lines to retrieve. Default is 100. Use larger values to see more history." },
-                    "project": { "type": "string", "description": "Optional project name to scope the process lookup. Useful when multiple projects have processes with the same name." }
-                },
-                "required": ["name"]
+                    "project": { "type": "string", "description": "Optional project name to scope the process lookup. Useful when multiple projects have processes with the same name." },
+                    "run": { "type": "string", "description": "Only return the output of one run: 'current' (latest run), 'previous' (the run before it, e.g. before a restart or crash) or a run ID or ID prefix from get_run_history" },
+                    "labels": { "type": "string", "description": "Label selector such as 'role=frontend' to get the logs of every matching process instead of one by name. Requirements are comma-separated: key=value, key!=value, key or !key" }
+                }
             }),
         }
     }
@@ -58,22 +61,35 @@ impl ToolHandler for LogsTool {
 
         // Determine project name if not provided
         let project = resolve_mcp_project_name(params.project)?;
+        if params.name.is_none() && params.labels.is_none() {
+            return Err(McpError::InvalidParams(
+                "Either name or labels must be given".to_string(),
+            ));
+        }
+        let multiple = params.name.is_none();
 
         // Use gRPC get_logs method instead of direct file access
         let mut client = self.client.clone();
         let request = proto::GetLogsRequest {
-            process_names: vec![params.name.clone()],
+            process_names: params.name.into_iter().collect(),
             tail: params.tail,
             follow: Some(false),
             project,
             include_events: Some(false),
+            run: params.run,
+            label_selector: params.labels.into_iter().collect(),
         };
 
         let mut stream = client
             .inner()
             .get_logs(request)
             .await
-            .map_err(|e| McpError::Internal(e.to_string()))?
+            .map_err(|e| match e.code() {
+                tonic::Code::InvalidArgument | tonic::Code::NotFound => {
+                    McpError::InvalidParams(e.message().to_string())
+                }
+                _ => McpError::Internal(e.to_string()),
+            })?
             .into_inner();
 
         let mut all_logs = Vec::new();
@@ -98,6 +114,10 @@ impl ToolHandler for LogsTool {
                                     String::from_utf8_lossy(&strip(entry.content.as_bytes()))
                                         .to_string();
 
+                                let content = match &entry.process_name {
+                                    Some(name) if multiple => format!("[{}] {}", name, content),
+                                    _ => content,
+                                };
                                 let formatted = if timestamp.is_empty() {
                                     format!("{} {}", level, content)
                                 } else {
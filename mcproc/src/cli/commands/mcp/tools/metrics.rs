@@ -0,0 +1,121 @@
+//! Resource usage tool implementation
+
+use crate::cli::utils::resolve_mcp_project_name;
+use crate::client::DaemonClient;
+use crate::common::metrics::{history_window, io_rates, rss_growth_per_minute};
+use crate::common::status::format_status;
+use async_trait::async_trait;
+use mcp_rs::{Error as McpError, Result as McpResult, ToolHandler, ToolInfo};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+pub struct MetricsTool {
+    client: DaemonClient,
+}
+
+impl MetricsTool {
+    pub fn new(client: DaemonClient) -> Self {
+        Self { client }
+    }
+}
+
+#[derive(Deserialize)]
+struct MetricsParams {
+    name: Option<String>,
+    project: Option<String>,
+}
+
+#[async_trait]
+impl ToolHandler for MetricsTool {
+    fn tool_info(&self) -> ToolInfo {
+        ToolInfo {
+            name: "get_process_metrics".to_string(),
+            description: "Get CPU, memory (RSS), thread, open file descriptor and disk I/O usage of running processes, summed over each process tree, together with their recent history. Use this to spot memory leaks (steadily positive rss_growth_per_minute), runaway CPU use or descriptor leaks instead of polling ps. Sampling is only available on Linux.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string", "description": "Name of the process to report on. Omit to report on every running process of the project" },
+                    "project": { "type": "string", "description": "Optional project name to scope the process lookup. Useful when multiple projects have processes with the same name." }
+                }
+            }),
+        }
+    }
+
+    async fn handle(
+        &self,
+        params: Option<Value>,
+        _context: mcp_rs::ToolContext,
+    ) -> McpResult<Value> {
+        let params: MetricsParams = serde_json::from_value(params.unwrap_or_else(|| json!({})))
+            .map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+        // Determine project name if not provided
+        let project = resolve_mcp_project_name(params.project)?;
+
+        let request = proto::ListProcessesRequest {
+            status_filter: None,
+            project_filter: Some(project.clone()),
+            label_selector: vec![],
+        };
+        let mut client = self.client.clone();
+        let processes: Vec<_> = client
+            .inner()
+            .list_processes(request)
+            .await
+            .map_err(|e| McpError::Internal(e.message().to_string()))?
+            .into_inner()
+            .processes
+            .into_iter()
+            .filter(|p| {
+                p.status == proto::ProcessStatus::Running as i32
+                    || p.status == proto::ProcessStatus::Paused as i32
+            })
+            .filter(|p| params.name.as_ref().map_or(true, |name| &p.name == name))
+            .collect();
+
+        if let Some(name) = &params.name {
+            if processes.is_empty() {
+                return Err(McpError::InvalidParams(format!(
+                    "Process '{}' is not running in project '{}'",
+                    name, project
+                )));
+            }
+        }
+
+        let processes: Vec<Value> = processes.iter().map(process_metrics).collect();
+        Ok(json!({ "project": project, "processes": processes }))
+    }
+}
+
+fn process_metrics(process: &proto::ProcessInfo) -> Value {
+    let history = &process.metrics_history;
+    let metrics = process.metrics.as_ref();
+    let rates = io_rates(history);
+    json!({
+        "name": process.name,
+        "project": process.project,
+        "pid": process.pid,
+        "status": format_status(process.status),
+        "processes": metrics.map(|m| m.processes),
+        "cpu_percent": metrics.and_then(|m| m.cpu_percent),
+        "rss_bytes": metrics.map(|m| m.rss_bytes),
+        "threads": metrics.map(|m| m.threads),
+        "open_fds": metrics.map(|m| m.open_fds),
+        "read_bytes": metrics.map(|m| m.read_bytes),
+        "write_bytes": metrics.map(|m| m.write_bytes),
+        "read_bytes_per_sec": rates.map(|(read, _)| read),
+        "write_bytes_per_sec": rates.map(|(_, write)| write),
+        "sampled_at": metrics.and_then(|m| m.sampled_at).map(|t| {
+            let ts = chrono::DateTime::<chrono::Utc>::from_timestamp(t.seconds, t.nanos as u32)
+                .unwrap_or_else(chrono::Utc::now);
+            ts.to_rfc3339()
+        }),
+        "rss_growth_per_minute": rss_growth_per_minute(history),
+        "history": {
+            "window_secs": history_window(history),
+            "cpu_percent": history.iter().map(|m| m.cpu_percent).collect::<Vec<_>>(),
+            "rss_bytes": history.iter().map(|m| m.rss_bytes).collect::<Vec<_>>(),
+            "open_fds": history.iter().map(|m| m.open_fds).collect::<Vec<_>>(),
+        },
+    })
+}
@@ -1,17 +1,33 @@
 //! MCP tool implementations
 
+pub mod down;
 pub mod grep;
+pub mod history;
 pub mod logs;
+pub mod metrics;
+pub mod port;
 pub mod ps;
 pub mod restart;
+pub mod run;
+pub mod send;
+pub mod signal;
 pub mod start;
 pub mod status;
 pub mod stop;
+pub mod up;
 
+pub use down::DownTool;
 pub use grep::GrepTool;
+pub use history::RunHistoryTool;
 pub use logs::LogsTool;
+pub use metrics::MetricsTool;
+pub use port::LookupPortTool;
 pub use ps::PsTool;
 pub use restart::RestartTool;
+pub use run::RunTaskTool;
+pub use send::SendInputTool;
+pub use signal::SignalTool;
 pub use start::StartTool;
 pub use status::StatusTool;
 pub use stop::StopTool;
+pub use up::UpTool;
@@ -0,0 +1,106 @@
+//! Port lookup tool implementation
+
+use crate::client::DaemonClient;
+use crate::common::status::format_status;
+use async_trait::async_trait;
+use mcp_rs::{Error as McpError, Result as McpResult, ToolHandler, ToolInfo};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+pub struct LookupPortTool {
+    client: DaemonClient,
+}
+
+impl LookupPortTool {
+    pub fn new(client: DaemonClient) -> Self {
+        Self { client }
+    }
+}
+
+#[derive(Deserialize)]
+struct LookupPortParams {
+    port: u16,
+    #[serde(default)]
+    kill_holder: bool,
+}
+
+#[async_trait]
+impl ToolHandler for LookupPortTool {
+    fn tool_info(&self) -> ToolInfo {
+        ToolInfo {
+            name: "lookup_port".to_string(),
+            description: "Find out which process holds a port. Use this when a start fails with 'address already in use' / EADDRINUSE instead of guessing: each holder is reported either as an mcproc process (project and name) or as an unmanaged PID with its command line. Set kill_holder=true to stop the managed processes holding the port; unmanaged processes are never touched.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "port": { "type": "integer", "minimum": 1, "maximum": 65535, "description": "Port number to look up" },
+                    "kill_holder": { "type": "boolean", "description": "Stop the mcproc processes holding the port (default: false)" }
+                },
+                "required": ["port"]
+            }),
+        }
+    }
+
+    async fn handle(
+        &self,
+        params: Option<Value>,
+        _context: mcp_rs::ToolContext,
+    ) -> McpResult<Value> {
+        let params =
+            params.ok_or_else(|| McpError::InvalidParams("Missing parameters".to_string()))?;
+
+        let params: LookupPortParams =
+            serde_json::from_value(params).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+        let request = proto::LookupPortRequest {
+            port: u32::from(params.port),
+            kill_holder: Some(params.kill_holder),
+        };
+
+        let mut client = self.client.clone();
+        let response = client
+            .inner()
+            .lookup_port(request)
+            .await
+            .map_err(|e| McpError::Internal(e.message().to_string()))?
+            .into_inner();
+
+        let holders = response
+            .holders
+            .iter()
+            .map(|holder| {
+                let mut value = json!({
+                    "pid": holder.pid,
+                    "command": holder.command,
+                    "managed": holder.process.is_some(),
+                });
+                if let Some(listener) = &holder.listener {
+                    value["protocol"] = json!(listener.protocol);
+                    value["address"] = json!(listener.address);
+                }
+                if let Some(process) = &holder.process {
+                    value["project"] = json!(process.project);
+                    value["name"] = json!(process.name);
+                    value["status"] = json!(format_status(process.status));
+                }
+                value
+            })
+            .collect::<Vec<_>>();
+
+        let mut result = json!({
+            "port": response.port,
+            "in_use": !holders.is_empty(),
+            "holders": holders,
+        });
+        if let Some(process) = &response.allocated_to {
+            result["allocated_to"] = json!({
+                "project": process.project,
+                "name": process.name,
+            });
+        }
+        if params.kill_holder {
+            result["stopped"] = json!(response.stopped);
+        }
+        Ok(result)
+    }
+}
@@ -1,9 +1,12 @@
 //! Process list tool implementation
 
 use crate::client::DaemonClient;
+use crate::common::health::format_health;
+use crate::common::restart_policy::format_restart_policy;
 use crate::common::status::format_status;
 use async_trait::async_trait;
 use mcp_rs::{Error as McpError, Result as McpResult, ToolHandler, ToolInfo};
+use serde::Deserialize;
 use serde_json::{json, Value};
 
 pub struct PsTool {
@@ -16,27 +19,38 @@ impl PsTool {
     }
 }
 
+#[derive(Deserialize)]
+struct PsParams {
+    labels: Option<String>,
+}
+
 #[async_trait]
 impl ToolHandler for PsTool {
     fn tool_info(&self) -> ToolInfo {
         ToolInfo {
             name: "list_processes".to_string(),
-            description: "List all processes managed by mcproc across all projects. Shows process names, status (running/stopped/failed), PIDs, start times, and detected ports. Use this to see what's currently running before starting new processes or to find process names for other commands.".to_string(),
+            description: "List all processes managed by mcproc across all projects. Shows process names, status (running/paused/stopped/failed/crash-looping, with a status_reason such as 'daemon restarted' for processes that were running when the daemon restarted), health (healthy/unhealthy, for processes with a health check), watchdog_flags (labels such as 'stalled' set by watchdog flag rules), PIDs, start times, detected ports, automatic restart counts, and the labels and description given when the process was started. Use this to see what's currently running before starting new processes or to find process names for other commands.".to_string(),
             input_schema: json!({
                 "type": "object",
-                "properties": {}
+                "properties": {
+                    "labels": { "type": "string", "description": "Optional label selector to list only matching processes, e.g. 'role=frontend' or 'role=worker,!experimental'. Requirements are comma-separated and must all hold: key=value, key!=value, key (label set) or !key (label not set)" }
+                }
             }),
         }
     }
 
     async fn handle(
         &self,
-        _params: Option<Value>,
+        params: Option<Value>,
         _context: mcp_rs::ToolContext,
     ) -> McpResult<Value> {
+        let params: PsParams = serde_json::from_value(params.unwrap_or_else(|| json!({})))
+            .map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
         let request = proto::ListProcessesRequest {
             status_filter: None,
             project_filter: None,
+            label_selector: params.labels.into_iter().collect(),
         };
 
         let mut client = self.client.clone();
@@ -44,7 +58,10 @@ impl ToolHandler for PsTool {
             .inner()
             .list_processes(request)
             .await
-            .map_err(|e| McpError::Internal(e.to_string()))?
+            .map_err(|e| match e.code() {
+                tonic::Code::InvalidArgument => McpError::InvalidParams(e.message().to_string()),
+                _ => McpError::Internal(e.to_string()),
+            })?
             .into_inner();
 
         let processes: Vec<Value> = response.processes.into_iter().map(|p| {
@@ -54,6 +71,12 @@ impl ToolHandler for PsTool {
                 "name": p.name,
                 "pid": p.pid,
                 "status": format_status(p.status),
+                "status_reason": p.status_reason,
+                "health": format_health(p.health),
+                "health_error": p.health_error,
+                "watchdog_flags": p.watchdog_flags,
+                "labels": p.labels,
+                "description": p.description,
                 "cmd": p.cmd,
                 "log_file": p.log_file,
                 "start_time": p.start_time.map(|t| {
@@ -62,6 +85,13 @@ impl ToolHandler for PsTool {
                     ts.to_rfc3339()
                 }),
                 "ports": p.ports,
+                "restart_policy": format_restart_policy(p.restart_policy),
+                "restart_count": p.restart_count,
+                "last_restart_time": p.last_restart_time.map(|t| {
+                    let ts = chrono::DateTime::<chrono::Utc>::from_timestamp(t.seconds, t.nanos as u32)
+                        .unwrap_or_else(chrono::Utc::now);
+                    ts.to_rfc3339()
+                }),
             })
         }).collect();
 
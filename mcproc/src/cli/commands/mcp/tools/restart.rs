@@ -2,7 +2,9 @@
 
 use crate::cli::utils::resolve_mcp_project_name;
 use crate::client::DaemonClient;
-use crate::common::status::format_status;
+use crate::common::config::Config;
+use crate::common::process_pattern::is_pattern;
+use crate::common::status::{format_status, parse_status};
 use async_trait::async_trait;
 use mcp_rs::{Error as McpError, Result as McpResult, ToolHandler, ToolInfo};
 use serde::Deserialize;
@@ -22,8 +24,11 @@ impl RestartTool {
 
 #[derive(Deserialize)]
 struct RestartParams {
-    name: String,
+    name: Option<String>,
     project: Option<String>,
+    status: Option<String>,
+    labels: Option<String>,
+    parallelism: Option<u32>,
     wait_for_log: Option<String>,
     wait_timeout: Option<u32>,
 }
@@ -33,22 +38,24 @@ impl ToolHandler for RestartTool {
     fn tool_info(&self) -> ToolInfo {
         ToolInfo {
             name: "restart_process".to_string(),
-            description: "Restart a running process by stopping it and starting it again. By default, uses the same wait_for_log pattern and timeout from the original start. You can override these values to change the startup detection behavior. This is especially useful when the server's startup log pattern changes or when you need to adjust the timeout. The process will be restarted with the same command, working directory, and environment variables.".to_string(),
+            description: "Restart a running process by stopping it and starting it again. By default, uses the same wait_for_log pattern and timeout from the original start. You can override these values to change the startup detection behavior. This is especially useful when the server's startup log pattern changes or when you need to adjust the timeout. The process will be restarted with the same command, working directory, and environment variables. To restart several processes at once, give a name pattern such as 'worker-*' or 'myapp/*', a status, or a label selector; they are restarted concurrently, after the processes they depend on, and the result lists each matching process.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
-                    "name": { "type": "string", "description": "Name of the process to restart (must be currently running or recently stopped)" },
+                    "name": { "type": "string", "description": "Name of the process to restart (must be currently running or recently stopped). A pattern with * and ? wildcards restarts every matching process, e.g. 'worker-*'; prefix it with a project pattern to reach other projects, e.g. 'myapp/*' or '*/worker-*'. Required unless status or labels is given" },
+                    "status": { "type": "string", "description": "Restart every process in this status, e.g. 'failed'" },
+                    "labels": { "type": "string", "description": "Label selector such as 'role=frontend' to restart every matching process. Requirements are comma-separated: key=value, key!=value, key or !key" },
+                    "parallelism": { "type": "integer", "description": "How many processes to restart at once when several are selected. Defaults to the daemon configuration" },
                     "project": { "type": "string", "description": "Optional project name to scope the process lookup. Useful when multiple projects have processes with the same name." },
                     "wait_for_log": { 
                         "type": "string", 
-                        "description": "Override the regex pattern to wait for in logs before considering the process ready. If not specified, uses the pattern from the original start command. Use this when the server's startup message has changed or to detect a different ready state." 
+                        "description": "Override the regex pattern to wait for in logs before considering the process ready. If not specified, uses the pattern from the original start command. Use this when the server's startup message has changed or to detect a different ready state. Only for a single process." 
                     },
                     "wait_timeout": { 
                         "type": "integer", 
-                        "description": "Override the timeout in seconds for waiting for the log pattern. If not specified, uses the timeout from the original start command. Increase this if the server takes longer to start after updates." 
+                        "description": "Override the timeout in seconds for waiting for the log pattern. If not specified, uses the timeout from the original start command. Increase this if the server takes longer to start after updates. Only for a single process." 
                     }
-                },
-                "required": ["name"]
+                }
             }),
         }
     }
@@ -66,114 +73,220 @@ impl ToolHandler for RestartTool {
 
         let project = resolve_mcp_project_name(params.project)?;
 
-        let wait_timeout = params.wait_timeout;
+        let config = Config::load().map_err(|e| McpError::Internal(e.to_string()))?;
+        let mut client = self.client.clone();
+        let name = params.name.unwrap_or_default();
+        let status_filter = params
+            .status
+            .as_deref()
+            .map(parse_status)
+            .transpose()
+            .map_err(McpError::InvalidParams)?;
+        let label_selector = params.labels.into_iter().collect::<Vec<_>>();
+
+        if !is_pattern(&name) && status_filter.is_none() && label_selector.is_empty() {
+            if name.is_empty() {
+                return Err(McpError::InvalidParams(
+                    "name is required unless status or labels is given".to_string(),
+                ));
+            }
+            return restart_process(
+                &mut client,
+                &config,
+                &context,
+                name,
+                &project,
+                params.wait_for_log,
+                params.wait_timeout,
+            )
+            .await;
+        }
+
         let grpc_request = proto::RestartProcessRequest {
-            name: params.name.clone(),
+            name,
             project,
             wait_for_log: params.wait_for_log,
-            wait_timeout,
+            wait_timeout: params.wait_timeout,
+            started_by: Some("mcp".to_string()),
+            status_filter,
+            label_selector,
+            parallelism: params.parallelism,
         };
+        // No deadline: how long it takes depends on how many processes are selected
+        let mut stream = client
+            .inner()
+            .restart_process(grpc_request)
+            .await
+            .map_err(|e| match e.code() {
+                tonic::Code::InvalidArgument | tonic::Code::NotFound => {
+                    McpError::InvalidParams(e.message().to_string())
+                }
+                _ => McpError::Internal(e.message().to_string()),
+            })?
+            .into_inner();
 
-        let config =
-            crate::common::config::Config::load().map_err(|e| McpError::Internal(e.to_string()))?;
-        let mut request = Request::new(grpc_request);
-        request.set_timeout(crate::cli::utils::restart_deadline(
-            config.process.restart.process_stop_timeout_ms,
-            wait_timeout,
-            config.process.startup.default_wait_timeout_secs,
-        ));
-
-        let mut client = self.client.clone();
-        match client.inner().restart_process(request).await {
-            Ok(response) => {
-                let mut stream = response.into_inner();
-                let mut process_info = None;
-                let mut _log_count = 0;
-
-                // Process streaming responses
-                while let Some(msg) = stream
-                    .message()
-                    .await
-                    .map_err(|e| McpError::Internal(e.to_string()))?
-                {
-                    match msg.response {
-                        Some(proto::restart_process_response::Response::LogEntry(entry)) => {
-                            // Send log entry as notification
-                            context
-                                .send_log(mcp_rs::MessageLevel::Info, entry.content.clone())
-                                .await?;
-                            _log_count += 1;
-                        }
-                        Some(proto::restart_process_response::Response::Process(info)) => {
-                            process_info = Some(info);
-                        }
-                        None => {}
-                    }
+        let mut processes = Vec::new();
+        while let Some(msg) = stream
+            .message()
+            .await
+            .map_err(|e| McpError::Internal(e.to_string()))?
+        {
+            let Some(proto::restart_process_response::Response::Result(result)) = msg.response
+            else {
+                continue;
+            };
+            let mut entry = json!({
+                "name": result.name,
+                "project": result.project,
+                "success": result.success,
+                "message": result.message,
+            });
+            if let Some(process) = result.process {
+                entry["pid"] = json!(process.pid);
+                entry["status"] = json!(format_status(process.status));
+                if let Some(exit_code) = process.exit_code {
+                    entry["exit_code"] = json!(exit_code);
+                }
+                if let Some(stderr_tail) = process.stderr_tail {
+                    entry["stderr_tail"] = json!(stderr_tail);
                 }
+            }
+            processes.push(entry);
+        }
 
-                let process = process_info
-                    .ok_or_else(|| McpError::Internal("No process info returned".to_string()))?;
-                let mut response = json!({
-                    "id": process.id,
-                    "project": process.project,
-                    "name": process.name,
-                    "pid": process.pid,
-                    "status": format_status(process.status),
-                    "log_file": process.log_file,
-                    "start_time": process.start_time.map(|t| {
-                        let ts = chrono::DateTime::<chrono::Utc>::from_timestamp(t.seconds, t.nanos as u32)
-                            .unwrap_or_else(chrono::Utc::now);
-                        ts.to_rfc3339()
-                    }),
-                    "ports": process.ports,
-                });
-
-                // Add exit information if process failed
-                if process.status == proto::ProcessStatus::Failed as i32 {
-                    if let Some(exit_code) = process.exit_code {
-                        response["exit_code"] = json!(exit_code);
-                    }
-                    if let Some(exit_reason) = process.exit_reason {
-                        response["exit_reason"] = json!(exit_reason);
+        let restarted = processes
+            .iter()
+            .filter(|process| process["success"] == true)
+            .count();
+        Ok(json!({
+            "success": restarted == processes.len(),
+            "message": format!("Restarted {} of {} processes", restarted, processes.len()),
+            "processes": processes,
+        }))
+    }
+}
+
+async fn restart_process(
+    client: &mut DaemonClient,
+    config: &Config,
+    context: &mcp_rs::ToolContext,
+    name: String,
+    project: &str,
+    wait_for_log: Option<String>,
+    wait_timeout: Option<u32>,
+) -> McpResult<Value> {
+    let stop_timeout_ms = crate::cli::utils::process_stop_timeout_ms(
+        client,
+        &name,
+        project,
+        config.process.restart.process_stop_timeout_ms,
+    )
+    .await;
+
+    let grpc_request = proto::RestartProcessRequest {
+        name,
+        project: project.to_string(),
+        wait_for_log,
+        wait_timeout,
+        started_by: Some("mcp".to_string()),
+        ..Default::default()
+    };
+    let mut request = Request::new(grpc_request);
+    request.set_timeout(crate::cli::utils::restart_deadline(
+        stop_timeout_ms,
+        wait_timeout,
+        config.process.startup.default_wait_timeout_secs,
+    ));
+
+    match client.inner().restart_process(request).await {
+        Ok(response) => {
+            let mut stream = response.into_inner();
+            let mut process_info = None;
+            let mut _log_count = 0;
+
+            // Process streaming responses
+            while let Some(msg) = stream
+                .message()
+                .await
+                .map_err(|e| McpError::Internal(e.to_string()))?
+            {
+                match msg.response {
+                    Some(proto::restart_process_response::Response::LogEntry(entry)) => {
+                        // Send log entry as notification
+                        context
+                            .send_log(mcp_rs::MessageLevel::Info, entry.content.clone())
+                            .await?;
+                        _log_count += 1;
                     }
-                    if let Some(stderr_tail) = process.stderr_tail {
-                        response["stderr_tail"] = json!(stderr_tail);
+                    Some(proto::restart_process_response::Response::Process(info)) => {
+                        process_info = Some(info);
                     }
+                    Some(proto::restart_process_response::Response::Result(_)) | None => {}
                 }
+            }
 
-                // Add wait pattern match info if process has wait_for_log configured (strip ANSI codes)
-                if !process.log_context.is_empty() {
-                    let cleaned_context: Vec<String> = process
-                        .log_context
-                        .iter()
-                        .map(|line| String::from_utf8_lossy(&strip(line.as_bytes())).to_string())
-                        .collect();
-                    response["log_context"] = json!(cleaned_context);
-                }
+            let process = process_info
+                .ok_or_else(|| McpError::Internal("No process info returned".to_string()))?;
+            let mut response = json!({
+                "id": process.id,
+                "project": process.project,
+                "name": process.name,
+                "pid": process.pid,
+                "status": format_status(process.status),
+                "log_file": process.log_file,
+                "start_time": process.start_time.map(|t| {
+                    let ts = chrono::DateTime::<chrono::Utc>::from_timestamp(t.seconds, t.nanos as u32)
+                        .unwrap_or_else(chrono::Utc::now);
+                    ts.to_rfc3339()
+                }),
+                "ports": process.ports,
+            });
 
-                if let Some(matched_line) = process.matched_line {
-                    let cleaned_line =
-                        String::from_utf8_lossy(&strip(matched_line.as_bytes())).to_string();
-                    response["matched_line"] = json!(cleaned_line);
+            // Add exit information if process failed
+            if process.status == proto::ProcessStatus::Failed as i32 {
+                if let Some(exit_code) = process.exit_code {
+                    response["exit_code"] = json!(exit_code);
                 }
-
-                // Add timeout information if available
-                if let Some(timeout_occurred) = process.wait_timeout_occurred {
-                    if timeout_occurred {
-                        response["wait_timeout_occurred"] = json!(true);
-                        response["message"] = json!(
-                            "Process restarted but wait_for_log pattern was not found within timeout"
-                        );
-                    } else {
-                        response["pattern_matched"] = json!(true);
-                        response["message"] =
-                            json!("Process restarted successfully. Pattern matched in logs.");
-                    }
+                if let Some(exit_reason) = process.exit_reason {
+                    response["exit_reason"] = json!(exit_reason);
+                }
+                if let Some(stderr_tail) = process.stderr_tail {
+                    response["stderr_tail"] = json!(stderr_tail);
                 }
+            }
+
+            // Add wait pattern match info if process has wait_for_log configured (strip ANSI codes)
+            if !process.log_context.is_empty() {
+                let cleaned_context: Vec<String> = process
+                    .log_context
+                    .iter()
+                    .map(|line| String::from_utf8_lossy(&strip(line.as_bytes())).to_string())
+                    .collect();
+                response["log_context"] = json!(cleaned_context);
+            }
 
-                Ok(response)
+            if let Some(matched_line) = process.matched_line {
+                let cleaned_line =
+                    String::from_utf8_lossy(&strip(matched_line.as_bytes())).to_string();
+                response["matched_line"] = json!(cleaned_line);
             }
-            Err(e) => Err(McpError::Internal(e.message().to_string())),
+
+            // Add timeout information if available
+            if let Some(timeout_occurred) = process.wait_timeout_occurred {
+                if timeout_occurred {
+                    response["wait_timeout_occurred"] = json!(true);
+                    response["message"] = json!(
+                        "Process restarted but wait_for_log pattern was not found within timeout"
+                    );
+                } else {
+                    response["pattern_matched"] = json!(true);
+                    response["message"] =
+                        json!("Process restarted successfully. Pattern matched in logs.");
+                }
+            }
+
+            Ok(response)
         }
+        Err(e) => Err(McpError::Internal(e.message().to_string())),
     }
 }
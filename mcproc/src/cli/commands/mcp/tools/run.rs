@@ -0,0 +1,210 @@
+//! Run task tool implementation
+
+use crate::cli::utils::resolve_mcp_project_name;
+use crate::client::DaemonClient;
+use crate::common::status::format_status;
+use crate::common::task::format_task_outcome;
+use crate::common::validation::validate_process_name;
+use async_trait::async_trait;
+use mcp_rs::{Error as McpError, Result as McpResult, ToolHandler, ToolInfo};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use strip_ansi_escapes::strip;
+
+pub struct RunTaskTool {
+    client: DaemonClient,
+}
+
+impl RunTaskTool {
+    pub fn new(client: DaemonClient) -> Self {
+        Self { client }
+    }
+}
+
+#[derive(Deserialize)]
+struct RunTaskParams {
+    name: String,
+    #[serde(default)]
+    cmd: Option<String>,
+    #[serde(default)]
+    args: Option<Vec<String>>,
+    cwd: Option<String>,
+    project: Option<String>,
+    env: Option<std::collections::HashMap<String, String>>,
+    #[serde(default)]
+    toolchain: Option<String>,
+    #[serde(default)]
+    depends_on: Vec<String>,
+    #[serde(default)]
+    success_exit_codes: Vec<i32>,
+    #[serde(default)]
+    timeout_secs: Option<u32>,
+    #[serde(default)]
+    tail_lines: Option<u32>,
+}
+
+#[async_trait]
+impl ToolHandler for RunTaskTool {
+    fn tool_info(&self) -> ToolInfo {
+        ToolInfo {
+            name: "run_task".to_string(),
+            description: "Run a one-shot command to completion and return its exit code, duration and the last lines of its output. Use this for builds, migrations, test suites and other commands that exit on their own, instead of start_process which is meant for long-running servers. The call blocks until the task exits or its timeout expires; a task that times out is stopped and reported as failed. The task's status becomes 'Succeeded' when it exits with one of success_exit_codes (default [0]) and 'Failed' otherwise. Its full output stays available through get_process_logs and search_process_logs under the same name.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string", "description": "Name for this task, e.g. 'build', 'migrate' or 'test'. Do NOT include the project name. Running a task again with the same name replaces the previous run once it has finished." },
+                    "cmd": { "type": "string", "description": "Shell command to execute, e.g. 'npm run build' or 'cargo test'. Choose either 'cmd' or 'args', not both." },
+                    "args": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Command and arguments as an array for direct execution without shell interpretation, e.g. ['npm', 'run', 'build']. Choose either 'cmd' or 'args', not both."
+                    },
+                    "cwd": { "type": "string", "description": "Working directory path. Absolute paths are recommended. Defaults to current directory if not specified." },
+                    "project": { "type": "string", "description": "Project name (defaults to directory name)" },
+                    "env": {
+                        "type": "object",
+                        "description": "Environment variables to set for the task",
+                        "additionalProperties": { "type": "string" }
+                    },
+                    "toolchain": {
+                        "type": "string",
+                        "description": format!("Version management tool to use for executing the command. Supported tools: {}.", crate::daemon::process::toolchain::Toolchain::all_supported())
+                    },
+                    "depends_on": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Processes in the same project that must already be running and ready, e.g. the database a migration needs"
+                    },
+                    "success_exit_codes": {
+                        "type": "array",
+                        "items": { "type": "integer" },
+                        "description": "Exit codes that count as success (default: [0]). For example [0, 1] for a linter that exits 1 when it only reports warnings."
+                    },
+                    "timeout_secs": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Stop the task and report it as failed after this many seconds (default: 600)"
+                    },
+                    "tail_lines": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "maximum": 1000,
+                        "description": "Number of output lines to return from the end of the task's output (default: 20)"
+                    }
+                },
+                "required": ["name"],
+                "anyOf": [
+                    { "required": ["cmd"] },
+                    { "required": ["args"] }
+                ]
+            }),
+        }
+    }
+
+    async fn handle(
+        &self,
+        params: Option<Value>,
+        context: mcp_rs::ToolContext,
+    ) -> McpResult<Value> {
+        let params =
+            params.ok_or_else(|| McpError::InvalidParams("Missing parameters".to_string()))?;
+        let params: RunTaskParams =
+            serde_json::from_value(params).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+        validate_process_name(&params.name)
+            .map_err(|e| McpError::InvalidParams(format!("Invalid process name: {}", e)))?;
+        let args = params.args.unwrap_or_default();
+        match (&params.cmd, args.is_empty()) {
+            (Some(_), false) => {
+                return Err(McpError::InvalidParams(
+                    "Cannot specify both 'cmd' and 'args'".to_string(),
+                ));
+            }
+            (None, true) => {
+                return Err(McpError::InvalidParams(
+                    "Must specify either 'cmd' or 'args'".to_string(),
+                ));
+            }
+            _ => {}
+        }
+
+        let project = resolve_mcp_project_name(params.project)?;
+        let name = params.name.clone();
+        let request = proto::RunTaskRequest {
+            process: Some(proto::StartProcessRequest {
+                name: params.name,
+                cmd: params.cmd,
+                args,
+                cwd: params.cwd,
+                project,
+                env: params.env.unwrap_or_default(),
+                toolchain: params.toolchain,
+                depends_on: params.depends_on,
+                started_by: Some("mcp".to_string()),
+                ..Default::default()
+            }),
+            task: Some(proto::TaskOptions {
+                success_exit_codes: params.success_exit_codes,
+                timeout_secs: params.timeout_secs,
+            }),
+            tail_lines: params.tail_lines,
+        };
+
+        let mut client = self.client.clone();
+        let mut stream = match client.inner().run_task(request).await {
+            Ok(response) => response.into_inner(),
+            Err(e) if e.code() == tonic::Code::InvalidArgument => {
+                return Err(McpError::InvalidParams(e.message().to_string()));
+            }
+            Err(e) => return Err(McpError::Internal(e.message().to_string())),
+        };
+
+        let mut result = None;
+        while let Some(msg) = stream
+            .message()
+            .await
+            .map_err(|e| McpError::Internal(e.message().to_string()))?
+        {
+            match msg.response {
+                Some(proto::run_task_response::Response::LogEntry(entry)) => {
+                    context
+                        .send_log(mcp_rs::MessageLevel::Info, entry.content)
+                        .await?;
+                }
+                Some(proto::run_task_response::Response::Result(task_result)) => {
+                    result = Some(task_result);
+                }
+                None => {}
+            }
+        }
+        let result =
+            result.ok_or_else(|| McpError::Internal("No task result returned".to_string()))?;
+        let process = result
+            .process
+            .as_ref()
+            .ok_or_else(|| McpError::Internal("No process info returned".to_string()))?;
+
+        let tail: Vec<String> = result
+            .tail
+            .iter()
+            .map(|line| String::from_utf8_lossy(&strip(line.as_bytes())).to_string())
+            .collect();
+        let mut response = json!({
+            "name": name,
+            "project": process.project,
+            "status": format_status(process.status),
+            "succeeded": result.succeeded,
+            "exit_code": result.exit_code,
+            "timed_out": result.timed_out,
+            "duration_ms": result.duration_ms,
+            "tail": tail,
+            "log_file": process.log_file,
+            "message": format!("Task '{}' {}", name, format_task_outcome(&result)),
+        });
+        if let Some(exit_reason) = process.exit_reason.as_ref().filter(|_| !result.succeeded) {
+            response["exit_reason"] = json!(exit_reason);
+        }
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,94 @@
+//! Send input tool implementation
+
+use crate::cli::utils::resolve_mcp_project_name;
+use crate::client::DaemonClient;
+use crate::common::status::format_status;
+use async_trait::async_trait;
+use mcp_rs::{Error as McpError, Result as McpResult, ToolHandler, ToolInfo};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+pub struct SendInputTool {
+    client: DaemonClient,
+}
+
+impl SendInputTool {
+    pub fn new(client: DaemonClient) -> Self {
+        Self { client }
+    }
+}
+
+fn default_newline() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct SendInputParams {
+    name: String,
+    input: String,
+    project: Option<String>,
+    #[serde(default = "default_newline")]
+    newline: bool,
+}
+
+#[async_trait]
+impl ToolHandler for SendInputTool {
+    fn tool_info(&self) -> ToolInfo {
+        ToolInfo {
+            name: "send_process_input".to_string(),
+            description: "Write text to the stdin of a running process to drive interactive dev tools: answer a prompt, type 'rs' to restart nodemon, or 'r' for Flutter hot reload. The process must have been started with stdin=true or pty=true. A newline is appended by default so the text is submitted as a line. Check get_process_logs afterwards to see how the process reacted.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string", "description": "Name of the process to send input to (the same name used when starting it with start_process)" },
+                    "input": { "type": "string", "description": "Text to write to the process's stdin" },
+                    "project": { "type": "string", "description": "Optional project name to scope the process lookup. Useful when multiple projects have processes with the same name." },
+                    "newline": { "type": "boolean", "description": "Append a newline to submit the input as a line (default: true)" }
+                },
+                "required": ["name", "input"]
+            }),
+        }
+    }
+
+    async fn handle(
+        &self,
+        params: Option<Value>,
+        _context: mcp_rs::ToolContext,
+    ) -> McpResult<Value> {
+        let params =
+            params.ok_or_else(|| McpError::InvalidParams("Missing parameters".to_string()))?;
+
+        let params: SendInputParams =
+            serde_json::from_value(params).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+        let project = resolve_mcp_project_name(params.project)?;
+
+        let mut input = params.input.into_bytes();
+        if params.newline {
+            input.push(b'\n');
+        }
+        let bytes = input.len();
+        let request = proto::SendInputRequest {
+            name: params.name.clone(),
+            project,
+            input,
+        };
+
+        let mut client = self.client.clone();
+        let process = client
+            .inner()
+            .send_input(request)
+            .await
+            .map_err(|e| McpError::Internal(e.message().to_string()))?
+            .into_inner()
+            .process
+            .ok_or_else(|| McpError::Internal("No process info returned".to_string()))?;
+
+        Ok(json!({
+            "success": true,
+            "name": params.name,
+            "bytes": bytes,
+            "status": format_status(process.status),
+        }))
+    }
+}
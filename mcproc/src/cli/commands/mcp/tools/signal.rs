@@ -0,0 +1,89 @@
+//! Signal tool implementation
+
+use crate::cli::utils::resolve_mcp_project_name;
+use crate::client::DaemonClient;
+use crate::common::signal::normalize_signal;
+use crate::common::status::format_status;
+use async_trait::async_trait;
+use mcp_rs::{Error as McpError, Result as McpResult, ToolHandler, ToolInfo};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+pub struct SignalTool {
+    client: DaemonClient,
+}
+
+impl SignalTool {
+    pub fn new(client: DaemonClient) -> Self {
+        Self { client }
+    }
+}
+
+#[derive(Deserialize)]
+struct SignalParams {
+    name: String,
+    signal: String,
+    project: Option<String>,
+    #[serde(default)]
+    leader_only: bool,
+}
+
+#[async_trait]
+impl ToolHandler for SignalTool {
+    fn tool_info(&self) -> ToolInfo {
+        ToolInfo {
+            name: "signal_process".to_string(),
+            description: "Send a signal to a running process without stopping it, e.g. SIGHUP to reload configuration, SIGUSR1 to dump state, or SIGSTOP/SIGCONT to pause and resume a CPU-hungry watcher. By default the whole process group receives the signal; set leader_only=true to signal only the process started by mcproc. A process paused with SIGSTOP (or SIGTSTP) reports the status 'Paused' until it receives SIGCONT. Use stop_process instead to shut a process down.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string", "description": "Name of the process to signal (the same name used when starting it with start_process)" },
+                    "signal": { "type": "string", "description": "Signal name or number, e.g. 'SIGHUP', 'USR1', 'SIGSTOP', 'SIGCONT' or '15'" },
+                    "project": { "type": "string", "description": "Optional project name to scope the process lookup. Useful when multiple projects have processes with the same name." },
+                    "leader_only": { "type": "boolean", "description": "Signal only the process leader instead of its whole process group (default: false)" }
+                },
+                "required": ["name", "signal"]
+            }),
+        }
+    }
+
+    async fn handle(
+        &self,
+        params: Option<Value>,
+        _context: mcp_rs::ToolContext,
+    ) -> McpResult<Value> {
+        let params =
+            params.ok_or_else(|| McpError::InvalidParams("Missing parameters".to_string()))?;
+
+        let params: SignalParams =
+            serde_json::from_value(params).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+        let signal = normalize_signal(&params.signal).map_err(McpError::InvalidParams)?;
+        let project = resolve_mcp_project_name(params.project)?;
+
+        let request = proto::SignalProcessRequest {
+            name: params.name.clone(),
+            project,
+            signal: signal.clone(),
+            leader_only: Some(params.leader_only),
+        };
+
+        let mut client = self.client.clone();
+        let process = client
+            .inner()
+            .signal_process(request)
+            .await
+            .map_err(|e| McpError::Internal(e.message().to_string()))?
+            .into_inner()
+            .process
+            .ok_or_else(|| McpError::Internal("No process info returned".to_string()))?;
+
+        Ok(json!({
+            "success": true,
+            "name": params.name,
+            "signal": signal,
+            "target": if params.leader_only { "leader" } else { "group" },
+            "status": format_status(process.status),
+        }))
+    }
+}
@@ -2,8 +2,16 @@
 
 use crate::cli::utils::resolve_mcp_project_name;
 use crate::client::DaemonClient;
+use crate::common::file_watch::{format_watch, WatchSpec};
+use crate::common::health::HealthCheckSpec;
+use crate::common::limits::{format_limits, LimitsSpec};
+use crate::common::readiness::{http_probe, parse_tcp_probe, port_detected_probe};
+use crate::common::restart_policy::{format_restart_policy, parse_restart_policy};
+use crate::common::schedule::parse_schedule;
 use crate::common::status::format_status;
+use crate::common::timestamp::format_timestamp_rfc3339;
 use crate::common::validation::validate_process_name;
+use crate::common::watchdog::{format_watchdog_rule, parse_watchdog_rule};
 use async_trait::async_trait;
 use mcp_rs::{Error as McpError, Result as McpResult, ToolHandler, ToolInfo};
 use serde::Deserialize;
@@ -37,6 +45,105 @@ struct StartParams {
     force_restart: Option<bool>,
     #[serde(default)]
     toolchain: Option<String>,
+    #[serde(default)]
+    restart_policy: Option<String>,
+    #[serde(default)]
+    depends_on: Vec<String>,
+    #[serde(default)]
+    readiness_probes: Vec<ProbeParams>,
+    #[serde(default)]
+    health_check: Option<HealthParams>,
+    #[serde(default)]
+    stop_signal: Option<String>,
+    #[serde(default)]
+    stop_command: Option<String>,
+    #[serde(default)]
+    stop_timeout: Option<u32>,
+    #[serde(default)]
+    stdin: Option<bool>,
+    #[serde(default)]
+    pty: Option<bool>,
+    #[serde(default)]
+    pty_cols: Option<u32>,
+    #[serde(default)]
+    pty_rows: Option<u32>,
+    #[serde(default)]
+    ports: Vec<String>,
+    #[serde(default)]
+    limits: Option<LimitsSpec>,
+    #[serde(default)]
+    watchdog: Vec<String>,
+    #[serde(default)]
+    watch: Option<WatchSpec>,
+    #[serde(default)]
+    schedule: Option<String>,
+    #[serde(default)]
+    labels: std::collections::HashMap<String, String>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ProbeParams {
+    Tcp { port: u16, host: Option<String> },
+    Http { url: String, status: Option<String> },
+    Port,
+}
+
+impl ProbeParams {
+    fn into_proto(self) -> Result<proto::ReadinessProbe, String> {
+        match self {
+            ProbeParams::Tcp { port, host: None } => parse_tcp_probe(&port.to_string()),
+            ProbeParams::Tcp {
+                port,
+                host: Some(host),
+            } => parse_tcp_probe(&format!("{host}:{port}")),
+            ProbeParams::Http { url, status } => http_probe(&url, status.as_deref()),
+            ProbeParams::Port => Ok(port_detected_probe()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct HealthParams {
+    #[serde(flatten)]
+    check: HealthCheckParams,
+    interval_secs: Option<u32>,
+    timeout_secs: Option<u32>,
+    restart_after_failures: Option<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum HealthCheckParams {
+    Tcp { port: u16, host: Option<String> },
+    Http { url: String, status: Option<String> },
+    Command { cmd: String },
+}
+
+impl HealthParams {
+    fn into_proto(self) -> Result<proto::HealthCheck, String> {
+        let mut spec = HealthCheckSpec {
+            interval: self.interval_secs,
+            timeout: self.timeout_secs,
+            restart_after: self.restart_after_failures,
+            ..Default::default()
+        };
+        match self.check {
+            HealthCheckParams::Tcp { port, host: None } => spec.tcp = Some(port.to_string()),
+            HealthCheckParams::Tcp {
+                port,
+                host: Some(host),
+            } => spec.tcp = Some(format!("{host}:{port}")),
+            HealthCheckParams::Http { url, status } => {
+                spec.http = Some(url);
+                spec.http_status = status;
+            }
+            HealthCheckParams::Command { cmd } => spec.cmd = Some(cmd),
+        }
+        spec.into_proto()
+    }
 }
 
 #[async_trait]
@@ -44,7 +151,7 @@ impl ToolHandler for StartTool {
     fn tool_info(&self) -> ToolInfo {
         ToolInfo {
             name: "start_process".to_string(),
-            description: "Start and manage a long-running development process (web servers, build watchers, etc). The process will continue running in the background and can be monitored/controlled later. Use this for commands like 'npm run dev', 'python app.py', 'cargo watch', etc. Each process needs a unique name for identification. Use force_restart=true to automatically stop and restart an existing process with the same name, which is useful when you're unsure if the process is already running.\n\nNOTE: Processes are NOT connected to a TTY, so many tools disable colored output by default. To enable colors:\n- For npm/yarn/pnpm: Add --color or --color=always flag (e.g., 'npm run dev --color')\n- For cargo: Set CARGO_TERM_COLOR=always in env parameter\n- For other tools: Check their documentation for color flags or use env parameter to set FORCE_COLOR=1\n\nTOOLCHAIN SUPPORT: If you're using version management tools like mise, asdf, nvm, etc., specify the toolchain parameter to ensure proper path resolution. The command will be executed through the specified tool (e.g., 'mise exec -- <command>' for mise).".to_string(),
+            description: "Start and manage a long-running development process (web servers, build watchers, etc). The process will continue running in the background and can be monitored/controlled later. Use this for commands like 'npm run dev', 'python app.py', 'cargo watch', etc. Each process needs a unique name for identification. Use force_restart=true to automatically stop and restart an existing process with the same name, which is useful when you're unsure if the process is already running.\n\nNOTE: Processes are NOT connected to a TTY unless pty=true, so many tools disable colored output and interactive features by default. Set pty=true for tools that need a terminal, or to enable colors:\n- For npm/yarn/pnpm: Add --color or --color=always flag (e.g., 'npm run dev --color')\n- For cargo: Set CARGO_TERM_COLOR=always in env parameter\n- For other tools: Check their documentation for color flags or use env parameter to set FORCE_COLOR=1\n\nTOOLCHAIN SUPPORT: If you're using version management tools like mise, asdf, nvm, etc., specify the toolchain parameter to ensure proper path resolution. The command will be executed through the specified tool (e.g., 'mise exec -- <command>' for mise).".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
@@ -77,6 +184,117 @@ impl ToolHandler for StartTool {
                     "toolchain": { 
                         "type": "string", 
                         "description": format!("Version management tool to use for executing the command. Supported tools: {}. When specified, the command will be executed through the tool (e.g., 'mise exec -- <command>'). This ensures proper PATH resolution for tool-managed environments.", crate::daemon::process::toolchain::Toolchain::all_supported())
+                    },
+                    "restart_policy": {
+                        "type": "string",
+                        "enum": ["never", "on-failure", "always"],
+                        "description": "Restart the process automatically when it exits on its own. 'on-failure' restarts after a non-zero exit or a crash, 'always' restarts after any exit. Restarts back off exponentially, and a process that keeps exiting quickly is marked as CrashLoop instead of being restarted forever. (default: never)"
+                    },
+                    "readiness_probes": {
+                        "type": "array",
+                        "description": "Readiness probes polled while the process starts, as an alternative or complement to wait_for_log when log banners are unreliable. The process is ready as soon as wait_for_log matches or any probe succeeds, within wait_timeout. Types: 'tcp' (connect to port, optional host, default 127.0.0.1), 'http' (GET an http:// url, optional status range like '200-299', default 200-399), 'port' (the process listens on any TCP port). The probe that succeeded is reported as ready_by.",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "type": { "type": "string", "enum": ["tcp", "http", "port"] },
+                                "port": { "type": "integer", "description": "Port for tcp probes" },
+                                "host": { "type": "string", "description": "Host for tcp probes (default: 127.0.0.1)" },
+                                "url": { "type": "string", "description": "URL for http probes" },
+                                "status": { "type": "string", "description": "Accepted status range for http probes, e.g. '200-299'" }
+                            },
+                            "required": ["type"]
+                        }
+                    },
+                    "health_check": {
+                        "type": "object",
+                        "description": "Liveness check run periodically while the process is running, to catch servers that stay alive but stop responding. Types: 'tcp' (connect to port, optional host), 'http' (GET an http:// url, optional status range, default 200-399), 'command' (shell command that must exit 0). The result is reported as health (healthy/unhealthy) by list_processes. Set restart_after_failures to restart the process after that many consecutive failures.",
+                        "properties": {
+                            "type": { "type": "string", "enum": ["tcp", "http", "command"] },
+                            "port": { "type": "integer", "description": "Port for tcp checks" },
+                            "host": { "type": "string", "description": "Host for tcp checks (default: 127.0.0.1)" },
+                            "url": { "type": "string", "description": "URL for http checks" },
+                            "status": { "type": "string", "description": "Accepted status range for http checks, e.g. '200-299'" },
+                            "cmd": { "type": "string", "description": "Shell command for command checks, run in the process's working directory and environment" },
+                            "interval_secs": { "type": "integer", "description": "Seconds between checks (default: 10)" },
+                            "timeout_secs": { "type": "integer", "description": "Seconds before a check counts as failed (default: 5)" },
+                            "restart_after_failures": { "type": "integer", "description": "Restart the process after this many consecutive failures (default: never restart)" }
+                        },
+                        "required": ["type"]
+                    },
+                    "stop_signal": {
+                        "type": "string",
+                        "description": "Signal sent to the process group to stop it, e.g. 'SIGINT' for tools that only shut down cleanly on Ctrl-C (default: SIGTERM)"
+                    },
+                    "stop_command": {
+                        "type": "string",
+                        "description": "Shell command run to stop the process, e.g. 'docker compose down', in the process's working directory and environment. The stop signal is only sent if the command fails."
+                    },
+                    "stop_timeout": {
+                        "type": "integer",
+                        "description": "Seconds to wait for the process to exit after the stop command or signal before it is killed (default: the daemon's process_stop_timeout_ms)"
+                    },
+                    "stdin": {
+                        "type": "boolean",
+                        "description": "Keep the process's stdin open so input can be sent later with send_process_input, e.g. to answer a prompt or type 'rs' into nodemon. (default: false)"
+                    },
+                    "pty": {
+                        "type": "boolean",
+                        "description": "Run the process on a pseudo-terminal so it behaves as if started from a shell: colors, progress output and interactive watchers work without extra flags. stdout and stderr are combined, and send_process_input writes to the terminal. (default: false)"
+                    },
+                    "pty_cols": {
+                        "type": "integer",
+                        "description": "Terminal width when pty is true (default: 80)"
+                    },
+                    "pty_rows": {
+                        "type": "integer",
+                        "description": "Terminal height when pty is true (default: 24)"
+                    },
+                    "ports": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Names of ports for the daemon to allocate, e.g. ['http', 'hmr']. Each gets a free port that no other managed process or listening socket uses, exported as MCPROC_PORT_<NAME>; the first is also exported as PORT. Use this instead of hard-coding ports so parallel projects and worktrees do not collide. The allocated ports are returned as allocated_ports."
+                    },
+                    "limits": {
+                        "type": "object",
+                        "description": "Resource limits, e.g. to keep a runaway watcher from exhausting the machine. Enforced through the process's cgroup on Linux when its controllers are available; otherwise memory falls back to a per-process address space limit, and cpus and pids are not enforced (a warning is logged). A process killed for exceeding the memory limit reports it in exit_reason, e.g. 'OOM-killed by memory limit 2G'; without a cgroup a SIGKILL is reported as 'likely out of memory'.",
+                        "properties": {
+                            "memory": { "type": "string", "description": "Memory ceiling of the process and its children, e.g. '2G' or '512M'" },
+                            "cpus": { "type": "number", "description": "CPU quota in cores, e.g. 1.5" },
+                            "pids": { "type": "integer", "description": "Maximum number of processes and threads" },
+                            "open_files": { "type": "integer", "description": "Maximum number of open files per process" }
+                        }
+                    },
+                    "watchdog": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Watchdog rules checked against the process's resource usage every few seconds, written as 'ACTION if CONDITION [and CONDITION...] [for DURATION]'. Actions: restart, stop, flag [LABEL]. Conditions: 'rss > SIZE', 'cpu > PERCENT', 'cpu < PERCENT', 'no-output'. Examples: 'restart if rss > 3G for 60s', 'stop if cpu > 95 for 10m', 'flag stalled if no-output and cpu < 1 for 15m'. Every action is written to the process log with the usage that triggered it; flags currently set are returned as watchdog_flags by get_process_status."
+                    },
+                    "watch": {
+                        "type": "object",
+                        "description": "Restart the process when files change, for backends without built-in reload. Directories ignored by .gitignore are never watched, and a burst of changes (e.g. git checkout) leads to a single restart. Each restart is written to the process log with the file that caused it.",
+                        "properties": {
+                            "paths": { "type": "array", "items": { "type": "string" }, "description": "Files or directories relative to cwd (default: cwd itself)" },
+                            "ignore": { "type": "array", "items": { "type": "string" }, "description": "Additional gitignore-style patterns to skip, e.g. ['*.log', 'tmp/']" },
+                            "debounce_ms": { "type": "integer", "description": "Milliseconds without changes before restarting (default: 500)" }
+                        }
+                    },
+                    "depends_on": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Names of processes in the same project that must already be running and ready (their wait_for_log pattern matched) before this process is started. The start is refused if any of them is not ready."
+                    },
+                    "schedule": {
+                        "type": "string",
+                        "description": "Run the command on a schedule instead of starting it now, for recurring housekeeping such as regenerating API clients or pruning a cache. Either a cron expression in local time ('*/15 * * * *', '0 3 * * mon-fri', '@hourly') or an interval ('every 10m', whose first run starts right away). Each run must exit on its own: runs are stopped and count as failed after 10 minutes, a run that is due while the previous one is still going is skipped, and so are runs whose depends_on processes are not ready. get_process_status returns the next run time and the exit codes of recent runs."
+                    },
+                    "labels": {
+                        "type": "object",
+                        "additionalProperties": { "type": "string" },
+                        "description": "Free-form key/value labels, e.g. {\"role\": \"frontend\"}, to select the process by later: list_processes, stop_process, restart_process and get_process_logs accept a label selector such as 'role=frontend'. Keys may contain letters, digits, '-', '_', '.' and '/'; values cannot contain commas."
+                    },
+                    "description": {
+                        "type": "string",
+                        "description": "Free-form note on why the process was started, e.g. 'started to reproduce issue 42'. Shown to humans in 'mcproc ps' and returned by list_processes and get_process_status."
                     }
                 },
                 "required": ["name"],
@@ -124,12 +342,55 @@ impl ToolHandler for StartTool {
             _ => {}
         }
 
+        let restart_policy = params
+            .restart_policy
+            .as_deref()
+            .map(parse_restart_policy)
+            .transpose()
+            .map_err(McpError::InvalidParams)?;
+
+        let readiness_probes = params
+            .readiness_probes
+            .into_iter()
+            .map(ProbeParams::into_proto)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(McpError::InvalidParams)?;
+        let health_check = params
+            .health_check
+            .map(HealthParams::into_proto)
+            .transpose()
+            .map_err(McpError::InvalidParams)?;
+        let limits = params
+            .limits
+            .map(LimitsSpec::into_proto)
+            .transpose()
+            .map_err(McpError::InvalidParams)?
+            .flatten();
+        let watchdog = params
+            .watchdog
+            .iter()
+            .map(|rule| parse_watchdog_rule(rule))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(McpError::InvalidParams)?;
+        let watch = params
+            .watch
+            .map(WatchSpec::into_proto)
+            .transpose()
+            .map_err(McpError::InvalidParams)?;
+        let schedule = params
+            .schedule
+            .as_deref()
+            .map(parse_schedule)
+            .transpose()
+            .map_err(McpError::InvalidParams)?;
+
         // Determine project name if not provided
         let project = resolve_mcp_project_name(params.project)?;
 
         // Use gRPC client to start process
         let name = params.name.clone();
         let wait_for_log_flag = params.wait_for_log.is_some();
+        let wait_for_probes = !readiness_probes.is_empty();
         let wait_timeout_value = params.wait_timeout;
 
         let grpc_request = proto::StartProcessRequest {
@@ -143,6 +404,25 @@ impl ToolHandler for StartTool {
             wait_timeout: params.wait_timeout,
             force_restart: params.force_restart,
             toolchain: params.toolchain,
+            restart_policy: restart_policy.map(|policy| policy as i32),
+            depends_on: params.depends_on,
+            readiness_probes,
+            health_check,
+            stop_signal: params.stop_signal,
+            stop_command: params.stop_command,
+            stop_timeout: params.stop_timeout,
+            stdin: params.stdin,
+            pty: params.pty,
+            pty_cols: params.pty_cols,
+            pty_rows: params.pty_rows,
+            ports: params.ports,
+            limits,
+            watchdog,
+            watch,
+            schedule,
+            started_by: Some("mcp".to_string()),
+            labels: params.labels,
+            description: params.description,
         };
 
         // Set timeout to wait_timeout + 5 seconds to allow for process startup
@@ -150,12 +430,20 @@ impl ToolHandler for StartTool {
         let mut request = Request::new(grpc_request);
         request.set_timeout(timeout);
 
-        // Send initial progress notification if we're waiting for log
-        if wait_for_log_flag {
+        // Send initial progress notification if we're waiting for log or probes
+        if wait_for_log_flag || wait_for_probes {
+            let waiting_for = if wait_for_log_flag {
+                "log pattern"
+            } else {
+                "readiness probes"
+            };
             context
                 .send_log(
                     mcp_rs::MessageLevel::Info,
-                    format!("Starting process '{}' and waiting for log pattern...", name),
+                    format!(
+                        "Starting process '{}' and waiting for {}...",
+                        name, waiting_for
+                    ),
                 )
                 .await?;
 
@@ -236,7 +524,43 @@ impl ToolHandler for StartTool {
                         ts.to_rfc3339()
                     }),
                     "ports": process.ports,
+                    "restart_policy": format_restart_policy(process.restart_policy),
                 });
+                if let Some(ready_by) = &process.ready_by {
+                    response["ready_by"] = json!(ready_by);
+                }
+                if let Some(limits) = &process.limits {
+                    response["limits"] = json!(format_limits(limits));
+                }
+                if !process.watchdog.is_empty() {
+                    response["watchdog"] = json!(process
+                        .watchdog
+                        .iter()
+                        .map(format_watchdog_rule)
+                        .collect::<Vec<_>>());
+                }
+                if let Some(watch) = &process.watch {
+                    response["watch"] = json!(format_watch(watch));
+                }
+                if !process.labels.is_empty() {
+                    response["labels"] = json!(process.labels);
+                }
+                if let Some(description) = &process.description {
+                    response["description"] = json!(description);
+                }
+                if let Some(schedule) = &process.schedule {
+                    response["schedule"] = json!(schedule);
+                    if let Some(next_run) = process.next_run_time.as_ref() {
+                        response["next_run_time"] = json!(format_timestamp_rfc3339(next_run));
+                    }
+                }
+                if !process.allocated_ports.is_empty() {
+                    response["allocated_ports"] = json!(process
+                        .allocated_ports
+                        .iter()
+                        .map(|allocated| json!({ "name": allocated.name, "port": allocated.port, "env": allocated.env }))
+                        .collect::<Vec<_>>());
+                }
 
                 // Add exit information if process failed
                 if process.status == proto::ProcessStatus::Failed as i32 {
@@ -255,9 +579,11 @@ impl ToolHandler for StartTool {
                 if let Some(timeout_occurred) = process.wait_timeout_occurred {
                     if timeout_occurred {
                         response["wait_timeout_occurred"] = json!(true);
-                        response["message"] = json!(
+                        response["message"] = json!(if wait_for_log_flag {
                             "Process started but wait_for_log pattern was not found within timeout"
-                        );
+                        } else {
+                            "Process started but no readiness probe succeeded within timeout"
+                        });
                     }
                 }
 
@@ -291,6 +617,12 @@ impl ToolHandler for StartTool {
                         ));
                     }
                 }
+                if let Some(ready_by) = process.ready_by.filter(|_| !has_matched_line) {
+                    response["message"] = json!(format!(
+                        "Process started successfully. Readiness probe succeeded: {}",
+                        ready_by
+                    ));
+                }
 
                 Ok(response)
             }
@@ -2,7 +2,14 @@
 
 use crate::cli::utils::resolve_mcp_project_name;
 use crate::client::DaemonClient;
+use crate::common::file_watch::format_watch;
+use crate::common::health::format_health;
+use crate::common::limits::format_limits;
+use crate::common::metrics::format_bytes;
+use crate::common::restart_policy::format_restart_policy;
 use crate::common::status::format_status;
+use crate::common::timestamp::format_timestamp_rfc3339;
+use crate::common::watchdog::format_watchdog_rule;
 use async_trait::async_trait;
 use mcp_rs::{Error as McpError, Result as McpResult, ToolHandler, ToolInfo};
 use serde::Deserialize;
@@ -70,7 +77,9 @@ impl ToolHandler for StatusTool {
                     .ok_or_else(|| McpError::Internal("No process info returned".to_string()))?;
 
                 // Calculate uptime if process is running
-                let uptime = if process.status == proto::ProcessStatus::Running as i32 {
+                let uptime = if process.status == proto::ProcessStatus::Running as i32
+                    || process.status == proto::ProcessStatus::Paused as i32
+                {
                     process.start_time.as_ref().map(|start_time| {
                         let start = chrono::DateTime::<chrono::Utc>::from_timestamp(
                             start_time.seconds,
@@ -108,6 +117,8 @@ impl ToolHandler for StatusTool {
                     follow: Some(false),
                     project: process.project.clone(),
                     include_events: Some(false),
+                    run: None,
+                    label_selector: vec![],
                 };
 
                 let mut logs_preview = Vec::new();
@@ -133,11 +144,12 @@ impl ToolHandler for StatusTool {
                     }
                 }
 
-                let response = json!({
+                let mut response = json!({
                     "id": process.id,
                     "project": process.project,
                     "name": process.name,
                     "status": format_status(process.status),
+                    "status_reason": process.status_reason,
                     "pid": process.pid,
                     "command": process.cmd,
                     "working_directory": process.cwd,
@@ -149,8 +161,61 @@ impl ToolHandler for StatusTool {
                     }),
                     "uptime": uptime,
                     "ports": process.ports,
+                    "allocated_ports": process
+                        .allocated_ports
+                        .iter()
+                        .map(|allocated| json!({ "name": allocated.name, "port": allocated.port, "env": allocated.env }))
+                        .collect::<Vec<_>>(),
+                    "listeners": process
+                        .listeners
+                        .iter()
+                        .map(|listener| json!({ "protocol": listener.protocol, "address": listener.address, "port": listener.port }))
+                        .collect::<Vec<_>>(),
+                    "restart_policy": format_restart_policy(process.restart_policy),
+                    "restart_count": process.restart_count,
+                    "last_restart_time": process.last_restart_time.map(|t| {
+                        let ts = chrono::DateTime::<chrono::Utc>::from_timestamp(t.seconds, t.nanos as u32)
+                            .unwrap_or_else(chrono::Utc::now);
+                        ts.to_rfc3339()
+                    }),
+                    "depends_on": process.depends_on,
+                    "health": format_health(process.health),
+                    "health_error": process.health_error,
+                    "stop_signal": process.stop_signal,
+                    "stop_command": process.stop_command,
+                    "stop_timeout": process.stop_timeout,
+                    "stdin": process.stdin,
+                    "limits": process.limits.as_ref().map(format_limits),
+                    "metrics": process.metrics.as_ref().map(|m| json!({
+                        "cpu_percent": m.cpu_percent,
+                        "memory": format_bytes(m.rss_bytes),
+                        "threads": m.threads,
+                        "open_fds": m.open_fds,
+                    })),
+                    "watchdog": process.watchdog.iter().map(format_watchdog_rule).collect::<Vec<_>>(),
+                    "watchdog_flags": process.watchdog_flags,
+                    "watch": process.watch.as_ref().map(format_watch),
+                    "schedule": process.schedule.as_ref().map(|schedule| json!({
+                        "expression": schedule,
+                        "next_run_time": process.next_run_time.as_ref().map(format_timestamp_rfc3339),
+                        "skipped_runs": process.skipped_runs,
+                        "recent_runs": process
+                            .scheduled_runs
+                            .iter()
+                            .map(|run| json!({
+                                "start_time": run.start_time.as_ref().map(format_timestamp_rfc3339),
+                                "exit_code": run.exit_code,
+                                "succeeded": run.succeeded,
+                                "duration_ms": run.duration_ms,
+                            }))
+                            .collect::<Vec<_>>(),
+                    })),
+                    "pty": process.pty_cols.zip(process.pty_rows).map(|(cols, rows)| json!({ "cols": cols, "rows": rows })),
                     "recent_logs": logs_preview,
                 });
+                // Added separately to stay within the json! macro's recursion limit
+                response["labels"] = json!(process.labels);
+                response["description"] = json!(process.description);
 
                 Ok(response)
             }
@@ -160,6 +225,7 @@ impl ToolHandler for StatusTool {
                     let list_request = proto::ListProcessesRequest {
                         status_filter: None,
                         project_filter: Some(project.clone()),
+                        label_selector: vec![],
                     };
 
                     let existing_processes = match client.inner().list_processes(list_request).await
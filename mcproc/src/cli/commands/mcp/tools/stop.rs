@@ -2,6 +2,9 @@
 
 use crate::cli::utils::resolve_mcp_project_name;
 use crate::client::DaemonClient;
+use crate::common::config::Config;
+use crate::common::process_pattern::is_pattern;
+use crate::common::status::{format_status, parse_status};
 use async_trait::async_trait;
 use mcp_rs::{Error as McpError, Result as McpResult, ToolHandler, ToolInfo};
 use serde::Deserialize;
@@ -20,8 +23,10 @@ impl StopTool {
 
 #[derive(Deserialize)]
 struct StopParams {
-    name: String,
+    name: Option<String>,
     project: Option<String>,
+    status: Option<String>,
+    labels: Option<String>,
 }
 
 #[async_trait]
@@ -29,14 +34,15 @@ impl ToolHandler for StopTool {
     fn tool_info(&self) -> ToolInfo {
         ToolInfo {
             name: "stop_process".to_string(),
-            description: "Gracefully stop a running process by name. This sends a SIGTERM signal to allow the process to clean up before exiting. Use this to stop servers, watchers, or any background process started with start_process.".to_string(),
+            description: "Gracefully stop a running process by name. This sends a SIGTERM signal to allow the process to clean up before exiting. Use this to stop servers, watchers, or any background process started with start_process. To stop several processes at once, give a name pattern such as 'worker-*' or 'myapp/*', a status, or a label selector; the result then lists each matching process.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
-                    "name": { "type": "string", "description": "Name of the process to stop (the same name used when starting it with start_process)" },
-                    "project": { "type": "string", "description": "Optional project name to scope the process lookup. Useful when multiple projects have processes with the same name." }
-                },
-                "required": ["name"]
+                    "name": { "type": "string", "description": "Name of the process to stop (the same name used when starting it with start_process). A pattern with * and ? wildcards stops every matching process, e.g. 'worker-*'; prefix it with a project pattern to reach other projects, e.g. 'myapp/*' or '*/worker-*'. Required unless status or labels is given" },
+                    "project": { "type": "string", "description": "Optional project name to scope the process lookup. Useful when multiple projects have processes with the same name." },
+                    "status": { "type": "string", "description": "Stop every process in this status, e.g. 'failed' or 'running'" },
+                    "labels": { "type": "string", "description": "Label selector such as 'role=frontend' to stop every matching process. Requirements are comma-separated: key=value, key!=value, key or !key" }
+                }
             }),
         }
     }
@@ -54,30 +60,104 @@ impl ToolHandler for StopTool {
 
         let project = resolve_mcp_project_name(params.project)?;
 
-        let grpc_request = proto::StopProcessRequest {
-            name: params.name,
-            force: None,
-            project,
-        };
+        let config = Config::load().map_err(|e| McpError::Internal(e.to_string()))?;
+        let mut client = self.client.clone();
 
-        let config =
-            crate::common::config::Config::load().map_err(|e| McpError::Internal(e.to_string()))?;
-        let mut request = Request::new(grpc_request);
-        request.set_timeout(crate::cli::utils::stop_deadline(
-            config.process.restart.process_stop_timeout_ms,
-        ));
+        let name = params.name.unwrap_or_default();
+        let status_filter = params
+            .status
+            .as_deref()
+            .map(parse_status)
+            .transpose()
+            .map_err(McpError::InvalidParams)?;
+        let label_selector = params.labels.into_iter().collect::<Vec<_>>();
 
-        let mut client = self.client.clone();
+        if !is_pattern(&name) && status_filter.is_none() && label_selector.is_empty() {
+            if name.is_empty() {
+                return Err(McpError::InvalidParams(
+                    "name is required unless status or labels is given".to_string(),
+                ));
+            }
+            return stop_process(&mut client, &config, name, &project).await;
+        }
+
+        // Several processes are stopped one after the other, each bounded by the daemon
         let response = client
             .inner()
-            .stop_process(request)
+            .stop_process(proto::StopProcessRequest {
+                name,
+                force: None,
+                project,
+                status_filter,
+                label_selector,
+            })
             .await
-            .map_err(|e| McpError::Internal(e.to_string()))?
+            .map_err(|e| match e.code() {
+                tonic::Code::InvalidArgument => McpError::InvalidParams(e.message().to_string()),
+                _ => McpError::Internal(e.message().to_string()),
+            })?
             .into_inner();
+        if response.results.is_empty() {
+            return Err(McpError::InvalidParams(
+                response
+                    .message
+                    .unwrap_or_else(|| "No processes match".to_string()),
+            ));
+        }
 
+        let processes = response
+            .results
+            .into_iter()
+            .map(|result| {
+                json!({
+                    "name": result.name,
+                    "project": result.project,
+                    "success": result.success,
+                    "message": result.message,
+                    "status": result.process.map(|process| format_status(process.status)),
+                })
+            })
+            .collect::<Vec<_>>();
         Ok(json!({
             "success": response.success,
             "message": response.message,
+            "processes": processes,
         }))
     }
 }
+
+async fn stop_process(
+    client: &mut DaemonClient,
+    config: &Config,
+    name: String,
+    project: &str,
+) -> McpResult<Value> {
+    let stop_timeout_ms = crate::cli::utils::process_stop_timeout_ms(
+        client,
+        &name,
+        project,
+        config.process.restart.process_stop_timeout_ms,
+    )
+    .await;
+
+    let grpc_request = proto::StopProcessRequest {
+        name,
+        force: None,
+        project: project.to_string(),
+        ..Default::default()
+    };
+    let mut request = Request::new(grpc_request);
+    request.set_timeout(crate::cli::utils::stop_deadline(stop_timeout_ms));
+
+    let response = client
+        .inner()
+        .stop_process(request)
+        .await
+        .map_err(|e| McpError::Internal(e.to_string()))?
+        .into_inner();
+
+    Ok(json!({
+        "success": response.success,
+        "message": response.message,
+    }))
+}
@@ -0,0 +1,123 @@
+//! Project up tool implementation
+
+use crate::cli::utils::load_mcp_manifest;
+use crate::client::DaemonClient;
+use crate::common::status::format_status;
+use async_trait::async_trait;
+use mcp_rs::{Error as McpError, Result as McpResult, ToolHandler, ToolInfo};
+use proto::manifest_process_result::Action;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use tonic::Request;
+
+pub struct UpTool {
+    client: DaemonClient,
+}
+
+impl UpTool {
+    pub fn new(client: DaemonClient) -> Self {
+        Self { client }
+    }
+}
+
+#[derive(Deserialize)]
+struct UpParams {
+    file: Option<PathBuf>,
+    project: Option<String>,
+    remove_orphans: Option<bool>,
+}
+
+fn format_action(action: i32) -> &'static str {
+    match Action::try_from(action).unwrap_or(Action::Unchanged) {
+        Action::Unchanged => "unchanged",
+        Action::Started => "started",
+        Action::Restarted => "restarted",
+        Action::Stopped => "stopped",
+        Action::Blocked => "blocked",
+    }
+}
+
+#[async_trait]
+impl ToolHandler for UpTool {
+    fn tool_info(&self) -> ToolInfo {
+        ToolInfo {
+            name: "start_project".to_string(),
+            description: "Start every process declared in the project's mcproc.toml manifest. Processes already running with the same definition are left untouched, processes whose definition changed are restarted, and missing ones are started. Use this instead of several start_process calls when the project has a manifest.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "file": { "type": "string", "description": "Path to the manifest. Defaults to the nearest mcproc.toml in the current directory or its parents." },
+                    "project": { "type": "string", "description": "Project name. Defaults to the manifest's 'project' key, then the manifest directory name." },
+                    "remove_orphans": { "type": "boolean", "description": "Also stop processes of the project that are not declared in the manifest (default: false)" }
+                }
+            }),
+        }
+    }
+
+    async fn handle(
+        &self,
+        params: Option<Value>,
+        _context: mcp_rs::ToolContext,
+    ) -> McpResult<Value> {
+        let params: UpParams = serde_json::from_value(params.unwrap_or_else(|| json!({})))
+            .map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+        let (manifest, project) = load_mcp_manifest(params.file.as_deref(), params.project)?;
+        let processes = manifest.start_requests(&project);
+
+        let config =
+            crate::common::config::Config::load().map_err(|e| McpError::Internal(e.to_string()))?;
+        let timeout = processes
+            .iter()
+            .map(|process| {
+                crate::cli::utils::restart_deadline(
+                    crate::cli::utils::stop_timeout_ms(
+                        process.stop_timeout,
+                        config.process.restart.process_stop_timeout_ms,
+                    ),
+                    process.wait_timeout,
+                    config.process.startup.default_wait_timeout_secs,
+                )
+            })
+            .fold(
+                crate::cli::utils::stop_deadline(config.process.restart.process_stop_timeout_ms),
+                |total, deadline| total.saturating_add(deadline),
+            );
+        let mut request = Request::new(proto::ApplyManifestRequest {
+            project: project.clone(),
+            processes,
+            remove_orphans: params.remove_orphans.unwrap_or(false),
+        });
+        request.set_timeout(timeout);
+
+        let mut client = self.client.clone();
+        let response = client
+            .inner()
+            .apply_manifest(request)
+            .await
+            .map_err(|e| McpError::Internal(e.to_string()))?
+            .into_inner();
+
+        let processes: Vec<Value> = response
+            .results
+            .into_iter()
+            .map(|result| {
+                json!({
+                    "name": result.name,
+                    "action": format_action(result.action),
+                    "status": result.process.as_ref().map(|p| format_status(p.status)),
+                    "pid": result.process.as_ref().and_then(|p| p.pid),
+                    "log_file": result.process.as_ref().map(|p| p.log_file.clone()),
+                    "error": result.error,
+                })
+            })
+            .collect();
+
+        Ok(json!({
+            "project": project,
+            "manifest": manifest.path.display().to_string(),
+            "processes": processes,
+        }))
+    }
+}
@@ -1,21 +1,39 @@
+pub mod attach;
 pub mod clean;
 pub mod daemon;
+pub mod down;
 pub mod grep;
+pub mod history;
 pub mod logs;
 pub mod mcp;
+pub mod port;
 pub mod ps;
 pub mod restart;
+pub mod run;
+pub mod send;
+pub mod signal;
 pub mod start;
 pub mod stop;
+pub mod top;
+pub mod up;
 pub mod version;
 
+pub use attach::AttachCommand;
 pub use clean::CleanCommand;
 pub use daemon::DaemonCommand;
+pub use down::DownCommand;
 pub use grep::GrepCommand;
+pub use history::HistoryCommand;
 pub use logs::LogsCommand;
 pub use mcp::McpCommand;
+pub use port::PortCommand;
 pub use ps::PsCommand;
 pub use restart::RestartCommand;
+pub use run::RunCommand;
+pub use send::SendCommand;
+pub use signal::SignalCommand;
 pub use start::StartCommand;
 pub use stop::StopCommand;
+pub use top::TopCommand;
+pub use up::UpCommand;
 pub use version::VersionCommand;
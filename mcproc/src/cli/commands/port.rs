@@ -0,0 +1,94 @@
+use crate::client::DaemonClient;
+use crate::common::status::format_status_colored;
+use clap::Args;
+use colored::*;
+use proto::LookupPortRequest;
+
+#[derive(Debug, Args)]
+pub struct PortCommand {
+    /// Port number
+    #[arg(value_parser = clap::value_parser!(u16).range(1..))]
+    port: u16,
+
+    /// Stop the managed processes holding the port
+    #[arg(long)]
+    kill_holder: bool,
+}
+
+impl PortCommand {
+    pub async fn execute(self, mut client: DaemonClient) -> Result<(), Box<dyn std::error::Error>> {
+        let request = LookupPortRequest {
+            port: u32::from(self.port),
+            kill_holder: Some(self.kill_holder),
+        };
+
+        let response = match client.inner().lookup_port(request).await {
+            Ok(response) => response.into_inner(),
+            Err(e) => {
+                println!(
+                    "{} Failed to look up port {}: {}",
+                    "✗".red(),
+                    self.port,
+                    e.message()
+                );
+                return Err(e.into());
+            }
+        };
+
+        if response.holders.is_empty() {
+            println!("{} Port {} is free", "✓".green(), self.port);
+        } else {
+            println!("Port {} is held by:", self.port.to_string().bright_white());
+        }
+        for holder in &response.holders {
+            let socket = holder
+                .listener
+                .as_ref()
+                .map(|listener| format!("{} {}", listener.protocol, listener.address))
+                .unwrap_or_default();
+            match (&holder.process, holder.pid) {
+                (Some(process), _) => println!(
+                    "  {}/{} (PID {}) {} {}",
+                    process.project,
+                    process.name.bright_white(),
+                    process.pid.unwrap_or_default(),
+                    format_status_colored(process.status),
+                    socket.dimmed()
+                ),
+                (None, Some(pid)) => println!(
+                    "  PID {} {} {} {}",
+                    pid,
+                    holder.command.as_deref().unwrap_or("").bright_white(),
+                    "(not managed by mcproc)".dimmed(),
+                    socket.dimmed()
+                ),
+                (None, None) => println!(
+                    "  {} {}",
+                    "A process mcproc cannot inspect".yellow(),
+                    socket.dimmed()
+                ),
+            }
+        }
+
+        if let Some(process) = &response.allocated_to {
+            println!(
+                "  Allocated to: {}/{}",
+                process.project,
+                process.name.bright_white()
+            );
+        }
+
+        for name in &response.stopped {
+            println!("{} Stopped {}", "✓".green(), name);
+        }
+        if self.kill_holder && response.stopped.is_empty() && !response.holders.is_empty() {
+            println!(
+                "{} No managed process holds port {}; nothing was stopped",
+                "!".yellow(),
+                self.port
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -1,5 +1,8 @@
 use crate::client::DaemonClient;
-use crate::common::status::format_status;
+use crate::common::health::format_health;
+use crate::common::labels::{format_labels, parse_selector_arg};
+use crate::common::status::{format_status, parse_status};
+use crate::common::timestamp::format_timestamp_short;
 use clap::Args;
 use proto::ListProcessesRequest;
 use tabled::{Table, Tabled};
@@ -9,6 +12,10 @@ pub struct PsCommand {
     /// Filter by status
     #[arg(short, long)]
     status: Option<String>,
+
+    /// Only show processes whose labels match, e.g. `role=frontend` (repeatable)
+    #[arg(short = 'l', long = "selector", value_name = "SELECTOR", value_parser = parse_selector_arg)]
+    selectors: Vec<String>,
 }
 
 #[derive(Tabled)]
@@ -25,11 +32,29 @@ struct ProcessRow {
     #[tabled(rename = "STATUS")]
     status: String,
 
+    #[tabled(rename = "HEALTH")]
+    health: String,
+
     #[tabled(rename = "PORTS")]
     ports: String,
 
+    #[tabled(rename = "RESTARTS")]
+    restarts: String,
+
+    #[tabled(rename = "NEXT RUN")]
+    next_run: String,
+
+    #[tabled(rename = "LAST RUN")]
+    last_run: String,
+
     #[tabled(rename = "COMMAND")]
     cmd: String,
+
+    #[tabled(rename = "LABELS")]
+    labels: String,
+
+    #[tabled(rename = "DESCRIPTION")]
+    description: String,
 }
 
 impl PsCommand {
@@ -37,6 +62,7 @@ impl PsCommand {
         let request = ListProcessesRequest {
             status_filter: self.status.as_deref().map(parse_status).transpose()?,
             project_filter: None,
+            label_selector: self.selectors,
         };
 
         let response = client.inner().list_processes(request).await?;
@@ -56,9 +82,39 @@ impl PsCommand {
                     .pid
                     .map(|pid| pid.to_string())
                     .unwrap_or_else(|| "-".to_string()),
-                status: format_status(p.status),
+                status: match &p.status_reason {
+                    Some(reason) => format!("{} ({})", format_status(p.status), reason),
+                    None => format_status(p.status),
+                },
+                health: format_health(p.health)
+                    .into_iter()
+                    .map(str::to_string)
+                    .chain(p.watchdog_flags)
+                    .reduce(|health, flag| format!("{health}, {flag}"))
+                    .unwrap_or_else(|| "-".to_string()),
                 ports: format_ports(&p.ports),
+                restarts: p.restart_count.to_string(),
+                next_run: p
+                    .next_run_time
+                    .as_ref()
+                    .map(|time| format_timestamp_short(Some(time)))
+                    .unwrap_or_else(|| "-".to_string()),
+                last_run: p
+                    .scheduled_runs
+                    .last()
+                    .map(format_last_run)
+                    .unwrap_or_else(|| "-".to_string()),
                 cmd: truncate(&p.cmd, 40),
+                labels: if p.labels.is_empty() {
+                    "-".to_string()
+                } else {
+                    format_labels(&p.labels)
+                },
+                description: p
+                    .description
+                    .as_deref()
+                    .map(|description| truncate(description, 40))
+                    .unwrap_or_else(|| "-".to_string()),
             })
             .collect();
 
@@ -69,19 +125,6 @@ impl PsCommand {
     }
 }
 
-fn parse_status(status: &str) -> Result<i32, String> {
-    let status = match status.to_ascii_lowercase().as_str() {
-        "unknown" => proto::ProcessStatus::Unknown,
-        "starting" => proto::ProcessStatus::Starting,
-        "running" => proto::ProcessStatus::Running,
-        "stopping" => proto::ProcessStatus::Stopping,
-        "stopped" => proto::ProcessStatus::Stopped,
-        "failed" => proto::ProcessStatus::Failed,
-        _ => return Err(format!("Invalid process status: {status}")),
-    };
-    Ok(status as i32)
-}
-
 fn truncate(s: &str, max_len: usize) -> String {
     if s.chars().count() <= max_len {
         s.to_string()
@@ -96,6 +139,19 @@ fn truncate(s: &str, max_len: usize) -> String {
     }
 }
 
+/// Start time and outcome of a scheduled run, e.g. "12:00:03 (exit 0)"
+fn format_last_run(run: &proto::ScheduledRun) -> String {
+    let outcome = match run.exit_code {
+        Some(code) => format!("exit {code}"),
+        None => "failed".to_string(),
+    };
+    format!(
+        "{} ({})",
+        format_timestamp_short(run.start_time.as_ref()),
+        outcome
+    )
+}
+
 fn format_ports(ports: &[u32]) -> String {
     if ports.is_empty() {
         "-".to_string()
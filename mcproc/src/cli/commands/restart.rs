@@ -1,5 +1,9 @@
 use crate::cli::utils::resolve_project_name;
 use crate::client::DaemonClient;
+use crate::common::config::Config;
+use crate::common::labels::parse_selector_arg;
+use crate::common::process_pattern::is_pattern;
+use crate::common::status::parse_status;
 use clap::Args;
 use colored::*;
 use proto::RestartProcessRequest;
@@ -7,107 +11,209 @@ use tonic::Request;
 
 #[derive(Debug, Args)]
 pub struct RestartCommand {
-    /// Process name or ID
-    name: String,
+    /// Process name or ID, or a pattern such as `worker-*` or `myapp/*` to restart several
+    #[arg(required_unless_present_any = ["selectors", "status"])]
+    name: Option<String>,
 
     /// Project name (optional, helps disambiguate)
     #[arg(short, long)]
     project: Option<String>,
+
+    /// Restart every process with this status, e.g. `failed`
+    #[arg(short, long, value_parser = parse_status)]
+    status: Option<i32>,
+
+    /// Restart every process whose labels match, e.g. `role=frontend` (repeatable)
+    #[arg(short = 'l', long = "selector", value_name = "SELECTOR", value_parser = parse_selector_arg)]
+    selectors: Vec<String>,
+
+    /// Processes restarted at once when several are selected (default from config)
+    #[arg(long = "parallel", value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    parallelism: Option<u32>,
 }
 
 impl RestartCommand {
     pub async fn execute(self, mut client: DaemonClient) -> Result<(), Box<dyn std::error::Error>> {
-        let grpc_request = RestartProcessRequest {
-            name: self.name.clone(),
-            project: resolve_project_name(self.project)?,
-            wait_for_log: None,
-            wait_timeout: None,
-        };
+        let project = resolve_project_name(self.project)?;
+        let name = self.name.unwrap_or_default();
 
-        println!("Restarting process '{}'...", self.name);
+        if is_pattern(&name) || self.status.is_some() || !self.selectors.is_empty() {
+            return restart_processes(
+                &mut client,
+                RestartProcessRequest {
+                    name,
+                    project,
+                    status_filter: self.status,
+                    label_selector: self.selectors,
+                    parallelism: self.parallelism,
+                    started_by: Some("cli".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await;
+        }
 
         // Load config to get timeout settings
-        let config = crate::common::config::Config::load()?;
-        // Set timeout based on config: process_stop_timeout + grpc_request_buffer
-        // Restart needs more time: stop + start
-        let timeout = crate::cli::utils::restart_deadline(
-            config.process.restart.process_stop_timeout_ms,
-            None,
-            config.process.startup.default_wait_timeout_secs,
-        );
-        let mut request = Request::new(grpc_request);
-        request.set_timeout(timeout);
-
-        match client.inner().restart_process(request).await {
-            Ok(response) => {
-                let mut stream = response.into_inner();
-                let mut process_info = None;
-
-                // Process streaming responses
-                while let Ok(Some(msg)) = stream.message().await {
-                    match msg.response {
-                        Some(proto::restart_process_response::Response::LogEntry(entry)) => {
-                            // Print log entries as they arrive
-                            println!("  {}", entry.content.dimmed());
-                        }
-                        Some(proto::restart_process_response::Response::Process(info)) => {
-                            process_info = Some(info);
-                        }
-                        None => {}
-                    }
-                }
+        let config = Config::load()?;
+        restart_process(&mut client, &config, &name, &project).await
+    }
+}
+
+/// Restart the processes selected by a request, reporting each as it finishes
+async fn restart_processes(
+    client: &mut DaemonClient,
+    request: RestartProcessRequest,
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("Restarting processes...");
+
+    // No deadline: how long it takes depends on how many processes are selected
+    let mut stream = client.inner().restart_process(request).await?.into_inner();
+    let mut restarted = 0;
+    let mut failed = Vec::new();
+
+    while let Some(msg) = stream.message().await? {
+        let Some(proto::restart_process_response::Response::Result(result)) = msg.response else {
+            continue;
+        };
+        let target = format!("{}/{}", result.project, result.name);
+        if result.success {
+            restarted += 1;
+            println!(
+                "{} Process '{}' restarted (PID: {})",
+                "✓".green(),
+                target.bright_white(),
+                result
+                    .process
+                    .and_then(|process| process.pid)
+                    .map(|pid| pid.to_string())
+                    .unwrap_or_else(|| "N/A".to_string())
+            );
+        } else {
+            println!(
+                "{} Process '{}': {}",
+                "✗".red(),
+                target,
+                result.message.as_deref().unwrap_or("Unknown error")
+            );
+            failed.push(target);
+        }
+    }
 
-                let process = match process_info {
-                    Some(p) => p,
-                    None => {
-                        println!("{} Failed to restart process", "✗".red());
-                        return Err(
-                            "No process info received - process may have failed to restart".into(),
-                        );
+    println!(
+        "Restarted {} of {} processes",
+        restarted,
+        restarted + failed.len()
+    );
+    if !failed.is_empty() {
+        return Err(format!("Failed to restart: {}", failed.join(", ")).into());
+    }
+    Ok(())
+}
+
+async fn restart_process(
+    client: &mut DaemonClient,
+    config: &Config,
+    name: &str,
+    project: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("Restarting process '{}'...", name);
+
+    // Set timeout based on the process's grace period + grpc_request_buffer
+    // Restart needs more time: stop + start
+    let stop_timeout_ms = crate::cli::utils::process_stop_timeout_ms(
+        client,
+        name,
+        project,
+        config.process.restart.process_stop_timeout_ms,
+    )
+    .await;
+    let timeout = crate::cli::utils::restart_deadline(
+        stop_timeout_ms,
+        None,
+        config.process.startup.default_wait_timeout_secs,
+    );
+    let grpc_request = RestartProcessRequest {
+        name: name.to_string(),
+        project: project.to_string(),
+        wait_for_log: None,
+        wait_timeout: None,
+        started_by: Some("cli".to_string()),
+        status_filter: None,
+        label_selector: vec![],
+        parallelism: None,
+    };
+    let mut request = Request::new(grpc_request);
+    request.set_timeout(timeout);
+
+    match client.inner().restart_process(request).await {
+        Ok(response) => {
+            let mut stream = response.into_inner();
+            let mut process_info = None;
+
+            // Process streaming responses
+            while let Ok(Some(msg)) = stream.message().await {
+                match msg.response {
+                    Some(proto::restart_process_response::Response::LogEntry(entry)) => {
+                        // Print log entries as they arrive
+                        println!("  {}", entry.content.dimmed());
                     }
-                };
-
-                // Check if process failed to restart
-                if process.status == proto::ProcessStatus::Failed as i32 {
-                    println!("{} Process '{}' failed to restart", "✗".red(), process.name);
-                    if let (Some(exit_code), Some(exit_reason)) =
-                        (process.exit_code, process.exit_reason)
-                    {
-                        println!("  Exit code: {}", exit_code);
-                        println!("  Reason: {}", exit_reason);
+                    Some(proto::restart_process_response::Response::Process(info)) => {
+                        process_info = Some(info);
                     }
-                    if let Some(stderr) = process.stderr_tail {
-                        if !stderr.is_empty() {
-                            println!("  Recent logs:");
-                            for line in stderr.lines() {
-                                println!("    {}", line.dimmed());
-                            }
+                    Some(proto::restart_process_response::Response::Result(_)) | None => {}
+                }
+            }
+
+            let process = match process_info {
+                Some(p) => p,
+                None => {
+                    println!("{} Failed to restart process", "✗".red());
+                    return Err(
+                        "No process info received - process may have failed to restart".into(),
+                    );
+                }
+            };
+
+            // Check if process failed to restart
+            if process.status == proto::ProcessStatus::Failed as i32 {
+                println!("{} Process '{}' failed to restart", "✗".red(), process.name);
+                if let (Some(exit_code), Some(exit_reason)) =
+                    (process.exit_code, process.exit_reason)
+                {
+                    println!("  Exit code: {}", exit_code);
+                    println!("  Reason: {}", exit_reason);
+                }
+                if let Some(stderr) = process.stderr_tail {
+                    if !stderr.is_empty() {
+                        println!("  Recent logs:");
+                        for line in stderr.lines() {
+                            println!("    {}", line.dimmed());
                         }
                     }
-                    return Err(format!(
-                        "Process failed with exit code: {}",
-                        process.exit_code.unwrap_or(-1)
-                    )
-                    .into());
                 }
-
-                println!("{} Process restarted successfully", "✓".green());
-                println!("  Name: {}", process.name.bright_white());
-                println!("  ID: {}", process.id);
-                println!(
-                    "  PID: {}",
-                    process
-                        .pid
-                        .map(|p| p.to_string())
-                        .unwrap_or_else(|| "N/A".to_string())
-                );
-
-                Ok(())
-            }
-            Err(e) => {
-                println!("{} Failed to restart process: {}", "✗".red(), e);
-                Err(e.into())
+                return Err(format!(
+                    "Process failed with exit code: {}",
+                    process.exit_code.unwrap_or(-1)
+                )
+                .into());
             }
+
+            println!("{} Process restarted successfully", "✓".green());
+            println!("  Name: {}", process.name.bright_white());
+            println!("  ID: {}", process.id);
+            println!(
+                "  PID: {}",
+                process
+                    .pid
+                    .map(|p| p.to_string())
+                    .unwrap_or_else(|| "N/A".to_string())
+            );
+
+            Ok(())
+        }
+        Err(e) => {
+            println!("{} Failed to restart process: {}", "✗".red(), e);
+            Err(e.into())
         }
     }
 }
@@ -0,0 +1,157 @@
+use crate::cli::utils::resolve_project_name;
+use crate::client::DaemonClient;
+use crate::common::status::format_status_colored;
+use crate::common::task::format_task_outcome;
+use crate::common::validation::validate_process_name;
+use clap::Args;
+use colored::*;
+use proto::{RunTaskRequest, StartProcessRequest, TaskOptions};
+
+/// Exit code of `mcproc run` when the task timed out, as with timeout(1)
+const TIMED_OUT_EXIT_CODE: i32 = 124;
+
+#[derive(Debug, Args)]
+pub struct RunCommand {
+    /// Task name (do not include project name - tasks are organized by project like processes)
+    name: String,
+
+    /// Command to execute (use with shell)
+    #[arg(short, long, conflicts_with = "args")]
+    cmd: Option<String>,
+
+    /// Command and arguments (direct execution)
+    #[arg(short, long, conflicts_with = "cmd", num_args = 1..)]
+    args: Option<Vec<String>>,
+
+    /// Working directory
+    #[arg(short = 'd', long)]
+    cwd: Option<String>,
+
+    /// Project name (defaults to directory name)
+    #[arg(short, long)]
+    project: Option<String>,
+
+    /// Environment variables (KEY=VALUE)
+    #[arg(short, long)]
+    env: Vec<String>,
+
+    /// Version management tool (mise, asdf, nvm, rbenv, pyenv, etc.)
+    #[arg(long)]
+    toolchain: Option<String>,
+
+    /// Processes in the same project that must be running and ready first
+    #[arg(long = "depends-on", value_name = "NAME")]
+    depends_on: Vec<String>,
+
+    /// Exit codes that count as success (default: 0)
+    #[arg(
+        long,
+        value_name = "CODES",
+        value_delimiter = ',',
+        allow_negative_numbers = true
+    )]
+    success_codes: Vec<i32>,
+
+    /// Stop the task and report a failure after this many seconds (default: 600)
+    #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u32).range(1..))]
+    timeout: Option<u32>,
+
+    /// Only print the last N lines of output once the task exits instead of streaming it
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(0..=1000))]
+    tail: Option<u32>,
+}
+
+impl RunCommand {
+    pub async fn execute(self, mut client: DaemonClient) -> Result<(), Box<dyn std::error::Error>> {
+        validate_process_name(&self.name)?;
+
+        if self.cmd.is_none() && self.args.is_none() {
+            return Err("Must provide either --cmd or --args".into());
+        }
+
+        let env = self
+            .env
+            .iter()
+            .filter_map(|env_str| env_str.split_once('='))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        let project = resolve_project_name(self.project)?;
+
+        let request = RunTaskRequest {
+            process: Some(StartProcessRequest {
+                name: self.name.clone(),
+                cmd: self.cmd,
+                args: self.args.unwrap_or_default(),
+                cwd: self.cwd,
+                project: project.clone(),
+                env,
+                toolchain: self.toolchain,
+                depends_on: self.depends_on,
+                started_by: Some("cli".to_string()),
+                ..Default::default()
+            }),
+            task: Some(TaskOptions {
+                success_exit_codes: self.success_codes,
+                timeout_secs: self.timeout,
+            }),
+            tail_lines: self.tail,
+        };
+
+        let mut stream = match client.inner().run_task(request).await {
+            Ok(response) => response.into_inner(),
+            Err(e) => {
+                println!("{} Failed to run task: {}", "✗".red(), e.message());
+                return Err(e.into());
+            }
+        };
+
+        let stream_output = self.tail.is_none();
+        let mut result = None;
+        while let Some(msg) = stream.message().await? {
+            match msg.response {
+                Some(proto::run_task_response::Response::LogEntry(entry)) if stream_output => {
+                    println!("{}", entry.content);
+                }
+                Some(proto::run_task_response::Response::LogEntry(_)) => {}
+                Some(proto::run_task_response::Response::Result(task_result)) => {
+                    result = Some(task_result);
+                }
+                None => {}
+            }
+        }
+        let Some(result) = result else {
+            return Err("Task stream ended without a result".into());
+        };
+
+        if !stream_output {
+            for line in &result.tail {
+                println!("{}", line);
+            }
+        }
+
+        let outcome = format_task_outcome(&result);
+        if result.succeeded {
+            println!("{} Task '{}' {}", "✓".green(), self.name, outcome);
+        } else {
+            println!("{} Task '{}' {}", "✗".red(), self.name, outcome);
+        }
+        if let Some(process) = &result.process {
+            println!("  Project: {}", project.bright_white());
+            println!("  Status: {}", format_status_colored(process.status));
+            if let Some(reason) = process.exit_reason.as_deref().filter(|_| !result.succeeded) {
+                println!("  Reason: {}", reason);
+            }
+            println!("  Log file: {}", process.log_file.dimmed());
+        }
+
+        // Exit with the task's own code so scripts can chain on the outcome
+        if !result.succeeded {
+            std::process::exit(if result.timed_out {
+                TIMED_OUT_EXIT_CODE
+            } else {
+                result.exit_code.filter(|code| *code != 0).unwrap_or(1)
+            });
+        }
+        Ok(())
+    }
+}
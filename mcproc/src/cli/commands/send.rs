@@ -0,0 +1,59 @@
+use crate::cli::utils::resolve_project_name;
+use crate::client::DaemonClient;
+use clap::Args;
+use colored::*;
+use proto::SendInputRequest;
+
+#[derive(Debug, Args)]
+pub struct SendCommand {
+    /// Process name or ID
+    name: String,
+
+    /// Text to write to the process's stdin
+    text: String,
+
+    /// Project name (optional, helps disambiguate)
+    #[arg(short, long)]
+    project: Option<String>,
+
+    /// Do not append a newline to the text
+    #[arg(short, long)]
+    no_newline: bool,
+}
+
+impl SendCommand {
+    pub async fn execute(self, mut client: DaemonClient) -> Result<(), Box<dyn std::error::Error>> {
+        let mut input = self.text.into_bytes();
+        if !self.no_newline {
+            input.push(b'\n');
+        }
+        let request = SendInputRequest {
+            name: self.name.clone(),
+            project: resolve_project_name(self.project)?,
+            input,
+        };
+
+        match client.inner().send_input(request).await {
+            Ok(_) => {
+                println!("{} Sent input to '{}'", "✓".green(), self.name);
+            }
+            Err(e) => {
+                println!(
+                    "{} Failed to send input to '{}': {}",
+                    "✗".red(),
+                    self.name,
+                    e.message()
+                );
+                if e.code() == tonic::Code::FailedPrecondition {
+                    println!(
+                        "  Use 'mcproc start {} --stdin ...' or '--pty' to accept input",
+                        self.name
+                    );
+                }
+                return Err(e.into());
+            }
+        }
+
+        Ok(())
+    }
+}
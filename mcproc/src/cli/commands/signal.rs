@@ -0,0 +1,68 @@
+use crate::cli::utils::resolve_project_name;
+use crate::client::DaemonClient;
+use crate::common::signal::normalize_signal;
+use crate::common::status::format_status_colored;
+use clap::Args;
+use colored::*;
+use proto::SignalProcessRequest;
+
+#[derive(Debug, Args)]
+pub struct SignalCommand {
+    /// Process name or ID
+    name: String,
+
+    /// Signal name or number (e.g. SIGHUP, USR1, STOP, CONT, 15)
+    #[arg(value_parser = normalize_signal)]
+    signal: String,
+
+    /// Project name (optional, helps disambiguate)
+    #[arg(short, long)]
+    project: Option<String>,
+
+    /// Signal only the process leader instead of its whole process group
+    #[arg(long)]
+    leader: bool,
+}
+
+impl SignalCommand {
+    pub async fn execute(self, mut client: DaemonClient) -> Result<(), Box<dyn std::error::Error>> {
+        let request = SignalProcessRequest {
+            name: self.name.clone(),
+            project: resolve_project_name(self.project)?,
+            signal: self.signal.clone(),
+            leader_only: Some(self.leader),
+        };
+
+        match client.inner().signal_process(request).await {
+            Ok(response) => {
+                let target = if self.leader {
+                    "process leader"
+                } else {
+                    "process group"
+                };
+                println!(
+                    "{} Sent {} to {} of '{}'",
+                    "✓".green(),
+                    self.signal,
+                    target,
+                    self.name
+                );
+                if let Some(process) = response.into_inner().process {
+                    println!("  Status: {}", format_status_colored(process.status));
+                }
+            }
+            Err(e) => {
+                println!(
+                    "{} Failed to send {} to '{}': {}",
+                    "✗".red(),
+                    self.signal,
+                    self.name,
+                    e.message()
+                );
+                return Err(e.into());
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,17 @@
 use crate::cli::utils::resolve_project_name;
 use crate::client::DaemonClient;
+use crate::common::file_watch::{format_watch, WatchSpec};
+use crate::common::health::HealthCheckSpec;
+use crate::common::labels::{format_labels, parse_label};
+use crate::common::limits::{format_limits, LimitsSpec};
+use crate::common::readiness::{http_probe, parse_tcp_probe, port_detected_probe};
+use crate::common::restart_policy::{format_restart_policy, parse_restart_policy};
+use crate::common::schedule::parse_schedule;
+use crate::common::signal::normalize_signal;
 use crate::common::status::format_status_colored;
+use crate::common::timestamp::format_timestamp_local;
 use crate::common::validation::validate_process_name;
+use crate::common::watchdog::{format_watchdog_rule, parse_watchdog_rule};
 use clap::Args;
 use colored::*;
 use proto::StartProcessRequest;
@@ -43,6 +53,137 @@ pub struct StartCommand {
     /// Version management tool (mise, asdf, nvm, rbenv, pyenv, etc.)
     #[arg(long)]
     toolchain: Option<String>,
+
+    /// Restart automatically when the process exits (never, on-failure, always)
+    #[arg(long, value_parser = parse_restart_policy)]
+    restart: Option<proto::RestartPolicy>,
+
+    /// Processes in the same project that must be running and ready first
+    #[arg(long = "depends-on", value_name = "NAME")]
+    depends_on: Vec<String>,
+
+    /// Consider the process ready once a TCP connection succeeds ([HOST:]PORT)
+    #[arg(long, value_name = "[HOST:]PORT", value_parser = parse_tcp_probe)]
+    ready_tcp: Vec<proto::ReadinessProbe>,
+
+    /// Consider the process ready once an HTTP GET to this URL succeeds
+    #[arg(long, value_name = "URL")]
+    ready_http: Vec<String>,
+
+    /// Accepted HTTP status range for --ready-http (default: 200-399)
+    #[arg(long, value_name = "RANGE", requires = "ready_http")]
+    ready_http_status: Option<String>,
+
+    /// Consider the process ready once it listens on any TCP port
+    #[arg(long)]
+    ready_port: bool,
+
+    /// Check liveness periodically with a TCP connection ([HOST:]PORT)
+    #[arg(long, value_name = "[HOST:]PORT", conflicts_with_all = ["health_http", "health_cmd"])]
+    health_tcp: Option<String>,
+
+    /// Check liveness periodically with an HTTP GET to this URL
+    #[arg(long, value_name = "URL", conflicts_with = "health_cmd")]
+    health_http: Option<String>,
+
+    /// Accepted HTTP status range for --health-http (default: 200-399)
+    #[arg(long, value_name = "RANGE", requires = "health_http")]
+    health_http_status: Option<String>,
+
+    /// Check liveness periodically by running a shell command (healthy on exit code 0)
+    #[arg(long, value_name = "CMD")]
+    health_cmd: Option<String>,
+
+    /// Seconds between health checks (default: 10)
+    #[arg(long, value_name = "SECS")]
+    health_interval: Option<u32>,
+
+    /// Seconds before a health check counts as failed (default: 5)
+    #[arg(long, value_name = "SECS")]
+    health_timeout: Option<u32>,
+
+    /// Restart the process after this many consecutive failed health checks
+    #[arg(long, value_name = "N")]
+    health_restart_after: Option<u32>,
+
+    /// Signal sent to stop the process (default: SIGTERM)
+    #[arg(long, value_name = "SIGNAL", value_parser = normalize_signal)]
+    stop_signal: Option<String>,
+
+    /// Shell command run to stop the process; the stop signal is sent only if it fails
+    #[arg(long, value_name = "CMD")]
+    stop_command: Option<String>,
+
+    /// Seconds to wait for the process to exit before killing it
+    #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u32).range(1..))]
+    stop_timeout: Option<u32>,
+
+    /// Keep stdin open so input can be sent with `mcproc send`
+    #[arg(long)]
+    stdin: bool,
+
+    /// Run the process on a pseudo-terminal, as if started from a shell
+    #[arg(long)]
+    pty: bool,
+
+    /// Terminal width for --pty (default: 80)
+    #[arg(long, value_name = "COLS", requires = "pty", value_parser = clap::value_parser!(u32).range(1..=65535))]
+    pty_cols: Option<u32>,
+
+    /// Terminal height for --pty (default: 24)
+    #[arg(long, value_name = "ROWS", requires = "pty", value_parser = clap::value_parser!(u32).range(1..=65535))]
+    pty_rows: Option<u32>,
+
+    /// Allocate free ports by name (e.g. http,hmr); the first is exported as PORT,
+    /// each as MCPROC_PORT_<NAME>
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    ports: Vec<String>,
+
+    /// Memory ceiling of the process and its children, e.g. 2G or 512M
+    #[arg(long, value_name = "SIZE")]
+    memory_limit: Option<String>,
+
+    /// CPU quota in cores, e.g. 1.5
+    #[arg(long, value_name = "CORES")]
+    cpu_limit: Option<f64>,
+
+    /// Maximum number of processes and threads
+    #[arg(long, value_name = "N")]
+    pids_limit: Option<u32>,
+
+    /// Maximum number of open files per process
+    #[arg(long, value_name = "N")]
+    open_files_limit: Option<u64>,
+
+    /// Watchdog rule, e.g. "restart if rss > 3G for 60s" or
+    /// "flag stalled if no-output and cpu < 1 for 15m" (repeatable)
+    #[arg(long, value_name = "RULE", value_parser = parse_watchdog_rule)]
+    watchdog: Vec<proto::WatchdogRule>,
+
+    /// Restart when files under this path change, relative to the working directory (repeatable)
+    #[arg(long, value_name = "PATH")]
+    watch: Vec<String>,
+
+    /// Skip changes matching this gitignore-style pattern, in addition to .gitignore (repeatable)
+    #[arg(long, value_name = "PATTERN", requires = "watch")]
+    watch_ignore: Vec<String>,
+
+    /// Milliseconds without changes before restarting, coalescing bursts (default: 500)
+    #[arg(long, value_name = "MS", requires = "watch")]
+    watch_debounce: Option<u32>,
+
+    /// Run the command on a schedule instead of now: a cron expression such as
+    /// "*/15 * * * *" or an interval such as "every 10m"
+    #[arg(long, value_name = "EXPR", value_parser = parse_schedule)]
+    schedule: Option<String>,
+
+    /// Attach a label, e.g. role=frontend, to select the process by later (repeatable)
+    #[arg(long = "label", value_name = "KEY=VALUE", value_parser = parse_label)]
+    labels: Vec<(String, String)>,
+
+    /// Free-form note shown in `mcproc ps`, e.g. why the process was started
+    #[arg(long)]
+    description: Option<String>,
 }
 
 impl StartCommand {
@@ -63,6 +204,63 @@ impl StartCommand {
             return Err("Must provide either --cmd or --args".into());
         }
 
+        let mut readiness_probes = self.ready_tcp;
+        for url in &self.ready_http {
+            readiness_probes.push(http_probe(url, self.ready_http_status.as_deref())?);
+        }
+        if self.ready_port {
+            readiness_probes.push(port_detected_probe());
+        }
+        let wait_for_readiness = self.wait_for_log.is_some() || !readiness_probes.is_empty();
+
+        let health_check = if self.health_tcp.is_some()
+            || self.health_http.is_some()
+            || self.health_cmd.is_some()
+        {
+            Some(
+                HealthCheckSpec {
+                    tcp: self.health_tcp,
+                    http: self.health_http,
+                    http_status: self.health_http_status,
+                    cmd: self.health_cmd,
+                    interval: self.health_interval,
+                    timeout: self.health_timeout,
+                    restart_after: self.health_restart_after,
+                }
+                .into_proto()?,
+            )
+        } else if self.health_interval.is_some()
+            || self.health_timeout.is_some()
+            || self.health_restart_after.is_some()
+        {
+            return Err(
+                "Health check options require --health-tcp, --health-http or --health-cmd".into(),
+            );
+        } else {
+            None
+        };
+
+        let limits = LimitsSpec {
+            memory: self.memory_limit,
+            cpus: self.cpu_limit,
+            pids: self.pids_limit,
+            open_files: self.open_files_limit,
+        }
+        .into_proto()?;
+
+        let watch = if self.watch.is_empty() {
+            None
+        } else {
+            Some(
+                WatchSpec {
+                    paths: self.watch,
+                    ignore: self.watch_ignore,
+                    debounce_ms: self.watch_debounce,
+                }
+                .into_proto()?,
+            )
+        };
+
         // Determine project name if not provided (use current working directory where mcproc is run)
         let project = resolve_project_name(self.project)?;
 
@@ -77,6 +275,25 @@ impl StartCommand {
             wait_timeout: Some(self.wait_timeout),
             force_restart: None,
             toolchain: self.toolchain,
+            restart_policy: self.restart.map(|policy| policy as i32),
+            depends_on: self.depends_on,
+            readiness_probes,
+            health_check,
+            stop_signal: self.stop_signal,
+            stop_command: self.stop_command,
+            stop_timeout: self.stop_timeout,
+            stdin: Some(self.stdin),
+            pty: Some(self.pty),
+            pty_cols: self.pty_cols,
+            pty_rows: self.pty_rows,
+            ports: self.ports,
+            limits,
+            watchdog: self.watchdog,
+            watch,
+            schedule: self.schedule,
+            started_by: Some("cli".to_string()),
+            labels: self.labels.into_iter().collect(),
+            description: self.description,
         };
 
         // Set timeout to wait_timeout + 5 seconds to allow for process startup and pattern matching
@@ -92,12 +309,13 @@ impl StartCommand {
                 // Process streaming responses
                 while let Ok(Some(msg)) = stream.message().await {
                     match msg.response {
-                        Some(proto::start_process_response::Response::LogEntry(entry)) => {
+                        Some(proto::start_process_response::Response::LogEntry(entry))
+                            if self.wait_for_log.is_some() =>
+                        {
                             // Print log entries as they arrive if wait_for_log is enabled
-                            if self.wait_for_log.is_some() {
-                                println!("  {}", entry.content.dimmed());
-                            }
+                            println!("  {}", entry.content.dimmed());
                         }
+                        Some(proto::start_process_response::Response::LogEntry(_)) => {}
                         Some(proto::start_process_response::Response::Process(info)) => {
                             process_info = Some(info);
                         }
@@ -139,7 +357,11 @@ impl StartCommand {
                     .into());
                 }
 
-                println!("{} Process started successfully", "✓".green());
+                if process.schedule.is_some() {
+                    println!("{} Process scheduled", "✓".green());
+                } else {
+                    println!("{} Process started successfully", "✓".green());
+                }
                 println!("  Project: {}", project.bright_white());
                 println!("  Name: {}", process.name.bright_white());
                 println!("  ID: {}", process.id);
@@ -152,21 +374,73 @@ impl StartCommand {
                 );
                 println!("  Status: {}", format_status_colored(process.status));
                 println!("  Log file: {}", process.log_file.dimmed());
+                for (index, allocated) in process.allocated_ports.iter().enumerate() {
+                    let env = if index == 0 {
+                        format!("PORT, {}", allocated.env)
+                    } else {
+                        allocated.env.clone()
+                    };
+                    println!(
+                        "  Port {}: {} {}",
+                        allocated.name,
+                        allocated.port.to_string().bright_white(),
+                        format!("({})", env).dimmed()
+                    );
+                }
+                if let Some(limits) = &process.limits {
+                    println!("  Limits: {}", format_limits(limits));
+                }
+                for rule in &process.watchdog {
+                    println!("  Watchdog: {}", format_watchdog_rule(rule));
+                }
+                if let Some(watch) = &process.watch {
+                    println!("  Watching: {}", format_watch(watch));
+                }
+                if !process.labels.is_empty() {
+                    println!("  Labels: {}", format_labels(&process.labels));
+                }
+                if let Some(description) = &process.description {
+                    println!("  Description: {}", description);
+                }
+                if let Some(schedule) = &process.schedule {
+                    println!("  Schedule: {}", schedule);
+                    if process.next_run_time.is_some() {
+                        println!(
+                            "  Next run: {}",
+                            format_timestamp_local(process.next_run_time.as_ref())
+                        );
+                    }
+                }
+                if process.restart_policy != proto::RestartPolicy::Never as i32 {
+                    println!(
+                        "  Restart policy: {}",
+                        format_restart_policy(process.restart_policy)
+                    );
+                }
 
-                if self.wait_for_log.is_some() {
+                if wait_for_readiness {
                     if let Some(matched_line) = &process.matched_line {
                         println!("  {} Process is ready (log pattern matched)", "✓".green());
                         println!("    Matched line: {}", matched_line.dimmed());
+                    } else if let Some(ready_by) = &process.ready_by {
+                        println!("  {} Process is ready ({})", "✓".green(), ready_by);
                     } else if process.wait_timeout_occurred.unwrap_or(false) {
-                        println!(
-                            "  {} Process started but pattern not found (timeout)",
-                            "⚠".yellow()
-                        );
-                        println!(
-                            "    Waited {} seconds for pattern: {}",
-                            self.wait_timeout,
-                            self.wait_for_log.as_deref().unwrap_or("N/A")
-                        );
+                        if let Some(pattern) = &self.wait_for_log {
+                            println!(
+                                "  {} Process started but pattern not found (timeout)",
+                                "⚠".yellow()
+                            );
+                            println!(
+                                "    Waited {} seconds for pattern: {}",
+                                self.wait_timeout, pattern
+                            );
+                        } else {
+                            println!(
+                                "  {} Process started but no readiness probe succeeded (timeout)",
+                                "⚠".yellow()
+                            );
+                            println!("    Waited {} seconds", self.wait_timeout);
+                        }
                     } else {
                         println!(
                             "  {} Process started (pattern matching in progress)",
@@ -1,5 +1,8 @@
 use crate::cli::utils::resolve_project_name;
 use crate::client::DaemonClient;
+use crate::common::labels::parse_selector_arg;
+use crate::common::process_pattern::is_pattern;
+use crate::common::status::parse_status;
 use clap::Args;
 use colored::*;
 use proto::StopProcessRequest;
@@ -7,8 +10,9 @@ use tonic::Request;
 
 #[derive(Debug, Args)]
 pub struct StopCommand {
-    /// Process name or ID
-    name: String,
+    /// Process name or ID, or a pattern such as `worker-*` or `myapp/*` to stop several
+    #[arg(required_unless_present_any = ["selectors", "status"])]
+    name: Option<String>,
 
     /// Project name (optional, helps disambiguate)
     #[arg(short, long)]
@@ -17,38 +21,74 @@ pub struct StopCommand {
     /// Force stop (SIGKILL)
     #[arg(short, long)]
     force: bool,
+
+    /// Stop every process with this status, e.g. `failed`
+    #[arg(short, long, value_parser = parse_status)]
+    status: Option<i32>,
+
+    /// Stop every process whose labels match, e.g. `role=frontend` (repeatable)
+    #[arg(short = 'l', long = "selector", value_name = "SELECTOR", value_parser = parse_selector_arg)]
+    selectors: Vec<String>,
 }
 
 impl StopCommand {
     pub async fn execute(self, mut client: DaemonClient) -> Result<(), Box<dyn std::error::Error>> {
+        let project = resolve_project_name(self.project)?;
+        let name = self.name.unwrap_or_default();
+        let bulk = is_pattern(&name) || self.status.is_some() || !self.selectors.is_empty();
+
         let grpc_request = StopProcessRequest {
-            name: self.name.clone(),
+            name: name.clone(),
             force: Some(self.force),
-            project: resolve_project_name(self.project)?,
+            project: project.clone(),
+            status_filter: self.status,
+            label_selector: self.selectors,
         };
-
-        // Load config to get timeout settings
-        let config = crate::common::config::Config::load()?;
-        // Set timeout based on config: process_stop_timeout + grpc_request_buffer
-        let timeout =
-            crate::cli::utils::stop_deadline(config.process.restart.process_stop_timeout_ms);
         let mut request = Request::new(grpc_request);
-        request.set_timeout(timeout);
+        // Several processes are stopped one after the other, each bounded by the daemon
+        if !bulk {
+            // Set timeout based on the process's grace period + grpc_request_buffer
+            let config = crate::common::config::Config::load()?;
+            let stop_timeout_ms = crate::cli::utils::process_stop_timeout_ms(
+                &mut client,
+                &name,
+                &project,
+                config.process.restart.process_stop_timeout_ms,
+            )
+            .await;
+            request.set_timeout(crate::cli::utils::stop_deadline(stop_timeout_ms));
+        }
 
         let response = client.inner().stop_process(request).await?;
         let result = response.into_inner();
 
-        if result.success {
-            println!(
-                "{} Process '{}' stopped successfully",
-                "✓".green(),
-                self.name
-            );
+        if bulk {
+            for process in &result.results {
+                let target = format!("{}/{}", process.project, process.name);
+                if process.success {
+                    println!("{} Process '{}' stopped", "✓".green(), target);
+                } else {
+                    println!(
+                        "{} Process '{}': {}",
+                        "✗".red(),
+                        target,
+                        process.message.as_deref().unwrap_or("Unknown error")
+                    );
+                }
+            }
+            let message = result.message.unwrap_or_default();
+            if result.success {
+                println!("{}", message);
+            } else {
+                println!("{} {}", "✗".red(), message);
+            }
+        } else if result.success {
+            println!("{} Process '{}' stopped successfully", "✓".green(), name);
         } else {
             println!(
                 "{} Failed to stop process '{}': {}",
                 "✗".red(),
-                self.name,
+                name,
                 result
                     .message
                     .unwrap_or_else(|| "Unknown error".to_string())
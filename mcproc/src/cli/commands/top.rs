@@ -0,0 +1,165 @@
+use crate::client::DaemonClient;
+use crate::common::metrics::{format_bytes, io_rates, sparkline};
+use clap::{Args, ValueEnum};
+use proto::{ListProcessesRequest, ProcessInfo, ProcessStatus};
+use std::time::Duration;
+use tabled::{Table, Tabled};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SortKey {
+    Cpu,
+    Memory,
+    Name,
+}
+
+#[derive(Debug, Args)]
+pub struct TopCommand {
+    /// Only show processes of this project
+    #[arg(short, long)]
+    project: Option<String>,
+
+    /// Print the table once instead of refreshing it
+    #[arg(long)]
+    once: bool,
+
+    /// Seconds between refreshes
+    #[arg(short = 'n', long, default_value = "2")]
+    interval: u64,
+
+    /// Column to sort by
+    #[arg(short, long, value_enum, default_value = "cpu")]
+    sort: SortKey,
+}
+
+#[derive(Tabled)]
+struct MetricsRow {
+    #[tabled(rename = "PROJECT")]
+    project: String,
+
+    #[tabled(rename = "NAME")]
+    name: String,
+
+    #[tabled(rename = "PID")]
+    pid: String,
+
+    #[tabled(rename = "CPU%")]
+    cpu: String,
+
+    #[tabled(rename = "MEM")]
+    memory: String,
+
+    #[tabled(rename = "THREADS")]
+    threads: String,
+
+    #[tabled(rename = "FDS")]
+    fds: String,
+
+    #[tabled(rename = "READ/s")]
+    read: String,
+
+    #[tabled(rename = "WRITE/s")]
+    write: String,
+
+    #[tabled(rename = "CPU HISTORY")]
+    cpu_history: String,
+
+    #[tabled(rename = "MEM HISTORY")]
+    memory_history: String,
+}
+
+/// Number of recent samples drawn in the history columns
+const HISTORY_WIDTH: usize = 20;
+
+impl TopCommand {
+    pub async fn execute(self, mut client: DaemonClient) -> Result<(), Box<dyn std::error::Error>> {
+        let interval = Duration::from_secs(self.interval.max(1));
+        loop {
+            let request = ListProcessesRequest {
+                status_filter: None,
+                project_filter: self.project.clone(),
+                label_selector: vec![],
+            };
+            let processes = client.inner().list_processes(request).await?.into_inner();
+            let table = self.render(processes.processes);
+
+            if self.once {
+                println!("{}", table);
+                return Ok(());
+            }
+            // Clear the screen and move the cursor to the top left
+            print!("\x1b[2J\x1b[H");
+            println!("{}", table);
+
+            tokio::select! {
+                _ = tokio::time::sleep(interval) => {}
+                _ = tokio::signal::ctrl_c() => return Ok(()),
+            }
+        }
+    }
+
+    fn render(&self, mut processes: Vec<ProcessInfo>) -> String {
+        processes.retain(|p| {
+            p.status == ProcessStatus::Running as i32 || p.status == ProcessStatus::Paused as i32
+        });
+        if processes.is_empty() {
+            return "No processes running".to_string();
+        }
+
+        let cpu = |p: &ProcessInfo| p.metrics.and_then(|m| m.cpu_percent).unwrap_or(0.0);
+        let memory = |p: &ProcessInfo| p.metrics.map_or(0, |m| m.rss_bytes);
+        match self.sort {
+            SortKey::Cpu => processes.sort_by(|a, b| cpu(b).total_cmp(&cpu(a))),
+            SortKey::Memory => processes.sort_by_key(|p| std::cmp::Reverse(memory(p))),
+            SortKey::Name => {
+                processes.sort_by(|a, b| (&a.project, &a.name).cmp(&(&b.project, &b.name)))
+            }
+        }
+
+        let rows: Vec<MetricsRow> = processes.into_iter().map(metrics_row).collect();
+        Table::new(rows).to_string()
+    }
+}
+
+fn metrics_row(p: ProcessInfo) -> MetricsRow {
+    let dash = || "-".to_string();
+    let pid = p.pid.map_or_else(dash, |pid| pid.to_string());
+    let Some(metrics) = p.metrics else {
+        return MetricsRow {
+            project: p.project,
+            name: p.name,
+            pid,
+            cpu: dash(),
+            memory: dash(),
+            threads: dash(),
+            fds: dash(),
+            read: dash(),
+            write: dash(),
+            cpu_history: dash(),
+            memory_history: dash(),
+        };
+    };
+
+    let rates = io_rates(&p.metrics_history);
+    let recent = &p.metrics_history[p.metrics_history.len().saturating_sub(HISTORY_WIDTH)..];
+    let cpu_history: Vec<f64> = recent
+        .iter()
+        .map(|m| m.cpu_percent.unwrap_or(0.0))
+        .collect();
+    let memory_history: Vec<f64> = recent.iter().map(|m| m.rss_bytes as f64).collect();
+
+    MetricsRow {
+        project: p.project,
+        name: p.name,
+        pid,
+        cpu: metrics
+            .cpu_percent
+            .map_or_else(dash, |cpu| format!("{cpu:.1}")),
+        memory: format_bytes(metrics.rss_bytes),
+        threads: metrics.threads.to_string(),
+        fds: metrics.open_fds.to_string(),
+        read: rates.map_or_else(dash, |(read, _)| format_bytes(read as u64)),
+        write: rates.map_or_else(dash, |(_, write)| format_bytes(write as u64)),
+        cpu_history: sparkline(&cpu_history),
+        memory_history: sparkline(&memory_history),
+    }
+}
@@ -0,0 +1,101 @@
+use crate::cli::manifest::load_manifest;
+use crate::client::DaemonClient;
+use crate::common::status::format_status_colored;
+use clap::Args;
+use colored::*;
+use proto::manifest_process_result::Action;
+use proto::ApplyManifestRequest;
+use std::path::PathBuf;
+use tonic::Request;
+
+/// Start the processes declared in mcproc.toml
+///
+/// Processes that are already running with the same definition are left alone,
+/// processes whose definition changed are restarted and missing ones are started.
+#[derive(Debug, Args)]
+pub struct UpCommand {
+    /// Path to the manifest (defaults to the nearest mcproc.toml)
+    #[arg(short, long)]
+    file: Option<PathBuf>,
+
+    /// Project name (defaults to the manifest's project or directory name)
+    #[arg(short, long)]
+    project: Option<String>,
+
+    /// Stop processes of the project that are not declared in the manifest
+    #[arg(long)]
+    remove_orphans: bool,
+}
+
+impl UpCommand {
+    pub async fn execute(self, mut client: DaemonClient) -> Result<(), Box<dyn std::error::Error>> {
+        let manifest = load_manifest(self.file.as_deref())?;
+        let project = manifest.project_name(self.project)?;
+        let processes = manifest.start_requests(&project);
+
+        // Each process may be stopped, restarted and waited for in turn,
+        // after orphans have been stopped
+        let config = crate::common::config::Config::load()?;
+        let timeout = processes
+            .iter()
+            .map(|process| {
+                crate::cli::utils::restart_deadline(
+                    crate::cli::utils::stop_timeout_ms(
+                        process.stop_timeout,
+                        config.process.restart.process_stop_timeout_ms,
+                    ),
+                    process.wait_timeout,
+                    config.process.startup.default_wait_timeout_secs,
+                )
+            })
+            .fold(
+                crate::cli::utils::stop_deadline(config.process.restart.process_stop_timeout_ms),
+                |total, deadline| total.saturating_add(deadline),
+            );
+        let mut request = Request::new(ApplyManifestRequest {
+            project: project.clone(),
+            processes,
+            remove_orphans: self.remove_orphans,
+        });
+        request.set_timeout(timeout);
+
+        let response = client.inner().apply_manifest(request).await?.into_inner();
+
+        println!(
+            "{} {} ({})",
+            "Project".bold(),
+            project.bright_white(),
+            manifest.path.display().to_string().dimmed()
+        );
+        let mut failed = 0;
+        for result in &response.results {
+            if let Some(error) = &result.error {
+                failed += 1;
+                println!("  {} {}: {}", "✗".red(), result.name, error.red());
+                continue;
+            }
+            let action = match Action::try_from(result.action).unwrap_or(Action::Unchanged) {
+                Action::Unchanged => "unchanged".dimmed(),
+                Action::Started => "started".green(),
+                Action::Restarted => "restarted".yellow(),
+                Action::Stopped => "stopped".red(),
+                Action::Blocked => "blocked".red(),
+            };
+            match &result.process {
+                Some(process) => println!(
+                    "  {} {} {} ({})",
+                    "•".cyan(),
+                    result.name.bright_white(),
+                    action,
+                    format_status_colored(process.status)
+                ),
+                None => println!("  {} {} {}", "•".cyan(), result.name.bright_white(), action),
+            }
+        }
+
+        if failed > 0 {
+            return Err(format!("{failed} process(es) failed").into());
+        }
+        Ok(())
+    }
+}
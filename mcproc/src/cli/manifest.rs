@@ -0,0 +1,579 @@
+//! Project manifest (`mcproc.toml`) loading
+//!
+//! A manifest declares the processes of a project:
+//!
+//! ```toml
+//! project = "myapp"  # optional, defaults to the manifest directory name
+//!
+//! [processes.web]
+//! cmd = "npm run dev"
+//! labels = { role = "frontend" }  # select it with `-l role=frontend`
+//! description = "Vite dev server"  # shown in `mcproc ps`
+//! cwd = "frontend"  # relative to the manifest directory
+//! env = { NODE_ENV = "development" }
+//! ports = ["http", "hmr"]  # free ports exported as PORT, MCPROC_PORT_HTTP and MCPROC_PORT_HMR
+//! wait_for_log = "Local:"
+//! wait_timeout = 60
+//! depends_on = ["api"]  # started once api is ready
+//! stdin = true  # accept input from `mcproc send`
+//! pty = true  # run on a pseudo-terminal (pty_cols / pty_rows set its size)
+//!
+//! [processes.api]
+//! cmd = "cargo run"
+//! stop_signal = "SIGINT"  # or stop_command = "...", sent instead of SIGTERM
+//! stop_timeout = 60  # seconds before the process is killed
+//! ready_http = "http://localhost:8080/health"  # or ready_tcp = "8080", ready_port = true
+//!
+//! [processes.api.health]  # liveness check while running
+//! http = "http://localhost:8080/health"  # or tcp = "8080", cmd = "..."
+//! interval = 10
+//! restart_after = 3  # restart after 3 consecutive failures
+//!
+//! [processes.api.limits]
+//! memory = "2G"
+//! cpus = 1.5
+//! pids = 256
+//! open_files = 4096
+//!
+//! [processes.worker]
+//! cmd = "bundle exec sidekiq"
+//! watchdog = ["restart if rss > 3G for 60s", "flag stalled if no-output and cpu < 1 for 15m"]
+//!
+//! [processes.worker.watch]  # restart when files change
+//! paths = ["app", "config"]  # relative to cwd, default "."
+//! ignore = ["*.log"]  # in addition to .gitignore
+//! debounce_ms = 500
+//!
+//! [processes.codegen]
+//! cmd = "npm run generate:api"
+//! schedule = "*/15 * * * *"  # or an interval such as "every 10m"
+//! ```
+
+use crate::common::dependency::dependency_order;
+use crate::common::file_watch::WatchSpec;
+use crate::common::health::HealthCheckSpec;
+use crate::common::labels::{validate_description, validate_labels};
+use crate::common::limits::LimitsSpec;
+use crate::common::readiness::{http_probe, parse_tcp_probe, port_detected_probe};
+use crate::common::restart_policy::parse_restart_policy;
+use crate::common::schedule::parse_schedule;
+use crate::common::signal::normalize_signal;
+use crate::common::validation::{
+    validate_port_names, validate_process_name, validate_project_name,
+};
+use crate::common::watchdog::parse_watchdog_rule;
+use proto::StartProcessRequest;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// File name looked up by `mcproc up` / `mcproc down`
+pub const MANIFEST_FILE_NAME: &str = "mcproc.toml";
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    /// Project name (defaults to the manifest directory name)
+    pub project: Option<String>,
+    /// Process definitions keyed by process name
+    #[serde(default)]
+    pub processes: BTreeMap<String, ManifestProcess>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestProcess {
+    /// Shell command to execute
+    pub cmd: Option<String>,
+    /// Command and arguments for direct execution
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Working directory, relative to the manifest directory
+    pub cwd: Option<PathBuf>,
+    /// Environment variables
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Wait for this log pattern before considering the process ready (regex)
+    pub wait_for_log: Option<String>,
+    /// Timeout for log wait in seconds
+    pub wait_timeout: Option<u32>,
+    /// Version management tool (mise, asdf, nvm, etc.)
+    pub toolchain: Option<String>,
+    /// Automatic restart policy (never, on-failure, always)
+    pub restart: Option<String>,
+    /// Processes that must be running and ready before this one starts
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Ready once a TCP connection to `[HOST:]PORT` succeeds
+    pub ready_tcp: Option<String>,
+    /// Ready once an HTTP GET to this URL succeeds
+    pub ready_http: Option<String>,
+    /// Accepted HTTP status range for `ready_http` (default: 200-399)
+    pub ready_http_status: Option<String>,
+    /// Ready once the process listens on any TCP port
+    #[serde(default)]
+    pub ready_port: bool,
+    /// Liveness check run periodically while the process is running
+    pub health: Option<HealthCheckSpec>,
+    /// Signal sent to stop the process (default: SIGTERM)
+    pub stop_signal: Option<String>,
+    /// Shell command run to stop the process before any signal
+    pub stop_command: Option<String>,
+    /// Seconds to wait for the process to exit before killing it
+    pub stop_timeout: Option<u32>,
+    /// Keep stdin open so input can be sent with `mcproc send`
+    #[serde(default)]
+    pub stdin: bool,
+    /// Run the process on a pseudo-terminal
+    #[serde(default)]
+    pub pty: bool,
+    /// Terminal width when running on a pseudo-terminal
+    pub pty_cols: Option<u32>,
+    /// Terminal height when running on a pseudo-terminal
+    pub pty_rows: Option<u32>,
+    /// Names of ports the daemon allocates for the process
+    #[serde(default)]
+    pub ports: Vec<String>,
+    /// Memory, CPU, process and open file limits
+    pub limits: Option<LimitsSpec>,
+    /// Watchdog rules, e.g. "restart if rss > 3G for 60s"
+    #[serde(default)]
+    pub watchdog: Vec<String>,
+    /// Files whose changes restart the process
+    pub watch: Option<WatchSpec>,
+    /// Cron expression or interval to run the command on, e.g. "every 10m"
+    pub schedule: Option<String>,
+    /// Labels to select the process by, e.g. `role = "frontend"`
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    /// Free-form note shown in `mcproc ps`
+    pub description: Option<String>,
+}
+
+impl ManifestProcess {
+    /// Readiness probes declared for this process
+    pub fn readiness_probes(&self) -> Result<Vec<proto::ReadinessProbe>, String> {
+        if self.ready_http_status.is_some() && self.ready_http.is_none() {
+            return Err("'ready_http_status' requires 'ready_http'".to_string());
+        }
+        let mut probes = Vec::new();
+        if let Some(target) = &self.ready_tcp {
+            probes.push(parse_tcp_probe(target)?);
+        }
+        if let Some(url) = &self.ready_http {
+            probes.push(http_probe(url, self.ready_http_status.as_deref())?);
+        }
+        if self.ready_port {
+            probes.push(port_detected_probe());
+        }
+        Ok(probes)
+    }
+
+    /// Liveness check declared for this process
+    pub fn health_check(&self) -> Result<Option<proto::HealthCheck>, String> {
+        self.health
+            .clone()
+            .map(HealthCheckSpec::into_proto)
+            .transpose()
+    }
+
+    /// Resource limits declared for this process
+    pub fn limits(&self) -> Result<Option<proto::ResourceLimits>, String> {
+        self.limits
+            .clone()
+            .map(LimitsSpec::into_proto)
+            .transpose()
+            .map(Option::flatten)
+    }
+
+    /// Watchdog rules declared for this process
+    pub fn watchdog(&self) -> Result<Vec<proto::WatchdogRule>, String> {
+        self.watchdog
+            .iter()
+            .map(|rule| parse_watchdog_rule(rule))
+            .collect()
+    }
+
+    /// File watch declared for this process
+    pub fn watch(&self) -> Result<Option<proto::FileWatch>, String> {
+        self.watch.clone().map(WatchSpec::into_proto).transpose()
+    }
+}
+
+/// A manifest together with the file it was loaded from
+#[derive(Debug)]
+pub struct LoadedManifest {
+    pub path: PathBuf,
+    pub manifest: Manifest,
+}
+
+/// Find the nearest manifest in `start` or one of its parent directories
+pub fn find_manifest(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(MANIFEST_FILE_NAME))
+        .find(|path| path.is_file())
+}
+
+/// Load the manifest at `path`, or the nearest one above the current directory
+pub fn load_manifest(path: Option<&Path>) -> Result<LoadedManifest, String> {
+    let path = match path {
+        Some(path) => path.to_path_buf(),
+        None => {
+            let cwd = std::env::current_dir()
+                .map_err(|e| format!("Failed to get current directory: {e}"))?;
+            find_manifest(&cwd).ok_or_else(|| {
+                format!(
+                    "No {MANIFEST_FILE_NAME} found in {} or any parent directory",
+                    cwd.display()
+                )
+            })?
+        }
+    };
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let manifest = parse_manifest(&contents)
+        .map_err(|e| format!("Invalid manifest {}: {e}", path.display()))?;
+    let path = path.canonicalize().unwrap_or(path);
+    Ok(LoadedManifest { path, manifest })
+}
+
+/// Parse and validate manifest contents
+pub fn parse_manifest(contents: &str) -> Result<Manifest, String> {
+    let manifest: Manifest = toml::from_str(contents).map_err(|e| e.to_string())?;
+    if let Some(project) = &manifest.project {
+        validate_project_name(project).map_err(|e| format!("Invalid project name: {e}"))?;
+    }
+    for (name, process) in &manifest.processes {
+        validate_process_name(name).map_err(|e| format!("Invalid process name '{name}': {e}"))?;
+        match (&process.cmd, process.args.is_empty()) {
+            (Some(_), false) => {
+                return Err(format!(
+                    "Process '{name}' cannot specify both 'cmd' and 'args'"
+                ))
+            }
+            (None, true) => {
+                return Err(format!(
+                    "Process '{name}' must specify either 'cmd' or 'args'"
+                ))
+            }
+            _ => {}
+        }
+        if let Some(restart) = &process.restart {
+            parse_restart_policy(restart).map_err(|e| format!("Process '{name}': {e}"))?;
+        }
+        process
+            .readiness_probes()
+            .map_err(|e| format!("Process '{name}': {e}"))?;
+        process
+            .health_check()
+            .map_err(|e| format!("Process '{name}': {e}"))?;
+        process
+            .limits()
+            .map_err(|e| format!("Process '{name}': limits: {e}"))?;
+        process
+            .watchdog()
+            .map_err(|e| format!("Process '{name}': {e}"))?;
+        process
+            .watch()
+            .map_err(|e| format!("Process '{name}': watch: {e}"))?;
+        if let Some(schedule) = &process.schedule {
+            parse_schedule(schedule).map_err(|e| format!("Process '{name}': {e}"))?;
+        }
+        validate_labels(&process.labels).map_err(|e| format!("Process '{name}': {e}"))?;
+        if let Some(description) = &process.description {
+            validate_description(description).map_err(|e| format!("Process '{name}': {e}"))?;
+        }
+        if let Some(signal) = &process.stop_signal {
+            normalize_signal(signal).map_err(|e| format!("Process '{name}': {e}"))?;
+        }
+        if process.stop_timeout == Some(0) {
+            return Err(format!(
+                "Process '{name}': 'stop_timeout' must be at least 1 second"
+            ));
+        }
+        validate_port_names(&process.ports).map_err(|e| format!("Process '{name}': {e}"))?;
+        for (field, value) in [
+            ("pty_cols", process.pty_cols),
+            ("pty_rows", process.pty_rows),
+        ] {
+            match value {
+                Some(_) if !process.pty => {
+                    return Err(format!("Process '{name}': '{field}' requires 'pty = true'"));
+                }
+                Some(value) if !(1..=u32::from(u16::MAX)).contains(&value) => {
+                    return Err(format!(
+                        "Process '{name}': '{field}' must be between 1 and {}",
+                        u16::MAX
+                    ));
+                }
+                _ => {}
+            }
+        }
+    }
+    manifest.start_order()?;
+    Ok(manifest)
+}
+
+impl Manifest {
+    /// Process names ordered so that dependencies come before their dependents
+    pub fn start_order(&self) -> Result<Vec<&str>, String> {
+        let nodes = self
+            .processes
+            .iter()
+            .map(|(name, process)| (name.as_str(), process.depends_on.as_slice()))
+            .collect::<Vec<_>>();
+        dependency_order(&nodes)
+    }
+}
+
+impl LoadedManifest {
+    /// Directory containing the manifest, used to resolve relative paths
+    pub fn base_dir(&self) -> &Path {
+        self.path.parent().unwrap_or(Path::new("."))
+    }
+
+    /// Resolve the project name: explicit override, manifest value, then directory name
+    pub fn project_name(&self, project: Option<String>) -> Result<String, String> {
+        let project = project
+            .or_else(|| self.manifest.project.clone())
+            .or_else(|| {
+                self.base_dir()
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .map(str::to_string)
+            })
+            .ok_or_else(|| "Unable to determine project name from manifest".to_string())?;
+        validate_project_name(&project).map_err(|e| format!("Invalid project name: {e}"))?;
+        Ok(project)
+    }
+
+    /// Convert the process definitions into start requests for `project`
+    pub fn start_requests(&self, project: &str) -> Vec<StartProcessRequest> {
+        let base_dir = self.base_dir();
+        self.manifest
+            .processes
+            .iter()
+            .map(|(name, process)| StartProcessRequest {
+                name: name.clone(),
+                cmd: process.cmd.clone(),
+                args: process.args.clone(),
+                cwd: Some(
+                    process
+                        .cwd
+                        .as_ref()
+                        .map(|cwd| base_dir.join(cwd))
+                        .unwrap_or_else(|| base_dir.to_path_buf())
+                        .to_string_lossy()
+                        .into_owned(),
+                ),
+                env: process.env.clone(),
+                project: project.to_string(),
+                wait_for_log: process.wait_for_log.clone(),
+                wait_timeout: process.wait_timeout,
+                force_restart: None,
+                toolchain: process.toolchain.clone(),
+                restart_policy: process
+                    .restart
+                    .as_deref()
+                    .and_then(|restart| parse_restart_policy(restart).ok())
+                    .map(|policy| policy as i32),
+                depends_on: process.depends_on.clone(),
+                readiness_probes: process.readiness_probes().unwrap_or_default(),
+                health_check: process.health_check().ok().flatten(),
+                stop_signal: process.stop_signal.clone(),
+                stop_command: process.stop_command.clone(),
+                stop_timeout: process.stop_timeout,
+                stdin: Some(process.stdin),
+                pty: Some(process.pty),
+                pty_cols: process.pty_cols,
+                pty_rows: process.pty_rows,
+                ports: process.ports.clone(),
+                limits: process.limits().ok().flatten(),
+                watchdog: process.watchdog().unwrap_or_default(),
+                watch: process.watch().ok().flatten(),
+                schedule: process.schedule.clone(),
+                started_by: Some("manifest".to_string()),
+                labels: process.labels.clone(),
+                description: process.description.clone(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn start_requests_resolve_cwd_relative_to_manifest() {
+        let manifest = parse_manifest(
+            r#"
+            [processes.web]
+            cmd = "npm run dev"
+            cwd = "frontend"
+            env = { PORT = "3000" }
+            wait_for_log = "Local:"
+            restart = "on-failure"
+            stdin = true
+            ports = ["http", "hmr"]
+            watchdog = ["restart if rss > 2G for 60s"]
+
+            [processes.api]
+            args = ["cargo", "run"]
+            ready_tcp = "8080"
+            ready_port = true
+            stop_signal = "SIGINT"
+            stop_timeout = 60
+
+            [processes.api.health]
+            cmd = "curl -fs localhost:8080/health"
+            restart_after = 3
+
+            [processes.api.limits]
+            memory = "512M"
+            open_files = 1024
+
+            [processes.api.watch]
+            paths = ["src"]
+            ignore = ["*.log"]
+            "#,
+        )
+        .unwrap();
+        let loaded = LoadedManifest {
+            path: PathBuf::from("/work/myapp/mcproc.toml"),
+            manifest,
+        };
+
+        let requests = loaded.start_requests("myapp");
+
+        assert_eq!(loaded.project_name(None).unwrap(), "myapp");
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].name, "api");
+        assert_eq!(requests[0].cwd.as_deref(), Some("/work/myapp"));
+        assert_eq!(requests[0].readiness_probes.len(), 2);
+        let health = requests[0].health_check.as_ref().unwrap();
+        assert_eq!(health.restart_after_failures, Some(3));
+        assert!(requests[1].health_check.is_none());
+        assert_eq!(requests[0].stop_signal.as_deref(), Some("SIGINT"));
+        assert_eq!(requests[0].stop_timeout, Some(60));
+        assert_eq!(requests[1].stop_timeout, None);
+        assert_eq!(requests[0].stdin, Some(false));
+        assert_eq!(requests[1].stdin, Some(true));
+        assert!(requests[0].ports.is_empty());
+        assert_eq!(requests[1].ports, vec!["http", "hmr"]);
+        let limits = requests[0].limits.as_ref().unwrap();
+        assert_eq!(limits.memory_bytes, Some(512 << 20));
+        assert_eq!(limits.open_files, Some(1024));
+        assert!(requests[1].limits.is_none());
+        assert!(requests[0].watchdog.is_empty());
+        assert_eq!(requests[1].watchdog[0].rss_above_bytes, Some(2 << 30));
+        let watch = requests[0].watch.as_ref().unwrap();
+        assert_eq!(watch.paths, vec!["src"]);
+        assert_eq!(watch.ignore, vec!["*.log"]);
+        assert_eq!(watch.debounce_ms, None);
+        assert!(requests[1].watch.is_none());
+        assert_eq!(requests[1].name, "web");
+        assert_eq!(requests[1].cwd.as_deref(), Some("/work/myapp/frontend"));
+        assert_eq!(requests[1].env["PORT"], "3000");
+        assert_eq!(
+            requests[1].restart_policy,
+            Some(proto::RestartPolicy::OnFailure as i32)
+        );
+    }
+
+    #[test]
+    fn parse_rejects_invalid_definitions() {
+        assert!(parse_manifest("[processes.web]\ncwd = \".\"\n")
+            .unwrap_err()
+            .contains("either 'cmd' or 'args'"));
+        assert!(
+            parse_manifest("[processes.web]\ncmd = \"x\"\nargs = [\"x\"]\n")
+                .unwrap_err()
+                .contains("both")
+        );
+        assert!(parse_manifest("[processes.web]\ncmd = \"x\"\nport = 1\n").is_err());
+        assert!(parse_manifest("[processes.web]\ncmd = \"x\"\nrestart = \"maybe\"\n").is_err());
+        assert!(
+            parse_manifest("[processes.web]\ncmd = \"x\"\nready_http_status = \"200\"\n").is_err()
+        );
+        assert!(
+            parse_manifest("[processes.web]\ncmd = \"x\"\nstop_signal = \"SIGNOPE\"\n")
+                .unwrap_err()
+                .contains("Unknown signal")
+        );
+        assert!(parse_manifest("[processes.web]\ncmd = \"x\"\nstop_timeout = 0\n").is_err());
+        assert!(
+            parse_manifest("[processes.web]\ncmd = \"x\"\npty_cols = 100\n")
+                .unwrap_err()
+                .contains("requires 'pty = true'")
+        );
+        assert!(
+            parse_manifest("[processes.web]\ncmd = \"x\"\npty = true\npty_rows = 0\n").is_err()
+        );
+        assert!(
+            parse_manifest("[processes.web]\ncmd = \"x\"\nports = [\"http\", \"http\"]\n")
+                .unwrap_err()
+                .contains("Duplicate port name")
+        );
+        assert!(parse_manifest(
+            "[processes.web]\ncmd = \"x\"\n[processes.web.health]\ntcp = \"80\"\ncmd = \"true\"\n"
+        )
+        .unwrap_err()
+        .contains("exactly one"));
+        assert!(parse_manifest(
+            "[processes.web]\ncmd = \"x\"\n[processes.web.limits]\nmemory = \"2 gigs\"\n"
+        )
+        .unwrap_err()
+        .contains("limits: Invalid size"));
+        assert!(parse_manifest(
+            "[processes.web]\ncmd = \"x\"\nwatchdog = [\"reboot if cpu > 90\"]\n"
+        )
+        .unwrap_err()
+        .contains("Invalid watchdog rule"));
+        assert!(parse_manifest(
+            "[processes.web]\ncmd = \"x\"\n[processes.web.watch]\ndebounce_ms = 0\n"
+        )
+        .unwrap_err()
+        .contains("watch: 'debounce_ms'"));
+        assert!(parse_manifest(
+            "[processes.web]\ncmd = \"x\"\nschedule = \"every now and then\"\n"
+        )
+        .unwrap_err()
+        .contains("Invalid schedule"));
+        assert!(
+            parse_manifest("[processes.web]\ncmd = \"x\"\nlabels = { \"bad key\" = \"x\" }\n")
+                .unwrap_err()
+                .contains("Label key 'bad key'")
+        );
+        assert!(parse_manifest(
+            "[processes.a]\ncmd = \"x\"\ndepends_on = [\"b\"]\n[processes.b]\ncmd = \"x\"\ndepends_on = [\"a\"]\n"
+        )
+        .unwrap_err()
+        .contains("Dependency cycle detected: a -> b -> a"));
+    }
+
+    #[test]
+    fn manifest_project_overrides_directory_name() {
+        let loaded = LoadedManifest {
+            path: PathBuf::from("/work/checkout/mcproc.toml"),
+            manifest: parse_manifest("project = \"shop\"\n").unwrap(),
+        };
+
+        assert_eq!(loaded.project_name(None).unwrap(), "shop");
+        assert_eq!(loaded.project_name(Some("other".into())).unwrap(), "other");
+    }
+
+    #[test]
+    fn find_manifest_searches_parent_directories() {
+        let root = std::env::temp_dir().join(format!("mcproc-manifest-{}", uuid::Uuid::new_v4()));
+        let nested = root.join("a/b");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(root.join(MANIFEST_FILE_NAME), "").unwrap();
+
+        let found = find_manifest(&nested);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(found, Some(root.join(MANIFEST_FILE_NAME)));
+    }
+}
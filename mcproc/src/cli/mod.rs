@@ -1,4 +1,5 @@
 pub mod commands;
+pub mod manifest;
 pub mod utils;
 
 use crate::client::DaemonClient;
@@ -25,7 +26,7 @@ pub struct Cli {
 #[derive(Subcommand)]
 pub enum Commands {
     /// Start or attach to a process
-    Start(StartCommand),
+    Start(Box<StartCommand>),
 
     /// Stop a running process
     Stop(StopCommand),
@@ -33,6 +34,18 @@ pub enum Commands {
     /// Restart a process
     Restart(RestartCommand),
 
+    /// Run a one-shot task to completion and exit with its result
+    Run(RunCommand),
+
+    /// Send a signal to a process (e.g. SIGHUP to reload, SIGSTOP/SIGCONT to pause/resume)
+    Signal(SignalCommand),
+
+    /// Write text to the stdin of a process started with --stdin
+    Send(SendCommand),
+
+    /// Stream a process's output and forward keyboard input to it
+    Attach(AttachCommand),
+
     /// List running processes
     Ps(PsCommand),
 
@@ -42,12 +55,27 @@ pub enum Commands {
     /// Search process logs
     Grep(GrepCommand),
 
+    /// Show past and current runs of processes
+    History(HistoryCommand),
+
+    /// Show CPU, memory and I/O usage of running processes
+    Top(TopCommand),
+
+    /// Show which process holds a port
+    Port(PortCommand),
+
     /// Get path to process log file
     Logfile {
         /// Process name
         name: String,
     },
 
+    /// Start the processes declared in mcproc.toml
+    Up(UpCommand),
+
+    /// Stop the processes declared in mcproc.toml
+    Down(DownCommand),
+
     /// Clean project (stop processes and delete logs)
     Clean(CleanCommand),
 
@@ -92,9 +120,16 @@ pub async fn run_cli() -> Result<(), Box<dyn std::error::Error>> {
         Commands::Start(cmd) => cmd.execute(client).await?,
         Commands::Stop(cmd) => cmd.execute(client).await?,
         Commands::Restart(cmd) => cmd.execute(client).await?,
+        Commands::Run(cmd) => cmd.execute(client).await?,
+        Commands::Signal(cmd) => cmd.execute(client).await?,
+        Commands::Send(cmd) => cmd.execute(client).await?,
+        Commands::Attach(cmd) => cmd.execute(client).await?,
         Commands::Ps(cmd) => cmd.execute(client).await?,
         Commands::Logs(cmd) => cmd.execute(client).await?,
         Commands::Grep(cmd) => cmd.execute(client).await?,
+        Commands::History(cmd) => cmd.execute(client).await?,
+        Commands::Top(cmd) => cmd.execute(client).await?,
+        Commands::Port(cmd) => cmd.execute(client).await?,
         Commands::Logfile { name } => {
             let config = Config::for_client();
             // Use current directory as project name
@@ -106,6 +141,8 @@ pub async fn run_cli() -> Result<(), Box<dyn std::error::Error>> {
                 .join(format!("{}.log", name));
             println!("{}", log_path.display());
         }
+        Commands::Up(cmd) => cmd.execute(client).await?,
+        Commands::Down(cmd) => cmd.execute(client).await?,
         Commands::Clean(mut cmd) => {
             cmd.verbose = cli.verbose;
             cmd.execute(client).await?
@@ -1,6 +1,9 @@
 //! Utility functions for mcproc
 
+use crate::cli::manifest::{load_manifest, LoadedManifest};
+use crate::client::DaemonClient;
 use crate::common::validation::validate_project_name;
+use std::path::Path;
 use std::time::Duration;
 
 pub(crate) fn start_deadline(wait_timeout: u32) -> Duration {
@@ -23,6 +26,58 @@ pub(crate) fn restart_deadline(
         .saturating_add(Duration::from_secs(20))
 }
 
+/// Grace period in milliseconds of a process with the given `stop_timeout` in seconds
+pub(crate) fn stop_timeout_ms(stop_timeout: Option<u32>, process_stop_timeout_ms: u64) -> u64 {
+    stop_timeout
+        .map(|secs| u64::from(secs) * 1000)
+        .unwrap_or(process_stop_timeout_ms)
+}
+
+/// Names of the processes the daemon has registered in a project
+///
+/// StopProcess reports an unknown name as an unsuccessful stop, so callers
+/// stopping a list of declared processes use this to tell the ones that are
+/// not running apart from real failures.
+pub(crate) async fn registered_process_names(
+    client: &mut DaemonClient,
+    project: &str,
+) -> Result<std::collections::HashSet<String>, tonic::Status> {
+    let request = proto::ListProcessesRequest {
+        project_filter: Some(project.to_string()),
+        ..Default::default()
+    };
+    Ok(client
+        .inner()
+        .list_processes(request)
+        .await?
+        .into_inner()
+        .processes
+        .into_iter()
+        .map(|process| process.name)
+        .collect())
+}
+
+/// Look up the grace period of an existing process, falling back to the configured one
+pub(crate) async fn process_stop_timeout_ms(
+    client: &mut DaemonClient,
+    name: &str,
+    project: &str,
+    process_stop_timeout_ms: u64,
+) -> u64 {
+    let request = proto::GetProcessRequest {
+        name: name.to_string(),
+        project: project.to_string(),
+    };
+    let stop_timeout = match client.inner().get_process(request).await {
+        Ok(response) => response
+            .into_inner()
+            .process
+            .and_then(|process| process.stop_timeout),
+        Err(_) => None,
+    };
+    stop_timeout_ms(stop_timeout, process_stop_timeout_ms)
+}
+
 /// Get the project name from environment variable
 /// Returns None if not set
 pub fn get_project_from_env() -> Option<String> {
@@ -85,9 +140,22 @@ pub fn resolve_mcp_project_name(params_project: Option<String>) -> Result<String
     ))
 }
 
+/// Load a project manifest for MCP tools and resolve its project name
+/// Prioritizes: params.project -> manifest `project` -> manifest directory name
+pub fn load_mcp_manifest(
+    file: Option<&Path>,
+    params_project: Option<String>,
+) -> Result<(LoadedManifest, String), mcp_rs::Error> {
+    let manifest = load_manifest(file).map_err(mcp_rs::Error::InvalidParams)?;
+    let project = manifest
+        .project_name(params_project)
+        .map_err(mcp_rs::Error::InvalidParams)?;
+    Ok((manifest, project))
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{restart_deadline, start_deadline, stop_deadline};
+    use super::{restart_deadline, start_deadline, stop_deadline, stop_timeout_ms};
     use std::time::Duration;
 
     #[test]
@@ -103,6 +171,12 @@ mod tests {
         assert_eq!(stop_deadline(30_000), Duration::from_secs(45));
     }
 
+    #[test]
+    fn stop_timeout_overrides_configured_grace_period() {
+        assert_eq!(stop_timeout_ms(Some(120), 30_000), 120_000);
+        assert_eq!(stop_timeout_ms(None, 30_000), 30_000);
+    }
+
     #[test]
     fn restart_deadline_covers_stop_wait_and_cleanup() {
         assert_eq!(
@@ -43,6 +43,10 @@ pub struct DaemonConfig {
     /// Time to wait after starting daemon before attempting connection (milliseconds)
     /// Note: This is now used as a maximum wait time with multiple checks
     pub client_startup_wait_ms: u64,
+    /// Relaunch processes that were running when the daemon last stopped,
+    /// instead of only restoring them as stopped
+    #[serde(default)]
+    pub relaunch_on_restart: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,6 +73,15 @@ pub struct ProcessConfig {
     pub startup: ProcessStartupConfig,
     /// Restart configuration
     pub restart: ProcessRestartConfig,
+    /// Port allocation configuration
+    #[serde(default)]
+    pub ports: ProcessPortConfig,
+    /// cgroup v2 tracking configuration
+    #[serde(default)]
+    pub cgroup: ProcessCgroupConfig,
+    /// Resource usage sampling configuration
+    #[serde(default)]
+    pub metrics: ProcessMetricsConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -81,12 +94,127 @@ pub struct ProcessStartupConfig {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessRestartConfig {
-    /// Maximum number of automatic restart attempts (not yet implemented)
+    /// Maximum number of consecutive automatic restarts before a process is
+    /// marked as crash looping
     pub max_attempts: u32,
     /// Delay between stop and start during restart (milliseconds)
     pub delay_ms: u64,
     /// Timeout for graceful shutdown of a single process (milliseconds)
     pub process_stop_timeout_ms: u64,
+    /// Delay before the first automatic restart, doubled on each consecutive attempt (milliseconds)
+    #[serde(default = "default_backoff_initial_ms")]
+    pub backoff_initial_ms: u64,
+    /// Upper bound for the automatic restart delay (milliseconds)
+    #[serde(default = "default_backoff_max_ms")]
+    pub backoff_max_ms: u64,
+    /// A process that stays up this long resets its consecutive restart count (seconds)
+    #[serde(default = "default_stable_after_secs")]
+    pub stable_after_secs: u64,
+    /// Processes restarted at once when a pattern or filter selects several
+    #[serde(default = "default_bulk_parallelism")]
+    pub bulk_parallelism: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessPortConfig {
+    /// First port handed out to processes that request ports by name
+    #[serde(default = "default_port_range_start")]
+    pub range_start: u16,
+    /// Last port handed out to processes that request ports by name
+    #[serde(default = "default_port_range_end")]
+    pub range_end: u16,
+}
+
+impl Default for ProcessPortConfig {
+    fn default() -> Self {
+        Self {
+            range_start: default_port_range_start(),
+            range_end: default_port_range_end(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessCgroupConfig {
+    /// Run each process in its own cgroup when a writable cgroup v2 hierarchy is available
+    #[serde(default = "default_cgroup_enabled")]
+    pub enabled: bool,
+    /// Delegated cgroup v2 directory to create process cgroups in
+    /// (default: `mcproc` under the daemon's own cgroup)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+}
+
+impl Default for ProcessCgroupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_cgroup_enabled(),
+            path: None,
+        }
+    }
+}
+
+fn default_cgroup_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessMetricsConfig {
+    /// Sample CPU, memory and I/O usage of running processes
+    #[serde(default = "default_metrics_enabled")]
+    pub enabled: bool,
+    /// Seconds between samples
+    #[serde(default = "default_metrics_interval_secs")]
+    pub interval_secs: u64,
+    /// Samples kept per process
+    #[serde(default = "default_metrics_history")]
+    pub history: usize,
+}
+
+impl Default for ProcessMetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_metrics_enabled(),
+            interval_secs: default_metrics_interval_secs(),
+            history: default_metrics_history(),
+        }
+    }
+}
+
+fn default_metrics_enabled() -> bool {
+    true
+}
+
+fn default_metrics_interval_secs() -> u64 {
+    2
+}
+
+fn default_metrics_history() -> usize {
+    150
+}
+
+fn default_port_range_start() -> u16 {
+    20000
+}
+
+fn default_port_range_end() -> u16 {
+    29999
+}
+
+fn default_backoff_initial_ms() -> u64 {
+    1000
+}
+
+fn default_backoff_max_ms() -> u64 {
+    60000
+}
+
+fn default_stable_after_secs() -> u64 {
+    60
+}
+
+fn default_bulk_parallelism() -> u32 {
+    4
 }
 
 impl Default for Config {
@@ -113,6 +241,7 @@ impl Default for Config {
                 stop_check_interval_ms: 100,
                 client_connection_timeout_secs: 5,
                 client_startup_wait_ms: 1000, // Max wait time with multiple checks
+                relaunch_on_restart: false,
             },
             process: ProcessConfig {
                 startup: ProcessStartupConfig {
@@ -123,7 +252,14 @@ impl Default for Config {
                     max_attempts: 3,
                     delay_ms: 1000,
                     process_stop_timeout_ms: 30000, // 30 seconds per process
+                    backoff_initial_ms: default_backoff_initial_ms(),
+                    backoff_max_ms: default_backoff_max_ms(),
+                    stable_after_secs: default_stable_after_secs(),
+                    bulk_parallelism: default_bulk_parallelism(),
                 },
+                ports: ProcessPortConfig::default(),
+                cgroup: ProcessCgroupConfig::default(),
+                metrics: ProcessMetricsConfig::default(),
             },
             logging: LoggingConfig {
                 max_size_mb: 100,
@@ -248,6 +384,27 @@ mod tests {
         (config, root)
     }
 
+    #[test]
+    fn restart_backoff_settings_default_when_missing_from_config_file() {
+        let contents = toml::to_string(&Config::default())
+            .unwrap()
+            .lines()
+            .filter(|line| {
+                !line.starts_with("backoff_")
+                    && !line.starts_with("stable_after_secs")
+                    && !line.starts_with("bulk_parallelism")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let config: Config = toml::from_str(&contents).unwrap();
+
+        assert_eq!(config.process.restart.backoff_initial_ms, 1000);
+        assert_eq!(config.process.restart.backoff_max_ms, 60000);
+        assert_eq!(config.process.restart.stable_after_secs, 60);
+        assert_eq!(config.process.restart.bulk_parallelism, 4);
+    }
+
     #[test]
     fn ensure_directories_creates_daemon_log_parent() {
         let (config, root) = test_config();
@@ -0,0 +1,115 @@
+//! Dependency ordering for process definitions
+
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Visit {
+    New,
+    InProgress,
+    Done,
+}
+
+/// Order processes so that each one comes after the processes it depends on
+///
+/// `nodes` pairs each process name with its `depends_on` list. Dependencies on
+/// names outside `nodes` are ignored, and independent processes keep their
+/// input order. Circular dependencies are rejected with the cycle spelled out.
+pub fn dependency_order<'a>(nodes: &[(&'a str, &'a [String])]) -> Result<Vec<&'a str>, String> {
+    let index = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, (name, _))| (*name, i))
+        .collect::<HashMap<_, _>>();
+    let mut state = vec![Visit::New; nodes.len()];
+    let mut path = Vec::new();
+    let mut order = Vec::with_capacity(nodes.len());
+
+    fn visit<'a>(
+        i: usize,
+        nodes: &[(&'a str, &'a [String])],
+        index: &HashMap<&str, usize>,
+        state: &mut [Visit],
+        path: &mut Vec<usize>,
+        order: &mut Vec<&'a str>,
+    ) -> Result<(), String> {
+        match state[i] {
+            Visit::Done => return Ok(()),
+            Visit::InProgress => {
+                let start = path.iter().position(|&p| p == i).unwrap_or(0);
+                let cycle = path[start..]
+                    .iter()
+                    .chain(std::iter::once(&i))
+                    .map(|&p| nodes[p].0)
+                    .collect::<Vec<_>>();
+                return Err(format!("Dependency cycle detected: {}", cycle.join(" -> ")));
+            }
+            Visit::New => {}
+        }
+
+        state[i] = Visit::InProgress;
+        path.push(i);
+        for dependency in nodes[i].1 {
+            if let Some(&d) = index.get(dependency.as_str()) {
+                visit(d, nodes, index, state, path, order)?;
+            }
+        }
+        path.pop();
+        state[i] = Visit::Done;
+        order.push(nodes[i].0);
+        Ok(())
+    }
+
+    for i in 0..nodes.len() {
+        visit(i, nodes, &index, &mut state, &mut path, &mut order)?;
+    }
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deps(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn dependencies_come_first_and_unrelated_order_is_kept() {
+        let api = deps(&["db", "cache"]);
+        let cache = deps(&["external"]);
+        let none = deps(&[]);
+        let nodes = [
+            ("api", api.as_slice()),
+            ("worker", none.as_slice()),
+            ("cache", cache.as_slice()),
+            ("db", none.as_slice()),
+        ];
+
+        assert_eq!(
+            dependency_order(&nodes).unwrap(),
+            vec!["db", "cache", "api", "worker"]
+        );
+    }
+
+    #[test]
+    fn cycles_are_reported_with_their_path() {
+        let a = deps(&["b"]);
+        let b = deps(&["c"]);
+        let c = deps(&["a"]);
+        let nodes = [
+            ("a", a.as_slice()),
+            ("b", b.as_slice()),
+            ("c", c.as_slice()),
+        ];
+        assert_eq!(
+            dependency_order(&nodes).unwrap_err(),
+            "Dependency cycle detected: a -> b -> c -> a"
+        );
+
+        let own = deps(&["self"]);
+        assert_eq!(
+            dependency_order(&[("self", own.as_slice())]).unwrap_err(),
+            "Dependency cycle detected: self -> self"
+        );
+    }
+}
@@ -0,0 +1,54 @@
+//! File watch options shared by the CLI, manifest and MCP tool
+
+use serde::Deserialize;
+
+/// Files watched for changes, as written in a manifest or an MCP call
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WatchSpec {
+    /// Files or directories relative to the working directory (default: the directory itself)
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Gitignore-style patterns to skip in addition to `.gitignore` files
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    /// Quiet period that coalesces a burst of changes
+    pub debounce_ms: Option<u32>,
+}
+
+impl WatchSpec {
+    /// Convert into the request message
+    pub fn into_proto(self) -> Result<proto::FileWatch, String> {
+        if self.paths.iter().any(|path| path.trim().is_empty()) {
+            return Err("watch paths must not be empty".to_string());
+        }
+        if self.debounce_ms == Some(0) {
+            return Err("'debounce_ms' must be greater than 0".to_string());
+        }
+        Ok(proto::FileWatch {
+            paths: self.paths,
+            ignore: self.ignore,
+            debounce_ms: self.debounce_ms,
+        })
+    }
+}
+
+/// Describe a file watch for display, e.g. "src, config (ignoring *.log; debounce 500ms)"
+pub fn format_watch(watch: &proto::FileWatch) -> String {
+    let mut formatted = if watch.paths.is_empty() {
+        ".".to_string()
+    } else {
+        watch.paths.join(", ")
+    };
+    let mut details = Vec::new();
+    if !watch.ignore.is_empty() {
+        details.push(format!("ignoring {}", watch.ignore.join(", ")));
+    }
+    if let Some(ms) = watch.debounce_ms {
+        details.push(format!("debounce {ms}ms"));
+    }
+    if !details.is_empty() {
+        formatted.push_str(&format!(" ({})", details.join("; ")));
+    }
+    formatted
+}
@@ -0,0 +1,106 @@
+//! Health check parsing utilities
+
+use crate::common::readiness::{http_probe, parse_tcp_probe};
+use proto::health_check::Check;
+use proto::readiness_probe::Probe;
+use serde::Deserialize;
+
+/// Liveness check settings as written in a manifest or on the command line
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HealthCheckSpec {
+    /// Healthy while a TCP connection to `[HOST:]PORT` succeeds
+    pub tcp: Option<String>,
+    /// Healthy while an HTTP GET to this URL succeeds
+    pub http: Option<String>,
+    /// Accepted HTTP status range for `http` (default: 200-399)
+    pub http_status: Option<String>,
+    /// Healthy while this shell command exits with code 0
+    pub cmd: Option<String>,
+    /// Seconds between checks (default: 10)
+    pub interval: Option<u32>,
+    /// Seconds before a single check counts as failed (default: 5)
+    pub timeout: Option<u32>,
+    /// Restart the process after this many consecutive failures
+    pub restart_after: Option<u32>,
+}
+
+impl HealthCheckSpec {
+    /// Convert into the request message, checking that exactly one check type is set
+    pub fn into_proto(self) -> Result<proto::HealthCheck, String> {
+        if self.http_status.is_some() && self.http.is_none() {
+            return Err("Health check 'http_status' requires 'http'".to_string());
+        }
+        let check = match (self.tcp, self.http, self.cmd) {
+            (Some(target), None, None) => match parse_tcp_probe(&target)?.probe {
+                Some(Probe::Tcp(tcp)) => Check::Tcp(tcp),
+                _ => unreachable!("parse_tcp_probe always builds a TCP probe"),
+            },
+            (None, Some(url), None) => match http_probe(&url, self.http_status.as_deref())?.probe {
+                Some(Probe::Http(http)) => Check::Http(http),
+                _ => unreachable!("http_probe always builds an HTTP probe"),
+            },
+            (None, None, Some(cmd)) => Check::Command(proto::CommandCheck { cmd }),
+            _ => return Err("Health check needs exactly one of 'tcp', 'http' or 'cmd'".to_string()),
+        };
+        Ok(proto::HealthCheck {
+            check: Some(check),
+            interval_secs: self.interval,
+            timeout_secs: self.timeout,
+            restart_after_failures: self.restart_after,
+        })
+    }
+}
+
+/// Format health status as string, or None when it is unknown
+pub fn format_health(health: i32) -> Option<&'static str> {
+    match proto::HealthStatus::try_from(health).unwrap_or(proto::HealthStatus::Unknown) {
+        proto::HealthStatus::Unknown => None,
+        proto::HealthStatus::Healthy => Some("healthy"),
+        proto::HealthStatus::Unhealthy => Some("unhealthy"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spec_requires_exactly_one_check_type() {
+        let check = HealthCheckSpec {
+            http: Some("http://localhost:3000/health".to_string()),
+            http_status: Some("200-299".to_string()),
+            interval: Some(5),
+            restart_after: Some(3),
+            ..Default::default()
+        }
+        .into_proto()
+        .unwrap();
+        assert_eq!(
+            check.check,
+            Some(Check::Http(proto::HttpProbe {
+                url: "http://localhost:3000/health".to_string(),
+                min_status: Some(200),
+                max_status: Some(299),
+            }))
+        );
+        assert_eq!(check.interval_secs, Some(5));
+        assert_eq!(check.restart_after_failures, Some(3));
+
+        assert!(HealthCheckSpec::default().into_proto().is_err());
+        assert!(HealthCheckSpec {
+            tcp: Some("5432".to_string()),
+            cmd: Some("pg_isready".to_string()),
+            ..Default::default()
+        }
+        .into_proto()
+        .is_err());
+        assert!(HealthCheckSpec {
+            cmd: Some("pg_isready".to_string()),
+            http_status: Some("200".to_string()),
+            ..Default::default()
+        }
+        .into_proto()
+        .is_err());
+    }
+}
@@ -0,0 +1,34 @@
+//! Run history helpers shared by the CLI and MCP tool
+
+use std::time::Duration;
+
+/// How long a run lasted, or None while it is going
+pub fn run_duration(run: &proto::RunRecord) -> Option<Duration> {
+    let (start, exit) = (run.start_time.as_ref()?, run.exit_time.as_ref()?);
+    let millis =
+        (exit.seconds - start.seconds) * 1000 + i64::from(exit.nanos - start.nanos) / 1_000_000;
+    u64::try_from(millis).ok().map(Duration::from_millis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_span_start_to_exit() {
+        let at = |seconds, nanos| Some(prost_types::Timestamp { seconds, nanos });
+        let run = proto::RunRecord {
+            start_time: at(100, 900_000_000),
+            exit_time: at(102, 100_000_000),
+            ..Default::default()
+        };
+        assert_eq!(run_duration(&run), Some(Duration::from_millis(1_200)));
+        assert_eq!(
+            run_duration(&proto::RunRecord {
+                exit_time: None,
+                ..run
+            }),
+            None
+        );
+    }
+}
@@ -0,0 +1,263 @@
+//! Labels, descriptions and label selectors of processes
+//!
+//! Labels are free-form `key=value` pairs attached to a process when it
+//! starts. A selector is a comma-separated list of requirements that must all
+//! hold: `key=value`, `key!=value`, `key` (the label is set) or `!key` (it is
+//! not).
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+const MAX_LABEL_KEY_LEN: usize = 63;
+const MAX_LABEL_VALUE_LEN: usize = 256;
+const MAX_LABELS: usize = 32;
+pub const MAX_DESCRIPTION_LEN: usize = 1024;
+
+/// Keys are short identifiers, e.g. `role` or `team.io/owner`
+pub fn validate_label_key(key: &str) -> Result<(), String> {
+    if key.is_empty() {
+        return Err("Label key cannot be empty".to_string());
+    }
+    if key.len() > MAX_LABEL_KEY_LEN {
+        return Err(format!(
+            "Label key '{key}' is longer than {MAX_LABEL_KEY_LEN} characters"
+        ));
+    }
+    if !key
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'))
+    {
+        return Err(format!(
+            "Label key '{key}' may only contain letters, digits, '-', '_', '.' and '/'"
+        ));
+    }
+    Ok(())
+}
+
+/// Values are free-form, except for the separators of selectors
+pub fn validate_label_value(key: &str, value: &str) -> Result<(), String> {
+    if value.chars().count() > MAX_LABEL_VALUE_LEN {
+        return Err(format!(
+            "Value of label '{key}' is longer than {MAX_LABEL_VALUE_LEN} characters"
+        ));
+    }
+    if value.contains(',') || value.chars().any(char::is_control) {
+        return Err(format!(
+            "Value of label '{key}' cannot contain commas or control characters"
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_labels<'a>(
+    labels: impl IntoIterator<Item = (&'a String, &'a String)>,
+) -> Result<(), String> {
+    let mut count = 0;
+    for (key, value) in labels {
+        validate_label_key(key)?;
+        validate_label_value(key, value)?;
+        count += 1;
+    }
+    if count > MAX_LABELS {
+        return Err(format!("A process can have at most {MAX_LABELS} labels"));
+    }
+    Ok(())
+}
+
+pub fn validate_description(description: &str) -> Result<(), String> {
+    if description.chars().count() > MAX_DESCRIPTION_LEN {
+        return Err(format!(
+            "Description is longer than {MAX_DESCRIPTION_LEN} characters"
+        ));
+    }
+    Ok(())
+}
+
+/// Parse a `key=value` label as given on the command line
+pub fn parse_label(label: &str) -> Result<(String, String), String> {
+    let (key, value) = label
+        .split_once('=')
+        .ok_or_else(|| format!("Invalid label '{label}': expected KEY=VALUE"))?;
+    let key = key.trim();
+    validate_label_key(key)?;
+    validate_label_value(key, value)?;
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Labels sorted by key, e.g. "role=frontend, team=web"
+pub fn format_labels<'a>(labels: impl IntoIterator<Item = (&'a String, &'a String)>) -> String {
+    let mut labels = labels.into_iter().collect::<Vec<_>>();
+    labels.sort();
+    labels
+        .into_iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// One condition of a label selector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LabelRequirement {
+    Equals(String, String),
+    NotEquals(String, String),
+    Exists(String),
+    NotExists(String),
+}
+
+impl LabelRequirement {
+    fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        match self {
+            Self::Equals(key, value) => labels.get(key) == Some(value),
+            Self::NotEquals(key, value) => labels.get(key) != Some(value),
+            Self::Exists(key) => labels.contains_key(key),
+            Self::NotExists(key) => !labels.contains_key(key),
+        }
+    }
+}
+
+impl FromStr for LabelRequirement {
+    type Err = String;
+
+    fn from_str(requirement: &str) -> Result<Self, Self::Err> {
+        let requirement = requirement.trim();
+        let parsed = if let Some((key, value)) = requirement.split_once("!=") {
+            Self::NotEquals(key.trim().to_string(), value.trim().to_string())
+        } else if let Some((key, value)) = requirement.split_once('=') {
+            let value = value.strip_prefix('=').unwrap_or(value);
+            Self::Equals(key.trim().to_string(), value.trim().to_string())
+        } else if let Some(key) = requirement.strip_prefix('!') {
+            Self::NotExists(key.trim().to_string())
+        } else {
+            Self::Exists(requirement.to_string())
+        };
+        let key = match &parsed {
+            Self::Equals(key, _)
+            | Self::NotEquals(key, _)
+            | Self::Exists(key)
+            | Self::NotExists(key) => key,
+        };
+        validate_label_key(key).map_err(|e| format!("Invalid selector '{requirement}': {e}"))?;
+        Ok(parsed)
+    }
+}
+
+impl fmt::Display for LabelRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Equals(key, value) => write!(f, "{key}={value}"),
+            Self::NotEquals(key, value) => write!(f, "{key}!={value}"),
+            Self::Exists(key) => write!(f, "{key}"),
+            Self::NotExists(key) => write!(f, "!{key}"),
+        }
+    }
+}
+
+/// Requirements on the labels of a process, all of which must hold
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LabelSelector {
+    requirements: Vec<LabelRequirement>,
+}
+
+impl LabelSelector {
+    /// Combine selectors given separately, e.g. by repeated `-l` flags
+    pub fn parse_all<S: AsRef<str>>(selectors: &[S]) -> Result<Self, String> {
+        let mut requirements = Vec::new();
+        for selector in selectors {
+            requirements.extend(selector.as_ref().parse::<Self>()?.requirements);
+        }
+        Ok(Self { requirements })
+    }
+
+    /// An empty selector matches every process
+    pub fn is_empty(&self) -> bool {
+        self.requirements.is_empty()
+    }
+
+    pub fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        self.requirements
+            .iter()
+            .all(|requirement| requirement.matches(labels))
+    }
+}
+
+impl FromStr for LabelSelector {
+    type Err = String;
+
+    fn from_str(selector: &str) -> Result<Self, Self::Err> {
+        if selector.trim().is_empty() {
+            return Err("Label selector cannot be empty".to_string());
+        }
+        let requirements = selector
+            .split(',')
+            .map(str::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { requirements })
+    }
+}
+
+impl fmt::Display for LabelSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let requirements = self
+            .requirements
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        f.write_str(&requirements.join(","))
+    }
+}
+
+/// Check a selector given on the command line, keeping it as written
+pub fn parse_selector_arg(selector: &str) -> Result<String, String> {
+    selector.parse::<LabelSelector>()?;
+    Ok(selector.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn selectors_combine_requirements() {
+        let frontend = labels(&[("role", "frontend"), ("team", "web")]);
+        let worker = labels(&[("role", "worker")]);
+        let select = |selectors: &[&str]| LabelSelector::parse_all(selectors).unwrap();
+
+        assert!(select(&["role=frontend"]).matches(&frontend));
+        assert!(!select(&["role=frontend"]).matches(&worker));
+        assert!(select(&["role==worker"]).matches(&worker));
+        assert!(select(&["role!=frontend"]).matches(&worker));
+        assert!(select(&["team"]).matches(&frontend));
+        assert!(select(&["!team"]).matches(&worker));
+        assert!(!select(&["role=frontend", "!team"]).matches(&frontend));
+        assert!(select(&["role=frontend, team=web"]).matches(&frontend));
+        assert!(LabelSelector::default().matches(&worker));
+        assert_eq!(
+            select(&["role = frontend", "!team"]).to_string(),
+            "role=frontend,!team"
+        );
+    }
+
+    #[test]
+    fn invalid_labels_and_selectors_are_rejected() {
+        assert_eq!(
+            parse_label("role=front end").unwrap(),
+            ("role".to_string(), "front end".to_string())
+        );
+        assert!(parse_label("role").is_err());
+        assert!(parse_label("=frontend").is_err());
+        assert!(parse_label("ro le=frontend").is_err());
+        assert!(parse_label("role=a,b").is_err());
+        assert!("".parse::<LabelSelector>().is_err());
+        assert!("role=frontend,".parse::<LabelSelector>().is_err());
+        assert!("=x".parse::<LabelSelector>().is_err());
+        assert!(validate_description(&"x".repeat(MAX_DESCRIPTION_LEN + 1)).is_err());
+    }
+}
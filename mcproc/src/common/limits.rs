@@ -0,0 +1,146 @@
+//! Resource limit parsing utilities
+
+use serde::Deserialize;
+
+/// Binary size suffixes, as used by cgroups and container runtimes
+const SIZE_UNITS: [(&str, u64); 4] = [
+    ("T", 1 << 40),
+    ("G", 1 << 30),
+    ("M", 1 << 20),
+    ("K", 1 << 10),
+];
+
+/// Resource limits as written in a manifest, on the command line or in an MCP call
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LimitsSpec {
+    /// Memory ceiling, e.g. "2G" or "512M"
+    pub memory: Option<String>,
+    /// CPU quota in cores, e.g. 1.5
+    pub cpus: Option<f64>,
+    /// Maximum number of processes and threads
+    pub pids: Option<u32>,
+    /// Maximum number of open file descriptors
+    pub open_files: Option<u64>,
+}
+
+impl LimitsSpec {
+    /// Convert into the request message, or None when no limit is set
+    pub fn into_proto(self) -> Result<Option<proto::ResourceLimits>, String> {
+        if self.memory.is_none()
+            && self.cpus.is_none()
+            && self.pids.is_none()
+            && self.open_files.is_none()
+        {
+            return Ok(None);
+        }
+        Ok(Some(proto::ResourceLimits {
+            memory_bytes: self.memory.as_deref().map(parse_size).transpose()?,
+            cpus: self.cpus,
+            pids: self.pids,
+            open_files: self.open_files,
+        }))
+    }
+}
+
+/// Parse a size such as `2G`, `512M`, `1.5G` or `1048576` into bytes
+pub fn parse_size(size: &str) -> Result<u64, String> {
+    let invalid = || format!("Invalid size: {size} (expected e.g. 512M or 2G)");
+    let trimmed = size.trim();
+    let upper = trimmed.to_ascii_uppercase();
+    let number = upper
+        .strip_suffix("IB")
+        .or_else(|| upper.strip_suffix('B'))
+        .unwrap_or(&upper);
+    let (number, multiplier) = SIZE_UNITS
+        .iter()
+        .find_map(|(suffix, multiplier)| {
+            number
+                .strip_suffix(suffix)
+                .map(|number| (number, *multiplier))
+        })
+        .unwrap_or((number, 1));
+    let value = number.trim().parse::<f64>().map_err(|_| invalid())?;
+    if !value.is_finite() || value <= 0.0 {
+        return Err(invalid());
+    }
+    let bytes = value * multiplier as f64;
+    if bytes < 1.0 || bytes >= u64::MAX as f64 {
+        return Err(invalid());
+    }
+    Ok(bytes as u64)
+}
+
+/// Format bytes in the largest unit that divides them, e.g. "2G" or "1536M"
+pub fn format_size(bytes: u64) -> String {
+    SIZE_UNITS
+        .iter()
+        .find(|(_, multiplier)| bytes >= *multiplier && bytes % multiplier == 0)
+        .map(|(suffix, multiplier)| format!("{}{}", bytes / multiplier, suffix))
+        .unwrap_or_else(|| bytes.to_string())
+}
+
+/// Describe limits for display, e.g. "memory 2G, cpus 1.5, pids 256"
+pub fn format_limits(limits: &proto::ResourceLimits) -> String {
+    let mut parts = Vec::new();
+    if let Some(memory) = limits.memory_bytes {
+        parts.push(format!("memory {}", format_size(memory)));
+    }
+    if let Some(cpus) = limits.cpus {
+        parts.push(format!("cpus {cpus}"));
+    }
+    if let Some(pids) = limits.pids {
+        parts.push(format!("pids {pids}"));
+    }
+    if let Some(open_files) = limits.open_files {
+        parts.push(format!("open files {open_files}"));
+    }
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes_are_parsed_and_formatted_in_binary_units() {
+        assert_eq!(parse_size("2G"), Ok(2 << 30));
+        assert_eq!(parse_size("512m"), Ok(512 << 20));
+        assert_eq!(parse_size("1.5GiB"), Ok(1536 << 20));
+        assert_eq!(parse_size(" 64KB "), Ok(64 << 10));
+        assert_eq!(parse_size("1048576"), Ok(1 << 20));
+        assert!(parse_size("0").is_err());
+        assert!(parse_size("-1G").is_err());
+        assert!(parse_size("lots").is_err());
+        assert!(parse_size("G").is_err());
+
+        assert_eq!(format_size(2 << 30), "2G");
+        assert_eq!(format_size(1536 << 20), "1536M");
+        assert_eq!(format_size(1000), "1000");
+    }
+
+    #[test]
+    fn spec_without_limits_converts_to_none() {
+        assert_eq!(LimitsSpec::default().into_proto(), Ok(None));
+        let limits = LimitsSpec {
+            memory: Some("2G".to_string()),
+            cpus: Some(1.5),
+            open_files: Some(4096),
+            ..Default::default()
+        }
+        .into_proto()
+        .unwrap()
+        .unwrap();
+        assert_eq!(limits.memory_bytes, Some(2 << 30));
+        assert_eq!(
+            format_limits(&limits),
+            "memory 2G, cpus 1.5, open files 4096"
+        );
+        assert!(LimitsSpec {
+            memory: Some("2X".to_string()),
+            ..Default::default()
+        }
+        .into_proto()
+        .is_err());
+    }
+}
@@ -0,0 +1,113 @@
+//! Resource usage formatting utilities
+
+/// Bars of increasing height for sparklines
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Format bytes in the largest binary unit that fits, e.g. "512B" or "1.5G"
+pub fn format_bytes(bytes: u64) -> String {
+    let mut value = bytes as f64;
+    let mut unit = "B";
+    for next in ["K", "M", "G", "T"] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+    if unit == "B" {
+        format!("{bytes}B")
+    } else {
+        format!("{value:.1}{unit}")
+    }
+}
+
+fn seconds(sample: &proto::ProcessMetrics) -> Option<f64> {
+    sample
+        .sampled_at
+        .as_ref()
+        .map(|at| at.seconds as f64 + f64::from(at.nanos) / 1e9)
+}
+
+/// Seconds between the first and the last sample
+pub fn history_window(history: &[proto::ProcessMetrics]) -> Option<f64> {
+    let elapsed = seconds(history.last()?)? - seconds(history.first()?)?;
+    (elapsed > 0.0).then_some(elapsed)
+}
+
+/// Bytes per second read and written between the last two samples
+pub fn io_rates(history: &[proto::ProcessMetrics]) -> Option<(f64, f64)> {
+    let [.., previous, latest] = history else {
+        return None;
+    };
+    let elapsed = history_window(&history[history.len() - 2..])?;
+    let rate = |current: u64, before: u64| current.saturating_sub(before) as f64 / elapsed;
+    Some((
+        rate(latest.read_bytes, previous.read_bytes),
+        rate(latest.write_bytes, previous.write_bytes),
+    ))
+}
+
+/// Change of resident memory per minute from the first to the last sample
+pub fn rss_growth_per_minute(history: &[proto::ProcessMetrics]) -> Option<f64> {
+    let elapsed = history_window(history)?;
+    let change = history.last()?.rss_bytes as f64 - history.first()?.rss_bytes as f64;
+    Some(change / elapsed * 60.0)
+}
+
+/// Draw values as bars scaled between their minimum and maximum
+pub fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|value| {
+            if max - min <= f64::EPSILON {
+                BARS[0]
+            } else {
+                let level = (value - min) / (max - min) * (BARS.len() - 1) as f64;
+                BARS[level.round() as usize]
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(seconds: i64, rss_bytes: u64, read_bytes: u64) -> proto::ProcessMetrics {
+        proto::ProcessMetrics {
+            sampled_at: Some(prost_types::Timestamp { seconds, nanos: 0 }),
+            rss_bytes,
+            read_bytes,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn bytes_are_formatted_in_binary_units() {
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(1536), "1.5K");
+        assert_eq!(format_bytes(3 << 30), "3.0G");
+    }
+
+    #[test]
+    fn rates_and_growth_are_derived_from_the_history() {
+        let history = [
+            sample(100, 100 << 20, 0),
+            sample(130, 130 << 20, 1000),
+            sample(160, 160 << 20, 3000),
+        ];
+        assert_eq!(io_rates(&history), Some((2000.0 / 30.0, 0.0)));
+        assert_eq!(rss_growth_per_minute(&history), Some((60 << 20) as f64));
+        assert_eq!(io_rates(&history[..1]), None);
+        assert_eq!(rss_growth_per_minute(&history[..1]), None);
+    }
+
+    #[test]
+    fn sparklines_scale_between_minimum_and_maximum() {
+        assert_eq!(sparkline(&[0.0, 50.0, 100.0]), "▁▅█");
+        assert_eq!(sparkline(&[7.0, 7.0]), "▁▁");
+        assert_eq!(sparkline(&[]), "");
+    }
+}
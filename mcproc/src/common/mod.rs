@@ -1,8 +1,22 @@
 pub mod config;
+pub mod dependency;
 pub mod exit_code;
+pub mod file_watch;
+pub mod health;
+pub mod history;
+pub mod labels;
+pub mod limits;
+pub mod metrics;
 pub mod process_key;
+pub mod process_pattern;
+pub mod readiness;
+pub mod restart_policy;
+pub mod schedule;
+pub mod signal;
 pub mod status;
+pub mod task;
 pub mod timestamp;
 pub mod validation;
 pub mod version;
+pub mod watchdog;
 pub mod xdg;
@@ -0,0 +1,128 @@
+//! Name patterns selecting several processes at once
+//!
+//! A pattern is a process name with `*` (any run of characters) and `?` (any
+//! single character) wildcards, such as `worker-*`, optionally preceded by a
+//! project pattern: `myapp/*` selects every process of `myapp` and
+//! `*/worker-*` the workers of every project. Neither wildcards nor `/` are
+//! allowed in process or project names, so a pattern is never mistaken for a
+//! single process.
+
+use std::fmt;
+
+/// Whether a process name given by a client is a pattern rather than one process
+pub fn is_pattern(name: &str) -> bool {
+    name.contains(['*', '?', '/'])
+}
+
+/// Match `text` against a pattern with `*` and `?` wildcards
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    let (mut p, mut t) = (0, 0);
+    // Where the last `*` was seen, and the text position it currently covers up to
+    let mut backtrack = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` swallow one more character
+                Some((star, covered)) => {
+                    p = star + 1;
+                    t = covered + 1;
+                    backtrack = Some((star, covered + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Processes selected by project and name patterns
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessPattern {
+    project: String,
+    name: String,
+}
+
+impl ProcessPattern {
+    /// Parse `name` or `project/name`, where bare names belong to `project`
+    pub fn parse(pattern: &str, project: &str) -> Result<Self, String> {
+        let (project, name) = match pattern.split_once('/') {
+            Some((project, name)) => (project, name),
+            None => (project, pattern),
+        };
+        if project.is_empty() || name.is_empty() {
+            return Err(format!(
+                "Invalid process pattern '{pattern}': expected NAME or PROJECT/NAME"
+            ));
+        }
+        if name.contains('/') {
+            return Err(format!(
+                "Invalid process pattern '{pattern}': only one '/' may separate project and name"
+            ));
+        }
+        Ok(Self {
+            project: project.to_string(),
+            name: name.to_string(),
+        })
+    }
+
+    pub fn matches(&self, project: &str, name: &str) -> bool {
+        glob_match(&self.project, project) && glob_match(&self.name, name)
+    }
+}
+
+impl fmt::Display for ProcessPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.project, self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards_match_any_run_or_single_character() {
+        assert!(glob_match("worker-*", "worker-1"));
+        assert!(glob_match("worker-*", "worker-"));
+        assert!(!glob_match("worker-*", "web"));
+        assert!(glob_match("*-api-*", "shop-api-v2"));
+        assert!(glob_match("w?b", "web"));
+        assert!(!glob_match("w?b", "webb"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("a*b*c", "aXbYbZc"));
+        assert!(!glob_match("a*b*c", "aXbYbZ"));
+        assert!(glob_match("日本*", "日本語"));
+    }
+
+    #[test]
+    fn patterns_select_by_project_and_name() {
+        let workers = ProcessPattern::parse("worker-*", "shop").unwrap();
+        assert!(workers.matches("shop", "worker-1"));
+        assert!(!workers.matches("blog", "worker-1"));
+
+        let shop = ProcessPattern::parse("shop/*", "other").unwrap();
+        assert!(shop.matches("shop", "web"));
+        assert!(!shop.matches("other", "web"));
+        assert_eq!(shop.to_string(), "shop/*");
+
+        let everywhere = ProcessPattern::parse("*/worker-*", "shop").unwrap();
+        assert!(everywhere.matches("blog", "worker-2"));
+
+        assert!(is_pattern("shop/web"));
+        assert!(!is_pattern("web"));
+        assert!(ProcessPattern::parse("/web", "shop").is_err());
+        assert!(ProcessPattern::parse("shop/", "shop").is_err());
+        assert!(ProcessPattern::parse("a/b/c", "shop").is_err());
+    }
+}
@@ -0,0 +1,105 @@
+//! Readiness probe parsing utilities
+
+use proto::readiness_probe::Probe;
+
+/// Parse a TCP probe target (`PORT` or `HOST:PORT`)
+pub fn parse_tcp_probe(target: &str) -> Result<proto::ReadinessProbe, String> {
+    let (host, port) = match target.rsplit_once(':') {
+        Some((host, port)) => (Some(host.trim_matches(['[', ']']).to_string()), port),
+        None => (None, target),
+    };
+    let port = port
+        .parse::<u16>()
+        .ok()
+        .filter(|port| *port != 0)
+        .ok_or_else(|| {
+            format!("Invalid TCP probe target: {target} (expected PORT or HOST:PORT)")
+        })?;
+    Ok(proto::ReadinessProbe {
+        probe: Some(Probe::Tcp(proto::TcpProbe {
+            host,
+            port: u32::from(port),
+        })),
+    })
+}
+
+/// Parse an HTTP status range (`200` or `200-299`)
+pub fn parse_status_range(range: &str) -> Result<(u32, u32), String> {
+    let parse = |value: &str| {
+        value
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|status| (100..=599).contains(status))
+            .ok_or_else(|| format!("Invalid HTTP status range: {range} (expected e.g. 200-299)"))
+    };
+    let (min, max) = match range.split_once('-') {
+        Some((min, max)) => (parse(min)?, parse(max)?),
+        None => {
+            let status = parse(range)?;
+            (status, status)
+        }
+    };
+    if min > max {
+        return Err(format!("Invalid HTTP status range: {range}"));
+    }
+    Ok((min, max))
+}
+
+/// Build an HTTP probe, optionally restricted to a status range
+pub fn http_probe(url: &str, status: Option<&str>) -> Result<proto::ReadinessProbe, String> {
+    if !url.starts_with("http://") {
+        return Err(format!("HTTP probe URL must start with http://: {url}"));
+    }
+    let range = status.map(parse_status_range).transpose()?;
+    Ok(proto::ReadinessProbe {
+        probe: Some(Probe::Http(proto::HttpProbe {
+            url: url.to_string(),
+            min_status: range.map(|(min, _)| min),
+            max_status: range.map(|(_, max)| max),
+        })),
+    })
+}
+
+/// Build a probe that succeeds once the process listens on any port
+pub fn port_detected_probe() -> proto::ReadinessProbe {
+    proto::ReadinessProbe {
+        probe: Some(Probe::PortDetected(proto::PortDetectedProbe {})),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tcp_targets_accept_port_or_host_and_port() {
+        let probe = parse_tcp_probe("5432").unwrap();
+        assert_eq!(
+            probe.probe,
+            Some(Probe::Tcp(proto::TcpProbe {
+                host: None,
+                port: 5432
+            }))
+        );
+        let probe = parse_tcp_probe("db.local:6379").unwrap();
+        assert_eq!(
+            probe.probe,
+            Some(Probe::Tcp(proto::TcpProbe {
+                host: Some("db.local".to_string()),
+                port: 6379
+            }))
+        );
+        assert!(parse_tcp_probe("localhost").is_err());
+        assert!(parse_tcp_probe("0").is_err());
+    }
+
+    #[test]
+    fn status_ranges_are_parsed_and_checked() {
+        assert_eq!(parse_status_range("200-299").unwrap(), (200, 299));
+        assert_eq!(parse_status_range("204").unwrap(), (204, 204));
+        assert!(parse_status_range("299-200").is_err());
+        assert!(parse_status_range("ok").is_err());
+        assert!(http_probe("https://localhost/", None).is_err());
+    }
+}
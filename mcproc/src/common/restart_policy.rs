@@ -0,0 +1,40 @@
+//! Restart policy parsing and formatting utilities
+
+/// Parse a restart policy name (never, on-failure, always)
+pub fn parse_restart_policy(policy: &str) -> Result<proto::RestartPolicy, String> {
+    match policy.to_ascii_lowercase().replace('_', "-").as_str() {
+        "never" | "no" => Ok(proto::RestartPolicy::Never),
+        "on-failure" => Ok(proto::RestartPolicy::OnFailure),
+        "always" => Ok(proto::RestartPolicy::Always),
+        _ => Err(format!(
+            "Invalid restart policy: {policy} (expected never, on-failure or always)"
+        )),
+    }
+}
+
+/// Format restart policy as string (from i32)
+pub fn format_restart_policy(policy: i32) -> &'static str {
+    match proto::RestartPolicy::try_from(policy).unwrap_or(proto::RestartPolicy::Never) {
+        proto::RestartPolicy::Never => "never",
+        proto::RestartPolicy::OnFailure => "on-failure",
+        proto::RestartPolicy::Always => "always",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_policy_names_and_round_trips_through_format() {
+        for name in ["never", "on-failure", "always"] {
+            let policy = parse_restart_policy(name).unwrap();
+            assert_eq!(format_restart_policy(policy as i32), name);
+        }
+        assert_eq!(
+            parse_restart_policy("ON_FAILURE").unwrap(),
+            proto::RestartPolicy::OnFailure
+        );
+        assert!(parse_restart_policy("sometimes").is_err());
+    }
+}
@@ -0,0 +1,375 @@
+//! Schedules of recurring processes
+//!
+//! A schedule is either a five-field cron expression evaluated in local time
+//! (`*/15 * * * *`, `0 3 * * mon-fri`), one of the macros `@hourly`, `@daily`,
+//! `@weekly`, `@monthly` and `@yearly`, or a fixed interval (`every 10m`).
+
+use crate::common::watchdog::{format_duration_secs, parse_duration_secs};
+use chrono::{
+    DateTime, Datelike, Duration as ChronoDuration, Local, NaiveDate, NaiveDateTime, TimeZone,
+    Timelike, Utc,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How far ahead a cron expression is searched for its next match
+const SEARCH_DAYS: i64 = 5 * 366;
+
+/// Longest month of each month, so that expressions like `0 0 30 2 *` are rejected
+const MAX_MONTH_DAYS: [u32; 12] = [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+const WEEKDAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// When a recurring process runs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Schedule {
+    /// Cron expression, evaluated in local time
+    Cron(CronSchedule),
+    /// Fixed time between the starts of consecutive runs; the first run starts right away
+    Every(Duration),
+}
+
+impl Schedule {
+    /// When the run after `previous` is due, or the first run when there was none
+    ///
+    /// A run that is overdue, because the daemon was busy or the previous run
+    /// was skipped, is due now rather than repeated for every missed slot.
+    pub fn next_run(
+        &self,
+        previous: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Every(interval) => Some(match previous {
+                Some(previous) => (previous + *interval).max(now),
+                None => now,
+            }),
+            Self::Cron(cron) => cron.next_after(previous.map_or(now, |previous| previous.max(now))),
+        }
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = String;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let expression = expression.trim();
+        let lower = expression.to_ascii_lowercase();
+        if let Some(interval) = lower
+            .strip_prefix("@every ")
+            .or_else(|| lower.strip_prefix("every "))
+        {
+            let secs = parse_duration_secs(interval)
+                .map_err(|e| format!("Invalid schedule '{expression}': {e}"))?;
+            if secs == 0 {
+                return Err(format!(
+                    "Invalid schedule '{expression}': the interval must be at least 1 second"
+                ));
+            }
+            return Ok(Self::Every(Duration::from_secs(u64::from(secs))));
+        }
+        let fields = match lower.as_str() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            macro_name if macro_name.starts_with('@') => {
+                return Err(format!(
+                    "Invalid schedule '{expression}': expected @hourly, @daily, @weekly, @monthly, @yearly or @every DURATION"
+                ))
+            }
+            _ => expression,
+        };
+        CronSchedule::parse(expression, fields).map(Self::Cron)
+    }
+}
+
+/// Check a schedule expression, keeping it as written
+pub fn parse_schedule(expression: &str) -> Result<String, String> {
+    expression.parse::<Schedule>()?;
+    Ok(expression.trim().to_string())
+}
+
+impl TryFrom<String> for Schedule {
+    type Error = String;
+
+    fn try_from(expression: String) -> Result<Self, Self::Error> {
+        expression.parse()
+    }
+}
+
+impl From<Schedule> for String {
+    fn from(schedule: Schedule) -> Self {
+        schedule.to_string()
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cron(cron) => f.write_str(&cron.expression),
+            Self::Every(interval) => write!(
+                f,
+                "every {}",
+                format_duration_secs(u32::try_from(interval.as_secs()).unwrap_or(u32::MAX))
+            ),
+        }
+    }
+}
+
+/// Parsed cron expression: minute, hour, day of month, month and day of week
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    /// The expression as written
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    /// Sunday is 0
+    weekdays: u64,
+    /// As in cron, a date matches either day field when both are restricted
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl CronSchedule {
+    fn parse(expression: &str, fields: &str) -> Result<Self, String> {
+        let invalid = |reason: String| format!("Invalid schedule '{expression}': {reason}");
+        let fields: Vec<&str> = fields.split_whitespace().collect();
+        let [minutes, hours, days, months, weekdays] = fields[..] else {
+            return Err(invalid(format!(
+                "expected 5 cron fields (minute hour day month weekday) or 'every DURATION', found {}",
+                fields.len()
+            )));
+        };
+
+        let mut weekday_bits =
+            parse_field(weekdays, "weekday", 0, 7, &WEEKDAY_NAMES).map_err(&invalid)?;
+        // Both 0 and 7 are Sunday
+        if weekday_bits & (1 << 7) != 0 {
+            weekday_bits = (weekday_bits | 1) & !(1 << 7);
+        }
+        let schedule = Self {
+            expression: expression.to_string(),
+            minutes: parse_field(minutes, "minute", 0, 59, &[]).map_err(&invalid)?,
+            hours: parse_field(hours, "hour", 0, 23, &[]).map_err(&invalid)?,
+            days: parse_field(days, "day", 1, 31, &[]).map_err(&invalid)?,
+            months: parse_field(months, "month", 1, 12, &MONTH_NAMES).map_err(&invalid)?,
+            weekdays: weekday_bits,
+            days_restricted: !days.starts_with('*'),
+            weekdays_restricted: !weekdays.starts_with('*'),
+        };
+
+        let some_day_exists = (1..=12u32).any(|month| {
+            has(schedule.months, month)
+                && (1..=MAX_MONTH_DAYS[month as usize - 1]).any(|day| has(schedule.days, day))
+        });
+        if schedule.days_restricted && !schedule.weekdays_restricted && !some_day_exists {
+            return Err(invalid("the day and month never match a date".to_string()));
+        }
+        Ok(schedule)
+    }
+
+    /// First matching minute strictly after `after`
+    ///
+    /// Local times skipped by a daylight saving change never match; times that
+    /// occur twice match once, on their first occurrence.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let local = after.with_timezone(&Local).naive_local();
+        let mut time =
+            local.date().and_hms_opt(local.hour(), local.minute(), 0)? + ChronoDuration::minutes(1);
+        let limit = time + ChronoDuration::days(SEARCH_DAYS);
+        while time < limit {
+            time = if !has(self.months, time.month()) {
+                let (year, month) = match time.month() {
+                    12 => (time.year() + 1, 1),
+                    month => (time.year(), month + 1),
+                };
+                NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?
+            } else if !self.matches_day(time.date()) {
+                time.date().succ_opt()?.and_hms_opt(0, 0, 0)?
+            } else if !has(self.hours, time.hour()) {
+                time.date().and_hms_opt(time.hour(), 0, 0)? + ChronoDuration::hours(1)
+            } else if !has(self.minutes, time.minute()) {
+                time + ChronoDuration::minutes(1)
+            } else {
+                match resolve_local(time) {
+                    Some(at) if at > after => return Some(at),
+                    _ => time + ChronoDuration::minutes(1),
+                }
+            };
+        }
+        None
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day = has(self.days, date.day());
+        let weekday = has(self.weekdays, date.weekday().num_days_from_sunday());
+        if self.days_restricted && self.weekdays_restricted {
+            day || weekday
+        } else {
+            day && weekday
+        }
+    }
+}
+
+fn resolve_local(time: NaiveDateTime) -> Option<DateTime<Utc>> {
+    Local
+        .from_local_datetime(&time)
+        .earliest()
+        .map(|at| at.with_timezone(&Utc))
+}
+
+fn has(bits: u64, value: u32) -> bool {
+    bits & (1 << value) != 0
+}
+
+/// Parse a comma-separated list of `*`, `N`, `N-M` and `/STEP` items into a bitset
+fn parse_field(field: &str, name: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let value = |text: &str| -> Result<u32, String> {
+        let lower = text.to_ascii_lowercase();
+        let parsed = match names.iter().position(|name| *name == lower) {
+            Some(index) => min + index as u32,
+            None => text
+                .parse()
+                .map_err(|_| format!("invalid {name} '{text}'"))?,
+        };
+        if (min..=max).contains(&parsed) {
+            Ok(parsed)
+        } else {
+            Err(format!("{name} {parsed} is out of range {min}-{max}"))
+        }
+    };
+
+    let mut bits = 0;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => match step.parse::<u32>() {
+                Ok(step) if step > 0 => (range, Some(step)),
+                _ => return Err(format!("invalid {name} step '{step}'")),
+            },
+            None => (item, None),
+        };
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (value(start)?, value(end)?),
+            // `N/STEP` runs from N to the end of the range
+            None if step.is_some() => (value(range)?, max),
+            None => {
+                let single = value(range)?;
+                (single, single)
+            }
+        };
+        if start > end {
+            return Err(format!("invalid {name} range '{range}'"));
+        }
+        for value in (start..=end).step_by(step.unwrap_or(1) as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Local
+            .with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn next(expression: &str, after: DateTime<Utc>) -> DateTime<Utc> {
+        match expression.parse::<Schedule>().unwrap() {
+            Schedule::Cron(cron) => cron.next_after(after).unwrap(),
+            Schedule::Every(_) => panic!("{expression} is not a cron expression"),
+        }
+    }
+
+    #[test]
+    fn schedules_are_parsed_and_formatted_back() {
+        assert_eq!(
+            "every 10m".parse::<Schedule>(),
+            Ok(Schedule::Every(Duration::from_secs(600)))
+        );
+        assert_eq!(
+            "@every 90".parse::<Schedule>().unwrap().to_string(),
+            "every 90s"
+        );
+        for expression in [
+            "*/15 * * * *",
+            "0 9-17 * * mon-fri",
+            "@daily",
+            "30 2 1,15 jan,jul *",
+        ] {
+            assert_eq!(
+                expression.parse::<Schedule>().unwrap().to_string(),
+                expression
+            );
+        }
+
+        for invalid in [
+            "",
+            "every 0s",
+            "every soon",
+            "@sometimes",
+            "* * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "5-1 * * * *",
+            "*/0 * * * *",
+            "* * * foo *",
+            "0 0 30 feb *",
+        ] {
+            assert!(invalid.parse::<Schedule>().is_err(), "{invalid:?} parsed");
+        }
+    }
+
+    #[test]
+    fn cron_expressions_find_the_next_matching_minute() {
+        // Tuesday
+        let now = local(2026, 3, 10, 12, 7) + ChronoDuration::seconds(30);
+        assert_eq!(next("*/15 * * * *", now), local(2026, 3, 10, 12, 15));
+        assert_eq!(next("7 * * * *", now), local(2026, 3, 10, 13, 7));
+        assert_eq!(next("@daily", now), local(2026, 3, 11, 0, 0));
+        assert_eq!(next("0 9 * * mon", now), local(2026, 3, 16, 9, 0));
+        assert_eq!(next("0 9 * * 7", now), local(2026, 3, 15, 9, 0));
+        assert_eq!(next("0 0 1 */2 *", now), local(2026, 5, 1, 0, 0));
+        assert_eq!(next("@yearly", now), local(2027, 1, 1, 0, 0));
+        assert_eq!(next("0 0 29 2 *", now), local(2028, 2, 29, 0, 0));
+        // Either day field matches when both are restricted
+        assert_eq!(next("0 0 20 * fri", now), local(2026, 3, 13, 0, 0));
+    }
+
+    #[test]
+    fn next_run_does_not_repeat_missed_runs() {
+        let now = local(2026, 3, 10, 12, 7);
+        let every = Schedule::Every(Duration::from_secs(600));
+        assert_eq!(every.next_run(None, now), Some(now));
+        assert_eq!(
+            every.next_run(Some(now), now),
+            Some(now + ChronoDuration::minutes(10))
+        );
+        let late = now + ChronoDuration::minutes(25);
+        assert_eq!(every.next_run(Some(now), late), Some(late));
+
+        // A run that woke up a little early is not run twice
+        let cron = "0 * * * *".parse::<Schedule>().unwrap();
+        let due = local(2026, 3, 10, 13, 0);
+        assert_eq!(
+            cron.next_run(Some(due), due - ChronoDuration::milliseconds(5)),
+            Some(local(2026, 3, 10, 14, 0))
+        );
+    }
+}
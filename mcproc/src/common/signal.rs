@@ -0,0 +1,41 @@
+//! Signal name parsing utilities
+
+use nix::sys::signal::Signal;
+use std::str::FromStr;
+
+/// Parse a signal given as `SIGINT`, `INT`, `int` or a signal number
+pub fn parse_signal(value: &str) -> Result<Signal, String> {
+    let value = value.trim();
+    if let Ok(number) = value.parse::<i32>() {
+        return Signal::try_from(number).map_err(|_| format!("Invalid signal number: {value}"));
+    }
+    let upper = value.to_ascii_uppercase();
+    let name = if upper.starts_with("SIG") {
+        upper
+    } else {
+        format!("SIG{upper}")
+    };
+    Signal::from_str(&name).map_err(|_| format!("Unknown signal: {value}"))
+}
+
+/// Parse a signal and return its canonical name, e.g. `SIGINT`
+pub fn normalize_signal(value: &str) -> Result<String, String> {
+    parse_signal(value).map(|signal| signal.as_str().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signals_parse_by_name_or_number() {
+        assert_eq!(parse_signal("SIGINT"), Ok(Signal::SIGINT));
+        assert_eq!(parse_signal("int"), Ok(Signal::SIGINT));
+        assert_eq!(parse_signal(" hup "), Ok(Signal::SIGHUP));
+        assert_eq!(parse_signal("15"), Ok(Signal::SIGTERM));
+        assert!(parse_signal("SIGNOPE").is_err());
+        assert!(parse_signal("0").is_err());
+        assert!(parse_signal("").is_err());
+        assert_eq!(normalize_signal("2"), Ok("SIGINT".to_string()));
+    }
+}
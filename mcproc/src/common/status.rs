@@ -11,6 +11,10 @@ pub fn format_status_enum(status: proto::ProcessStatus) -> String {
         proto::ProcessStatus::Stopping => "Stopping".to_string(),
         proto::ProcessStatus::Stopped => "Stopped".to_string(),
         proto::ProcessStatus::Failed => "Failed".to_string(),
+        proto::ProcessStatus::CrashLoop => "CrashLoop".to_string(),
+        proto::ProcessStatus::Paused => "Paused".to_string(),
+        proto::ProcessStatus::Succeeded => "Succeeded".to_string(),
+        proto::ProcessStatus::Scheduled => "Scheduled".to_string(),
     }
 }
 
@@ -21,6 +25,24 @@ pub fn format_status(status: i32) -> String {
     format_status_enum(status_enum)
 }
 
+/// Parse a status name given on the command line into a status filter
+pub fn parse_status(status: &str) -> Result<i32, String> {
+    let status = match status.to_ascii_lowercase().as_str() {
+        "unknown" => proto::ProcessStatus::Unknown,
+        "starting" => proto::ProcessStatus::Starting,
+        "running" => proto::ProcessStatus::Running,
+        "stopping" => proto::ProcessStatus::Stopping,
+        "stopped" => proto::ProcessStatus::Stopped,
+        "failed" => proto::ProcessStatus::Failed,
+        "crashloop" | "crash-loop" => proto::ProcessStatus::CrashLoop,
+        "paused" => proto::ProcessStatus::Paused,
+        "succeeded" => proto::ProcessStatus::Succeeded,
+        "scheduled" => proto::ProcessStatus::Scheduled,
+        _ => return Err(format!("Invalid process status: {status}")),
+    };
+    Ok(status as i32)
+}
+
 /// Format process status as colored string for display
 pub fn format_status_colored(status: i32) -> colored::ColoredString {
     let status_enum =
@@ -39,5 +61,9 @@ pub fn format_status_colored_enum(status: proto::ProcessStatus) -> colored::Colo
         proto::ProcessStatus::Stopping => status_str.yellow(),
         proto::ProcessStatus::Stopped => status_str.red(),
         proto::ProcessStatus::Failed => status_str.red().bold(),
+        proto::ProcessStatus::CrashLoop => status_str.red().bold(),
+        proto::ProcessStatus::Paused => status_str.cyan(),
+        proto::ProcessStatus::Succeeded => status_str.green(),
+        proto::ProcessStatus::Scheduled => status_str.cyan(),
     }
 }
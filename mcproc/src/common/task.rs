@@ -0,0 +1,44 @@
+//! Task result formatting shared by the CLI and MCP tool
+
+/// Describe how a task ended, e.g. "succeeded in 3.2s (exit code 0)"
+pub fn format_task_outcome(result: &proto::TaskResult) -> String {
+    let secs = result.duration_ms as f64 / 1000.0;
+    match result.exit_code {
+        _ if result.timed_out => format!("timed out after {secs:.1}s"),
+        Some(code) if result.succeeded => format!("succeeded in {secs:.1}s (exit code {code})"),
+        Some(code) => format!("failed in {secs:.1}s with exit code {code}"),
+        None => format!("failed in {secs:.1}s: terminated by a signal"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcomes_mention_duration_and_exit_code() {
+        let result = |exit_code, succeeded, timed_out| proto::TaskResult {
+            exit_code,
+            succeeded,
+            timed_out,
+            duration_ms: 3_240,
+            ..Default::default()
+        };
+        assert_eq!(
+            format_task_outcome(&result(Some(0), true, false)),
+            "succeeded in 3.2s (exit code 0)"
+        );
+        assert_eq!(
+            format_task_outcome(&result(Some(2), false, false)),
+            "failed in 3.2s with exit code 2"
+        );
+        assert_eq!(
+            format_task_outcome(&result(None, false, false)),
+            "failed in 3.2s: terminated by a signal"
+        );
+        assert_eq!(
+            format_task_outcome(&result(None, false, true)),
+            "timed out after 3.2s"
+        );
+    }
+}
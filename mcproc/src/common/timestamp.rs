@@ -14,6 +14,29 @@ pub fn format_timestamp_local(timestamp: Option<&prost_types::Timestamp>) -> Str
         .unwrap_or_default()
 }
 
+/// Format a prost timestamp compactly in local time: the time of day when it
+/// falls on today, otherwise the date and minute
+pub fn format_timestamp_short(timestamp: Option<&prost_types::Timestamp>) -> String {
+    timestamp
+        .and_then(|ts| DateTime::<Utc>::from_timestamp(ts.seconds, ts.nanos as u32))
+        .map(|utc| {
+            let local = utc.with_timezone(&Local);
+            if local.date_naive() == Local::now().date_naive() {
+                local.format("%H:%M:%S").to_string()
+            } else {
+                local.format("%Y-%m-%d %H:%M").to_string()
+            }
+        })
+        .unwrap_or_default()
+}
+
+/// Format a prost timestamp as RFC 3339 in UTC
+pub fn format_timestamp_rfc3339(timestamp: &prost_types::Timestamp) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp.seconds, timestamp.nanos as u32)
+        .map(|utc| utc.to_rfc3339())
+        .unwrap_or_default()
+}
+
 /// Format UTC datetime to RFC 3339 format with milliseconds
 pub fn format_datetime_utc_with_tz(dt: DateTime<Utc>) -> String {
     // Use RFC 3339 format for better compatibility
@@ -1,4 +1,6 @@
-//! Validation functions for project and process names
+//! Validation functions for project, process and port names
+
+use std::collections::HashSet;
 
 /// Validate project name
 /// Ensures the project name is valid for use as a directory name and identifier
@@ -94,6 +96,26 @@ pub fn validate_process_name(name: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Check requested port names: non-empty, unique, letters, digits, '-' and '_'
+pub fn validate_port_names(names: &[String]) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    for name in names {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!(
+                "Invalid port name '{name}': use letters, digits, '-' and '_'"
+            ));
+        }
+        if !seen.insert(name.to_ascii_lowercase()) {
+            return Err(format!("Duplicate port name '{name}'"));
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -197,4 +219,13 @@ mod tests {
         let max_name = "a".repeat(100);
         assert!(validate_process_name(&max_name).is_ok());
     }
+
+    #[test]
+    fn test_validate_port_names() {
+        let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert!(validate_port_names(&names(&["http", "hmr_2"])).is_ok());
+        assert!(validate_port_names(&names(&["http", "HTTP"])).is_err());
+        assert!(validate_port_names(&names(&["a b"])).is_err());
+        assert!(validate_port_names(&names(&[""])).is_err());
+    }
 }
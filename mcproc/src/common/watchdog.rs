@@ -0,0 +1,171 @@
+//! Watchdog rule parsing utilities
+//!
+//! Rules are written as `ACTION if CONDITION [and CONDITION...] [for DURATION]`,
+//! for example `restart if rss > 3G for 60s` or
+//! `flag stalled if no-output and cpu < 1 for 15m`.
+
+use crate::common::limits::{format_size, parse_size};
+use proto::WatchdogAction;
+
+/// Label of a flag rule that does not name one
+pub const DEFAULT_FLAG: &str = "flagged";
+
+/// Parse a rule such as `stop if cpu > 95% for 10m`
+pub fn parse_watchdog_rule(rule: &str) -> Result<proto::WatchdogRule, String> {
+    let invalid = |reason: &str| format!("Invalid watchdog rule '{rule}': {reason}");
+    let (action, rest) = rule
+        .trim()
+        .split_once(" if ")
+        .ok_or_else(|| invalid("expected e.g. 'restart if rss > 3G for 60s'"))?;
+
+    let mut parsed = proto::WatchdogRule::default();
+    let mut words = action.split_whitespace();
+    match (words.next(), words.next(), words.next()) {
+        (Some("restart"), None, _) => parsed.set_action(WatchdogAction::Restart),
+        (Some("stop"), None, _) => parsed.set_action(WatchdogAction::Stop),
+        (Some("flag"), label, None) => {
+            parsed.set_action(WatchdogAction::Flag);
+            parsed.flag = label.map(str::to_string);
+        }
+        _ => return Err(invalid("action must be restart, stop or flag [LABEL]")),
+    }
+
+    let (conditions, duration) = match rest.rsplit_once(" for ") {
+        Some((conditions, duration)) => (conditions, Some(duration)),
+        None => (rest, None),
+    };
+    if let Some(duration) = duration {
+        parsed.for_secs = parse_duration_secs(duration).map_err(|e| invalid(&e))?;
+    }
+
+    for condition in conditions.split(" and ").map(str::trim) {
+        if matches!(condition, "no-output" | "no_output") {
+            parsed.no_output = true;
+            continue;
+        }
+        let (metric, above, value) = match condition.split_once(['>', '<']) {
+            Some((metric, value)) => (metric.trim(), condition.contains('>'), value.trim()),
+            None => return Err(invalid(&format!("unknown condition '{condition}'"))),
+        };
+        match (metric, above) {
+            ("rss", true) => parsed.rss_above_bytes = Some(parse_size(value)?),
+            ("cpu", _) => {
+                let percent = value
+                    .trim_end_matches('%')
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|percent| percent.is_finite() && *percent >= 0.0)
+                    .ok_or_else(|| invalid(&format!("invalid CPU percentage '{value}'")))?;
+                if above {
+                    parsed.cpu_above_percent = Some(percent);
+                } else {
+                    parsed.cpu_below_percent = Some(percent);
+                }
+            }
+            _ => return Err(invalid(&format!("unknown condition '{condition}'"))),
+        }
+    }
+    Ok(parsed)
+}
+
+/// Parse a duration such as `90`, `60s`, `10m` or `1h` into seconds
+pub(crate) fn parse_duration_secs(duration: &str) -> Result<u32, String> {
+    let duration = duration.trim();
+    let (number, multiplier) = match duration.char_indices().last() {
+        Some((index, 's')) => (&duration[..index], 1),
+        Some((index, 'm')) => (&duration[..index], 60),
+        Some((index, 'h')) => (&duration[..index], 3600),
+        _ => (duration, 1),
+    };
+    number
+        .trim()
+        .parse::<u32>()
+        .ok()
+        .and_then(|number| number.checked_mul(multiplier))
+        .ok_or_else(|| format!("invalid duration '{duration}' (expected e.g. 60s or 10m)"))
+}
+
+/// Format seconds the way durations are written, e.g. `10m`
+pub(crate) fn format_duration_secs(secs: u32) -> String {
+    match secs {
+        secs if secs >= 3600 && secs % 3600 == 0 => format!("{}h", secs / 3600),
+        secs if secs >= 60 && secs % 60 == 0 => format!("{}m", secs / 60),
+        secs => format!("{secs}s"),
+    }
+}
+
+/// Format a rule the way it is written, e.g. "restart if rss > 3G for 60s"
+pub fn format_watchdog_rule(rule: &proto::WatchdogRule) -> String {
+    let action = match rule.action() {
+        WatchdogAction::Restart => "restart".to_string(),
+        WatchdogAction::Stop => "stop".to_string(),
+        WatchdogAction::Flag => format!("flag {}", rule.flag.as_deref().unwrap_or(DEFAULT_FLAG)),
+    };
+    let mut conditions = Vec::new();
+    if let Some(bytes) = rule.rss_above_bytes {
+        conditions.push(format!("rss > {}", format_size(bytes)));
+    }
+    if let Some(percent) = rule.cpu_above_percent {
+        conditions.push(format!("cpu > {percent}%"));
+    }
+    if let Some(percent) = rule.cpu_below_percent {
+        conditions.push(format!("cpu < {percent}%"));
+    }
+    if rule.no_output {
+        conditions.push("no-output".to_string());
+    }
+    let mut formatted = format!("{action} if {}", conditions.join(" and "));
+    if rule.for_secs > 0 {
+        formatted.push_str(&format!(" for {}", format_duration_secs(rule.for_secs)));
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_are_parsed_and_formatted_back() {
+        let rule = parse_watchdog_rule("restart if rss > 3G for 60s").unwrap();
+        assert_eq!(rule.action(), WatchdogAction::Restart);
+        assert_eq!(rule.rss_above_bytes, Some(3 << 30));
+        assert_eq!(rule.for_secs, 60);
+        assert_eq!(format_watchdog_rule(&rule), "restart if rss > 3G for 1m");
+
+        let rule = parse_watchdog_rule("stop if cpu>95% for 10m").unwrap();
+        assert_eq!(rule.action(), WatchdogAction::Stop);
+        assert_eq!(rule.cpu_above_percent, Some(95.0));
+        assert_eq!(rule.for_secs, 600);
+
+        let rule = parse_watchdog_rule("flag stalled if no-output and cpu < 1 for 15m").unwrap();
+        assert_eq!(rule.action(), WatchdogAction::Flag);
+        assert_eq!(rule.flag.as_deref(), Some("stalled"));
+        assert!(rule.no_output);
+        assert_eq!(rule.cpu_below_percent, Some(1.0));
+        assert_eq!(
+            format_watchdog_rule(&rule),
+            "flag stalled if cpu < 1% and no-output for 15m"
+        );
+
+        let rule = parse_watchdog_rule("flag if cpu > 50").unwrap();
+        assert_eq!(rule.for_secs, 0);
+        assert_eq!(format_watchdog_rule(&rule), "flag flagged if cpu > 50%");
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        for rule in [
+            "restart when rss > 3G",
+            "reboot if rss > 3G",
+            "flag a b if cpu > 1",
+            "restart if rss < 3G",
+            "restart if disk > 3G",
+            "stop if cpu > lots",
+            "stop if cpu > 90 for 10 minutes",
+        ] {
+            assert!(parse_watchdog_rule(rule).is_err(), "{rule}");
+        }
+    }
+}
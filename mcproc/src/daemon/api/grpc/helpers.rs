@@ -1,11 +1,22 @@
 use crate::common::exit_code::format_exit_reason;
+use crate::common::labels::LabelSelector;
+use crate::daemon::process::history::RunRecord;
+use crate::daemon::process::metrics::MetricsSample;
+use crate::daemon::process::port_allocator::port_env_var;
 use crate::daemon::process::proxy::ProxyInfo;
+use crate::daemon::process::schedule::ScheduledRun;
 use crate::daemon::process::ProcessStatus;
 use chrono::{DateTime, Utc};
 use proto::ProcessInfo;
 use std::path::Path;
+use tonic::Status;
 use tracing::debug;
 
+/// Combine the label selectors of a request, rejecting malformed ones
+pub fn parse_label_selector(selectors: &[String]) -> Result<LabelSelector, Status> {
+    LabelSelector::parse_all(selectors).map_err(Status::invalid_argument)
+}
+
 /// Extract port information from a process
 pub fn extract_ports(process: &ProxyInfo) -> Vec<u32> {
     if let Some(port) = process.port {
@@ -17,6 +28,84 @@ pub fn extract_ports(process: &ProxyInfo) -> Vec<u32> {
     }
 }
 
+/// Ports allocated by the daemon, with the variable each one is exported as
+pub fn allocated_ports(process: &ProxyInfo) -> Vec<proto::AllocatedPort> {
+    process
+        .allocated_ports
+        .iter()
+        .map(|(name, port)| proto::AllocatedPort {
+            name: name.clone(),
+            port: u32::from(*port),
+            env: port_env_var(name),
+        })
+        .collect()
+}
+
+/// Sockets the process was last seen listening on
+pub fn listeners(process: &ProxyInfo) -> Vec<proto::Listener> {
+    process
+        .listeners
+        .lock()
+        .map(|listeners| {
+            listeners
+                .iter()
+                .map(|listener| proto::Listener {
+                    protocol: listener.protocol.as_str().to_string(),
+                    address: listener.address.to_string(),
+                    port: u32::from(listener.port),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Convert a resource usage sample to its proto representation
+pub fn process_metrics(sample: &MetricsSample) -> proto::ProcessMetrics {
+    proto::ProcessMetrics {
+        sampled_at: create_timestamp(sample.sampled_at),
+        processes: sample.processes,
+        rss_bytes: sample.rss_bytes,
+        cpu_percent: sample.cpu_percent,
+        threads: sample.threads,
+        open_fds: sample.open_fds,
+        read_bytes: sample.read_bytes,
+        write_bytes: sample.write_bytes,
+    }
+}
+
+/// Convert the outcome of a scheduled run to its proto representation
+pub fn scheduled_run(run: &ScheduledRun) -> proto::ScheduledRun {
+    proto::ScheduledRun {
+        start_time: create_timestamp(run.start_time),
+        exit_code: run.exit_code,
+        succeeded: run.succeeded,
+        duration_ms: u64::try_from(run.duration.as_millis()).unwrap_or(u64::MAX),
+    }
+}
+
+/// Convert a run from the run history to its proto representation
+pub fn run_record(run: &RunRecord) -> proto::RunRecord {
+    proto::RunRecord {
+        id: run.id.clone(),
+        name: run.name.clone(),
+        project: run.project.clone(),
+        cmd: run.cmd.clone(),
+        args: run.args.clone(),
+        cwd: run.cwd.as_ref().map(|cwd| cwd.display().to_string()),
+        toolchain: run.toolchain.clone(),
+        started_by: run.started_by.clone(),
+        start_time: create_timestamp(run.start_time),
+        exit_time: run.exit_time.and_then(create_timestamp),
+        exit_code: run.exit_code,
+        exit_reason: run
+            .exit_reason
+            .clone()
+            .or_else(|| run.exit_code.map(format_exit_reason)),
+        status: convert_process_status(run.status),
+        ports: run.ports.iter().map(|port| u32::from(*port)).collect(),
+    }
+}
+
 /// Create a prost timestamp from a chrono DateTime
 pub fn create_timestamp(datetime: DateTime<Utc>) -> Option<prost_types::Timestamp> {
     Some(prost_types::Timestamp {
@@ -26,21 +115,26 @@ pub fn create_timestamp(datetime: DateTime<Utc>) -> Option<prost_types::Timestam
 }
 
 /// Extract exit details from a process (exit code, reason, stderr tail)
+///
+/// A reason recorded by the daemon, such as an OOM kill, takes precedence
+/// over the one derived from the exit code.
 pub fn extract_exit_details(
     process: &ProxyInfo,
     log_file_path: &Path,
 ) -> (Option<i32>, Option<String>, Option<String>) {
-    match process.exit_code.try_lock() {
-        Ok(code_guard) => {
-            if let Some(code) = *code_guard {
-                let reason = Some(format_exit_reason(code));
-                let stderr = extract_stderr_tail(log_file_path);
-                (Some(code), reason, Some(stderr))
-            } else {
-                (None, None, None)
-            }
+    let code = process.exit_code.try_lock().ok().and_then(|code| *code);
+    let reason = process
+        .exit_reason
+        .try_lock()
+        .ok()
+        .and_then(|reason| reason.clone())
+        .or_else(|| code.map(format_exit_reason));
+    match reason {
+        Some(reason) => {
+            let stderr = extract_stderr_tail(log_file_path);
+            (code, Some(reason), Some(stderr))
         }
-        Err(_) => (None, None, None),
+        None => (None, None, None),
     }
 }
 
@@ -81,6 +175,10 @@ pub fn convert_process_status(status: ProcessStatus) -> i32 {
         ProcessStatus::Stopping => proto::ProcessStatus::Stopping as i32,
         ProcessStatus::Stopped => proto::ProcessStatus::Stopped as i32,
         ProcessStatus::Failed => proto::ProcessStatus::Failed as i32,
+        ProcessStatus::CrashLoop => proto::ProcessStatus::CrashLoop as i32,
+        ProcessStatus::Paused => proto::ProcessStatus::Paused as i32,
+        ProcessStatus::Succeeded => proto::ProcessStatus::Succeeded as i32,
+        ProcessStatus::Scheduled => proto::ProcessStatus::Scheduled as i32,
     }
 }
 
@@ -97,7 +195,10 @@ pub fn create_process_info(
         .join(&process.project)
         .join(format!("{}.log", process.name.replace('/', "_")));
 
-    let (exit_code, exit_reason, stderr_tail) = if matches!(current_status, ProcessStatus::Failed) {
+    let (exit_code, exit_reason, stderr_tail) = if matches!(
+        current_status,
+        ProcessStatus::Failed | ProcessStatus::CrashLoop | ProcessStatus::Succeeded
+    ) {
         extract_exit_details(process, &log_file_path)
     } else {
         (None, None, None)
@@ -105,6 +206,44 @@ pub fn create_process_info(
 
     debug!("Generated log file path: {:?}", log_file_path);
 
+    let (health, health_error, health_failures) = process
+        .health
+        .lock()
+        .map(|health| {
+            (
+                proto::HealthStatus::from(health.status) as i32,
+                health.last_error.clone(),
+                health.consecutive_failures,
+            )
+        })
+        .unwrap_or_default();
+
+    let (metrics, metrics_history) = process
+        .metrics
+        .lock()
+        .map(|history| {
+            (
+                history.latest().map(process_metrics),
+                history.samples().map(process_metrics).collect(),
+            )
+        })
+        .unwrap_or_default();
+
+    let (next_run_time, last_run_time, scheduled_runs, skipped_runs) = process
+        .schedule_state
+        .lock()
+        .map(|state| {
+            (
+                state.next_run.and_then(create_timestamp),
+                state
+                    .last_run()
+                    .and_then(|run| create_timestamp(run.start_time)),
+                state.runs().map(scheduled_run).collect(),
+                state.skipped,
+            )
+        })
+        .unwrap_or_default();
+
     ProcessInfo {
         id: process.id.clone(),
         name: process.name.clone(),
@@ -116,11 +255,14 @@ pub fn create_process_info(
             .unwrap_or_default(),
         status: convert_process_status(current_status),
         start_time: create_timestamp(process.start_time),
-        pid: Some(process.pid),
+        // The PID of a restored process belongs to the previous daemon's run
+        pid: (!process.restored && process.pid != 0).then_some(process.pid),
         log_file: log_file_path.to_string_lossy().to_string(),
         project: process.project.clone(),
         ports: extract_ports(process),
-        wait_timeout_occurred: if process.wait_for_log.is_some() {
+        wait_timeout_occurred: if process.wait_for_log.is_some()
+            || !process.readiness_probes.is_empty()
+        {
             timeout_occurred
         } else {
             None
@@ -130,6 +272,55 @@ pub fn create_process_info(
         stderr_tail,
         log_context,
         matched_line,
+        restart_policy: proto::RestartPolicy::from(process.restart_policy) as i32,
+        restart_count: process.restart_state.count,
+        last_restart_time: process
+            .restart_state
+            .last_restart_time
+            .and_then(create_timestamp),
+        depends_on: process.depends_on.clone(),
+        ready_by: process
+            .ready_by
+            .lock()
+            .ok()
+            .and_then(|ready_by| ready_by.clone()),
+        health,
+        health_error,
+        health_failures,
+        status_reason: process.status_reason.clone(),
+        stop_signal: process.stop_policy.signal.clone(),
+        stop_command: process.stop_policy.command.clone(),
+        stop_timeout: process
+            .stop_policy
+            .timeout
+            .map(|timeout| timeout.as_secs() as u32),
+        stdin: process.stdin,
+        pty_cols: process.pty.map(|pty| u32::from(pty.cols)),
+        pty_rows: process.pty.map(|pty| u32::from(pty.rows)),
+        allocated_ports: allocated_ports(process),
+        listeners: listeners(process),
+        limits: (!process.limits.is_empty()).then(|| process.limits.into()),
+        metrics,
+        metrics_history,
+        watchdog: process.watchdog.iter().map(Into::into).collect(),
+        watch: process.watch.as_ref().map(Into::into),
+        task: process.task.as_ref().map(Into::into),
+        schedule: process.schedule.as_ref().map(ToString::to_string),
+        next_run_time,
+        last_run_time,
+        scheduled_runs,
+        skipped_runs,
+        labels: process
+            .labels
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect(),
+        description: process.description.clone(),
+        watchdog_flags: process
+            .watchdog_state
+            .lock()
+            .map(|state| state.flags(&process.watchdog))
+            .unwrap_or_default(),
     }
 }
 
@@ -170,8 +361,7 @@ pub fn create_failed_process_info(params: FailedProcessParams) -> ProcessInfo {
         exit_code: Some(params.exit_code),
         exit_reason: Some(params.exit_reason.to_string()),
         stderr_tail: Some(params.stderr.to_string()),
-        log_context: vec![],
-        matched_line: None,
+        ..Default::default()
     }
 }
 
@@ -190,6 +380,10 @@ mod tests {
             ProcessStatus::Stopping,
             ProcessStatus::Stopped,
             ProcessStatus::Failed,
+            ProcessStatus::CrashLoop,
+            ProcessStatus::Paused,
+            ProcessStatus::Succeeded,
+            ProcessStatus::Scheduled,
         ];
 
         for status in statuses {
@@ -201,11 +395,8 @@ mod tests {
                     cmd: Some("sleep 30".to_string()),
                     args: vec![],
                     cwd: Some("/tmp/work".into()),
-                    env: None,
-                    wait_for_log: None,
-                    wait_timeout: None,
-                    toolchain: None,
                     pid: 1234,
+                    ..Default::default()
                 });
                 process.set_status(status);
                 process.update_detected_port(&detected_port.into_iter().collect::<Vec<_>>());
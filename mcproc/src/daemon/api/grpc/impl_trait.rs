@@ -15,6 +15,10 @@ impl ProcessManagerService for GrpcService {
 
     type GetLogsStream = Pin<Box<dyn Stream<Item = Result<GetLogsResponse, Status>> + Send>>;
 
+    type AttachStream = Pin<Box<dyn Stream<Item = Result<AttachResponse, Status>> + Send>>;
+
+    type RunTaskStream = Pin<Box<dyn Stream<Item = Result<RunTaskResponse, Status>> + Send>>;
+
     async fn start_process(
         &self,
         request: Request<StartProcessRequest>,
@@ -29,6 +33,27 @@ impl ProcessManagerService for GrpcService {
         self.stop_process_impl(request).await
     }
 
+    async fn signal_process(
+        &self,
+        request: Request<SignalProcessRequest>,
+    ) -> Result<Response<SignalProcessResponse>, Status> {
+        self.signal_process_impl(request).await
+    }
+
+    async fn send_input(
+        &self,
+        request: Request<SendInputRequest>,
+    ) -> Result<Response<SendInputResponse>, Status> {
+        self.send_input_impl(request).await
+    }
+
+    async fn attach(
+        &self,
+        request: Request<tonic::Streaming<AttachRequest>>,
+    ) -> Result<Response<Self::AttachStream>, Status> {
+        self.attach_impl(request).await
+    }
+
     async fn restart_process(
         &self,
         request: Request<RestartProcessRequest>,
@@ -36,6 +61,13 @@ impl ProcessManagerService for GrpcService {
         self.restart_process_impl(request).await
     }
 
+    async fn run_task(
+        &self,
+        request: Request<RunTaskRequest>,
+    ) -> Result<Response<Self::RunTaskStream>, Status> {
+        self.run_task_impl(request).await
+    }
+
     async fn get_process(
         &self,
         request: Request<GetProcessRequest>,
@@ -71,10 +103,31 @@ impl ProcessManagerService for GrpcService {
         self.clean_project_impl(request).await
     }
 
+    async fn apply_manifest(
+        &self,
+        request: Request<ApplyManifestRequest>,
+    ) -> Result<Response<ApplyManifestResponse>, Status> {
+        self.apply_manifest_impl(request).await
+    }
+
     async fn get_daemon_status(
         &self,
         request: Request<GetDaemonStatusRequest>,
     ) -> Result<Response<GetDaemonStatusResponse>, Status> {
         self.get_daemon_status_impl(request).await
     }
+
+    async fn lookup_port(
+        &self,
+        request: Request<LookupPortRequest>,
+    ) -> Result<Response<LookupPortResponse>, Status> {
+        self.lookup_port_impl(request).await
+    }
+
+    async fn list_runs(
+        &self,
+        request: Request<ListRunsRequest>,
+    ) -> Result<Response<ListRunsResponse>, Status> {
+        self.list_runs_impl(request).await
+    }
 }
@@ -1,10 +1,12 @@
-use super::helpers::create_timestamp;
+use super::helpers::{create_timestamp, parse_label_selector};
 use super::service::GrpcService;
+use crate::daemon::log::segments::{self, RunSelector, SegmentRange};
 use crate::daemon::stream::{StreamEvent, StreamFilter};
 use proto::process_manager_server::ProcessManager as ProcessManagerService;
 use proto::*;
 use std::collections::HashSet;
 use std::path::Path;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
 use tonic::{Request, Response, Status};
 use tracing::{debug, error, info};
 
@@ -83,6 +85,26 @@ async fn discover_log_names(project_log_dir: &Path) -> Result<Vec<String>, std::
     Ok(names)
 }
 
+fn parse_run_selector(run: Option<&str>) -> Result<Option<RunSelector>, Status> {
+    run.map(RunSelector::parse)
+        .transpose()
+        .map_err(Status::invalid_argument)
+}
+
+/// Find the selected run in the log of a process
+///
+/// Returns its byte range and whether it is the latest run, or None when the
+/// log has no such run.
+fn find_run_segment(
+    log_file: &Path,
+    selector: &RunSelector,
+) -> Result<Option<(SegmentRange, bool)>, Status> {
+    let segments = segments::read_segments(log_file)
+        .map_err(|e| Status::internal(format!("Failed to read run index: {e}")))?;
+    let found = segments::resolve_segment(&segments, selector).map_err(Status::invalid_argument)?;
+    Ok(found.map(|(_, range, latest)| (range, latest)))
+}
+
 impl GrpcService {
     pub(super) async fn get_logs_impl(
         &self,
@@ -95,6 +117,8 @@ impl GrpcService {
         let tail = clamp_tail(req.tail.unwrap_or(100));
         let follow = req.follow.unwrap_or(false);
         let include_events = req.include_events.unwrap_or(false);
+        let run = parse_run_selector(req.run.as_deref())?;
+        let label_selector = parse_label_selector(&req.label_selector)?;
 
         let processes = self.process_manager.get_all_processes();
         let registry_matches: Vec<_> = processes
@@ -102,6 +126,7 @@ impl GrpcService {
             .filter(|process| {
                 process.project == project
                     && (process_names.is_empty() || process_names.contains(&process.name))
+                    && label_selector.matches(&process.labels)
             })
             .map(|process| {
                 crate::common::process_key::ProcessKey::new(
@@ -112,9 +137,27 @@ impl GrpcService {
             .collect();
         crate::common::validation::validate_project_name(&project)
             .map_err(|e| Status::invalid_argument(format!("Invalid project name: {e}")))?;
-        let discovered_log_names = discover_log_names(&self.config.paths.log_dir.join(&project))
-            .await
-            .map_err(|e| Status::internal(format!("Failed to list log files: {e}")))?;
+        // Labels are only known for processes the daemon still manages
+        let discovered_log_names = if label_selector.is_empty() {
+            discover_log_names(&self.config.paths.log_dir.join(&project))
+                .await
+                .map_err(|e| Status::internal(format!("Failed to list log files: {e}")))?
+        } else if registry_matches.is_empty() {
+            return Err(Status::not_found(format!(
+                "No processes in project '{}' match label selector '{}'",
+                project, label_selector
+            )));
+        } else {
+            Vec::new()
+        };
+        let process_names = if label_selector.is_empty() {
+            process_names
+        } else {
+            registry_matches
+                .iter()
+                .map(|key| key.name.clone())
+                .collect()
+        };
         let log_sources = resolve_log_sources(
             &project,
             registry_matches,
@@ -122,10 +165,44 @@ impl GrpcService {
             &discovered_log_names,
         )?;
 
+        // With a run selected, read only that run's segment of each log
+        let (log_sources, process_names) = match &run {
+            None => (
+                log_sources
+                    .into_iter()
+                    .map(|key| (key, None))
+                    .collect::<Vec<_>>(),
+                process_names,
+            ),
+            Some(selector) => {
+                let mut selected = Vec::new();
+                for key in log_sources {
+                    let log_file = self.log_hub.get_log_file_path_for_key(&key);
+                    if let Some((range, latest)) = find_run_segment(&log_file, selector)? {
+                        if follow && !latest {
+                            return Err(Status::invalid_argument(format!(
+                                "Cannot follow run '{}' of {}: only the current run can be followed",
+                                selector, key.name
+                            )));
+                        }
+                        selected.push((key, Some(range)));
+                    }
+                }
+                if selected.is_empty() {
+                    return Err(Status::not_found(format!(
+                        "No run '{}' found in the logs of project '{}'",
+                        selector, project
+                    )));
+                }
+                let names = selected.iter().map(|(key, _)| key.name.clone()).collect();
+                (selected, names)
+            }
+        };
+
         // Log the request details for debugging
         info!(
-            "get_logs request: project={}, process_names={:?}, tail={}, follow={}, include_events={}",
-            project, process_names, tail, follow, include_events
+            "get_logs request: project={}, process_names={:?}, tail={}, follow={}, include_events={}, run={:?}",
+            project, process_names, tail, follow, include_events, run
         );
 
         // Create filter based on request
@@ -149,11 +226,11 @@ impl GrpcService {
                 info!("Found {} matching log sources", log_sources.len());
 
                 // Read tail lines from each matching process's log file
-                for key in log_sources {
+                for (key, range) in log_sources {
                     let log_file = log_hub.get_log_file_path_for_key(&key);
 
                     if log_file.exists() {
-                        match tail_log_lines(&log_file, tail).await {
+                        match tail_log_lines(&log_file, tail, range).await {
                             Ok((start_idx, lines)) => {
                                 for (line_num, line) in lines.iter().enumerate() {
                                     let (timestamp, level, content) = parse_log_line(line);
@@ -219,6 +296,8 @@ impl GrpcService {
                                                             exit_code: None,
                                                             error: None,
                                                             timestamp: create_timestamp(chrono::Utc::now()),
+                                                            health: proto::HealthStatus::Unknown as i32,
+                                                            watchdog: None,
                                                         }
                                                     }
                                                     crate::daemon::process::event::ProcessEvent::Started { process_id, name, project, pid } => {
@@ -231,6 +310,8 @@ impl GrpcService {
                                                             exit_code: None,
                                                             error: None,
                                                             timestamp: create_timestamp(chrono::Utc::now()),
+                                                            health: proto::HealthStatus::Unknown as i32,
+                                                            watchdog: None,
                                                         }
                                                     }
                                                     crate::daemon::process::event::ProcessEvent::Stopping { process_id, name, project } => {
@@ -243,6 +324,8 @@ impl GrpcService {
                                                             exit_code: None,
                                                             error: None,
                                                             timestamp: create_timestamp(chrono::Utc::now()),
+                                                            health: proto::HealthStatus::Unknown as i32,
+                                                            watchdog: None,
                                                         }
                                                     }
                                                     crate::daemon::process::event::ProcessEvent::Stopped { process_id, name, project, exit_code } => {
@@ -255,6 +338,8 @@ impl GrpcService {
                                                             exit_code,
                                                             error: None,
                                                             timestamp: create_timestamp(chrono::Utc::now()),
+                                                            health: proto::HealthStatus::Unknown as i32,
+                                                            watchdog: None,
                                                         }
                                                     }
                                                     crate::daemon::process::event::ProcessEvent::Failed { process_id, name, project, error } => {
@@ -267,6 +352,41 @@ impl GrpcService {
                                                             exit_code: None,
                                                             error: Some(error),
                                                             timestamp: create_timestamp(chrono::Utc::now()),
+                                                            health: proto::HealthStatus::Unknown as i32,
+                                                            watchdog: None,
+                                                        }
+                                                    }
+                                                    crate::daemon::process::event::ProcessEvent::HealthChanged { process_id, name, project, health, error } => {
+                                                        let event_type = if health == crate::daemon::process::health::HealthStatus::Healthy {
+                                                            proto::process_lifecycle_event::EventType::Healthy
+                                                        } else {
+                                                            proto::process_lifecycle_event::EventType::Unhealthy
+                                                        };
+                                                        ProcessLifecycleEvent {
+                                                            event_type: event_type as i32,
+                                                            process_id,
+                                                            name,
+                                                            project,
+                                                            pid: None,
+                                                            exit_code: None,
+                                                            error,
+                                                            timestamp: create_timestamp(chrono::Utc::now()),
+                                                            health: proto::HealthStatus::from(health) as i32,
+                                                            watchdog: None,
+                                                        }
+                                                    }
+                                                    crate::daemon::process::event::ProcessEvent::Watchdog { process_id, name, project, reason } => {
+                                                        ProcessLifecycleEvent {
+                                                            event_type: proto::process_lifecycle_event::EventType::Watchdog as i32,
+                                                            process_id,
+                                                            name,
+                                                            project,
+                                                            pid: None,
+                                                            exit_code: None,
+                                                            error: None,
+                                                            timestamp: create_timestamp(chrono::Utc::now()),
+                                                            health: proto::HealthStatus::Unknown as i32,
+                                                            watchdog: Some(reason),
                                                         }
                                                     }
                                                 };
@@ -336,6 +456,9 @@ fn parse_log_line(line: &str) -> (Option<prost_types::Timestamp>, log_entry::Log
             return (timestamp, log_entry::LogLevel::Stderr, content.to_string());
         } else if let Some(content) = rest.strip_prefix("[INFO] ") {
             return (timestamp, log_entry::LogLevel::Stdout, content.to_string());
+        } else if let Some(content) = rest.strip_prefix("[RUN] ") {
+            // Header of a run's segment
+            return (timestamp, log_entry::LogLevel::Stdout, content.to_string());
         }
 
         // If we have a timestamp but no recognized level, return with default level
@@ -363,6 +486,19 @@ impl GrpcService {
             )));
         }
 
+        let range = match parse_run_selector(req.run.as_deref())? {
+            None => None,
+            Some(selector) => match find_run_segment(&log_file, &selector)? {
+                Some((range, _)) => Some(range),
+                None => {
+                    return Err(Status::not_found(format!(
+                        "No run '{}' found in the log of process '{}' in project '{}'",
+                        selector, req.name, req.project
+                    )))
+                }
+            },
+        };
+
         // Parse time filters
         let (since_time, until_time) =
             parse_time_filters(&req.since, &req.until, &req.last).map_err(|e| *e)?;
@@ -381,9 +517,11 @@ impl GrpcService {
             "grep_logs: Using file-based search for {}",
             log_file.display()
         );
-        let matches = grep_log_file(&log_file, &pattern, before, after, since_time, until_time)
-            .await
-            .map_err(|e| Status::internal(format!("Failed to grep log file: {}", e)))?;
+        let matches = grep_log_file(
+            &log_file, range, &pattern, before, after, since_time, until_time,
+        )
+        .await
+        .map_err(|e| Status::internal(format!("Failed to grep log file: {}", e)))?;
 
         Ok(Response::new(GrepLogsResponse { matches }))
     }
@@ -527,14 +665,30 @@ async fn next_line_lossy<R: tokio::io::AsyncBufRead + Unpin>(
     Ok(Some(String::from_utf8_lossy(bytes).into_owned()))
 }
 
+/// Open a log file for reading, limited to one run's segment when a range is given
+async fn open_log_reader(
+    log_file: &std::path::Path,
+    range: Option<SegmentRange>,
+) -> Result<tokio::io::BufReader<tokio::io::Take<tokio::fs::File>>, std::io::Error> {
+    let mut file = tokio::fs::File::open(log_file).await?;
+    let (start, end) = range.map_or((0, None), |range| (range.start, range.end));
+    file.seek(std::io::SeekFrom::Start(start)).await?;
+    let limit = end.map_or(u64::MAX, |end| end.saturating_sub(start));
+    Ok(tokio::io::BufReader::new(file.take(limit)))
+}
+
+/// Read the last lines of a log, or of one run's segment of it
+///
+/// Returns the index of the first line returned, counted from the start of
+/// what was read.
 async fn tail_log_lines(
     log_file: &std::path::Path,
     tail: usize,
+    range: Option<SegmentRange>,
 ) -> Result<(usize, Vec<String>), std::io::Error> {
     use std::collections::VecDeque;
 
-    let file = tokio::fs::File::open(log_file).await?;
-    let mut reader = tokio::io::BufReader::new(file);
+    let mut reader = open_log_reader(log_file, range).await?;
     let mut bytes = Vec::new();
     let mut lines = VecDeque::with_capacity(tail);
     let mut total_lines = 0usize;
@@ -587,6 +741,7 @@ struct PendingMatch {
 // Helper function to grep log file with streaming (memory-efficient)
 async fn grep_log_file(
     log_file: &std::path::Path,
+    range: Option<SegmentRange>,
     pattern: &regex::Regex,
     before: usize,
     after: usize,
@@ -594,8 +749,7 @@ async fn grep_log_file(
     until_time: Option<chrono::DateTime<chrono::Utc>>,
 ) -> Result<Vec<GrepMatch>, std::io::Error> {
     use std::collections::VecDeque;
-    let file = tokio::fs::File::open(log_file).await?;
-    let mut reader = tokio::io::BufReader::new(file);
+    let mut reader = open_log_reader(log_file, range).await?;
     let mut line_bytes = Vec::new();
 
     // Buffer for before-context lines (keeps only the last `before` lines)
@@ -711,6 +865,7 @@ mod tests {
     use crate::common::config::Config;
     use crate::common::process_key::ProcessKey;
     use crate::daemon::api::grpc::test_support::TestHarness;
+    use crate::daemon::log::segments;
     use crate::daemon::log::LogHub;
     use crate::daemon::process::event::ProcessEvent;
     use crate::daemon::stream::{StreamEvent, StreamEventHub};
@@ -744,6 +899,8 @@ mod tests {
             follow: Some(false),
             project: project.to_string(),
             include_events: Some(false),
+            run: None,
+            label_selector: vec![],
         }
     }
 
@@ -758,6 +915,7 @@ mod tests {
             since: None,
             until: None,
             last: None,
+            run: None,
         }
     }
 
@@ -851,7 +1009,7 @@ mod tests {
             .collect::<String>();
         std::fs::write(&path, contents).unwrap();
 
-        let (start_idx, lines) = tail_log_lines(&path, 10).await.unwrap();
+        let (start_idx, lines) = tail_log_lines(&path, 10, None).await.unwrap();
 
         assert_eq!(start_idx, 40);
         assert_eq!(lines.len(), 10);
@@ -875,6 +1033,7 @@ mod tests {
 
         let matches = grep_log_file(
             &path,
+            None,
             &regex::Regex::new("target").unwrap(),
             0,
             0,
@@ -907,9 +1066,17 @@ mod tests {
             .collect::<String>();
         std::fs::write(&path, contents).unwrap();
 
-        let matches = grep_log_file(&path, &regex::Regex::new(".*").unwrap(), 0, 0, None, None)
-            .await
-            .unwrap();
+        let matches = grep_log_file(
+            &path,
+            None,
+            &regex::Regex::new(".*").unwrap(),
+            0,
+            0,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
 
         assert_eq!(matches.len(), 1_000);
         std::fs::remove_file(path).unwrap();
@@ -1123,6 +1290,114 @@ mod tests {
         harness.cleanup().await;
     }
 
+    /// Write two runs of alpha/worker, each opening its own segment
+    fn write_two_runs(harness: &TestHarness) {
+        let path = log_path(harness, "alpha", "worker");
+        let append = |line: &str| {
+            use std::io::Write;
+            std::fs::OpenOptions::new()
+                .append(true)
+                .open(&path)
+                .unwrap()
+                .write_all(line.as_bytes())
+                .unwrap();
+        };
+        segments::open_segment(&path, "run-one", "run run-one started by cli").unwrap();
+        append("2025-07-15T03:13:10.375+00:00 [INFO] needle from the first run\n");
+        append("2025-07-15T03:13:11.375+00:00 [ERROR] crashed\n");
+        segments::open_segment(&path, "run-two", "run run-two started by restart policy").unwrap();
+        append("2025-07-15T03:13:20.375+00:00 [INFO] needle from the second run\n");
+    }
+
+    async fn log_contents(
+        harness: &TestHarness,
+        request: GetLogsRequest,
+    ) -> Result<Vec<String>, tonic::Status> {
+        let mut stream = harness
+            .service
+            .get_logs_impl(Request::new(request))
+            .await?
+            .into_inner();
+        let mut contents = Vec::new();
+        while let Some(response) = stream.next().await {
+            if let Some(get_logs_response::Content::LogEntry(entry)) = response?.content {
+                contents.push(entry.content);
+            }
+        }
+        Ok(contents)
+    }
+
+    #[tokio::test]
+    async fn get_logs_reads_only_the_selected_run() {
+        let harness = TestHarness::new();
+        write_two_runs(&harness);
+        let request = |run: &str| {
+            let mut request = get_logs_request("alpha", "worker");
+            request.tail = Some(100);
+            request.run = Some(run.to_string());
+            request
+        };
+
+        assert_eq!(
+            log_contents(&harness, request("previous")).await.unwrap(),
+            vec![
+                "run run-one started by cli",
+                "needle from the first run",
+                "crashed",
+            ]
+        );
+        assert_eq!(
+            log_contents(&harness, request("run-t")).await.unwrap(),
+            vec![
+                "run run-two started by restart policy",
+                "needle from the second run",
+            ]
+        );
+        assert_eq!(
+            log_contents(&harness, request("current")).await.unwrap(),
+            log_contents(&harness, request("run-two")).await.unwrap()
+        );
+
+        let missing = log_contents(&harness, request("nope")).await.unwrap_err();
+        assert_eq!(missing.code(), Code::NotFound);
+        let ambiguous = log_contents(&harness, request("run-")).await.unwrap_err();
+        assert_eq!(ambiguous.code(), Code::InvalidArgument);
+        let mut follow_previous = request("previous");
+        follow_previous.follow = Some(true);
+        let error = log_contents(&harness, follow_previous).await.unwrap_err();
+        assert_eq!(error.code(), Code::InvalidArgument);
+        harness.cleanup().await;
+    }
+
+    #[tokio::test]
+    async fn grep_logs_searches_only_the_selected_run() {
+        let harness = TestHarness::new();
+        write_two_runs(&harness);
+        let mut request = grep_logs_request("alpha", "worker", "needle");
+        request.run = Some("previous".to_string());
+
+        let response = harness
+            .service
+            .grep_logs_impl(Request::new(request.clone()))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(response.matches.len(), 1);
+        let matched = response.matches[0].matched_line.as_ref().unwrap();
+        assert_eq!(matched.content, "needle from the first run");
+        assert_eq!(matched.line_number, 2);
+
+        request.run = Some("missing".to_string());
+        let error = harness
+            .service
+            .grep_logs_impl(Request::new(request))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), Code::NotFound);
+        harness.cleanup().await;
+    }
+
     #[tokio::test]
     async fn added_log_tests_grep_logs_rejects_invalid_regex() {
         let harness = TestHarness::new();
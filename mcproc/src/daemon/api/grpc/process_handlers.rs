@@ -1,12 +1,37 @@
 use super::helpers::{
-    create_failed_process_info, create_process_info, create_timestamp, FailedProcessParams,
+    create_failed_process_info, create_process_info, create_timestamp, parse_label_selector,
+    run_record, FailedProcessParams,
 };
 use super::service::GrpcService;
+use crate::common::labels::{validate_description, validate_labels};
+use crate::common::process_pattern::{is_pattern, ProcessPattern};
+use crate::common::schedule::Schedule;
+use crate::common::signal::parse_signal;
+use crate::common::status::format_status;
 use crate::daemon::error::McprocdError;
+use crate::daemon::process::event::ProcessEvent;
+use crate::daemon::process::file_watch::FileWatch;
+use crate::daemon::process::health::HealthCheck;
+use crate::daemon::process::limits::ResourceLimits;
+use crate::daemon::process::manager::ProcessManager;
+use crate::daemon::process::proxy::ProxyInfo;
+use crate::daemon::process::pty::PtySize;
+use crate::daemon::process::readiness::ReadinessProbe;
+use crate::daemon::process::restart::RestartPolicy;
+use crate::daemon::process::stop::StopPolicy;
+use crate::daemon::process::task::{TaskOptions, DEFAULT_TAIL_LINES, MAX_TAIL_LINES};
+use crate::daemon::process::types::{ReconcileAction, StartProcessParams};
+use crate::daemon::process::watchdog::WatchdogRule;
+use crate::daemon::stream::StreamEvent;
 use proto::process_manager_server::ProcessManager as ProcessManagerService;
 use proto::*;
-use tonic::{Request, Response, Status};
-use tracing::error;
+use std::sync::Arc;
+use tokio::sync::{broadcast, mpsc};
+use tonic::{Request, Response, Status, Streaming};
+use tracing::{debug, error};
+
+/// Runs returned by ListRuns when the request sets no limit
+const DEFAULT_RUN_LIMIT: usize = 20;
 
 /// Convert McprocdError to appropriate gRPC Status
 fn mcprocd_error_to_status(e: &McprocdError) -> Status {
@@ -16,6 +41,10 @@ fn mcprocd_error_to_status(e: &McprocdError) -> Status {
         McprocdError::InvalidRequest(_) => Status::invalid_argument(e.to_string()),
         McprocdError::InvalidCommand { .. } => Status::invalid_argument(e.to_string()),
         McprocdError::InvalidRegex { .. } => Status::invalid_argument(e.to_string()),
+        McprocdError::DependencyNotReady { .. } => Status::failed_precondition(e.to_string()),
+        McprocdError::ProcessNotRunning { .. } => Status::failed_precondition(e.to_string()),
+        McprocdError::StdinUnavailable { .. } => Status::failed_precondition(e.to_string()),
+        McprocdError::PortAllocationFailed(_) => Status::resource_exhausted(e.to_string()),
         // All other errors are internal
         _ => Status::internal(e.to_string()),
     }
@@ -35,12 +64,206 @@ fn validate_wait_timeout(wait_timeout: Option<u32>) -> Result<(), Status> {
     Ok(())
 }
 
+fn parse_restart_policy(policy: Option<i32>) -> Result<RestartPolicy, Status> {
+    let Some(policy) = policy else {
+        return Ok(RestartPolicy::default());
+    };
+    proto::RestartPolicy::try_from(policy)
+        .map(RestartPolicy::from)
+        .map_err(|_| Status::invalid_argument(format!("Unknown restart_policy value: {policy}")))
+}
+
+/// Validate a StartProcessRequest and convert it into start parameters
+fn start_params_from_request(req: StartProcessRequest) -> Result<StartProcessParams, Status> {
+    validate_wait_timeout(req.wait_timeout)?;
+    let restart_policy = parse_restart_policy(req.restart_policy)?;
+
+    // Validate process name
+    if let Err(e) = crate::common::validation::validate_process_name(&req.name) {
+        return Err(Status::invalid_argument(format!(
+            "Invalid process name: {}",
+            e
+        )));
+    }
+
+    // Validate project name
+    if let Err(e) = crate::common::validation::validate_project_name(&req.project) {
+        return Err(Status::invalid_argument(format!(
+            "Invalid project name: {}",
+            e
+        )));
+    }
+
+    let readiness_probes = req
+        .readiness_probes
+        .into_iter()
+        .map(ReadinessProbe::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| Status::invalid_argument(format!("Invalid readiness probe: {}", e)))?;
+    let health_check = req
+        .health_check
+        .map(HealthCheck::try_from)
+        .transpose()
+        .map_err(|e| Status::invalid_argument(format!("Invalid health check: {}", e)))?;
+    let stop_policy = StopPolicy::from_request(req.stop_signal, req.stop_command, req.stop_timeout)
+        .map_err(|e| Status::invalid_argument(format!("Invalid stop settings: {}", e)))?;
+    let limits = req
+        .limits
+        .map(ResourceLimits::try_from)
+        .transpose()
+        .map_err(|e| Status::invalid_argument(format!("Invalid resource limits: {}", e)))?
+        .unwrap_or_default();
+    let watchdog = req
+        .watchdog
+        .into_iter()
+        .map(WatchdogRule::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| Status::invalid_argument(format!("Invalid watchdog rule: {}", e)))?;
+    let watch = req
+        .watch
+        .map(FileWatch::try_from)
+        .transpose()
+        .map_err(|e| Status::invalid_argument(format!("Invalid file watch: {}", e)))?;
+    let schedule = req
+        .schedule
+        .as_deref()
+        .map(str::parse::<Schedule>)
+        .transpose()
+        .map_err(Status::invalid_argument)?;
+    validate_labels(&req.labels)
+        .map_err(|e| Status::invalid_argument(format!("Invalid labels: {}", e)))?;
+    let description = req
+        .description
+        .map(|description| description.trim().to_string())
+        .filter(|description| !description.is_empty());
+    if let Some(description) = &description {
+        validate_description(description).map_err(Status::invalid_argument)?;
+    }
+    let pty = if req.pty.unwrap_or(false) {
+        Some(
+            PtySize::from_request(req.pty_cols, req.pty_rows)
+                .map_err(|e| Status::invalid_argument(format!("Invalid pty size: {}", e)))?,
+        )
+    } else if req.pty_cols.is_some() || req.pty_rows.is_some() {
+        return Err(Status::invalid_argument(
+            "pty_cols and pty_rows require pty to be enabled",
+        ));
+    } else {
+        None
+    };
+
+    Ok(StartProcessParams {
+        name: req.name,
+        project: Some(req.project),
+        cmd: req.cmd,
+        args: req.args,
+        cwd: req.cwd.map(std::path::PathBuf::from),
+        env: Some(req.env),
+        wait_for_log: req.wait_for_log,
+        wait_timeout: req.wait_timeout,
+        toolchain: req.toolchain,
+        restart_policy,
+        depends_on: req.depends_on,
+        readiness_probes,
+        health_check,
+        stop_policy,
+        stdin: req.stdin.unwrap_or(false),
+        pty,
+        ports: req.ports,
+        limits,
+        watchdog,
+        watch,
+        schedule,
+        started_by: req.started_by,
+        labels: req.labels.into_iter().collect(),
+        description,
+        ..Default::default()
+    })
+}
+
+/// Reject settings that only make sense for long-running processes
+///
+/// `kind` names what is being started, e.g. "Tasks run once to completion".
+fn validate_task_params(params: &StartProcessParams, kind: &str) -> Result<(), Status> {
+    let unsupported = [
+        (
+            params.restart_policy != RestartPolicy::Never,
+            "restart_policy",
+        ),
+        (params.wait_for_log.is_some(), "wait_for_log"),
+        (!params.readiness_probes.is_empty(), "readiness_probes"),
+        (params.health_check.is_some(), "health_check"),
+        (params.watch.is_some(), "watch"),
+    ];
+    match unsupported.iter().find(|(set, _)| *set) {
+        Some((_, setting)) => Err(Status::invalid_argument(format!(
+            "{kind} and do not support {setting}"
+        ))),
+        None => Ok(()),
+    }
+}
+
 fn validate_status_filter(filter: i32) -> Result<(), Status> {
     proto::ProcessStatus::try_from(filter)
         .map(|_| ())
         .map_err(|_| Status::invalid_argument(format!("Unknown status_filter value: {filter}")))
 }
 
+/// Processes selected at once by a stop or restart request
+struct Selection {
+    /// What was asked for, e.g. "'alpha/worker-*' with status Running"
+    description: String,
+    /// Sorted by project and name
+    processes: Vec<Arc<ProxyInfo>>,
+}
+
+/// Processes selected by a name pattern, status filter or label selector
+///
+/// Returns None when the request names a single process. An empty name
+/// stands for every process of the project.
+fn select_processes(
+    process_manager: &ProcessManager,
+    name: &str,
+    project: &str,
+    status_filter: Option<i32>,
+    label_selector: &[String],
+) -> Result<Option<Selection>, Status> {
+    if !is_pattern(name) && status_filter.is_none() && label_selector.is_empty() {
+        return Ok(None);
+    }
+    let pattern = ProcessPattern::parse(if name.is_empty() { "*" } else { name }, project)
+        .map_err(Status::invalid_argument)?;
+    if let Some(status_filter) = status_filter {
+        validate_status_filter(status_filter)?;
+    }
+    let selector = parse_label_selector(label_selector)?;
+
+    let mut processes = process_manager
+        .get_all_processes()
+        .into_iter()
+        .filter(|process| pattern.matches(&process.project, &process.name))
+        .filter(|process| {
+            status_filter.map_or(true, |filter| {
+                matches_status_filter(process.get_status(), filter)
+            })
+        })
+        .filter(|process| selector.matches(&process.labels))
+        .collect::<Vec<_>>();
+    processes.sort_by(|a, b| (&a.project, &a.name).cmp(&(&b.project, &b.name)));
+
+    let mut description = format!("'{pattern}'");
+    if let Some(status_filter) = status_filter {
+        description.push_str(&format!(" with status {}", format_status(status_filter)));
+    }
+    if !selector.is_empty() {
+        description.push_str(&format!(" with labels {selector}"));
+    }
+    Ok(Some(Selection {
+        description,
+        processes,
+    }))
+}
+
 fn force_restart_stop_result(result: Result<(), McprocdError>) -> Result<(), Status> {
     result.map_err(|error| {
         Status::failed_precondition(format!(
@@ -49,42 +272,56 @@ fn force_restart_stop_result(result: Result<(), McprocdError>) -> Result<(), Sta
     })
 }
 
+/// Add who holds the port to the exit reason of a process that failed with address-in-use
+async fn diagnose_port_conflict(
+    process_manager: &ProcessManager,
+    process: &ProxyInfo,
+    info: &mut ProcessInfo,
+) {
+    if info.status != proto::ProcessStatus::Failed as i32 {
+        return;
+    }
+    let Some(stderr) = info.stderr_tail.as_deref() else {
+        return;
+    };
+    if let Some(diagnosis) = process_manager
+        .diagnose_port_conflict(process, stderr)
+        .await
+    {
+        info.exit_reason = Some(match info.exit_reason.take() {
+            Some(reason) => format!("{reason}; {diagnosis}"),
+            None => diagnosis,
+        });
+    }
+}
+
 impl GrpcService {
     pub(super) async fn start_process_impl(
         &self,
         request: Request<StartProcessRequest>,
     ) -> Result<Response<<Self as ProcessManagerService>::StartProcessStream>, Status> {
         let req = request.into_inner();
-        validate_wait_timeout(req.wait_timeout)?;
-
-        // Validate process name
-        if let Err(e) = crate::common::validation::validate_process_name(&req.name) {
-            return Err(Status::invalid_argument(format!(
-                "Invalid process name: {}",
-                e
-            )));
-        }
-
-        // Validate project name
-        if let Err(e) = crate::common::validation::validate_project_name(&req.project) {
-            return Err(Status::invalid_argument(format!(
-                "Invalid project name: {}",
-                e
-            )));
+        let force_restart = req.force_restart.unwrap_or(false);
+        let params = start_params_from_request(req)?;
+        if params.schedule.is_some() {
+            validate_task_params(&params, "Scheduled processes run to completion")?;
         }
 
-        let cwd = req.cwd.map(std::path::PathBuf::from);
-        let name = req.name.clone();
-        let project = req.project.clone();
-        let wait_for_log = req.wait_for_log.clone();
-        let wait_timeout = req.wait_timeout;
-        let cmd_for_error = req.cmd.clone();
-        let cwd_for_error = cwd.clone();
+        let name = params.name.clone();
+        let project = params.project.clone().unwrap_or_default();
+        let cmd_for_error = params.cmd.clone();
+        let cwd_for_error = params.cwd.clone();
         let log_dir = self.config.paths.log_dir.clone();
-        let force_restart = req.force_restart.unwrap_or(false);
 
         let process_manager = self.process_manager.clone();
 
+        // Dependencies must already be running and ready; each scheduled run checks them itself
+        if params.schedule.is_none() {
+            process_manager
+                .check_dependencies(&project, &name, &params.depends_on)
+                .map_err(|e| mcprocd_error_to_status(&e))?;
+        }
+
         // Handle force_restart
         if force_restart {
             if let Some(existing) = process_manager
@@ -107,26 +344,17 @@ impl GrpcService {
         // Create the response stream
         let stream = async_stream::try_stream! {
             // Start the process with log streaming
-            match process_manager.start_process_with_log_stream(
-                name.clone(),
-                Some(project.clone()),
-                req.cmd,
-                req.args,
-                cwd,
-                Some(req.env),
-                wait_for_log.clone(),
-                wait_timeout,
-                req.toolchain,
-            ).await {
+            match process_manager.start_process_with_log_stream(params).await {
                 Ok((process, timeout_occurred, _pattern_matched, log_context, matched_line)) => {
                     // Create ProcessInfo using helper
-                    let info = create_process_info(
+                    let mut info = create_process_info(
                         &process,
                         &log_dir,
                         Some(timeout_occurred),
                         log_context,
                         matched_line,
                     );
+                    diagnose_port_conflict(&process_manager, &process, &mut info).await;
 
                     yield StartProcessResponse {
                         response: Some(start_process_response::Response::Process(info)),
@@ -181,6 +409,52 @@ impl GrpcService {
         let project = req.project.clone();
         let force = req.force.unwrap_or(false);
 
+        if let Some(Selection {
+            description,
+            processes,
+        }) = select_processes(
+            &process_manager,
+            &name,
+            &project,
+            req.status_filter,
+            &req.label_selector,
+        )? {
+            if processes.is_empty() {
+                return Ok(Response::new(StopProcessResponse {
+                    success: false,
+                    message: Some(format!("No processes match {description}")),
+                    results: vec![],
+                }));
+            }
+
+            let total = processes.len();
+            let log_dir = &self.config.paths.log_dir;
+            let results = process_manager
+                .stop_processes(processes, force)
+                .await
+                .into_iter()
+                .map(|(process, result)| ProcessResult {
+                    name: process.name.clone(),
+                    project: process.project.clone(),
+                    success: result.is_ok(),
+                    message: Some(match result {
+                        Ok(()) => "Stopped".to_string(),
+                        Err(e) => format!("Failed to stop: {e}"),
+                    }),
+                    process: Some(create_process_info(&process, log_dir, None, vec![], None)),
+                })
+                .collect::<Vec<_>>();
+            let stopped = results.iter().filter(|result| result.success).count();
+
+            return Ok(Response::new(StopProcessResponse {
+                success: stopped == total,
+                message: Some(format!(
+                    "Stopped {stopped} of {total} processes matching {description}"
+                )),
+                results,
+            }));
+        }
+
         // Check if process exists
         if process_manager
             .get_process_by_name_or_id_with_project(&name, Some(&project))
@@ -189,6 +463,7 @@ impl GrpcService {
             return Ok(Response::new(StopProcessResponse {
                 success: false,
                 message: Some(format!("Process '{}' not found", name)),
+                results: vec![],
             }));
         }
 
@@ -202,6 +477,7 @@ impl GrpcService {
                 Ok(Response::new(StopProcessResponse {
                     success: true,
                     message: Some(format!("Process '{}' stopped successfully", name)),
+                    results: vec![],
                 }))
             }
             Err(e) => {
@@ -209,11 +485,146 @@ impl GrpcService {
                 Ok(Response::new(StopProcessResponse {
                     success: false,
                     message: Some(format!("Failed to stop process '{}': {}", name, e)),
+                    results: vec![],
                 }))
             }
         }
     }
 
+    pub(super) async fn signal_process_impl(
+        &self,
+        request: Request<SignalProcessRequest>,
+    ) -> Result<Response<SignalProcessResponse>, Status> {
+        let req = request.into_inner();
+        let signal = parse_signal(&req.signal).map_err(Status::invalid_argument)?;
+        let group = !req.leader_only.unwrap_or(false);
+
+        let process = self
+            .process_manager
+            .signal_process(&req.name, Some(req.project.as_str()), signal, group)
+            .map_err(|e| mcprocd_error_to_status(&e))?;
+
+        Ok(Response::new(SignalProcessResponse {
+            process: Some(create_process_info(
+                &process,
+                &self.config.paths.log_dir,
+                None,
+                vec![],
+                None,
+            )),
+        }))
+    }
+
+    pub(super) async fn send_input_impl(
+        &self,
+        request: Request<SendInputRequest>,
+    ) -> Result<Response<SendInputResponse>, Status> {
+        let req = request.into_inner();
+
+        let process = self
+            .process_manager
+            .send_input(&req.name, Some(req.project.as_str()), &req.input)
+            .await
+            .map_err(|e| mcprocd_error_to_status(&e))?;
+
+        Ok(Response::new(SendInputResponse {
+            process: Some(create_process_info(
+                &process,
+                &self.config.paths.log_dir,
+                None,
+                vec![],
+                None,
+            )),
+        }))
+    }
+
+    pub(super) async fn attach_impl(
+        &self,
+        request: Request<Streaming<AttachRequest>>,
+    ) -> Result<Response<<Self as ProcessManagerService>::AttachStream>, Status> {
+        let mut requests = request.into_inner();
+        let target = match requests.message().await? {
+            Some(AttachRequest {
+                request: Some(attach_request::Request::Target(target)),
+            }) => target,
+            _ => {
+                return Err(Status::invalid_argument(
+                    "The first attach message must name the target process",
+                ))
+            }
+        };
+
+        // Subscribe before looking the process up so its exit cannot be missed
+        let mut events = self.event_hub.subscribe();
+        let (process, mut output) = self
+            .process_manager
+            .attach_process(&target.name, Some(target.project.as_str()))
+            .map_err(|e| mcprocd_error_to_status(&e))?;
+        let log_dir = self.config.paths.log_dir.clone();
+        let process_info =
+            move |process: &ProxyInfo| create_process_info(process, &log_dir, None, vec![], None);
+
+        let stream = async_stream::try_stream! {
+            yield AttachResponse {
+                response: Some(attach_response::Response::Attached(process_info(&process))),
+            };
+
+            let mut input_error = None;
+            loop {
+                tokio::select! {
+                    request = requests.message() => match request {
+                        Ok(Some(AttachRequest { request: Some(attach_request::Request::Input(input)) })) => {
+                            if !process.accepts_input() {
+                                continue;
+                            }
+                            if let Err(e) = process.write_input(&input).await {
+                                input_error = Some(Status::failed_precondition(e));
+                                break;
+                            }
+                        }
+                        Ok(Some(_)) => {}
+                        // The client detached
+                        Ok(None) | Err(_) => break,
+                    },
+                    chunk = output.recv() => match chunk {
+                        Ok(chunk) => {
+                            yield AttachResponse {
+                                response: Some(attach_response::Response::Output(chunk.to_vec())),
+                            };
+                        }
+                        Err(broadcast::error::RecvError::Lagged(count)) => {
+                            debug!("Attached client of {} lagged by {} chunks", process.name, count);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                    event = events.recv() => match event {
+                        Ok(StreamEvent::Process(
+                            ProcessEvent::Stopped { process_id, .. } | ProcessEvent::Failed { process_id, .. },
+                        )) if process_id == process.id => {
+                            // Forward what the process wrote before it exited
+                            while let Ok(chunk) = output.try_recv() {
+                                yield AttachResponse {
+                                    response: Some(attach_response::Response::Output(chunk.to_vec())),
+                                };
+                            }
+                            yield AttachResponse {
+                                response: Some(attach_response::Response::Exited(process_info(&process))),
+                            };
+                            break;
+                        }
+                        Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                }
+            }
+            if let Some(e) = input_error {
+                Err(e)?;
+            }
+        };
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+
     pub(super) async fn restart_process_impl(
         &self,
         request: Request<RestartProcessRequest>,
@@ -224,10 +635,106 @@ impl GrpcService {
         let project = req.project.clone();
         let wait_for_log = req.wait_for_log.clone();
         let wait_timeout = req.wait_timeout;
+        let started_by = req.started_by;
 
         let process_manager = self.process_manager.clone();
         let log_dir = self.config.paths.log_dir.clone();
 
+        if let Some(Selection {
+            description,
+            processes,
+        }) = select_processes(
+            &process_manager,
+            &name,
+            &project,
+            req.status_filter,
+            &req.label_selector,
+        )? {
+            if wait_for_log.is_some() || wait_timeout.is_some() {
+                return Err(Status::invalid_argument(
+                    "wait_for_log and wait_timeout apply to a single process",
+                ));
+            }
+            if req.parallelism == Some(0) {
+                return Err(Status::invalid_argument("parallelism must be at least 1"));
+            }
+            if processes.is_empty() {
+                return Err(Status::not_found(format!(
+                    "No processes match {description}"
+                )));
+            }
+            let parallelism = req
+                .parallelism
+                .unwrap_or(self.config.process.restart.bulk_parallelism)
+                as usize;
+
+            // The restarts carry on even if the client goes away
+            let (result_tx, mut result_rx) = mpsc::unbounded_channel();
+            let manager = process_manager.clone();
+            tokio::spawn(async move {
+                manager
+                    .restart_processes(processes, parallelism, started_by, result_tx)
+                    .await;
+            });
+
+            let stream = async_stream::try_stream! {
+                while let Some((process, result)) = result_rx.recv().await {
+                    let result = match result {
+                        Ok((restarted, timeout_occurred, _pattern_matched, log_context, matched_line)) => {
+                            let mut info = create_process_info(
+                                &restarted,
+                                &log_dir,
+                                Some(timeout_occurred),
+                                log_context,
+                                matched_line,
+                            );
+                            diagnose_port_conflict(&process_manager, &restarted, &mut info).await;
+                            ProcessResult {
+                                name: process.name.clone(),
+                                project: process.project.clone(),
+                                success: true,
+                                message: Some("Restarted".to_string()),
+                                process: Some(info),
+                            }
+                        }
+                        Err(McprocdError::ProcessFailedToStart { name, exit_code, exit_reason, stderr }) => {
+                            error!("Process '{}' failed to restart: {} (exit code: {})", name, exit_reason, exit_code);
+                            ProcessResult {
+                                name: process.name.clone(),
+                                project: process.project.clone(),
+                                success: false,
+                                message: Some(format!("Failed to restart: {exit_reason} (exit code: {exit_code})")),
+                                process: Some(create_failed_process_info(FailedProcessParams {
+                                    name: &name,
+                                    project: &process.project,
+                                    cmd: None,
+                                    cwd: None,
+                                    log_dir: &log_dir,
+                                    exit_code,
+                                    exit_reason: &exit_reason,
+                                    stderr: &stderr,
+                                })),
+                            }
+                        }
+                        Err(e) => {
+                            error!("Failed to restart process {}: {}", process.name, e);
+                            ProcessResult {
+                                name: process.name.clone(),
+                                project: process.project.clone(),
+                                success: false,
+                                message: Some(format!("Failed to restart: {e}")),
+                                process: None,
+                            }
+                        }
+                    };
+                    yield RestartProcessResponse {
+                        response: Some(restart_process_response::Response::Result(result)),
+                    };
+                }
+            };
+            return Ok(Response::new(Box::pin(stream)));
+        }
+
         let stream = async_stream::try_stream! {
             match process_manager
                 .restart_process_with_log_stream(
@@ -235,6 +742,7 @@ impl GrpcService {
                     Some(project.clone()),
                     wait_for_log.clone(),
                     wait_timeout,
+                    started_by.clone(),
                 )
                 .await
             {
@@ -253,13 +761,14 @@ impl GrpcService {
                     }
 
                     // Create ProcessInfo using helper
-                    let info = create_process_info(
+                    let mut info = create_process_info(
                         &process,
                         &log_dir,
                         Some(timeout_occurred),
                         log_context,
                         matched_line,
                     );
+                    diagnose_port_conflict(&process_manager, &process, &mut info).await;
 
                     yield RestartProcessResponse {
                         response: Some(restart_process_response::Response::Process(info)),
@@ -298,6 +807,153 @@ impl GrpcService {
         Ok(Response::new(Box::pin(stream)))
     }
 
+    pub(super) async fn run_task_impl(
+        &self,
+        request: Request<RunTaskRequest>,
+    ) -> Result<Response<<Self as ProcessManagerService>::RunTaskStream>, Status> {
+        let req = request.into_inner();
+        let process = req
+            .process
+            .ok_or_else(|| Status::invalid_argument("A task needs a process to run"))?;
+        if process.force_restart.unwrap_or(false) {
+            return Err(Status::invalid_argument(
+                "Tasks run once to completion and do not support force_restart",
+            ));
+        }
+        let params = start_params_from_request(process)?;
+        validate_task_params(&params, "Tasks run once to completion")?;
+        if params.schedule.is_some() {
+            return Err(Status::invalid_argument(
+                "Tasks run once to completion and do not support schedule",
+            ));
+        }
+        let task = TaskOptions::try_from(req.task.unwrap_or_default())
+            .map_err(|e| Status::invalid_argument(format!("Invalid task options: {}", e)))?;
+        let tail_lines = req
+            .tail_lines
+            .map_or(DEFAULT_TAIL_LINES, |lines| lines as usize);
+        if tail_lines > MAX_TAIL_LINES {
+            return Err(Status::invalid_argument(format!(
+                "tail_lines must not exceed {MAX_TAIL_LINES}"
+            )));
+        }
+
+        let name = params.name.clone();
+        let project = params.project.clone().unwrap_or_default();
+        self.process_manager
+            .check_dependencies(&project, &name, &params.depends_on)
+            .map_err(|e| mcprocd_error_to_status(&e))?;
+
+        // The task keeps running to completion even if the client goes away
+        let (log_tx, mut log_rx) = mpsc::unbounded_channel();
+        let process_manager = self.process_manager.clone();
+        let run = tokio::spawn(async move {
+            process_manager
+                .run_task(params, task, tail_lines, Some(log_tx))
+                .await
+        });
+        let log_dir = self.config.paths.log_dir.clone();
+
+        let stream = async_stream::try_stream! {
+            let mut line_number = 0;
+            while let Some(mut entry) = log_rx.recv().await {
+                entry.line_number = line_number;
+                line_number += 1;
+                yield RunTaskResponse {
+                    response: Some(run_task_response::Response::LogEntry(entry)),
+                };
+            }
+
+            let result = run
+                .await
+                .map_err(|e| Status::internal(format!("Task {name} did not finish: {e}")))?
+                .map_err(|e| mcprocd_error_to_status(&e))?;
+            let info = create_process_info(&result.process, &log_dir, None, vec![], None);
+            yield RunTaskResponse {
+                response: Some(run_task_response::Response::Result(proto::TaskResult {
+                    process: Some(info),
+                    exit_code: result.exit_code,
+                    succeeded: result.succeeded,
+                    timed_out: result.timed_out,
+                    duration_ms: u64::try_from(result.duration.as_millis()).unwrap_or(u64::MAX),
+                    tail: result.tail,
+                })),
+            };
+        };
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    pub(super) async fn lookup_port_impl(
+        &self,
+        request: Request<LookupPortRequest>,
+    ) -> Result<Response<LookupPortResponse>, Status> {
+        let req = request.into_inner();
+        let port = u16::try_from(req.port)
+            .ok()
+            .filter(|port| *port > 0)
+            .ok_or_else(|| Status::invalid_argument("port must be between 1 and 65535"))?;
+        let log_dir = &self.config.paths.log_dir;
+
+        let owners = self.process_manager.lookup_port(port).await;
+        let stopped = if req.kill_holder.unwrap_or(false) {
+            self.process_manager
+                .stop_port_holders(&owners)
+                .await
+                .map_err(|e| mcprocd_error_to_status(&e))?
+        } else {
+            Vec::new()
+        };
+
+        let holders = owners
+            .iter()
+            .map(|owner| PortHolder {
+                listener: Some(Listener {
+                    protocol: owner.holder.listener.protocol.as_str().to_string(),
+                    address: owner.holder.listener.address.to_string(),
+                    port: u32::from(owner.holder.listener.port),
+                }),
+                pid: owner.holder.pid,
+                command: owner.holder.command.clone(),
+                process: owner
+                    .process
+                    .as_ref()
+                    .map(|process| create_process_info(process, log_dir, None, vec![], None)),
+            })
+            .collect();
+        let allocated_to = self
+            .process_manager
+            .port_allocated_to(port)
+            .map(|process| create_process_info(&process, log_dir, None, vec![], None));
+
+        Ok(Response::new(LookupPortResponse {
+            port: u32::from(port),
+            holders,
+            allocated_to,
+            stopped,
+        }))
+    }
+
+    pub(super) async fn list_runs_impl(
+        &self,
+        request: Request<ListRunsRequest>,
+    ) -> Result<Response<ListRunsResponse>, Status> {
+        let req = request.into_inner();
+        let limit = match req.limit {
+            Some(0) => return Err(Status::invalid_argument("limit must be at least 1")),
+            Some(limit) => limit as usize,
+            None => DEFAULT_RUN_LIMIT,
+        };
+
+        let runs = self
+            .process_manager
+            .list_runs(req.project.as_deref(), req.name.as_deref(), limit)
+            .iter()
+            .map(run_record)
+            .collect();
+        Ok(Response::new(ListRunsResponse { runs }))
+    }
+
     pub(super) async fn get_process_impl(
         &self,
         request: Request<GetProcessRequest>,
@@ -343,6 +999,9 @@ impl GrpcService {
             processes.retain(|p| matches_status_filter(p.get_status(), status_filter));
         }
 
+        let selector = parse_label_selector(&req.label_selector)?;
+        processes.retain(|p| selector.matches(&p.labels));
+
         let log_dir = self.config.paths.log_dir.clone();
         let process_infos: Vec<ProcessInfo> = processes
             .into_iter()
@@ -362,20 +1021,86 @@ impl GrpcService {
             processes: process_infos,
         }))
     }
+
+    pub(super) async fn apply_manifest_impl(
+        &self,
+        request: Request<ApplyManifestRequest>,
+    ) -> Result<Response<ApplyManifestResponse>, Status> {
+        let req = request.into_inner();
+        if let Err(e) = crate::common::validation::validate_project_name(&req.project) {
+            return Err(Status::invalid_argument(format!(
+                "Invalid project name: {}",
+                e
+            )));
+        }
+
+        let mut names = std::collections::HashSet::new();
+        let mut definitions = Vec::with_capacity(req.processes.len());
+        for mut process in req.processes {
+            if !names.insert(process.name.clone()) {
+                return Err(Status::invalid_argument(format!(
+                    "Process '{}' is listed more than once",
+                    process.name
+                )));
+            }
+            process.project = req.project.clone();
+            definitions.push(start_params_from_request(process)?);
+        }
+
+        let results = self
+            .process_manager
+            .reconcile_project(&req.project, definitions, req.remove_orphans)
+            .await
+            .map_err(|e| mcprocd_error_to_status(&e))?;
+
+        let log_dir = &self.config.paths.log_dir;
+        let results = results
+            .into_iter()
+            .map(|result| {
+                let action = match result.action {
+                    ReconcileAction::Unchanged => manifest_process_result::Action::Unchanged,
+                    ReconcileAction::Started => manifest_process_result::Action::Started,
+                    ReconcileAction::Restarted => manifest_process_result::Action::Restarted,
+                    ReconcileAction::Stopped => manifest_process_result::Action::Stopped,
+                    ReconcileAction::Blocked => manifest_process_result::Action::Blocked,
+                };
+                let (process, error) = match result.result {
+                    Ok(process) => (
+                        process.map(|process| {
+                            create_process_info(&process, log_dir, None, vec![], None)
+                        }),
+                        None,
+                    ),
+                    Err(e) => (None, Some(e.to_string())),
+                };
+                ManifestProcessResult {
+                    name: result.name,
+                    action: action as i32,
+                    process,
+                    error,
+                }
+            })
+            .collect();
+
+        Ok(Response::new(ApplyManifestResponse { results }))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        force_restart_stop_result, matches_status_filter, validate_status_filter,
-        validate_wait_timeout,
+        force_restart_stop_result, matches_status_filter, parse_restart_policy,
+        validate_status_filter, validate_wait_timeout,
     };
     use crate::daemon::api::grpc::test_support::{process_from_restart_stream, TestHarness};
     use crate::daemon::error::McprocdError;
+    use crate::daemon::process::restart::RestartPolicy;
     use crate::daemon::process::ProcessStatus;
     use proto::{
-        GetProcessRequest, ListProcessesRequest, RestartProcessRequest, StopProcessRequest,
+        ApplyManifestRequest, GetProcessRequest, ListProcessesRequest, LookupPortRequest,
+        RestartProcessRequest, RunTaskRequest, StopProcessRequest,
     };
+    use tokio_stream::StreamExt;
     use tonic::{Code, Request};
 
     #[test]
@@ -406,6 +1131,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn restart_policy_defaults_to_never_and_rejects_unknown_values() {
+        assert_eq!(parse_restart_policy(None).unwrap(), RestartPolicy::Never);
+        assert_eq!(
+            parse_restart_policy(Some(proto::RestartPolicy::OnFailure as i32)).unwrap(),
+            RestartPolicy::OnFailure
+        );
+        assert_eq!(
+            parse_restart_policy(Some(999)).unwrap_err().code(),
+            tonic::Code::InvalidArgument
+        );
+    }
+
     #[test]
     fn force_restart_propagates_stop_failure() {
         let result =
@@ -443,6 +1181,51 @@ mod tests {
         assert_eq!(result.err().unwrap().code(), Code::InvalidArgument);
     }
 
+    #[tokio::test]
+    async fn grpc_rpc_run_task_rejects_settings_of_long_running_processes() {
+        let harness = TestHarness::new();
+        let mut process = TestHarness::start_request("build", "alpha");
+        process.restart_policy = Some(proto::RestartPolicy::Always as i32);
+        let result = harness
+            .service
+            .run_task_impl(Request::new(RunTaskRequest {
+                process: Some(process),
+                ..Default::default()
+            }))
+            .await;
+        harness.cleanup().await;
+
+        let status = result.err().unwrap();
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert!(status.message().contains("restart_policy"));
+    }
+
+    #[tokio::test]
+    async fn grpc_rpc_start_process_rejects_invalid_schedules_and_long_running_settings() {
+        let harness = TestHarness::new();
+        let mut invalid = TestHarness::start_request("codegen", "alpha");
+        invalid.schedule = Some("*/15 * * *".to_string());
+        let invalid = harness
+            .service
+            .start_process_impl(Request::new(invalid))
+            .await;
+        let mut watched = TestHarness::start_request("codegen", "alpha");
+        watched.schedule = Some("every 10m".to_string());
+        watched.wait_for_log = Some("ready".to_string());
+        let watched = harness
+            .service
+            .start_process_impl(Request::new(watched))
+            .await;
+        harness.cleanup().await;
+
+        let status = invalid.err().unwrap();
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert!(status.message().contains("expected 5 cron fields"));
+        let status = watched.err().unwrap();
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert!(status.message().contains("Scheduled processes"));
+    }
+
     #[tokio::test]
     async fn grpc_rpc_start_process_rejects_wait_timeout_over_one_hour() {
         let harness = TestHarness::new();
@@ -485,6 +1268,79 @@ mod tests {
         );
     }
 
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn grpc_rpc_lookup_port_names_the_holder_and_diagnoses_failed_starts() {
+        let harness = TestHarness::new();
+        let mut request = TestHarness::start_request("holder", "alpha");
+        request.args = vec![
+            "python3".to_string(),
+            "-c".to_string(),
+            "import socket, time\n\
+             s = socket.socket()\n\
+             s.bind(('127.0.0.1', 0))\n\
+             s.listen()\n\
+             print('listening', s.getsockname()[1], flush=True)\n\
+             time.sleep(30)"
+                .to_string(),
+        ];
+        request.wait_for_log = Some("^listening ".to_string());
+        request.wait_timeout = Some(10);
+        let holder = harness.start_with_request(request).await.unwrap();
+        let port = holder.matched_line.unwrap()["listening ".len()..]
+            .trim()
+            .parse::<u32>()
+            .unwrap();
+        let lookup = |port: u32, kill_holder: bool| {
+            harness
+                .service
+                .lookup_port_impl(Request::new(LookupPortRequest {
+                    port,
+                    kill_holder: Some(kill_holder),
+                }))
+        };
+
+        let found = lookup(port, false).await.unwrap().into_inner();
+        assert_eq!(found.holders.len(), 1, "{:?}", found.holders);
+        assert_eq!(found.holders[0].process.as_ref().unwrap().name, "holder");
+        assert!(found.holders[0]
+            .command
+            .as_deref()
+            .is_some_and(|command| command.contains("python3")));
+        assert!(found.stopped.is_empty());
+
+        let mut request = TestHarness::start_request("clash", "alpha");
+        request.cmd = Some(
+            "python3 -c \"import os, socket; socket.socket().bind(('127.0.0.1', int(os.environ['PORT'])))\""
+                .to_string(),
+        );
+        request.args.clear();
+        request.env.insert("PORT".to_string(), port.to_string());
+        let clash = harness.start_with_request(request).await.unwrap();
+        assert_eq!(clash.status, proto::ProcessStatus::Failed as i32);
+        let reason = clash.exit_reason.unwrap();
+        assert!(
+            reason.contains(&format!(
+                "port {port} is held by mcproc process alpha/holder"
+            )),
+            "{reason}"
+        );
+
+        let killed = lookup(port, true).await.unwrap().into_inner();
+        assert_eq!(killed.stopped, vec!["alpha/holder".to_string()]);
+        assert!(lookup(port, false)
+            .await
+            .unwrap()
+            .into_inner()
+            .holders
+            .is_empty());
+        assert_eq!(
+            lookup(0, false).await.unwrap_err().code(),
+            Code::InvalidArgument
+        );
+        harness.cleanup().await;
+    }
+
     #[cfg(unix)]
     #[tokio::test]
     async fn grpc_rpc_start_process_force_restart_replaces_running_process() {
@@ -499,6 +1355,92 @@ mod tests {
         assert_eq!(replacement.status, proto::ProcessStatus::Running as i32);
     }
 
+    #[tokio::test]
+    async fn grpc_rpc_start_process_rejects_invalid_readiness_probe() {
+        let harness = TestHarness::new();
+        let mut request = TestHarness::start_request("worker", "alpha");
+        request.readiness_probes = vec![proto::ReadinessProbe {
+            probe: Some(proto::readiness_probe::Probe::Http(proto::HttpProbe {
+                url: "https://localhost/health".to_string(),
+                min_status: None,
+                max_status: None,
+            })),
+        }];
+        let result = harness
+            .service
+            .start_process_impl(Request::new(request))
+            .await;
+        harness.cleanup().await;
+
+        assert_eq!(result.err().unwrap().code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn grpc_rpc_start_process_refuses_missing_dependency() {
+        let harness = TestHarness::new();
+        let mut request = TestHarness::start_request("api", "alpha");
+        request.depends_on = vec!["db".to_string()];
+        let result = harness
+            .service
+            .start_process_impl(Request::new(request))
+            .await;
+        harness.cleanup().await;
+
+        let status = result.err().unwrap();
+        assert_eq!(status.code(), Code::FailedPrecondition);
+        assert!(status.message().contains("dependency db is not running"));
+    }
+
+    #[tokio::test]
+    async fn grpc_rpc_apply_manifest_rejects_duplicate_process_names() {
+        let harness = TestHarness::new();
+        let result = harness
+            .service
+            .apply_manifest_impl(Request::new(ApplyManifestRequest {
+                project: "alpha".to_string(),
+                processes: vec![
+                    TestHarness::start_request("worker", "alpha"),
+                    TestHarness::start_request("worker", "alpha"),
+                ],
+                remove_orphans: false,
+            }))
+            .await;
+        harness.cleanup().await;
+
+        assert_eq!(result.err().unwrap().code(), Code::InvalidArgument);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn grpc_rpc_apply_manifest_leaves_unchanged_process_running() {
+        let harness = TestHarness::new();
+        let apply = || {
+            harness
+                .service
+                .apply_manifest_impl(Request::new(ApplyManifestRequest {
+                    project: "alpha".to_string(),
+                    processes: vec![TestHarness::start_request("worker", "alpha")],
+                    remove_orphans: false,
+                }))
+        };
+        let first = apply().await.unwrap().into_inner().results;
+        let second = apply().await.unwrap().into_inner().results;
+        harness.cleanup().await;
+
+        assert_eq!(
+            first[0].action,
+            proto::manifest_process_result::Action::Started as i32
+        );
+        assert_eq!(
+            second[0].action,
+            proto::manifest_process_result::Action::Unchanged as i32
+        );
+        assert_eq!(
+            first[0].process.as_ref().unwrap().pid,
+            second[0].process.as_ref().unwrap().pid
+        );
+    }
+
     #[cfg(unix)]
     #[tokio::test]
     async fn grpc_rpc_stop_process_handles_existing_and_missing_processes() {
@@ -510,6 +1452,7 @@ mod tests {
                 name: "worker".to_string(),
                 project: "alpha".to_string(),
                 force: Some(true),
+                ..Default::default()
             }))
             .await
             .unwrap()
@@ -525,6 +1468,7 @@ mod tests {
                 name: "absent".to_string(),
                 project: "alpha".to_string(),
                 force: Some(true),
+                ..Default::default()
             }))
             .await
             .unwrap()
@@ -571,6 +1515,118 @@ mod tests {
         assert_eq!(missing.unwrap_err().code(), Code::NotFound);
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn grpc_rpc_stop_process_by_pattern_reports_each_process() {
+        let harness = TestHarness::new();
+        for (name, project) in [
+            ("worker-1", "alpha"),
+            ("worker-2", "alpha"),
+            ("web", "alpha"),
+            ("worker-1", "beta"),
+        ] {
+            harness.start(name, project).await.unwrap();
+        }
+        let stop = |name: &str| {
+            harness
+                .service
+                .stop_process_impl(Request::new(StopProcessRequest {
+                    name: name.to_string(),
+                    project: "alpha".to_string(),
+                    force: Some(true),
+                    ..Default::default()
+                }))
+        };
+        let workers = stop("worker-*").await.unwrap().into_inner();
+        let everywhere = stop("*/worker-*").await.unwrap().into_inner();
+        let none = stop("db-*").await.unwrap().into_inner();
+        let invalid = stop("alpha/").await.unwrap_err();
+        let remaining = harness
+            .service
+            .process_manager
+            .get_all_processes()
+            .iter()
+            .map(|process| format!("{}/{}", process.project, process.name))
+            .collect::<Vec<_>>();
+        harness.cleanup().await;
+
+        assert!(workers.success);
+        let stopped = workers
+            .results
+            .iter()
+            .map(|result| {
+                (
+                    result.project.as_str(),
+                    result.name.as_str(),
+                    result.success,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            stopped,
+            [("alpha", "worker-1", true), ("alpha", "worker-2", true)]
+        );
+        assert_eq!(everywhere.results.len(), 1);
+        assert_eq!(everywhere.results[0].project, "beta");
+        assert_eq!(remaining, ["alpha/web"]);
+        assert!(!none.success);
+        assert!(none.message.unwrap().contains("No processes match"));
+        assert_eq!(invalid.code(), Code::InvalidArgument);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn grpc_rpc_restart_process_by_status_restarts_each_process() {
+        let harness = TestHarness::new();
+        let mut pids = Vec::new();
+        for name in ["worker-1", "worker-2", "worker-3"] {
+            pids.push(harness.start(name, "alpha").await.unwrap().pid);
+        }
+        let response = harness
+            .service
+            .restart_process_impl(Request::new(RestartProcessRequest {
+                project: "alpha".to_string(),
+                status_filter: Some(proto::ProcessStatus::Running as i32),
+                parallelism: Some(2),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        let mut stream = response.into_inner();
+        let mut results = Vec::new();
+        while let Some(response) = stream.next().await {
+            if let Some(proto::restart_process_response::Response::Result(result)) =
+                response.unwrap().response
+            {
+                results.push(result);
+            }
+        }
+        let zero = harness
+            .service
+            .restart_process_impl(Request::new(RestartProcessRequest {
+                name: "worker-*".to_string(),
+                project: "alpha".to_string(),
+                parallelism: Some(0),
+                ..Default::default()
+            }))
+            .await;
+        harness.cleanup().await;
+
+        results.sort_by(|a, b| a.name.cmp(&b.name));
+        let names = results
+            .iter()
+            .map(|result| result.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["worker-1", "worker-2", "worker-3"]);
+        for (result, pid) in results.iter().zip(pids) {
+            assert!(result.success);
+            let process = result.process.as_ref().unwrap();
+            assert_eq!(process.status, proto::ProcessStatus::Running as i32);
+            assert_ne!(process.pid, pid);
+        }
+        assert_eq!(zero.err().unwrap().code(), Code::InvalidArgument);
+    }
+
     #[cfg(unix)]
     #[tokio::test]
     async fn grpc_rpc_get_process_returns_metadata_and_not_found() {
@@ -645,4 +1701,45 @@ mod tests {
             .all(|process| process.status == proto::ProcessStatus::Running as i32));
         assert_eq!(invalid.unwrap_err().code(), Code::InvalidArgument);
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn grpc_rpc_list_processes_selects_by_labels_and_returns_description() {
+        let harness = TestHarness::new();
+        let mut web = TestHarness::start_request("web", "alpha");
+        web.labels = [("role".to_string(), "frontend".to_string())].into();
+        web.description = Some("  started to reproduce issue 42 ".to_string());
+        let web = harness.start_with_request(web).await.unwrap();
+        let mut worker = TestHarness::start_request("worker", "alpha");
+        worker.labels = [("role".to_string(), "worker".to_string())].into();
+        harness.start_with_request(worker).await.unwrap();
+        let mut invalid_labels = TestHarness::start_request("invalid", "alpha");
+        invalid_labels.labels = [("bad key".to_string(), "x".to_string())].into();
+        let invalid_labels = harness.start_with_request(invalid_labels).await;
+
+        let list = |selector: &str| {
+            harness
+                .service
+                .list_processes_impl(Request::new(ListProcessesRequest {
+                    label_selector: vec![selector.to_string()],
+                    ..Default::default()
+                }))
+        };
+        let frontend = list("role=frontend").await.unwrap().into_inner();
+        let not_frontend = list("role!=frontend").await.unwrap().into_inner();
+        let invalid_selector = list("role=frontend,").await;
+        harness.cleanup().await;
+
+        assert_eq!(web.labels["role"], "frontend");
+        assert_eq!(
+            web.description.as_deref(),
+            Some("started to reproduce issue 42")
+        );
+        assert_eq!(frontend.processes.len(), 1);
+        assert_eq!(frontend.processes[0].name, "web");
+        assert_eq!(not_frontend.processes.len(), 1);
+        assert_eq!(not_frontend.processes[0].name, "worker");
+        assert_eq!(invalid_labels.unwrap_err().code(), Code::InvalidArgument);
+        assert_eq!(invalid_selector.unwrap_err().code(), Code::InvalidArgument);
+    }
 }
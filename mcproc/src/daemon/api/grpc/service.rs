@@ -1,3 +1,4 @@
+use super::helpers::parse_label_selector;
 use crate::common::config::Config;
 use crate::common::version::VERSION;
 use crate::daemon::log::LogHub;
@@ -41,12 +42,13 @@ impl GrpcService {
         request: Request<CleanProjectRequest>,
     ) -> Result<Response<CleanProjectResponse>, Status> {
         let req = request.into_inner();
+        let selector = parse_label_selector(&req.label_selector)?;
 
         if req.all_projects {
             // Clean all projects
             let results = self
                 .process_manager
-                .clean_all_projects(req.force)
+                .clean_all_projects(req.force, &selector)
                 .await
                 .map_err(|e| Status::internal(format!("Failed to clean all projects: {}", e)))?;
 
@@ -78,7 +80,7 @@ impl GrpcService {
             let project = req.project.as_deref().unwrap_or("default");
             let (stopped_names, deleted_log_paths) = self
                 .process_manager
-                .clean_project(project, req.force)
+                .clean_project(project, req.force, &selector)
                 .await
                 .map_err(|e| {
                     Status::internal(format!("Failed to clean project {}: {}", project, e))
@@ -169,6 +171,38 @@ mod tests {
         assert!(registry_empty);
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn grpc_rpc_clean_project_with_selector_keeps_other_processes() {
+        let harness = TestHarness::new();
+        let mut web = TestHarness::start_request("web", "alpha");
+        web.labels = [("role".to_string(), "frontend".to_string())].into();
+        harness.start_with_request(web).await.unwrap();
+        harness.start("worker", "alpha").await.unwrap();
+        let response = harness
+            .service
+            .clean_project_impl(Request::new(CleanProjectRequest {
+                project: Some("alpha".to_string()),
+                force: true,
+                label_selector: vec!["role=frontend".to_string()],
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let remaining = harness
+            .service
+            .process_manager
+            .get_all_processes()
+            .iter()
+            .map(|process| process.name.clone())
+            .collect::<Vec<_>>();
+        harness.cleanup().await;
+
+        assert_eq!(response.stopped_process_names, ["web"]);
+        assert_eq!(remaining, ["worker"]);
+    }
+
     #[cfg(unix)]
     #[tokio::test]
     async fn grpc_rpc_daemon_status_reports_current_pid_and_active_process_count() {
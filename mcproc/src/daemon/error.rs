@@ -15,6 +15,18 @@ pub enum McprocdError {
     #[error("Failed to stop process: {0}")]
     StopError(String),
 
+    #[error("Process {name} is not running (status: {status})")]
+    ProcessNotRunning { name: String, status: String },
+
+    #[error("Failed to signal process: {0}")]
+    SignalError(String),
+
+    #[error("Process {name} does not accept input: it was started without stdin or pty")]
+    StdinUnavailable { name: String },
+
+    #[error("Failed to send input: {0}")]
+    InputError(String),
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
@@ -35,6 +47,9 @@ pub enum McprocdError {
         stderr: String,
     },
 
+    #[error("Failed to allocate ports: {0}")]
+    PortAllocationFailed(String),
+
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
 
@@ -46,6 +61,13 @@ pub enum McprocdError {
 
     #[error("Invalid regex pattern {pattern}: {error}")]
     InvalidRegex { pattern: String, error: String },
+
+    #[error("Process {name} is blocked: dependency {dependency} {reason}")]
+    DependencyNotReady {
+        name: String,
+        dependency: String,
+        reason: String,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, McprocdError>;
@@ -1,3 +1,4 @@
+use super::segments;
 use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -20,7 +21,7 @@ pub fn delete_project_logs(project_log_dir: &Path, exclude: &HashSet<PathBuf>) -
         }
     };
 
-    let mut candidates = entries
+    let candidates = entries
         .filter_map(|entry| {
             let entry = entry.ok()?;
             let path = entry.path();
@@ -30,12 +31,33 @@ pub fn delete_project_logs(project_log_dir: &Path, exclude: &HashSet<PathBuf>) -
             (is_log_file && !exclude.contains(&path)).then_some(path)
         })
         .collect::<Vec<_>>();
-    candidates.sort();
+    let deleted = delete_log_files(candidates);
 
-    let mut deleted = Vec::with_capacity(candidates.len());
-    for path in candidates {
+    let directory_is_empty =
+        fs::read_dir(project_log_dir).is_ok_and(|mut entries| entries.next().is_none());
+    if directory_is_empty {
+        let _ = fs::remove_dir(project_log_dir);
+    }
+
+    deleted
+}
+
+/// Delete the given log files along with their run index.
+/// Files that do not exist are skipped.
+/// Returns the paths of files actually deleted, sorted.
+pub fn delete_log_files(mut paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    paths.sort();
+    paths.dedup();
+
+    let mut deleted = Vec::with_capacity(paths.len());
+    for path in paths {
         match fs::remove_file(&path) {
-            Ok(()) => deleted.push(path),
+            Ok(()) => {
+                // The run index is meaningless without its log
+                let _ = fs::remove_file(segments::index_path(&path));
+                deleted.push(path)
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
             Err(error) => warn!(
                 path = %path.display(),
                 %error,
@@ -43,13 +65,6 @@ pub fn delete_project_logs(project_log_dir: &Path, exclude: &HashSet<PathBuf>) -
             ),
         }
     }
-
-    let directory_is_empty =
-        fs::read_dir(project_log_dir).is_ok_and(|mut entries| entries.next().is_none());
-    if directory_is_empty {
-        let _ = fs::remove_dir(project_log_dir);
-    }
-
     deleted
 }
 
@@ -75,6 +90,25 @@ mod tests {
         assert!(!second.exists());
     }
 
+    #[test]
+    fn deletes_run_index_with_its_log_file() {
+        let temp = tempdir().unwrap();
+        let project_dir = temp.path().join("project");
+        fs::create_dir(&project_dir).unwrap();
+        let deleted_log = project_dir.join("delete.log");
+        let excluded_log = project_dir.join("keep.log");
+        for log in [&deleted_log, &excluded_log] {
+            fs::write(log, "log").unwrap();
+            fs::write(segments::index_path(log), "{}").unwrap();
+        }
+        let exclude = HashSet::from([excluded_log.clone()]);
+
+        delete_project_logs(&project_dir, &exclude);
+
+        assert!(!segments::index_path(&deleted_log).exists());
+        assert!(segments::index_path(&excluded_log).exists());
+    }
+
     #[test]
     fn keeps_excluded_log_file() {
         let temp = tempdir().unwrap();
@@ -158,4 +192,20 @@ mod tests {
 
         assert!(project_dir.exists());
     }
+
+    #[test]
+    fn deletes_only_given_log_files() {
+        let temp = tempdir().unwrap();
+        let project_dir = temp.path().join("project");
+        fs::create_dir(&project_dir).unwrap();
+        let selected_log = project_dir.join("web.log");
+        let other_log = project_dir.join("worker.log");
+        fs::write(&selected_log, "web").unwrap();
+        fs::write(&other_log, "worker").unwrap();
+
+        let deleted = delete_log_files(vec![selected_log.clone(), project_dir.join("gone.log")]);
+
+        assert_eq!(deleted, vec![selected_log]);
+        assert!(other_log.exists());
+    }
 }
@@ -1,5 +1,6 @@
 pub mod batch_writer;
 pub mod cleaner;
+pub mod segments;
 
 use crate::common::config::Config;
 use crate::common::process_key::ProcessKey;
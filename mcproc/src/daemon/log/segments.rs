@@ -0,0 +1,278 @@
+//! Per-run segments of a process log file
+//!
+//! Every run appends to the same `<name>.log`. When a run starts, a header
+//! line is appended and the byte offset where it begins is recorded in the
+//! `<name>.runs` index next to the log, so the output of one run can be read
+//! back on its own.
+
+use crate::common::timestamp::format_datetime_utc_with_tz;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Extension of the segment index kept next to each log file
+pub const INDEX_EXTENSION: &str = "runs";
+
+/// Level marker of segment header lines in the log file
+pub const HEADER_LEVEL: &str = "RUN";
+
+/// Where one run starts in the log file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Segment {
+    /// Process ID of the run
+    pub run: String,
+    /// Byte offset of the header line
+    pub offset: u64,
+    pub start_time: DateTime<Utc>,
+}
+
+/// Byte range of one run in the log file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SegmentRange {
+    pub start: u64,
+    /// Unset for the latest run, which grows as it writes
+    pub end: Option<u64>,
+}
+
+/// Which run of a process to read, as given by clients
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunSelector {
+    Current,
+    Previous,
+    /// Run ID, or a unique prefix of one
+    Id(String),
+}
+
+impl RunSelector {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim() {
+            "" => Err("run must be 'current', 'previous' or a run ID".to_string()),
+            "current" => Ok(Self::Current),
+            "previous" => Ok(Self::Previous),
+            id => Ok(Self::Id(id.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for RunSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Current => f.write_str("current"),
+            Self::Previous => f.write_str("previous"),
+            Self::Id(id) => f.write_str(id),
+        }
+    }
+}
+
+pub fn index_path(log_file_path: &Path) -> PathBuf {
+    log_file_path.with_extension(INDEX_EXTENSION)
+}
+
+/// Start the segment of a new run: append its header line to the log and
+/// record where it begins in the index
+pub fn open_segment(
+    log_file_path: &Path,
+    run: &str,
+    header: &str,
+) -> Result<Segment, std::io::Error> {
+    if let Some(parent) = log_file_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut log_file = OpenOptions::new()
+        .create(true)
+        .read(true)
+        .append(true)
+        .open(log_file_path)?;
+
+    let mut offset = log_file.metadata()?.len();
+    if offset > 0 {
+        // The header must start a line of its own
+        log_file.seek(SeekFrom::End(-1))?;
+        let mut last_byte = [0];
+        log_file.read_exact(&mut last_byte)?;
+        if last_byte[0] != b'\n' {
+            log_file.write_all(b"\n")?;
+            offset += 1;
+        }
+    }
+
+    let segment = Segment {
+        run: run.to_string(),
+        offset,
+        start_time: Utc::now(),
+    };
+    log_file.write_all(
+        format!(
+            "{} [{}] {}\n",
+            format_datetime_utc_with_tz(segment.start_time),
+            HEADER_LEVEL,
+            header
+        )
+        .as_bytes(),
+    )?;
+
+    let mut line = serde_json::to_vec(&segment).map_err(std::io::Error::other)?;
+    line.push(b'\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(index_path(log_file_path))?
+        .write_all(&line)?;
+
+    Ok(segment)
+}
+
+/// Segments recorded for a log file, oldest first
+///
+/// Entries pointing past the end of the log are dropped: they belong to a
+/// log that has since been deleted and started over.
+pub fn read_segments(log_file_path: &Path) -> Result<Vec<Segment>, std::io::Error> {
+    let contents = match std::fs::read_to_string(index_path(log_file_path)) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let log_len = match std::fs::metadata(log_file_path) {
+        Ok(metadata) => metadata.len(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut segments = contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Segment>(line).ok())
+        .filter(|segment| segment.offset < log_len)
+        .collect::<Vec<_>>();
+    // A truncated log may have been written over: keep only the offsets that still increase
+    if let Some(restart) = segments
+        .windows(2)
+        .rposition(|pair| pair[1].offset <= pair[0].offset)
+    {
+        segments.drain(..=restart);
+    }
+    Ok(segments)
+}
+
+/// Find the selected run among the segments
+///
+/// Returns the run ID, its byte range, and whether it is the latest run.
+pub fn resolve_segment(
+    segments: &[Segment],
+    selector: &RunSelector,
+) -> Result<Option<(String, SegmentRange, bool)>, String> {
+    let position = match selector {
+        RunSelector::Current => segments.len().checked_sub(1),
+        RunSelector::Previous => segments.len().checked_sub(2),
+        RunSelector::Id(id) => match segments.iter().position(|segment| segment.run == *id) {
+            Some(position) => Some(position),
+            None => {
+                let mut matching = segments
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, segment)| segment.run.starts_with(id.as_str()))
+                    .map(|(position, _)| position);
+                match (matching.next(), matching.next()) {
+                    (Some(position), None) => Some(position),
+                    (Some(_), Some(_)) => {
+                        return Err(format!("Run ID prefix '{id}' matches more than one run"))
+                    }
+                    (None, _) => None,
+                }
+            }
+        },
+    };
+
+    Ok(position.map(|position| {
+        let segment = &segments[position];
+        let range = SegmentRange {
+            start: segment.offset,
+            end: segments.get(position + 1).map(|next| next.offset),
+        };
+        (segment.run.clone(), range, position + 1 == segments.len())
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log_path(dir: &Path) -> PathBuf {
+        dir.join("project").join("api.log")
+    }
+
+    #[test]
+    fn segments_cover_the_output_of_each_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = log_path(dir.path());
+        open_segment(&path, "aaaa-1", "run aaaa-1").unwrap();
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"first run output")
+            .unwrap();
+        open_segment(&path, "bbbb-2", "run bbbb-2").unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+
+        let segments = read_segments(&path).unwrap();
+        assert_eq!(segments.len(), 2);
+
+        let (run, range, latest) = resolve_segment(&segments, &RunSelector::Previous)
+            .unwrap()
+            .unwrap();
+        assert_eq!(run, "aaaa-1");
+        assert!(!latest);
+        let previous = &contents[range.start as usize..range.end.unwrap() as usize];
+        assert!(previous.ends_with("[RUN] run aaaa-1\nfirst run output\n"));
+
+        let (run, range, latest) = resolve_segment(&segments, &RunSelector::Current)
+            .unwrap()
+            .unwrap();
+        assert_eq!((run.as_str(), range.end, latest), ("bbbb-2", None, true));
+        assert!(contents[range.start as usize..].ends_with("[RUN] run bbbb-2\n"));
+    }
+
+    #[test]
+    fn runs_are_selected_by_id_or_unique_prefix() {
+        let segment = |run: &str, offset| Segment {
+            run: run.to_string(),
+            offset,
+            start_time: Utc::now(),
+        };
+        let segments = vec![segment("abc-1", 0), segment("abd-2", 10), segment("x", 20)];
+        let select = |value: &str| {
+            resolve_segment(&segments, &RunSelector::parse(value).unwrap())
+                .map(|found| found.map(|(run, range, _)| (run, range.start)))
+        };
+
+        assert_eq!(select("abd"), Ok(Some(("abd-2".to_string(), 10))));
+        assert_eq!(select("x"), Ok(Some(("x".to_string(), 20))));
+        assert!(select("ab").is_err());
+        assert_eq!(select("zzz"), Ok(None));
+        assert_eq!(
+            resolve_segment(&segments[..1], &RunSelector::Previous),
+            Ok(None)
+        );
+        assert!(RunSelector::parse(" ").is_err());
+    }
+
+    #[test]
+    fn index_entries_of_a_deleted_log_are_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = log_path(dir.path());
+        open_segment(&path, "old-1", "run old-1").unwrap();
+        open_segment(&path, "old-2", "run old-2").unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(read_segments(&path).unwrap().is_empty());
+
+        open_segment(&path, "new-1", "run new-1").unwrap();
+        let runs = read_segments(&path)
+            .unwrap()
+            .into_iter()
+            .map(|segment| segment.run)
+            .collect::<Vec<_>>();
+        assert_eq!(runs, vec!["new-1"]);
+    }
+}
@@ -94,9 +94,33 @@ pub async fn run_daemon() -> Result<(), Box<dyn std::error::Error>> {
         event_hub.clone(),
     ));
 
+    // Restore the processes known to the previous daemon and keep the registry saved
+    let interrupted_processes = process_manager.restore_processes();
+    process_manager.start_state_persistence();
+
     // Start periodic process state synchronization
     process_manager.start_periodic_sync();
 
+    // Relaunch processes according to their restart policy
+    process_manager.start_restart_supervisor();
+
+    // Run periodic liveness checks
+    process_manager.start_health_monitor();
+
+    // Restart processes when their watched files change
+    process_manager.start_file_watcher();
+
+    // Launch the runs of scheduled processes
+    process_manager.start_scheduler();
+
+    // Relaunch processes that were running when the previous daemon stopped
+    if config.daemon.relaunch_on_restart && !interrupted_processes.is_empty() {
+        let relaunch_pm = process_manager.clone();
+        tokio::spawn(async move {
+            relaunch_pm.relaunch_processes(interrupted_processes).await;
+        });
+    }
+
     info!("Components initialized successfully");
 
     // Start servers
@@ -132,6 +156,9 @@ pub async fn run_daemon() -> Result<(), Box<dyn std::error::Error>> {
 
     info!("Shutting down mcprocd daemon");
 
+    // Save the registry while processes still run so the next daemon sees them
+    process_manager.persist_for_shutdown();
+
     // Stop all managed processes
     info!("Stopping all managed processes...");
     let processes = process_manager.get_all_processes();
@@ -143,6 +170,7 @@ pub async fn run_daemon() -> Result<(), Box<dyn std::error::Error>> {
                 p.get_status(),
                 crate::daemon::process::ProcessStatus::Running
                     | crate::daemon::process::ProcessStatus::Starting
+                    | crate::daemon::process::ProcessStatus::Paused
             )
         })
         .collect();
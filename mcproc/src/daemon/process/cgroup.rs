@@ -0,0 +1,346 @@
+//! Per-process cgroups in a delegated cgroup v2 subtree
+//!
+//! A cgroup keeps track of every descendant of a managed process, including
+//! children that start their own session or double-fork out of its process
+//! group. Where no writable cgroup v2 hierarchy is available the daemon keeps
+//! relying on process groups alone.
+
+use crate::common::config::ProcessCgroupConfig;
+use crate::common::process_key::ProcessKey;
+use nix::sys::signal::{kill, Signal};
+use nix::unistd::Pid;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+use uuid::Uuid;
+
+/// Directory created under the daemon's own cgroup when no path is configured
+const DEFAULT_SUBTREE: &str = "mcproc";
+
+/// Controllers enabled for process cgroups, when offered, to enforce resource limits
+const CONTROLLERS: [&str; 3] = ["memory", "cpu", "pids"];
+
+/// The cgroup v2 directory process cgroups are created in
+#[derive(Debug, Clone)]
+pub struct CgroupRoot {
+    path: PathBuf,
+}
+
+impl CgroupRoot {
+    /// Find a writable cgroup v2 directory for process cgroups, if there is one
+    pub fn detect(config: &ProcessCgroupConfig) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        let path = match &config.path {
+            Some(path) => path.clone(),
+            None => own_cgroup_dir()?.join(DEFAULT_SUBTREE),
+        };
+        match Self::prepare(&path) {
+            Ok(root) => {
+                info!("Tracking processes with cgroups under {}", path.display());
+                Some(root)
+            }
+            Err(e) => {
+                info!(
+                    "cgroup v2 directory {} is not usable ({}), using process groups only",
+                    path.display(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    fn prepare(path: &Path) -> io::Result<Self> {
+        std::fs::create_dir_all(path)?;
+        if !path.join("cgroup.procs").exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "not a cgroup v2 directory",
+            ));
+        }
+        enable_controllers(path);
+        Ok(Self {
+            path: path.to_path_buf(),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Create an empty cgroup for one run of a process
+    pub fn create(&self, key: &ProcessKey) -> io::Result<Cgroup> {
+        let suffix = Uuid::new_v4().simple().to_string();
+        let path = self
+            .path
+            .join(format!("{}.{}.{}", key.project, key.name, &suffix[..8]));
+        std::fs::create_dir(&path)?;
+        debug!("Created cgroup {}", path.display());
+        Ok(Cgroup { path })
+    }
+}
+
+/// The cgroup a managed process and all of its descendants run in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cgroup {
+    path: PathBuf,
+}
+
+impl Cgroup {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Open the member list for writing, so a child can move itself in before exec
+    pub fn procs_file(&self) -> io::Result<File> {
+        OpenOptions::new()
+            .write(true)
+            .open(self.path.join("cgroup.procs"))
+    }
+
+    /// PIDs of every process in the cgroup
+    pub fn pids(&self) -> Vec<u32> {
+        std::fs::read_to_string(self.path.join("cgroup.procs"))
+            .map(|procs| {
+                procs
+                    .lines()
+                    .filter_map(|pid| pid.trim().parse().ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Whether a process belongs to the cgroup
+    pub fn contains(&self, pid: u32) -> bool {
+        let Ok(membership) = std::fs::read_to_string(format!("/proc/{pid}/cgroup")) else {
+            return false;
+        };
+        membership
+            .lines()
+            .filter_map(|line| line.strip_prefix("0::"))
+            .map(|path| path.trim_start_matches('/'))
+            .any(|path| !path.is_empty() && self.path.ends_with(path))
+    }
+
+    /// Whether any process is left in the cgroup
+    pub fn is_populated(&self) -> bool {
+        match std::fs::read_to_string(self.path.join("cgroup.events")) {
+            Ok(events) => events.lines().any(|line| line == "populated 1"),
+            // Without cgroup.events, fall back to listing the members
+            Err(_) => !self.pids().is_empty(),
+        }
+    }
+
+    /// Send a signal to every process in the cgroup except the given process group
+    ///
+    /// The group itself is signalled with killpg, so this reaches only the
+    /// descendants that left it.
+    pub fn signal_outside_group(&self, pgid: u32, signal: Signal) {
+        for pid in self.pids() {
+            let pid = Pid::from_raw(pid as i32);
+            if nix::unistd::getpgid(Some(pid)).ok() != Some(Pid::from_raw(pgid as i32)) {
+                let _ = kill(pid, signal);
+            }
+        }
+    }
+
+    /// Set a controller value, such as memory.max
+    ///
+    /// Fails with NotFound when the controller is not enabled for the cgroup.
+    pub fn set_control(&self, file: &str, value: &str) -> io::Result<()> {
+        OpenOptions::new()
+            .write(true)
+            .open(self.path.join(file))?
+            .write_all(value.as_bytes())
+    }
+
+    /// Number of processes in the cgroup killed by the OOM killer
+    pub fn oom_kills(&self) -> u64 {
+        std::fs::read_to_string(self.path.join("memory.events"))
+            .ok()
+            .and_then(|events| {
+                events
+                    .lines()
+                    .find_map(|line| line.strip_prefix("oom_kill "))
+                    .and_then(|count| count.trim().parse().ok())
+            })
+            .unwrap_or(0)
+    }
+
+    /// Kill every process in the cgroup
+    pub fn kill(&self) -> io::Result<()> {
+        match OpenOptions::new()
+            .write(true)
+            .open(self.path.join("cgroup.kill"))
+        {
+            Ok(mut file) => file.write_all(b"1"),
+            // Kernels before 5.14 have no cgroup.kill
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                for pid in self.pids() {
+                    let _ = kill(Pid::from_raw(pid as i32), Signal::SIGKILL);
+                }
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Remove the cgroup once it is empty
+    pub fn remove(&self) -> io::Result<()> {
+        match std::fs::remove_dir(&self.path) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+}
+
+/// Let the cgroups below a directory use the controllers it offers
+///
+/// Controllers that cannot be enabled, for example because the parent does not
+/// delegate them, are left off; limits that need them fall back to setrlimit.
+fn enable_controllers(path: &Path) {
+    let available = std::fs::read_to_string(path.join("cgroup.controllers")).unwrap_or_default();
+    let enabled = std::fs::read_to_string(path.join("cgroup.subtree_control")).unwrap_or_default();
+    for controller in CONTROLLERS {
+        if !available.split_whitespace().any(|name| name == controller)
+            || enabled.split_whitespace().any(|name| name == controller)
+        {
+            continue;
+        }
+        let result = OpenOptions::new()
+            .write(true)
+            .open(path.join("cgroup.subtree_control"))
+            .and_then(|mut file| file.write_all(format!("+{controller}").as_bytes()));
+        if let Err(e) = result {
+            debug!(
+                "Cannot enable the {} controller in {}: {}",
+                controller,
+                path.display(),
+                e
+            );
+        }
+    }
+}
+
+/// The daemon's own cgroup v2 directory, from /proc/self/cgroup and the cgroup2 mount
+fn own_cgroup_dir() -> Option<PathBuf> {
+    let membership = std::fs::read_to_string("/proc/self/cgroup").ok()?;
+    let relative = membership
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))?
+        .trim_start_matches('/')
+        .to_string();
+    let mountinfo = std::fs::read_to_string("/proc/self/mountinfo").ok()?;
+    Some(cgroup2_mount_point(&mountinfo)?.join(relative))
+}
+
+/// Mount point of the cgroup v2 hierarchy in /proc/self/mountinfo
+fn cgroup2_mount_point(mountinfo: &str) -> Option<PathBuf> {
+    mountinfo.lines().find_map(|line| {
+        // ID PARENT MAJ:MIN ROOT MOUNT_POINT OPTIONS [OPTIONAL...] - FSTYPE SOURCE SUPER_OPTIONS
+        let (mount, filesystem) = line.split_once(" - ")?;
+        if filesystem.split_whitespace().next()? != "cgroup2" {
+            return None;
+        }
+        mount.split_whitespace().nth(4).map(PathBuf::from)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cgroup2_mount_point_is_found_in_unified_and_hybrid_layouts() {
+        let unified = "24 30 0:22 / /sys/fs/cgroup rw,nosuid,nodev,noexec,relatime shared:9 - cgroup2 cgroup2 rw,nsdelegate\n";
+        assert_eq!(
+            cgroup2_mount_point(unified),
+            Some(PathBuf::from("/sys/fs/cgroup"))
+        );
+        let hybrid = "31 25 0:26 / /sys/fs/cgroup rw - tmpfs tmpfs rw,mode=755\n\
+                      33 31 0:28 / /sys/fs/cgroup/memory rw - cgroup cgroup rw,memory\n\
+                      42 32 0:38 / /sys/fs/cgroup/unified rw,relatime - cgroup2 cgroup2 rw\n";
+        assert_eq!(
+            cgroup2_mount_point(hybrid),
+            Some(PathBuf::from("/sys/fs/cgroup/unified"))
+        );
+        assert_eq!(
+            cgroup2_mount_point(&hybrid[..hybrid.find("42 ").unwrap()]),
+            None
+        );
+    }
+
+    #[test]
+    fn disabled_or_unusable_cgroups_fall_back_to_process_groups() {
+        let dir = std::env::temp_dir().join(format!("mcproc-cgroup-{}", Uuid::new_v4()));
+        let config = |enabled, path: &Path| ProcessCgroupConfig {
+            enabled,
+            path: Some(path.to_path_buf()),
+        };
+
+        assert!(CgroupRoot::detect(&config(false, &dir)).is_none());
+        // A plain directory has no cgroup.procs
+        assert!(CgroupRoot::detect(&config(true, &dir)).is_none());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn cgroup_tracks_escaped_children_until_killed() {
+        let Some(root) = CgroupRoot::detect(&ProcessCgroupConfig::default()) else {
+            eprintln!("skipping: no writable cgroup v2 hierarchy");
+            return;
+        };
+        let cgroup = root
+            .create(&ProcessKey::new("tests".to_string(), "escape".to_string()))
+            .unwrap();
+        assert!(!cgroup.is_populated());
+
+        let procs = cgroup.procs_file().unwrap();
+        let fd = std::os::fd::AsRawFd::as_raw_fd(&procs);
+        let mut command = tokio::process::Command::new("sh");
+        // The grandchild starts its own session, leaving the process group
+        command.args(["-c", "setsid sleep 30 & echo $!; wait"]);
+        command.stdout(std::process::Stdio::piped());
+        unsafe {
+            command.pre_exec(move || {
+                libc::write(fd, b"0".as_ptr().cast(), 1);
+                Ok(())
+            });
+        }
+        let mut child = command.spawn().unwrap();
+        drop(procs);
+        let leader = child.id().unwrap();
+        assert!(cgroup.contains(leader));
+
+        let mut pids = Vec::new();
+        for _ in 0..50 {
+            pids = cgroup.pids();
+            if pids.len() >= 2 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert!(pids.contains(&leader) && pids.len() >= 2, "{pids:?}");
+        assert!(cgroup.is_populated());
+        assert!(
+            cgroup.remove().is_err(),
+            "a populated cgroup cannot be removed"
+        );
+
+        cgroup.kill().unwrap();
+        child.wait().await.unwrap();
+        for _ in 0..50 {
+            if !cgroup.is_populated() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert!(!cgroup.is_populated());
+        cgroup.remove().unwrap();
+        assert!(!cgroup.path().exists());
+    }
+}
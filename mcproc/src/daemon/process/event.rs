@@ -1,3 +1,5 @@
+use crate::daemon::process::health::HealthStatus;
+
 /// Events related to process lifecycle
 #[derive(Debug, Clone)]
 pub enum ProcessEvent {
@@ -34,6 +36,22 @@ pub enum ProcessEvent {
         project: String,
         error: String,
     },
+    /// A liveness check changed the process health
+    HealthChanged {
+        process_id: String,
+        name: String,
+        project: String,
+        health: HealthStatus,
+        error: Option<String>,
+    },
+    /// A watchdog rule fired and its action was taken
+    Watchdog {
+        process_id: String,
+        name: String,
+        project: String,
+        /// The rule and the usage it fired on
+        reason: String,
+    },
 }
 
 impl ProcessEvent {
@@ -44,6 +62,8 @@ impl ProcessEvent {
             ProcessEvent::Stopping { name, .. } => name,
             ProcessEvent::Stopped { name, .. } => name,
             ProcessEvent::Failed { name, .. } => name,
+            ProcessEvent::HealthChanged { name, .. } => name,
+            ProcessEvent::Watchdog { name, .. } => name,
         }
     }
 
@@ -54,6 +74,8 @@ impl ProcessEvent {
             ProcessEvent::Stopping { project, .. } => project,
             ProcessEvent::Stopped { project, .. } => project,
             ProcessEvent::Failed { project, .. } => project,
+            ProcessEvent::HealthChanged { project, .. } => project,
+            ProcessEvent::Watchdog { project, .. } => project,
         }
     }
 }
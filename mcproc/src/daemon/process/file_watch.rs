@@ -0,0 +1,349 @@
+//! File watching that restarts processes when their sources change
+//!
+//! Every directory under the watched paths gets its own non-recursive watch,
+//! so directories excluded by `.gitignore` (build output, dependencies) are
+//! never watched. Changes are coalesced until the debounce period passes
+//! without another one, so a `git checkout` leads to a single restart.
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+/// Quiet period that ends a burst of changes when none is configured
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Files watched for changes that restart a process
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileWatch {
+    /// Files or directories, relative to the working directory
+    pub paths: Vec<String>,
+    /// Gitignore-style patterns skipped in addition to `.gitignore` files
+    pub ignore: Vec<String>,
+    pub debounce: Duration,
+}
+
+impl TryFrom<proto::FileWatch> for FileWatch {
+    type Error = String;
+
+    fn try_from(watch: proto::FileWatch) -> Result<Self, Self::Error> {
+        if watch.paths.iter().any(|path| path.trim().is_empty()) {
+            return Err("Watch paths must not be empty".to_string());
+        }
+        let mut builder = GitignoreBuilder::new("");
+        for pattern in &watch.ignore {
+            builder
+                .add_line(None, pattern)
+                .map_err(|e| format!("Invalid watch ignore pattern '{pattern}': {e}"))?;
+        }
+        if watch.debounce_ms == Some(0) {
+            return Err("Watch debounce_ms must be greater than 0".to_string());
+        }
+
+        let paths = if watch.paths.is_empty() {
+            vec![".".to_string()]
+        } else {
+            watch.paths
+        };
+        Ok(Self {
+            paths,
+            ignore: watch.ignore,
+            debounce: watch
+                .debounce_ms
+                .map_or(DEFAULT_DEBOUNCE, |ms| Duration::from_millis(u64::from(ms))),
+        })
+    }
+}
+
+impl From<&FileWatch> for proto::FileWatch {
+    fn from(watch: &FileWatch) -> Self {
+        Self {
+            paths: watch.paths.clone(),
+            ignore: watch.ignore.clone(),
+            debounce_ms: Some(u32::try_from(watch.debounce.as_millis()).unwrap_or(u32::MAX)),
+        }
+    }
+}
+
+/// Ignore rules gathered from the configured patterns and `.gitignore` files
+struct IgnoreRules {
+    /// The configured patterns, rooted at the working directory
+    patterns: Gitignore,
+    /// `.gitignore` files, deepest directory first so nested files take precedence
+    gitignores: Vec<Gitignore>,
+}
+
+impl IgnoreRules {
+    fn new(cwd: &Path, patterns: &[String]) -> Result<Self, String> {
+        let mut builder = GitignoreBuilder::new(cwd);
+        for pattern in patterns {
+            builder
+                .add_line(None, pattern)
+                .map_err(|e| format!("Invalid watch ignore pattern '{pattern}': {e}"))?;
+        }
+        Ok(Self {
+            patterns: builder.build().map_err(|e| e.to_string())?,
+            gitignores: Vec::new(),
+        })
+    }
+
+    /// Load the `.gitignore` file of a directory, if it has one and it is not loaded yet
+    fn add_gitignore(&mut self, dir: &Path) {
+        let file = dir.join(".gitignore");
+        if !file.is_file() || self.gitignores.iter().any(|rules| rules.path() == dir) {
+            return;
+        }
+        let (rules, error) = Gitignore::new(&file);
+        if let Some(e) = error {
+            warn!("Some patterns in {} were not loaded: {}", file.display(), e);
+        }
+        self.gitignores.push(rules);
+        self.gitignores
+            .sort_by_key(|rules| std::cmp::Reverse(rules.path().components().count()));
+    }
+
+    /// Load the `.gitignore` files from a path up to the root of its repository
+    fn add_parent_gitignores(&mut self, path: &Path) {
+        for dir in path.ancestors().skip(1) {
+            self.add_gitignore(dir);
+            if dir.join(".git").exists() {
+                break;
+            }
+        }
+    }
+
+    fn is_ignored(&self, path: &Path) -> bool {
+        if path
+            .components()
+            .any(|component| component.as_os_str() == ".git")
+        {
+            return true;
+        }
+        let is_dir = path.is_dir();
+        std::iter::once(&self.patterns)
+            .chain(&self.gitignores)
+            .filter(|rules| path.starts_with(rules.path()))
+            .map(|rules| rules.matched_path_or_any_parents(path, is_dir))
+            .find(|matched| !matched.is_none())
+            .is_some_and(|matched| matches!(matched, Match::Ignore(_)))
+    }
+}
+
+/// Watches the files of one process and reports bursts of changes
+pub struct FileWatcher {
+    watcher: RecommendedWatcher,
+    events: mpsc::UnboundedReceiver<notify::Result<notify::Event>>,
+    rules: IgnoreRules,
+    debounce: Duration,
+}
+
+impl FileWatcher {
+    /// Start watching the paths of `watch`, resolved against the process's working directory
+    pub fn new(watch: &FileWatch, cwd: &Path) -> Result<Self, String> {
+        let cwd = cwd
+            .canonicalize()
+            .map_err(|e| format!("Cannot watch {}: {}", cwd.display(), e))?;
+        let (tx, events) = mpsc::unbounded_channel();
+        let watcher = notify::recommended_watcher(move |event| {
+            let _ = tx.send(event);
+        })
+        .map_err(|e| format!("Failed to start file watcher: {e}"))?;
+
+        let mut file_watcher = Self {
+            watcher,
+            events,
+            rules: IgnoreRules::new(&cwd, &watch.ignore)?,
+            debounce: watch.debounce,
+        };
+        for path in &watch.paths {
+            let root = cwd
+                .join(path)
+                .canonicalize()
+                .map_err(|e| format!("Cannot watch {path}: {e}"))?;
+            file_watcher.rules.add_parent_gitignores(&root);
+            file_watcher.watch_tree(&root);
+        }
+        Ok(file_watcher)
+    }
+
+    /// Watch a file, or a directory and every directory below it that is not ignored
+    fn watch_tree(&mut self, path: &Path) {
+        if path.is_dir() {
+            self.rules.add_gitignore(path);
+        }
+        if let Err(e) = self.watcher.watch(path, RecursiveMode::NonRecursive) {
+            warn!("Failed to watch {}: {}", path.display(), e);
+            return;
+        }
+        let Ok(entries) = std::fs::read_dir(path) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let child = entry.path();
+            if entry.file_type().is_ok_and(|kind| kind.is_dir()) && !self.rules.is_ignored(&child) {
+                self.watch_tree(&child);
+            }
+        }
+    }
+
+    /// Wait for the next burst of changes and return the changed paths in order
+    ///
+    /// Returns `None` once the watcher has stopped.
+    pub async fn next_change(&mut self) -> Option<Vec<PathBuf>> {
+        let mut changed = Vec::new();
+        loop {
+            let event = if changed.is_empty() {
+                self.events.recv().await?
+            } else {
+                match tokio::time::timeout(self.debounce, self.events.recv()).await {
+                    Ok(Some(event)) => event,
+                    Ok(None) | Err(_) => return Some(changed),
+                }
+            };
+            match event {
+                Ok(event) => self.record(event, &mut changed),
+                Err(e) => warn!("File watcher error: {}", e),
+            }
+        }
+    }
+
+    fn record(&mut self, event: notify::Event, changed: &mut Vec<PathBuf>) {
+        // Reads and closes do not change anything
+        if matches!(event.kind, EventKind::Access(_)) {
+            return;
+        }
+        for path in event.paths {
+            if self.rules.is_ignored(&path) {
+                continue;
+            }
+            if matches!(event.kind, EventKind::Create(_)) && path.is_dir() {
+                debug!("Watching new directory {}", path.display());
+                self.watch_tree(&path);
+            }
+            if path.file_name().is_some_and(|name| name == ".gitignore") {
+                if let Some(dir) = path.parent() {
+                    self.rules.gitignores.retain(|rules| rules.path() != dir);
+                    self.rules.add_gitignore(dir);
+                }
+            }
+            if !changed.contains(&path) {
+                changed.push(path);
+            }
+        }
+    }
+}
+
+/// Describe what triggered a restart, e.g. "src/main.rs and 3 other files changed"
+pub fn describe_change(changed: &[PathBuf], cwd: &Path) -> String {
+    let cwd = cwd.canonicalize().unwrap_or_else(|_| cwd.to_path_buf());
+    let Some(first) = changed.first() else {
+        return "files changed".to_string();
+    };
+    let first = first.strip_prefix(&cwd).unwrap_or(first).display();
+    match changed.len() {
+        1 => format!("{first} changed"),
+        2 => format!("{first} and 1 other file changed"),
+        count => format!("{first} and {} other files changed", count - 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn watch(paths: &[&str], ignore: &[&str]) -> FileWatch {
+        FileWatch::try_from(proto::FileWatch {
+            paths: paths.iter().map(|path| path.to_string()).collect(),
+            ignore: ignore.iter().map(|pattern| pattern.to_string()).collect(),
+            debounce_ms: Some(200),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn proto_watches_are_validated_with_defaults() {
+        let watch = FileWatch::try_from(proto::FileWatch::default()).unwrap();
+        assert_eq!(watch.paths, vec!["."]);
+        assert_eq!(watch.debounce, DEFAULT_DEBOUNCE);
+        assert_eq!(
+            FileWatch::try_from(proto::FileWatch::from(&watch)),
+            Ok(watch)
+        );
+
+        for invalid in [
+            proto::FileWatch {
+                paths: vec![" ".to_string()],
+                ..Default::default()
+            },
+            proto::FileWatch {
+                debounce_ms: Some(0),
+                ..Default::default()
+            },
+        ] {
+            assert!(FileWatch::try_from(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn gitignore_files_and_patterns_are_respected() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::create_dir_all(root.join("app/dist")).unwrap();
+        std::fs::write(root.join(".gitignore"), "target/\n*.log\n").unwrap();
+        std::fs::write(root.join("app/.gitignore"), "dist/\n!keep.log\n").unwrap();
+
+        let mut rules = IgnoreRules::new(&root.join("app"), &["*.tmp".to_string()]).unwrap();
+        rules.add_parent_gitignores(&root.join("app"));
+        rules.add_gitignore(&root.join("app"));
+
+        assert!(rules.is_ignored(&root.join("target/debug/app")));
+        assert!(rules.is_ignored(&root.join("app/dist/bundle.js")));
+        assert!(rules.is_ignored(&root.join("app/server.log")));
+        assert!(!rules.is_ignored(&root.join("app/keep.log")));
+        assert!(rules.is_ignored(&root.join("app/cache.tmp")));
+        assert!(rules.is_ignored(&root.join(".git/index")));
+        assert!(!rules.is_ignored(&root.join("app/src/main.rs")));
+    }
+
+    #[tokio::test]
+    async fn bursts_of_changes_are_coalesced_and_ignored_paths_skipped() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::write(root.join(".gitignore"), "target/\n").unwrap();
+
+        let mut watcher = FileWatcher::new(&watch(&["."], &["*.log"]), &root).unwrap();
+        std::fs::write(root.join("target/out"), "ignored").unwrap();
+        std::fs::write(root.join("server.log"), "ignored").unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}").unwrap();
+        std::fs::create_dir(root.join("src/nested")).unwrap();
+
+        let changed = tokio::time::timeout(Duration::from_secs(5), watcher.next_change())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            changed,
+            vec![root.join("src/main.rs"), root.join("src/nested")]
+        );
+        assert_eq!(
+            describe_change(&changed, &root),
+            "src/main.rs and 1 other file changed"
+        );
+
+        // The new directory is watched as well
+        std::fs::write(root.join("src/nested/lib.rs"), "").unwrap();
+        let changed = tokio::time::timeout(Duration::from_secs(5), watcher.next_change())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(changed, vec![root.join("src/nested/lib.rs")]);
+    }
+}
@@ -0,0 +1,253 @@
+use crate::daemon::process::proxy::ProxyInfo;
+use crate::daemon::process::readiness::ReadinessProbe;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Interval between health checks when none is configured
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(10);
+/// Timeout of a single health check when none is configured
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Liveness check run periodically while a process is running
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthCheck {
+    pub kind: HealthCheckKind,
+    pub interval: Duration,
+    pub timeout: Duration,
+    /// Restart the process after this many consecutive failures
+    pub restart_after_failures: Option<u32>,
+}
+
+/// What a health check does
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthCheckKind {
+    /// A TCP connect or HTTP GET, as used for readiness
+    Probe(ReadinessProbe),
+    /// A shell command that must exit with code 0
+    Command(String),
+}
+
+impl fmt::Display for HealthCheckKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HealthCheckKind::Probe(probe) => write!(f, "{probe}"),
+            HealthCheckKind::Command(cmd) => write!(f, "command `{cmd}`"),
+        }
+    }
+}
+
+impl TryFrom<proto::HealthCheck> for HealthCheck {
+    type Error = String;
+
+    fn try_from(check: proto::HealthCheck) -> Result<Self, Self::Error> {
+        let probe = |probe| ReadinessProbe::try_from(proto::ReadinessProbe { probe: Some(probe) });
+        let kind = match check.check {
+            Some(proto::health_check::Check::Tcp(tcp)) => {
+                HealthCheckKind::Probe(probe(proto::readiness_probe::Probe::Tcp(tcp))?)
+            }
+            Some(proto::health_check::Check::Http(http)) => {
+                HealthCheckKind::Probe(probe(proto::readiness_probe::Probe::Http(http))?)
+            }
+            Some(proto::health_check::Check::Command(command)) => {
+                if command.cmd.trim().is_empty() {
+                    return Err("Health check command must not be empty".to_string());
+                }
+                HealthCheckKind::Command(command.cmd)
+            }
+            None => return Err("Health check must specify tcp, http or command".to_string()),
+        };
+        let seconds = |value: Option<u32>, default: Duration, field: &str| match value {
+            Some(0) => Err(format!("Health check {field} must be at least 1 second")),
+            Some(secs) => Ok(Duration::from_secs(u64::from(secs))),
+            None => Ok(default),
+        };
+        if check.restart_after_failures == Some(0) {
+            return Err("Health check restart_after_failures must be at least 1".to_string());
+        }
+
+        Ok(HealthCheck {
+            kind,
+            interval: seconds(check.interval_secs, DEFAULT_INTERVAL, "interval")?,
+            timeout: seconds(check.timeout_secs, DEFAULT_TIMEOUT, "timeout")?,
+            restart_after_failures: check.restart_after_failures,
+        })
+    }
+}
+
+impl HealthCheck {
+    /// Run the check once against a process, returning why it failed
+    pub async fn run(&self, process: &ProxyInfo) -> Result<(), String> {
+        let result = tokio::time::timeout(self.timeout, async {
+            match &self.kind {
+                HealthCheckKind::Probe(probe) => probe
+                    .check(process)
+                    .await
+                    .map(|_| ())
+                    .ok_or_else(|| format!("{probe} failed")),
+                HealthCheckKind::Command(cmd) => process.run_shell_command(cmd).await,
+            }
+        })
+        .await;
+        result.unwrap_or_else(|_| {
+            Err(format!(
+                "{} timed out after {}s",
+                self.kind,
+                self.timeout.as_secs()
+            ))
+        })
+    }
+}
+
+/// Outcome of the most recent health checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HealthStatus {
+    /// No health check configured, or no result yet
+    #[default]
+    Unknown,
+    Healthy,
+    Unhealthy,
+}
+
+impl From<HealthStatus> for proto::HealthStatus {
+    fn from(status: HealthStatus) -> Self {
+        match status {
+            HealthStatus::Unknown => proto::HealthStatus::Unknown,
+            HealthStatus::Healthy => proto::HealthStatus::Healthy,
+            HealthStatus::Unhealthy => proto::HealthStatus::Unhealthy,
+        }
+    }
+}
+
+/// Health bookkeeping for one run of a process
+#[derive(Debug, Default)]
+pub struct HealthState {
+    pub status: HealthStatus,
+    /// Failures since the last successful check
+    pub consecutive_failures: u32,
+    /// Why the last check failed
+    pub last_error: Option<String>,
+    /// When the next check is due; unset until the process is first seen running
+    next_check: Option<Instant>,
+    /// Set while a check is in flight
+    checking: bool,
+}
+
+impl HealthState {
+    /// Claim the next check if it is due, scheduling the first one an interval from now
+    pub fn begin_check(&mut self, interval: Duration, now: Instant) -> bool {
+        match self.next_check {
+            _ if self.checking => false,
+            None => {
+                self.next_check = Some(now + interval);
+                false
+            }
+            Some(due) if due > now => false,
+            Some(_) => {
+                self.checking = true;
+                true
+            }
+        }
+    }
+
+    /// Record a check result, returning the new status if it changed
+    pub fn finish_check(
+        &mut self,
+        result: Result<(), String>,
+        interval: Duration,
+        now: Instant,
+    ) -> Option<HealthStatus> {
+        self.checking = false;
+        self.next_check = Some(now + interval);
+        let previous = self.status;
+        match result {
+            Ok(()) => {
+                self.status = HealthStatus::Healthy;
+                self.consecutive_failures = 0;
+                self.last_error = None;
+            }
+            Err(error) => {
+                self.status = HealthStatus::Unhealthy;
+                self.consecutive_failures += 1;
+                self.last_error = Some(error);
+            }
+        }
+        (self.status != previous).then_some(self.status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proto_checks_are_validated_and_defaulted() {
+        let check = HealthCheck::try_from(proto::HealthCheck {
+            check: Some(proto::health_check::Check::Tcp(proto::TcpProbe {
+                host: None,
+                port: 8080,
+            })),
+            interval_secs: None,
+            timeout_secs: Some(2),
+            restart_after_failures: Some(3),
+        })
+        .unwrap();
+        assert_eq!(check.kind.to_string(), "tcp 127.0.0.1:8080");
+        assert_eq!(check.interval, DEFAULT_INTERVAL);
+        assert_eq!(check.timeout, Duration::from_secs(2));
+        assert_eq!(check.restart_after_failures, Some(3));
+
+        let invalid = |check: Option<proto::health_check::Check>, interval_secs| {
+            HealthCheck::try_from(proto::HealthCheck {
+                check,
+                interval_secs,
+                timeout_secs: None,
+                restart_after_failures: None,
+            })
+            .is_err()
+        };
+        let command = |cmd: &str| {
+            Some(proto::health_check::Check::Command(proto::CommandCheck {
+                cmd: cmd.to_string(),
+            }))
+        };
+        assert!(invalid(None, None));
+        assert!(invalid(command(" "), None));
+        assert!(invalid(command("true"), Some(0)));
+        assert!(!invalid(command("true"), Some(1)));
+    }
+
+    #[test]
+    fn state_schedules_checks_and_reports_transitions() {
+        let interval = Duration::from_secs(10);
+        let start = Instant::now();
+        let mut state = HealthState::default();
+
+        assert!(!state.begin_check(interval, start));
+        assert!(!state.begin_check(interval, start + Duration::from_secs(5)));
+        assert!(state.begin_check(interval, start + interval));
+        assert!(!state.begin_check(interval, start + interval));
+
+        let now = start + interval;
+        assert_eq!(
+            state.finish_check(Err("down".to_string()), interval, now),
+            Some(HealthStatus::Unhealthy)
+        );
+        assert!(state.begin_check(interval, now + interval));
+        assert_eq!(
+            state.finish_check(Err("down".to_string()), interval, now + interval),
+            None
+        );
+        assert_eq!(state.consecutive_failures, 2);
+
+        assert!(state.begin_check(interval, now + interval * 2));
+        assert_eq!(
+            state.finish_check(Ok(()), interval, now + interval * 2),
+            Some(HealthStatus::Healthy)
+        );
+        assert_eq!(state.consecutive_failures, 0);
+        assert_eq!(state.last_error, None);
+    }
+}
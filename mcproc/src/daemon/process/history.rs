@@ -0,0 +1,324 @@
+//! Record of past and current runs, kept in the data directory
+
+use crate::daemon::process::port_detector;
+use crate::daemon::process::proxy::{ProcessStatus, ProxyInfo};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, warn};
+
+/// File in the data directory holding the run history
+const HISTORY_FILE_NAME: &str = "runs.json";
+
+/// Runs remembered per process, the oldest are forgotten first
+const MAX_RUNS_PER_PROCESS: usize = 50;
+
+/// Runs remembered across all processes
+const MAX_RUNS: usize = 1000;
+
+/// One run of a process, from its start to its exit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// Process ID of the run
+    pub id: String,
+    pub name: String,
+    pub project: String,
+    pub cmd: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    pub cwd: Option<PathBuf>,
+    pub toolchain: Option<String>,
+    pub started_by: Option<String>,
+    pub start_time: DateTime<Utc>,
+    /// Unset while the run is going, or when the daemon went away before it exited
+    pub exit_time: Option<DateTime<Utc>>,
+    pub exit_code: Option<i32>,
+    pub exit_reason: Option<String>,
+    pub status: ProcessStatus,
+    /// Ports allocated to the run or seen listening, sorted
+    #[serde(default)]
+    pub ports: Vec<u16>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct HistoryFile {
+    /// Recorded runs, oldest first
+    runs: Vec<RunRecord>,
+}
+
+impl RunRecord {
+    pub fn from_process(process: &ProxyInfo) -> Self {
+        let mut ports = process
+            .allocated_ports
+            .iter()
+            .map(|(_, port)| *port)
+            .chain(process.port)
+            .chain(process.detected_port.lock().ok().and_then(|port| *port))
+            .collect::<Vec<_>>();
+        if let Ok(listeners) = process.listeners.lock() {
+            ports.extend(
+                port_detector::tcp_ports(&listeners)
+                    .into_iter()
+                    .filter_map(|port| u16::try_from(port).ok()),
+            );
+        }
+        ports.sort_unstable();
+        ports.dedup();
+
+        // A process stopped on request usually dies by the stop signal, which is no crash
+        let status = match process.get_status() {
+            ProcessStatus::Failed if process.is_stop_requested() && process.task.is_none() => {
+                ProcessStatus::Stopped
+            }
+            status => status,
+        };
+
+        Self {
+            id: process.id.clone(),
+            name: process.name.clone(),
+            project: process.project.clone(),
+            cmd: process.cmd.clone(),
+            args: process.args.clone(),
+            cwd: process.cwd.clone(),
+            toolchain: process.toolchain.clone(),
+            started_by: process.started_by.clone(),
+            start_time: process.start_time,
+            exit_time: process.exit_time.lock().ok().and_then(|time| *time),
+            exit_code: process.exit_code.lock().ok().and_then(|code| *code),
+            exit_reason: process
+                .exit_reason
+                .lock()
+                .ok()
+                .and_then(|reason| reason.clone())
+                .or_else(|| process.status_reason.clone()),
+            status,
+            ports,
+        }
+    }
+
+    /// Update the record with a newer view of the same run
+    ///
+    /// Ports are accumulated because listeners come and go during a run. A
+    /// run that has exited keeps its outcome: later views without an exit
+    /// time come from a daemon that lost track of it.
+    pub fn update(&mut self, newer: RunRecord) {
+        let mut ports = std::mem::take(&mut self.ports);
+        ports.extend(&newer.ports);
+        ports.sort_unstable();
+        ports.dedup();
+        if self.exit_time.is_none() || newer.exit_time.is_some() {
+            *self = newer;
+        }
+        self.ports = ports;
+    }
+}
+
+/// Keeps the runs of every process in the data directory
+///
+/// Runs are recorded in memory and written out on tokio's blocking pool, so
+/// recording never stalls the runtime. Changes made while a write is pending
+/// are saved by that write.
+pub struct RunHistory {
+    path: PathBuf,
+    history: Mutex<HistoryFile>,
+    /// Serializes writers, so the file always ends up with the latest runs
+    write_lock: Mutex<()>,
+    /// Whether a write has been queued and has not yet taken its snapshot
+    save_pending: AtomicBool,
+}
+
+impl RunHistory {
+    /// Open the history in the data directory, starting empty when none is usable
+    pub fn new(data_dir: &Path) -> Self {
+        let path = data_dir.join(HISTORY_FILE_NAME);
+        let history = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                warn!("Ignoring unreadable run history {:?}: {}", path, e);
+                HistoryFile::default()
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HistoryFile::default(),
+            Err(e) => {
+                warn!("Failed to read run history {:?}: {}", path, e);
+                HistoryFile::default()
+            }
+        };
+        Self {
+            path,
+            history: Mutex::new(history),
+            write_lock: Mutex::new(()),
+            save_pending: AtomicBool::new(false),
+        }
+    }
+
+    /// Record the current state of a run, adding it when it is new
+    pub fn record(self: &Arc<Self>, process: &ProxyInfo) {
+        let record = RunRecord::from_process(process);
+        let Ok(mut history) = self.history.lock() else {
+            return;
+        };
+        let runs = &mut history.runs;
+        match runs.iter_mut().rev().find(|run| run.id == record.id) {
+            Some(run) => run.update(record),
+            None => {
+                let same_process =
+                    |run: &RunRecord| run.project == record.project && run.name == record.name;
+                if runs.iter().filter(|run| same_process(run)).count() >= MAX_RUNS_PER_PROCESS {
+                    if let Some(oldest) = runs.iter().position(same_process) {
+                        runs.remove(oldest);
+                    }
+                }
+                if runs.len() >= MAX_RUNS {
+                    runs.remove(0);
+                }
+                runs.push(record);
+            }
+        }
+        drop(history);
+
+        if self.save_pending.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => {
+                let history = self.clone();
+                runtime.spawn_blocking(move || history.save());
+            }
+            Err(_) => self.save(),
+        }
+    }
+
+    /// Write the current runs to the history file
+    fn save(&self) {
+        let Ok(_writing) = self.write_lock.lock() else {
+            return;
+        };
+        // Later changes queue another write
+        self.save_pending.store(false, Ordering::Release);
+        let contents = match self.history.lock() {
+            Ok(history) => serde_json::to_vec_pretty(&*history),
+            Err(_) => return,
+        };
+
+        let result = contents
+            .map_err(std::io::Error::other)
+            .and_then(|contents| {
+                // Write to a temporary file first so a crash never leaves a truncated history
+                let tmp_path = self.path.with_extension("json.tmp");
+                std::fs::write(&tmp_path, contents)?;
+                std::fs::rename(&tmp_path, &self.path)
+            });
+        match result {
+            Ok(()) => debug!("Saved run history to {:?}", self.path),
+            Err(e) => warn!("Failed to save run history {:?}: {}", self.path, e),
+        }
+    }
+
+    /// The most recent runs, newest first, optionally of one project or process
+    pub fn list(&self, project: Option<&str>, name: Option<&str>, limit: usize) -> Vec<RunRecord> {
+        let Ok(history) = self.history.lock() else {
+            return Vec::new();
+        };
+        let mut matching = history
+            .runs
+            .iter()
+            .filter(|run| project.map_or(true, |project| run.project == project))
+            .filter(|run| name.map_or(true, |name| run.name == name))
+            .cloned()
+            .collect::<Vec<_>>();
+        matching.sort_by_key(|run| std::cmp::Reverse(run.start_time));
+        matching.truncate(limit);
+        matching
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::daemon::process::types::ProxyInfoParams;
+
+    fn run(id: &str, name: &str) -> ProxyInfo {
+        let process = ProxyInfo::new(ProxyInfoParams {
+            id: id.to_string(),
+            name: name.to_string(),
+            project: "shop".to_string(),
+            cmd: Some("npm run dev".to_string()),
+            allocated_ports: vec![("http".to_string(), 20000)],
+            started_by: Some("cli".to_string()),
+            pid: 4242,
+            ..Default::default()
+        });
+        process.set_status(ProcessStatus::Running);
+        process
+    }
+
+    fn exit(process: &ProxyInfo, code: i32) {
+        *process.exit_code.lock().unwrap() = Some(code);
+        *process.exit_time.lock().unwrap() = Some(Utc::now());
+        process.set_status(process.exit_status(Some(code)));
+    }
+
+    #[test]
+    fn runs_are_recorded_until_they_exit_and_survive_a_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = Arc::new(RunHistory::new(dir.path()));
+        let process = run("id-1", "api");
+        history.record(&process);
+        process.update_detected_port(&[3000]);
+        exit(&process, 1);
+        history.record(&process);
+
+        // A restored view of the run does not erase how it ended
+        let mut restored = run("id-1", "api");
+        restored.status_reason = Some("daemon restarted".to_string());
+        restored.set_status(ProcessStatus::Stopped);
+        history.record(&restored);
+
+        let runs = RunHistory::new(dir.path()).list(Some("shop"), Some("api"), 10);
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, ProcessStatus::Failed);
+        assert_eq!(runs[0].exit_code, Some(1));
+        assert_eq!(runs[0].started_by.as_deref(), Some("cli"));
+        assert_eq!(runs[0].ports, vec![3000, 20000]);
+    }
+
+    #[tokio::test]
+    async fn runs_recorded_on_the_runtime_are_saved_in_the_background() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = Arc::new(RunHistory::new(dir.path()));
+        for i in 0..20 {
+            history.record(&run(&format!("id-{i}"), "api"));
+        }
+
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while RunHistory::new(dir.path()).list(None, None, 100).len() < 20 {
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("the runs were not saved");
+    }
+
+    #[test]
+    fn only_recent_runs_are_kept_and_listed_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = Arc::new(RunHistory::new(dir.path()));
+        for i in 0..MAX_RUNS_PER_PROCESS + 2 {
+            let mut process = run(&format!("id-{i}"), "api");
+            process.start_time += chrono::Duration::seconds(i as i64);
+            history.record(&process);
+        }
+        history.record(&run("id-worker", "worker"));
+
+        let runs = history.list(Some("shop"), Some("api"), usize::MAX);
+        assert_eq!(runs.len(), MAX_RUNS_PER_PROCESS);
+        assert_eq!(runs[0].id, format!("id-{}", MAX_RUNS_PER_PROCESS + 1));
+        assert_eq!(runs.last().unwrap().id, "id-2");
+        assert_eq!(history.list(None, None, 3).len(), 3);
+        assert!(history.list(Some("other"), None, 10).is_empty());
+
+        std::fs::write(dir.path().join(HISTORY_FILE_NAME), "not json").unwrap();
+        assert!(RunHistory::new(dir.path()).list(None, None, 10).is_empty());
+    }
+}
@@ -7,7 +7,7 @@ use regex::Regex;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use tokio::io::{AsyncRead, AsyncReadExt};
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{broadcast, mpsc, oneshot};
 use tracing::{debug, error, info, warn};
 
 const CHUNK_SIZE: usize = 8192; // 8KB chunks
@@ -29,6 +29,10 @@ pub struct HyperLogConfig {
     pub is_stderr: bool,
     pub log_file_path: Option<PathBuf>,
     pub log_hub: Arc<LogHub>,
+    /// Receives every chunk as it was read, for attached clients
+    pub output_tx: Option<broadcast::Sender<Bytes>>,
+    /// Set to the time of every chunk read, for the watchdog
+    pub last_output: Option<Arc<Mutex<std::time::Instant>>>,
 }
 
 pub struct HyperLogStreamer {
@@ -77,6 +81,8 @@ impl HyperLogStreamer {
         stream: R,
     ) -> tokio::task::JoinHandle<()> {
         let chunk_tx = self.chunk_tx.clone();
+        let output_tx = self.config.output_tx.clone();
+        let last_output = self.config.last_output.clone();
         let process_key = self.config.process_key.clone();
         let stream_name = self.config.stream_name;
 
@@ -95,6 +101,15 @@ impl HyperLogStreamer {
                     Ok(n) => {
                         // Send chunk without copying
                         let chunk = buffer.split_to(n).freeze();
+                        if let Some(last_output) = &last_output {
+                            if let Ok(mut last_output) = last_output.lock() {
+                                *last_output = std::time::Instant::now();
+                            }
+                        }
+                        if let Some(output_tx) = &output_tx {
+                            // No receivers simply means nobody is attached
+                            let _ = output_tx.send(chunk.clone());
+                        }
                         if chunk_tx.send(chunk).await.is_err() {
                             debug!("Channel closed, stopping reader");
                             break;
@@ -466,6 +481,8 @@ mod tests {
             is_stderr: false,
             log_file_path: Some(path.clone()),
             log_hub,
+            output_tx: None,
+            last_output: None,
         });
         let (mut input, output) = tokio::io::duplex(64);
         let handle = streamer.spawn(output).await;
@@ -504,6 +521,8 @@ mod tests {
                 is_stderr: false,
                 log_file_path: None,
                 log_hub,
+                output_tx: None,
+                last_output: None,
             },
             receiver,
         )
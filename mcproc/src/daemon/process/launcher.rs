@@ -1,13 +1,27 @@
 use crate::common::process_key::ProcessKey;
+use crate::common::schedule::Schedule;
 use crate::daemon::error::{McprocdError, Result};
+use crate::daemon::process::cgroup::Cgroup;
+use crate::daemon::process::file_watch::FileWatch;
+use crate::daemon::process::health::HealthCheck;
+use crate::daemon::process::limits::ResourceLimits;
+use crate::daemon::process::port_allocator::{port_env, AllocatedPort};
 use crate::daemon::process::proxy::ProxyInfo;
+use crate::daemon::process::pty::{self, PtyMaster, PtySize};
+use crate::daemon::process::readiness::ReadinessProbe;
+use crate::daemon::process::restart::{RestartPolicy, RestartState};
+use crate::daemon::process::schedule::ScheduleState;
+use crate::daemon::process::stop::StopPolicy;
+use crate::daemon::process::task::TaskOptions;
 use crate::daemon::process::toolchain::Toolchain;
 use crate::daemon::process::types::ProxyInfoParams;
+use crate::daemon::process::watchdog::WatchdogRule;
+use nix::sys::resource::{setrlimit, Resource};
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use std::process::Stdio;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tokio::process::Command;
 use tracing::{debug, error, info};
 use uuid::Uuid;
@@ -38,9 +52,17 @@ pub struct LaunchProcessParams {
     pub cwd: Option<PathBuf>,
     pub env: Option<HashMap<String, String>>,
     pub toolchain: Option<String>,
+    pub stdin: bool,
+    pub pty: Option<PtySize>,
+    pub allocated_ports: Vec<AllocatedPort>,
+    /// cgroup the process moves itself into before exec
+    pub cgroup: Option<Cgroup>,
+    /// Resource limits set with setrlimit before exec
+    pub rlimits: Vec<(Resource, u64)>,
 }
 
 /// Parameters for creating a ProxyInfo via launcher
+#[derive(Default)]
 pub struct CreateProxyInfoParams {
     pub name: String,
     pub project: String,
@@ -51,6 +73,25 @@ pub struct CreateProxyInfoParams {
     pub wait_for_log: Option<String>,
     pub wait_timeout: Option<u32>,
     pub toolchain: Option<String>,
+    pub restart_policy: RestartPolicy,
+    pub restart_state: RestartState,
+    pub depends_on: Vec<String>,
+    pub readiness_probes: Vec<ReadinessProbe>,
+    pub health_check: Option<HealthCheck>,
+    pub stop_policy: StopPolicy,
+    pub stdin: bool,
+    pub pty: Option<PtySize>,
+    pub allocated_ports: Vec<AllocatedPort>,
+    pub limits: ResourceLimits,
+    pub watchdog: Vec<WatchdogRule>,
+    pub watch: Option<FileWatch>,
+    pub task: Option<TaskOptions>,
+    pub schedule: Option<Schedule>,
+    pub schedule_state: Arc<Mutex<ScheduleState>>,
+    pub started_by: Option<String>,
+    pub labels: BTreeMap<String, String>,
+    pub description: Option<String>,
+    pub cgroup: Option<Cgroup>,
     pub pid: u32,
 }
 
@@ -63,10 +104,13 @@ impl ProcessLauncher {
     }
 
     /// Build and spawn a process with the given configuration
+    ///
+    /// Processes started with a pseudo-terminal also return its master side,
+    /// which carries their combined output and accepts their input.
     pub async fn launch_process(
         &self,
         params: LaunchProcessParams,
-    ) -> Result<(tokio::process::Child, ProcessKey)> {
+    ) -> Result<(tokio::process::Child, ProcessKey, Option<PtyMaster>)> {
         let process_key = ProcessKey::new(params.project.clone(), params.name.clone());
 
         // Build command - always use shell execution for consistency
@@ -141,6 +185,12 @@ impl ProcessLauncher {
         }
 
         // Set environment variables
+        if params.pty.is_some() {
+            // Terminal-aware tools look at TERM to decide on colors and progress output
+            command.env("TERM", "xterm-256color");
+        }
+        // Explicitly configured variables take precedence over allocated ports
+        command.envs(port_env(&params.allocated_ports));
         if let Some(env_vars) = params.env {
             for (key, value) in env_vars {
                 command.env(key, value);
@@ -148,9 +198,28 @@ impl ProcessLauncher {
         }
 
         // Setup stdio
-        command.stdout(Stdio::piped());
-        command.stderr(Stdio::piped());
-        command.stdin(Stdio::null());
+        let pty_master = if let Some(size) = params.pty {
+            let spawn_error = |e: std::io::Error| McprocdError::ProcessSpawnFailed {
+                name: params.name.clone(),
+                error: format!("Failed to open pseudo-terminal: {}", e),
+            };
+            let (master, slave) = pty::open(size).map_err(spawn_error)?;
+            command.stdin(Stdio::from(slave.try_clone().map_err(spawn_error)?));
+            command.stdout(Stdio::from(slave.try_clone().map_err(spawn_error)?));
+            command.stderr(Stdio::from(slave));
+            Some(master)
+        } else {
+            command.stdout(Stdio::piped());
+            command.stderr(Stdio::piped());
+            // Keep stdin open only when input will be sent to the process
+            command.stdin(if params.stdin {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            });
+            None
+        };
+        let controlling_terminal = pty_master.is_some();
 
         // IMPORTANT: Do NOT use kill_on_drop(true) as it sends SIGKILL immediately
         // and prevents graceful shutdown. ProxyInfo::stop() signals the process group
@@ -162,11 +231,18 @@ impl ProcessLauncher {
         #[cfg(unix)]
         {
             unsafe {
-                command.pre_exec(|| {
+                command.pre_exec(move || {
                     if libc::setsid() == -1 {
                         return Err(std::io::Error::last_os_error());
                     }
 
+                    // Make the pseudo-terminal (already on stdin) the session's terminal
+                    if controlling_terminal
+                        && libc::ioctl(libc::STDIN_FILENO, libc::TIOCSCTTY as _, 0) == -1
+                    {
+                        return Err(std::io::Error::last_os_error());
+                    }
+
                     #[cfg(target_os = "linux")]
                     {
                         // PR_SET_PDEATHSIG = 1
@@ -185,6 +261,40 @@ impl ProcessLauncher {
             debug!("Configured dedicated process group for {}", params.name);
         }
 
+        // Join the cgroup before exec, so every descendant is tracked from the start.
+        // The caller checks membership afterwards and falls back to the process group.
+        #[cfg(unix)]
+        let cgroup_procs = params.cgroup.as_ref().and_then(|cgroup| {
+            cgroup
+                .procs_file()
+                .map_err(|e| debug!("Cannot open {}: {}", cgroup.path().display(), e))
+                .ok()
+        });
+        #[cfg(unix)]
+        if let Some(procs) = &cgroup_procs {
+            let fd = std::os::fd::AsRawFd::as_raw_fd(procs);
+            unsafe {
+                command.pre_exec(move || {
+                    libc::write(fd, b"0".as_ptr().cast(), 1);
+                    Ok(())
+                });
+            }
+        }
+
+        // Both the soft and the hard limit are set, so the process cannot raise them again
+        #[cfg(unix)]
+        if !params.rlimits.is_empty() {
+            let rlimits = params.rlimits;
+            unsafe {
+                command.pre_exec(move || {
+                    for (resource, limit) in &rlimits {
+                        setrlimit(*resource, *limit, *limit)?;
+                    }
+                    Ok(())
+                });
+            }
+        }
+
         // Log file will be created automatically on first write
         info!(
             "Starting process {} in project {}",
@@ -206,17 +316,24 @@ impl ProcessLauncher {
             child.id()
         );
 
-        Ok((child, process_key))
+        // Drop the command so the daemon holds no slave descriptor and reads
+        // from the master end once the process and its children exit
+        drop(command);
+        #[cfg(unix)]
+        drop(cgroup_procs);
+
+        Ok((child, process_key, pty_master))
     }
 
     /// Create a ProxyInfo instance for the launched process
     pub fn create_proxy_info(&self, params: CreateProxyInfoParams) -> Arc<ProxyInfo> {
-        // Extract port from environment if available
+        // Extract port from environment if available, else use the first allocated one
         let port = params
             .env
             .as_ref()
             .and_then(|e| e.get("PORT"))
-            .and_then(|p| p.parse::<u16>().ok());
+            .and_then(|p| p.parse::<u16>().ok())
+            .or_else(|| params.allocated_ports.first().map(|(_, port)| *port));
         let id = Uuid::new_v4().to_string();
         let mut proxy = ProxyInfo::new(ProxyInfoParams {
             id,
@@ -229,6 +346,25 @@ impl ProcessLauncher {
             wait_for_log: params.wait_for_log,
             wait_timeout: params.wait_timeout,
             toolchain: params.toolchain,
+            restart_policy: params.restart_policy,
+            restart_state: params.restart_state,
+            depends_on: params.depends_on,
+            readiness_probes: params.readiness_probes,
+            health_check: params.health_check,
+            stop_policy: params.stop_policy,
+            stdin: params.stdin,
+            pty: params.pty,
+            allocated_ports: params.allocated_ports,
+            limits: params.limits,
+            watchdog: params.watchdog,
+            watch: params.watch,
+            task: params.task,
+            schedule: params.schedule,
+            schedule_state: params.schedule_state,
+            started_by: params.started_by,
+            labels: params.labels,
+            description: params.description,
+            cgroup: params.cgroup,
             pid: params.pid,
         });
         proxy.port = port;
@@ -274,7 +410,7 @@ mod tests {
     #[tokio::test]
     async fn launch_process_preserves_direct_argument_boundaries() {
         let launcher = ProcessLauncher::new();
-        let (mut child, _) = launcher
+        let (mut child, _, _) = launcher
             .launch_process(LaunchProcessParams {
                 name: "quoted-args".into(),
                 project: "test".into(),
@@ -283,6 +419,11 @@ mod tests {
                 cwd: None,
                 env: None,
                 toolchain: None,
+                stdin: false,
+                pty: None,
+                allocated_ports: Vec::new(),
+                cgroup: None,
+                rlimits: vec![],
             })
             .await
             .unwrap();
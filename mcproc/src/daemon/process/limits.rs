@@ -0,0 +1,247 @@
+//! Resource limits of managed processes
+//!
+//! Limits are written to the process's cgroup where its controllers are
+//! enabled. Otherwise the memory limit falls back to RLIMIT_AS, which caps
+//! each process of the tree separately, and the CPU and process limits are not
+//! enforced. The open file limit is always set with setrlimit.
+
+use crate::common::exit_code::format_exit_reason;
+use crate::common::limits::format_size;
+use crate::daemon::process::cgroup::Cgroup;
+use nix::sys::resource::Resource;
+use serde::{Deserialize, Serialize};
+
+/// Period cgroups distribute CPU time over, in microseconds
+const CPU_PERIOD_US: u64 = 100_000;
+
+/// Smallest CPU quota a cgroup accepts, in cores
+const MIN_CPUS: f64 = 0.01;
+
+/// Exit code of a shell whose child was killed with SIGKILL
+const SIGKILL_EXIT_CODE: i32 = 128 + libc::SIGKILL;
+
+/// Memory, CPU, process and open file limits of a process
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    /// Memory ceiling in bytes
+    pub memory: Option<u64>,
+    /// CPU quota in cores
+    pub cpus: Option<f64>,
+    /// Maximum number of processes and threads
+    pub pids: Option<u32>,
+    /// Maximum number of open file descriptors per process
+    pub open_files: Option<u64>,
+}
+
+impl TryFrom<proto::ResourceLimits> for ResourceLimits {
+    type Error = String;
+
+    fn try_from(limits: proto::ResourceLimits) -> Result<Self, Self::Error> {
+        if limits.memory_bytes == Some(0) {
+            return Err("memory limit must be greater than 0".to_string());
+        }
+        if limits
+            .cpus
+            .is_some_and(|cpus| !cpus.is_finite() || cpus < MIN_CPUS)
+        {
+            return Err(format!("cpus limit must be at least {MIN_CPUS}"));
+        }
+        if limits.pids == Some(0) {
+            return Err("pids limit must be greater than 0".to_string());
+        }
+        if limits.open_files == Some(0) {
+            return Err("open_files limit must be greater than 0".to_string());
+        }
+        Ok(Self {
+            memory: limits.memory_bytes,
+            cpus: limits.cpus,
+            pids: limits.pids,
+            open_files: limits.open_files,
+        })
+    }
+}
+
+impl From<ResourceLimits> for proto::ResourceLimits {
+    fn from(limits: ResourceLimits) -> Self {
+        Self {
+            memory_bytes: limits.memory,
+            cpus: limits.cpus,
+            pids: limits.pids,
+            open_files: limits.open_files,
+        }
+    }
+}
+
+/// How the limits of one run are enforced
+#[derive(Debug, Default, PartialEq)]
+pub struct AppliedLimits {
+    /// Limits written to the cgroup
+    pub in_cgroup: Vec<&'static str>,
+    /// Limits set with setrlimit in the child before exec
+    pub rlimits: Vec<(Resource, u64)>,
+    /// Limits nothing enforces
+    pub unenforced: Vec<&'static str>,
+}
+
+impl ResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Write the limits to the cgroup where it has the controllers, falling back to setrlimit
+    pub fn apply(&self, cgroup: Option<&Cgroup>) -> AppliedLimits {
+        let mut applied = AppliedLimits::default();
+        let set = |file: &str, value: String| {
+            cgroup.is_some_and(|cgroup| cgroup.set_control(file, &value).is_ok())
+        };
+
+        if let Some(memory) = self.memory {
+            if set("memory.max", memory.to_string()) {
+                // Otherwise the process tree swaps instead of reaching the limit
+                set("memory.swap.max", "0".to_string());
+                applied.in_cgroup.push("memory");
+            } else {
+                applied.rlimits.push((Resource::RLIMIT_AS, memory));
+            }
+        }
+        if let Some(cpus) = self.cpus {
+            let quota = (cpus * CPU_PERIOD_US as f64).round() as u64;
+            if set("cpu.max", format!("{quota} {CPU_PERIOD_US}")) {
+                applied.in_cgroup.push("cpus");
+            } else {
+                applied.unenforced.push("cpus");
+            }
+        }
+        if let Some(pids) = self.pids {
+            if set("pids.max", pids.to_string()) {
+                applied.in_cgroup.push("pids");
+            } else {
+                applied.unenforced.push("pids");
+            }
+        }
+        if let Some(open_files) = self.open_files {
+            applied.rlimits.push((Resource::RLIMIT_NOFILE, open_files));
+        }
+        applied
+    }
+
+    /// Why the process exited, if it was likely killed for exceeding the memory limit
+    ///
+    /// With a cgroup the OOM kills it counted tell for certain. `exit_code` and
+    /// `signal` describe how the process itself exited; when it survived, one
+    /// of its children was killed instead. Without a cgroup a SIGKILL is only
+    /// taken as a hint, so callers should rule out kills of their own first.
+    pub fn exit_reason(
+        &self,
+        cgroup: Option<&Cgroup>,
+        exit_code: Option<i32>,
+        signal: Option<i32>,
+    ) -> Option<String> {
+        let memory = self.memory?;
+        let killed = matches!(
+            (exit_code, signal),
+            (Some(SIGKILL_EXIT_CODE), _) | (_, Some(libc::SIGKILL))
+        );
+        let Some(cgroup) = cgroup else {
+            return killed.then(|| {
+                format!(
+                    "Killed (SIGKILL) with memory limit {} set, likely out of memory",
+                    format_size(memory)
+                )
+            });
+        };
+        if cgroup.oom_kills() == 0 {
+            return None;
+        }
+        let limit = format!("OOM-killed by memory limit {}", format_size(memory));
+        let exit = match (exit_code, signal) {
+            _ if killed => return Some(limit),
+            (Some(code), _) => format_exit_reason(code),
+            (None, Some(signal)) => format!("Terminated by signal {signal}"),
+            (None, None) => "Exited".to_string(),
+        };
+        Some(format!("{exit}; a child process was {limit}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_are_validated() {
+        let limits = ResourceLimits::try_from(proto::ResourceLimits {
+            memory_bytes: Some(2 << 30),
+            cpus: Some(1.5),
+            pids: None,
+            open_files: Some(4096),
+        })
+        .unwrap();
+        assert_eq!(limits.memory, Some(2 << 30));
+        assert_eq!(proto::ResourceLimits::from(limits).cpus, Some(1.5));
+        assert!(ResourceLimits::default().is_empty());
+
+        for invalid in [
+            proto::ResourceLimits {
+                memory_bytes: Some(0),
+                ..Default::default()
+            },
+            proto::ResourceLimits {
+                cpus: Some(0.001),
+                ..Default::default()
+            },
+            proto::ResourceLimits {
+                cpus: Some(f64::NAN),
+                ..Default::default()
+            },
+            proto::ResourceLimits {
+                pids: Some(0),
+                ..Default::default()
+            },
+        ] {
+            assert!(ResourceLimits::try_from(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn limits_fall_back_to_setrlimit_without_cgroup_controllers() {
+        let limits = ResourceLimits {
+            memory: Some(1 << 30),
+            cpus: Some(0.5),
+            pids: Some(64),
+            open_files: Some(256),
+        };
+        assert_eq!(
+            limits.apply(None),
+            AppliedLimits {
+                in_cgroup: vec![],
+                rlimits: vec![
+                    (Resource::RLIMIT_AS, 1 << 30),
+                    (Resource::RLIMIT_NOFILE, 256)
+                ],
+                unenforced: vec!["cpus", "pids"],
+            }
+        );
+    }
+
+    #[test]
+    fn kills_without_a_cgroup_are_reported_as_likely_out_of_memory() {
+        let limits = ResourceLimits {
+            memory: Some(64 << 20),
+            ..Default::default()
+        };
+        assert_eq!(
+            limits
+                .exit_reason(None, None, Some(libc::SIGKILL))
+                .as_deref(),
+            Some("Killed (SIGKILL) with memory limit 64M set, likely out of memory")
+        );
+        assert!(limits
+            .exit_reason(None, Some(SIGKILL_EXIT_CODE), None)
+            .is_some());
+        assert!(limits.exit_reason(None, Some(1), None).is_none());
+        assert!(ResourceLimits::default()
+            .exit_reason(None, None, Some(libc::SIGKILL))
+            .is_none());
+    }
+}
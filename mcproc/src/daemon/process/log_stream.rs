@@ -1,11 +1,12 @@
 use crate::common::process_key::ProcessKey;
 use crate::daemon::log::LogHub;
 use crate::daemon::process::hyperlog::{HyperLogConfig, HyperLogStreamer};
+use bytes::Bytes;
 use regex::Regex;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use tokio::io::AsyncRead;
-use tokio::sync::oneshot;
+use tokio::sync::{broadcast, oneshot};
 
 pub struct LogStreamConfig {
     pub stream_name: &'static str, // "stdout" or "stderr"
@@ -19,6 +20,8 @@ pub struct LogStreamConfig {
     pub matched_line: Arc<Mutex<Option<String>>>, // The line that matched the pattern
     pub log_file_path: Option<PathBuf>,
     pub log_hub: Arc<LogHub>,
+    pub output_tx: Option<broadcast::Sender<Bytes>>,
+    pub last_output: Option<Arc<Mutex<std::time::Instant>>>,
 }
 
 impl LogStreamConfig {
@@ -40,6 +43,8 @@ impl LogStreamConfig {
             is_stderr: self.stream_name == "stderr",
             log_file_path: self.log_file_path,
             log_hub: self.log_hub,
+            output_tx: self.output_tx,
+            last_output: self.last_output,
         };
 
         let streamer = HyperLogStreamer::new(hyperlog_config);
@@ -1,28 +1,75 @@
 use crate::common::config::Config;
+use crate::common::dependency::dependency_order;
+use crate::common::labels::LabelSelector;
 use crate::common::process_key::ProcessKey;
+use crate::common::validation::validate_port_names;
 use crate::daemon::error::{McprocdError, Result};
-use crate::daemon::log::{cleaner, LogHub};
+use crate::daemon::log::{cleaner, segments, LogHub};
+use crate::daemon::process::cgroup::{Cgroup, CgroupRoot};
 use crate::daemon::process::exit_handler::ExitHandler;
+use crate::daemon::process::file_watch::{self, FileWatcher};
+use crate::daemon::process::health::HealthStatus;
+use crate::daemon::process::history::{RunHistory, RunRecord};
 use crate::daemon::process::launcher::ProcessLauncher;
 use crate::daemon::process::log_stream::LogStreamConfig;
-use crate::daemon::process::port_detector;
+use crate::daemon::process::persistence::ProcessStore;
+use crate::daemon::process::port_allocator::{AllocatedPort, PortAllocator};
+use crate::daemon::process::port_conflict::{self, PortOwner};
+use crate::daemon::process::port_detector::{self, PortHolder};
 use crate::daemon::process::proxy::{ProcessStatus, ProxyInfo};
+use crate::daemon::process::readiness;
 use crate::daemon::process::registry::ProcessRegistry;
+use crate::daemon::process::restart::{self, RestartPolicy, RestartState};
+use crate::daemon::process::schedule::ScheduledRun;
+use crate::daemon::process::task::{self, TaskOptions, TaskResult};
+use crate::daemon::process::types::{ReconcileAction, ReconcileResult, StartProcessParams};
+use crate::daemon::process::watchdog::{self, WatchdogAction, WatchdogTransition};
 use crate::daemon::stream::{SharedStreamEventHub, StreamEvent};
+use bytes::Bytes;
 use colored::Colorize;
-use std::collections::{HashMap, HashSet};
+use nix::sys::signal::Signal;
+use nix::unistd::{getpgid, Pid};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
-use tokio::sync::oneshot;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, mpsc, oneshot};
 use tracing::{debug, error, info, warn};
 
+/// How often the health monitor looks for due liveness checks
+const HEALTH_MONITOR_TICK: Duration = Duration::from_secs(1);
+
+/// How long a finished task's remaining output is waited for
+const TASK_OUTPUT_GRACE: Duration = Duration::from_secs(2);
+
+/// A restarted process, whether waiting for its log pattern timed out and
+/// whether it matched, the log lines seen while waiting and the matching line
+pub type RestartOutcome = (Arc<ProxyInfo>, bool, bool, Vec<String>, Option<String>);
+
 pub struct ProcessManager {
     registry: ProcessRegistry,
     config: Arc<Config>,
     log_hub: Arc<LogHub>,
     launcher: ProcessLauncher,
     event_hub: Option<SharedStreamEventHub>,
+    /// Exited processes whose restart policy asks for a relaunch
+    restart_tx: mpsc::UnboundedSender<Arc<ProxyInfo>>,
+    restart_rx: Mutex<Option<mpsc::UnboundedReceiver<Arc<ProxyInfo>>>>,
+    /// Started processes whose files are to be watched for changes
+    watch_tx: mpsc::UnboundedSender<Arc<ProxyInfo>>,
+    watch_rx: Mutex<Option<mpsc::UnboundedReceiver<Arc<ProxyInfo>>>>,
+    /// Registered scheduled processes whose runs are to be launched on schedule
+    schedule_tx: mpsc::UnboundedSender<Arc<ProxyInfo>>,
+    schedule_rx: Mutex<Option<mpsc::UnboundedReceiver<Arc<ProxyInfo>>>>,
+    /// Registry snapshot kept in the data directory across daemon restarts
+    store: ProcessStore,
+    /// Past and current runs of every process, kept in the data directory
+    history: Arc<RunHistory>,
+    /// Hands out ports to processes that request them by name
+    ports: PortAllocator,
+    /// Where process cgroups are created, when a writable cgroup v2 hierarchy exists
+    cgroups: Option<CgroupRoot>,
 }
 
 struct ProcessNameReservation {
@@ -60,12 +107,30 @@ impl ProcessManager {
         event_hub: SharedStreamEventHub,
     ) -> Self {
         let launcher = ProcessLauncher::new();
+        let (restart_tx, restart_rx) = mpsc::unbounded_channel();
+        let (watch_tx, watch_rx) = mpsc::unbounded_channel();
+        let (schedule_tx, schedule_rx) = mpsc::unbounded_channel();
+        let store = ProcessStore::new(&config.paths.data_dir);
+        let history = Arc::new(RunHistory::new(&config.paths.data_dir));
+        let ports =
+            PortAllocator::new(config.process.ports.range_start..=config.process.ports.range_end);
+        let cgroups = CgroupRoot::detect(&config.process.cgroup);
         Self {
             registry: ProcessRegistry::new(),
             config,
             log_hub,
             launcher,
             event_hub: Some(event_hub),
+            restart_tx,
+            restart_rx: Mutex::new(Some(restart_rx)),
+            watch_tx,
+            watch_rx: Mutex::new(Some(watch_rx)),
+            schedule_tx,
+            schedule_rx: Mutex::new(Some(schedule_rx)),
+            store,
+            history,
+            ports,
+            cgroups,
         }
     }
 
@@ -103,6 +168,47 @@ impl ProcessManager {
         tokio::time::sleep(Duration::from_millis(self.config.process.restart.delay_ms)).await;
     }
 
+    /// Allocate the named ports, avoiding ports other managed processes use
+    fn allocate_ports(&self, key: &ProcessKey, names: &[String]) -> Result<Vec<AllocatedPort>> {
+        validate_port_names(names).map_err(McprocdError::InvalidRequest)?;
+        let busy = self
+            .registry
+            .get_all_processes()
+            .iter()
+            .filter(|process| &process.key != key)
+            .flat_map(|process| {
+                [
+                    process.port,
+                    process.detected_port.lock().ok().and_then(|port| *port),
+                ]
+            })
+            .flatten()
+            .collect::<HashSet<_>>();
+        self.ports
+            .allocate(
+                key,
+                names,
+                |claimed_by| self.registry.is_reserved(claimed_by),
+                &busy,
+            )
+            .map_err(McprocdError::PortAllocationFailed)
+    }
+
+    /// Create a cgroup for a new run of a process, if cgroups are in use
+    fn create_cgroup(&self, key: &ProcessKey) -> Option<Cgroup> {
+        let root = self.cgroups.as_ref()?;
+        root.create(key)
+            .map_err(|e| {
+                warn!(
+                    "Failed to create cgroup for {} under {}: {}",
+                    key,
+                    root.path().display(),
+                    e
+                )
+            })
+            .ok()
+    }
+
     /// Publish a process event to the event hub
     fn publish_process_event(&self, event: crate::daemon::process::event::ProcessEvent) {
         if let Some(ref event_hub) = self.event_hub {
@@ -110,19 +216,39 @@ impl ProcessManager {
         }
     }
 
-    #[allow(clippy::too_many_arguments)]
     pub async fn start_process_with_log_stream(
         &self,
-        name: String,
-        project: Option<String>,
-        cmd: Option<String>,
-        args: Vec<String>,
-        cwd: Option<PathBuf>,
-        env: Option<HashMap<String, String>>,
-        wait_for_log: Option<String>,
-        wait_timeout: Option<u32>,
-        toolchain: Option<String>,
+        params: StartProcessParams,
     ) -> Result<(Arc<ProxyInfo>, bool, bool, Vec<String>, Option<String>)> {
+        let StartProcessParams {
+            name,
+            project,
+            cmd,
+            args,
+            cwd,
+            env,
+            wait_for_log,
+            wait_timeout,
+            toolchain,
+            restart_policy,
+            restart_state,
+            depends_on,
+            readiness_probes,
+            health_check,
+            stop_policy,
+            stdin,
+            pty,
+            ports,
+            limits,
+            watchdog,
+            watch,
+            task,
+            schedule,
+            schedule_state,
+            started_by,
+            labels,
+            description,
+        } = params;
         let project = project.unwrap_or_else(|| {
             cwd.as_ref()
                 .and_then(|p| p.file_name())
@@ -132,17 +258,26 @@ impl ProcessManager {
         });
 
         let process_key = ProcessKey::new(project.clone(), name.clone());
+        let existing = self
+            .registry
+            .get_process_by_name_with_project(&name, &project);
+        // The scheduler launches runs with the state of the entry they replace;
+        // any other start of a scheduled process registers a new schedule
+        let scheduled_run = schedule.is_some()
+            && existing
+                .as_ref()
+                .is_some_and(|existing| Arc::ptr_eq(&existing.schedule_state, &schedule_state));
 
         // Remove a reusable terminal entry before atomically reserving this project/name.
-        if let Some(existing) = self
-            .registry
-            .get_process_by_name_with_project(&name, &project)
-        {
+        if let Some(existing) = existing {
             match existing.get_status() {
-                ProcessStatus::Running => {
+                ProcessStatus::Running | ProcessStatus::Paused => {
                     return Err(McprocdError::ProcessAlreadyExists(name));
                 }
-                ProcessStatus::Failed | ProcessStatus::Stopped => {
+                ProcessStatus::Failed
+                | ProcessStatus::Stopped
+                | ProcessStatus::CrashLoop
+                | ProcessStatus::Succeeded => {
                     // Remove failed/stopped process from registry to allow reuse of the name
                     info!(
                         "Removing {:?} process '{}' from registry to allow restart",
@@ -151,6 +286,9 @@ impl ProcessManager {
                     );
                     self.registry.remove_process(&existing.id);
                 }
+                ProcessStatus::Scheduled if scheduled_run => {
+                    self.registry.remove_process(&existing.id);
+                }
                 _ => {
                     return Err(McprocdError::ProcessAlreadyExists(name));
                 }
@@ -162,6 +300,65 @@ impl ProcessManager {
         }
         let reservation = ProcessNameReservation::new(self.registry.clone(), process_key);
 
+        let allocated_ports = self.allocate_ports(&reservation.key, &ports)?;
+        if !allocated_ports.is_empty() {
+            info!("Allocated ports for {}: {:?}", name, allocated_ports);
+        }
+
+        // A scheduled process is only registered here; the scheduler launches its runs
+        if let Some(schedule) = schedule.as_ref().filter(|_| !scheduled_run) {
+            if let Ok(mut state) = schedule_state.lock() {
+                state.next_run = schedule.next_run(None, chrono::Utc::now());
+            }
+            let proxy_arc = self.launcher.create_proxy_info(
+                crate::daemon::process::launcher::CreateProxyInfoParams {
+                    name: name.clone(),
+                    project: project.clone(),
+                    cmd,
+                    args,
+                    cwd,
+                    env,
+                    wait_for_log,
+                    wait_timeout,
+                    toolchain,
+                    restart_policy,
+                    restart_state,
+                    depends_on,
+                    readiness_probes,
+                    health_check,
+                    stop_policy,
+                    stdin,
+                    pty,
+                    allocated_ports,
+                    limits,
+                    watchdog,
+                    watch,
+                    task,
+                    schedule: Some(schedule.clone()),
+                    schedule_state,
+                    started_by,
+                    labels,
+                    description,
+                    // Nothing runs until the first run is due
+                    cgroup: None,
+                    pid: 0,
+                },
+            );
+            proxy_arc.set_status(ProcessStatus::Scheduled);
+
+            let message = format!("Scheduled process {} to run {}", name, schedule);
+            info!("{}", message);
+            let log_msg = format!("{} {}\n", "[mcproc]".yellow().bold(), message.yellow());
+            self.log_hub
+                .publish_log_event(&reservation.key, &log_msg, false);
+
+            self.registry.add_process(proxy_arc.clone());
+            reservation.keep();
+            self.persist_processes();
+            let _ = self.schedule_tx.send(proxy_arc.clone());
+            return Ok((proxy_arc, false, false, Vec::new(), None));
+        }
+
         // Parse wait pattern if provided
         let log_pattern = self.launcher.parse_wait_pattern(wait_for_log.clone())?;
 
@@ -178,8 +375,12 @@ impl ProcessManager {
         let timeout_occurred = Arc::new(Mutex::new(false));
         let matched_line = Arc::new(Mutex::new(None::<String>));
 
+        // Track the process and all of its descendants in a cgroup of its own
+        let cgroup = self.create_cgroup(&reservation.key);
+        let mut applied_limits = limits.apply(cgroup.as_ref());
+
         // Launch the process
-        let (mut child, process_key) = self
+        let launched = self
             .launcher
             .launch_process(crate::daemon::process::launcher::LaunchProcessParams {
                 name: name.clone(),
@@ -189,14 +390,45 @@ impl ProcessManager {
                 cwd: cwd.clone(),
                 env: env.clone(),
                 toolchain: toolchain.clone(),
+                stdin,
+                pty,
+                allocated_ports: allocated_ports.clone(),
+                cgroup: cgroup.clone(),
+                rlimits: applied_limits.rlimits.clone(),
             })
-            .await?;
+            .await;
+        let (mut child, process_key, pty_master) = match launched {
+            Ok(launched) => launched,
+            Err(e) => {
+                if let Some(cgroup) = &cgroup {
+                    let _ = cgroup.remove();
+                }
+                return Err(e);
+            }
+        };
 
         let pid = child.id().ok_or_else(|| McprocdError::ProcessSpawnFailed {
             name: name.clone(),
             error: "Failed to get PID".to_string(),
         })?;
 
+        // Moving into the cgroup fails where the daemon's own cgroup is not delegated
+        let cgroup = cgroup.and_then(|cgroup| {
+            if cgroup.contains(pid) {
+                return Some(cgroup);
+            }
+            warn!(
+                "Process {} could not join cgroup {}, tracking its process group only",
+                name,
+                cgroup.path().display()
+            );
+            let _ = cgroup.remove();
+            applied_limits
+                .unenforced
+                .append(&mut applied_limits.in_cgroup);
+            None
+        });
+
         // Create proxy info
         let proxy_arc = self.launcher.create_proxy_info(
             crate::daemon::process::launcher::CreateProxyInfoParams {
@@ -209,6 +441,25 @@ impl ProcessManager {
                 wait_for_log: wait_for_log.clone(),
                 wait_timeout,
                 toolchain,
+                restart_policy,
+                restart_state,
+                depends_on,
+                readiness_probes: readiness_probes.clone(),
+                health_check,
+                stop_policy,
+                stdin,
+                pty,
+                allocated_ports,
+                limits,
+                watchdog,
+                watch,
+                task,
+                schedule,
+                schedule_state,
+                started_by,
+                labels,
+                description,
+                cgroup,
                 pid,
             },
         );
@@ -216,6 +467,7 @@ impl ProcessManager {
         // Add to registry
         self.registry.add_process(proxy_arc.clone());
         reservation.keep();
+        self.history.record(&proxy_arc);
 
         // Publish Starting event
         self.publish_process_event(crate::daemon::process::event::ProcessEvent::Starting {
@@ -226,16 +478,40 @@ impl ProcessManager {
 
         // The start event is already logged via LogHub publish in launcher.rs
 
-        // Setup stdout/stderr capture
-        let stdout = child.stdout.take().expect("stdout should be captured");
-        let stderr = child.stderr.take().expect("stderr should be captured");
+        if !applied_limits.unenforced.is_empty() {
+            let message = format!(
+                "Not enforcing the {} limit: no cgroup controller is available",
+                applied_limits.unenforced.join(" and ")
+            );
+            warn!("Process {}: {}", name, message);
+            let log_msg = format!("{} {}\n", "[mcproc]".yellow().bold(), message.yellow());
+            self.log_hub
+                .publish_log_event(&process_key, &log_msg, false);
+        }
+
+        if !proxy_arc.watchdog.is_empty() && !self.config.process.metrics.enabled {
+            let message = "Not evaluating watchdog rules: metrics sampling is disabled";
+            warn!("Process {}: {}", name, message);
+            let log_msg = format!("{} {}\n", "[mcproc]".yellow().bold(), message.yellow());
+            self.log_hub
+                .publish_log_event(&process_key, &log_msg, false);
+        }
 
         // Get log file path
         let log_file_path = self.log_hub.get_log_file_path_for_key(&process_key);
 
-        // Spawn stdout reader
-        let stdout_config = LogStreamConfig {
-            stream_name: "stdout",
+        // Each run writes its own segment of the log, starting with a header
+        let header = format!(
+            "run {} started by {}",
+            proxy_arc.id,
+            proxy_arc.started_by.as_deref().unwrap_or("unknown")
+        );
+        if let Err(e) = segments::open_segment(&log_file_path, &proxy_arc.id, &header) {
+            warn!("Failed to start log segment for {}: {}", name, e);
+        }
+
+        let log_stream_config = |stream_name: &'static str| LogStreamConfig {
+            stream_name,
             process_key: process_key.clone(),
             log_pattern: log_pattern.clone(),
             log_ready_tx: log_ready_tx.clone(),
@@ -246,33 +522,34 @@ impl ProcessManager {
             matched_line: matched_line.clone(),
             log_file_path: Some(log_file_path.clone()),
             log_hub: self.log_hub.clone(),
+            output_tx: Some(proxy_arc.output.clone()),
+            last_output: Some(proxy_arc.last_output.clone()),
         };
-        let stdout_handle = stdout_config.spawn_log_reader(stdout).await;
 
-        // Store the handle
-        if let Ok(mut handles) = proxy_arc.hyperlog_handles.lock() {
-            handles.push(stdout_handle);
-        }
+        let log_handles = if let Some(master) = pty_master {
+            // A terminal combines stdout and stderr; input is written to the same terminal
+            match master.try_clone() {
+                Ok(writer) => *proxy_arc.stdin_pipe.lock().await = Some(Box::new(writer)),
+                Err(e) => warn!("Failed to open terminal input for {}: {}", name, e),
+            }
+            vec![log_stream_config("stdout").spawn_log_reader(master).await]
+        } else {
+            if let Some(stdin) = child.stdin.take() {
+                *proxy_arc.stdin_pipe.lock().await = Some(Box::new(stdin));
+            }
 
-        // Spawn stderr reader
-        let stderr_config = LogStreamConfig {
-            stream_name: "stderr",
-            process_key: process_key.clone(),
-            log_pattern: log_pattern.clone(),
-            log_ready_tx: log_ready_tx.clone(),
-            pattern_matched: pattern_matched.clone(),
-            timeout_occurred: timeout_occurred.clone(),
-            wait_timeout,
-            default_wait_timeout_secs: self.config.process.startup.default_wait_timeout_secs,
-            matched_line: matched_line.clone(),
-            log_file_path: Some(log_file_path),
-            log_hub: self.log_hub.clone(),
+            // Setup stdout/stderr capture
+            let stdout = child.stdout.take().expect("stdout should be captured");
+            let stderr = child.stderr.take().expect("stderr should be captured");
+            vec![
+                log_stream_config("stdout").spawn_log_reader(stdout).await,
+                log_stream_config("stderr").spawn_log_reader(stderr).await,
+            ]
         };
-        let stderr_handle = stderr_config.spawn_log_reader(stderr).await;
 
-        // Store the handle
+        // Store the handles
         if let Ok(mut handles) = proxy_arc.hyperlog_handles.lock() {
-            handles.push(stderr_handle);
+            handles.extend(log_handles);
         }
 
         // Spawn process monitor
@@ -283,6 +560,8 @@ impl ProcessManager {
         let _monitor_registry = self.registry.clone();
         let monitor_event_hub = self.event_hub.clone();
         let monitor_project = project.clone();
+        let monitor_restart_tx = self.restart_tx.clone();
+        let monitor_history = self.history.clone();
 
         let monitor_handle = tokio::spawn(async move {
             match child.wait().await {
@@ -291,18 +570,51 @@ impl ProcessManager {
                     if let Ok(mut code_guard) = monitor_proxy.exit_code.lock() {
                         *code_guard = exit_code;
                     }
+                    // Without a cgroup a SIGKILL from stopping the process would look like
+                    // an OOM kill
+                    let exit_reason =
+                        if monitor_proxy.cgroup.is_none() && monitor_proxy.is_stop_requested() {
+                            None
+                        } else {
+                            monitor_proxy.limits.exit_reason(
+                                monitor_proxy.cgroup.as_ref(),
+                                exit_code,
+                                std::os::unix::process::ExitStatusExt::signal(&status),
+                            )
+                        };
+                    // A reason given before the exit, e.g. a task timing out, takes precedence
+                    let preset_reason = monitor_proxy
+                        .exit_reason
+                        .lock()
+                        .ok()
+                        .and_then(|reason| reason.clone());
+                    let exit_reason = preset_reason.or(exit_reason);
+                    // Set status based on exit code; a timed out task failed however it exited
+                    let new_status = if monitor_proxy.is_timed_out() {
+                        ProcessStatus::Failed
+                    } else {
+                        monitor_proxy.exit_status(exit_code)
+                    };
+
+                    // A task may succeed with an exit code that normally means an error
+                    let exit_reason = exit_reason.or_else(|| match exit_code {
+                        Some(code) if new_status == ProcessStatus::Succeeded && code != 0 => {
+                            Some(format!("Exited with success exit code {code}"))
+                        }
+                        _ => None,
+                    });
+                    if let Ok(mut reason_guard) = monitor_proxy.exit_reason.lock() {
+                        *reason_guard = exit_reason.clone();
+                    }
 
                     // Set exit time
-                    if let Ok(mut exit_time) = monitor_proxy.exit_time.lock() {
-                        *exit_time = Some(chrono::Utc::now());
+                    let exit_time = chrono::Utc::now();
+                    if let Ok(mut exit_time_guard) = monitor_proxy.exit_time.lock() {
+                        *exit_time_guard = Some(exit_time);
                     }
 
-                    // Set status based on exit code
-                    match exit_code {
-                        Some(0) => monitor_proxy.set_status(ProcessStatus::Stopped),
-                        Some(_) => monitor_proxy.set_status(ProcessStatus::Failed),
-                        None => monitor_proxy.set_status(ProcessStatus::Failed), // Terminated by signal
-                    }
+                    monitor_proxy.set_status(new_status);
+                    monitor_history.record(&monitor_proxy);
 
                     // Publish Stopped event
                     if let Some(ref event_hub) = monitor_event_hub {
@@ -316,19 +628,42 @@ impl ProcessManager {
                         ));
                     }
 
-                    let exit_msg = ExitHandler::format_exit_message(&monitor_name, exit_code);
+                    let exit_msg = match (&monitor_proxy.task, &exit_reason) {
+                        (Some(_), _) => task::format_task_exit(
+                            &monitor_name,
+                            new_status,
+                            exit_code,
+                            (exit_time - monitor_proxy.start_time)
+                                .to_std()
+                                .unwrap_or_default(),
+                        ),
+                        (None, Some(reason)) => {
+                            format!("Process {} exited: {}", monitor_name, reason)
+                        }
+                        (None, None) => ExitHandler::format_exit_message(&monitor_name, exit_code),
+                    };
                     info!("{}", exit_msg);
 
-                    // Log the exit with appropriate color based on exit code
-                    let log_msg = match exit_code {
-                        Some(0) => format!("{} {}\n", "[mcproc]".green().bold(), exit_msg.green()),
+                    // Log the exit with appropriate color based on the outcome
+                    let log_msg = match new_status {
+                        ProcessStatus::Stopped | ProcessStatus::Succeeded => {
+                            format!("{} {}\n", "[mcproc]".green().bold(), exit_msg.green())
+                        }
                         _ => format!("{} {}\n", "[mcproc]".red().bold(), exit_msg.red()),
                     };
                     monitor_log_hub.publish_log_event(&monitor_key, &log_msg, true);
+
+                    // Exits caused by a stop request are never restarted
+                    if !monitor_proxy.is_stop_requested()
+                        && monitor_proxy.restart_policy.should_restart(exit_code)
+                    {
+                        let _ = monitor_restart_tx.send(monitor_proxy.clone());
+                    }
                 }
                 Err(e) => {
                     error!("Failed to wait for process {}: {}", monitor_name, e);
                     monitor_proxy.set_status(ProcessStatus::Failed);
+                    monitor_history.record(&monitor_proxy);
 
                     // Publish Failed event
                     if let Some(ref event_hub) = monitor_event_hub {
@@ -354,31 +689,69 @@ impl ProcessManager {
             handles.push(monitor_handle);
         }
 
-        // Wait for pattern match or initial startup time with timeout
-        if let Some(rx) = log_ready_rx {
-            let wait_duration = tokio::time::Duration::from_secs(
-                wait_timeout.unwrap_or(self.config.process.startup.default_wait_timeout_secs)
-                    as u64
-                    + 10, // Extra 10 seconds buffer (longer than CLI timeout)
-            );
+        if proxy_arc.watch.is_some() {
+            let _ = self.watch_tx.send(proxy_arc.clone());
+        }
 
-            match tokio::time::timeout(wait_duration, rx).await {
-                Ok(Ok(_)) => {
-                    debug!("Log pattern matched for process {}", name);
-                    // Pattern matched - but still need to verify process is running
+        // Wait for the log pattern or a readiness probe, whichever succeeds first,
+        // or for the initial startup time when neither is configured
+        let mut ready_by = None;
+        if log_ready_rx.is_some() || !readiness_probes.is_empty() {
+            let wait_timeout_secs = u64::from(
+                wait_timeout.unwrap_or(self.config.process.startup.default_wait_timeout_secs),
+            );
+            let log_wait = async {
+                let Some(rx) = log_ready_rx else {
+                    return std::future::pending().await;
+                };
+                // Extra 10 seconds buffer (longer than CLI timeout)
+                let wait_duration = Duration::from_secs(wait_timeout_secs + 10);
+                match tokio::time::timeout(wait_duration, rx).await {
+                    Ok(Ok(_)) => {
+                        debug!("Log pattern matched for process {}", name);
+                        // Pattern matched - but still need to verify process is running
+                        Some("wait_for_log".to_string())
+                    }
+                    Ok(Err(_)) => {
+                        // Channel closed without match (likely timeout in HyperLogStreamer)
+                        debug!("Pattern match channel closed for process {}", name);
+                        None
+                    }
+                    Err(_) => {
+                        // Timeout in ProcessManager (safety net)
+                        warn!(
+                            "ProcessManager timeout waiting for pattern match for process {}",
+                            name
+                        );
+                        None
+                    }
                 }
-                Ok(Err(_)) => {
-                    // Channel closed without match (likely timeout in HyperLogStreamer)
-                    debug!("Pattern match channel closed for process {}", name);
+            };
+            let probe_wait = async {
+                if readiness_probes.is_empty() {
+                    return std::future::pending().await;
                 }
-                Err(_) => {
-                    // Timeout in ProcessManager (safety net)
-                    warn!(
-                        "ProcessManager timeout waiting for pattern match for process {}",
-                        name
-                    );
+                let wait_duration = Duration::from_secs(wait_timeout_secs);
+                match tokio::time::timeout(
+                    wait_duration,
+                    readiness::wait_for_probes(&readiness_probes, &proxy_arc),
+                )
+                .await
+                {
+                    Ok(ready_by) => ready_by,
+                    Err(_) => {
+                        debug!("Readiness probes timed out for process {}", name);
+                        if let Ok(mut timeout) = timeout_occurred.lock() {
+                            *timeout = true;
+                        }
+                        None
+                    }
                 }
-            }
+            };
+            ready_by = tokio::select! {
+                ready_by = log_wait => ready_by,
+                ready_by = probe_wait => ready_by,
+            };
         } else {
             // No wait_for_log pattern, wait a bit to collect initial logs
             tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
@@ -391,6 +764,14 @@ impl ProcessManager {
         // Check final status and publish appropriate event
         let current_status = proxy_arc.get_status();
         if matches!(current_status, ProcessStatus::Running) {
+            // Dependents may start once wait_for_log or a readiness probe (if any) succeeded
+            if ready_by.is_some() || (log_pattern.is_none() && readiness_probes.is_empty()) {
+                proxy_arc.mark_ready();
+            }
+            if let Ok(mut guard) = proxy_arc.ready_by.lock() {
+                *guard = ready_by;
+            }
+
             // Publish Started event only if actually running
             self.publish_process_event(crate::daemon::process::event::ProcessEvent::Started {
                 process_id: proxy_arc.id.clone(),
@@ -414,12 +795,15 @@ impl ProcessManager {
 
         // Detect ports before returning process info
         if matches!(proxy_arc.get_status(), ProcessStatus::Running) {
-            let ports = port_detector::detect_ports(proxy_arc.pid).await;
-            if !ports.is_empty() {
-                debug!("Detected ports for process {}: {:?}", name, ports);
+            let listeners =
+                port_detector::detect_process_listeners(proxy_arc.pid, proxy_arc.cgroup.as_ref())
+                    .await;
+            if !listeners.is_empty() {
+                debug!("Detected listeners for process {}: {:?}", name, listeners);
             }
-            proxy_arc.update_detected_port(&ports);
+            proxy_arc.update_listeners(listeners);
         }
+        self.history.record(&proxy_arc);
 
         Ok((
             proxy_arc,
@@ -430,6 +814,125 @@ impl ProcessManager {
         ))
     }
 
+    /// Run a process to completion as a one-shot task
+    ///
+    /// Output lines are forwarded to `log_tx` while the task runs. A task still
+    /// running when its timeout expires is stopped and reported as failed.
+    pub async fn run_task(
+        &self,
+        mut params: StartProcessParams,
+        task: TaskOptions,
+        tail_lines: usize,
+        log_tx: Option<mpsc::UnboundedSender<proto::LogEntry>>,
+    ) -> Result<TaskResult> {
+        let timeout = task.timeout;
+        params.restart_policy = RestartPolicy::Never;
+        params.task = Some(task);
+
+        // Subscribe before starting so no output is missed
+        let mut events = self
+            .event_hub
+            .as_ref()
+            .map(|event_hub| event_hub.subscribe());
+        let deadline = tokio::time::Instant::now() + timeout;
+        let (process, ..) = self.start_process_with_log_stream(params).await?;
+        let mut output = TaskOutput {
+            process: &process,
+            tail: VecDeque::with_capacity(tail_lines),
+            tail_lines,
+            log_tx,
+        };
+
+        let mut timed_out = false;
+        let mut poll = tokio::time::interval(Duration::from_millis(100));
+        while matches!(
+            process.get_status(),
+            ProcessStatus::Starting
+                | ProcessStatus::Running
+                | ProcessStatus::Paused
+                | ProcessStatus::Stopping
+        ) {
+            tokio::select! {
+                event = next_event(&mut events) => match event {
+                    Ok(event) => output.collect(event),
+                    Err(RecvError::Lagged(count)) => {
+                        debug!("Task {} lagged by {} events", process.name, count);
+                    }
+                    Err(RecvError::Closed) => events = None,
+                },
+                _ = poll.tick() => {}
+                _ = tokio::time::sleep_until(deadline) => {
+                    timed_out = true;
+                    break;
+                }
+            }
+        }
+
+        let process_key = ProcessKey::new(process.project.clone(), process.name.clone());
+        if timed_out {
+            let message = format!(
+                "Task {} timed out after {}s, stopping it",
+                process.name,
+                timeout.as_secs()
+            );
+            warn!("{}", message);
+            let log_msg = format!("{} {}\n", "[mcproc]".red().bold(), message.red());
+            self.log_hub.publish_log_event(&process_key, &log_msg, true);
+            // Set before stopping so the run is recorded as timed out when it exits
+            process.mark_timed_out();
+            if let Ok(mut reason) = process.exit_reason.lock() {
+                *reason = Some(format!("Timed out after {}s", timeout.as_secs()));
+            }
+            if let Err(e) = process
+                .stop(false, self.config.process.restart.process_stop_timeout_ms)
+                .await
+            {
+                error!("Failed to stop timed out task {}: {}", process.name, e);
+            }
+        }
+
+        // The log readers finish once the process and its descendants close their output
+        let grace = tokio::time::Instant::now() + TASK_OUTPUT_GRACE;
+        while tokio::time::Instant::now() < grace
+            && process
+                .hyperlog_handles
+                .lock()
+                .map(|handles| !handles.iter().all(|handle| handle.is_finished()))
+                .unwrap_or(false)
+        {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        if let Some(events) = &mut events {
+            while let Ok(event) = events.try_recv() {
+                output.collect(event);
+            }
+        }
+
+        if timed_out && process.get_status() != ProcessStatus::Failed {
+            process.set_status(ProcessStatus::Failed);
+            self.history.record(&process);
+        }
+
+        let exit_code = process.exit_code.lock().ok().and_then(|code| *code);
+        let exit_time = process
+            .exit_time
+            .lock()
+            .ok()
+            .and_then(|time| *time)
+            .unwrap_or_else(chrono::Utc::now);
+        let tail = output.tail.into();
+        Ok(TaskResult {
+            succeeded: process.get_status() == ProcessStatus::Succeeded,
+            duration: (exit_time - process.start_time)
+                .to_std()
+                .unwrap_or_default(),
+            process,
+            exit_code,
+            timed_out,
+            tail,
+        })
+    }
+
     pub async fn stop_process(
         &self,
         name_or_id: &str,
@@ -463,10 +966,11 @@ impl ProcessManager {
             project: project.clone(),
         });
 
-        // Stop the process with a timeout (add 5 seconds buffer to shutdown_timeout)
-        let stop_timeout = tokio::time::Duration::from_millis(
-            self.config.process.restart.process_stop_timeout_ms + 5000,
-        );
+        // Stop the process with a timeout (add 5 seconds buffer to its grace period)
+        let stop_timeout = process
+            .stop_policy
+            .timeout(self.config.process.restart.process_stop_timeout_ms)
+            + tokio::time::Duration::from_secs(5);
         match tokio::time::timeout(
             stop_timeout,
             process.stop(force, self.config.process.restart.process_stop_timeout_ms),
@@ -514,6 +1018,7 @@ impl ProcessManager {
         } else {
             warn!("Process {} was not found in registry", process.id);
         }
+        self.persist_processes();
 
         // Log stopping event via event hub
         let log_msg = format!(
@@ -531,137 +1036,635 @@ impl ProcessManager {
         Ok(())
     }
 
-    pub async fn restart_process_with_log_stream(
+    /// Look up a process that this daemon launched and that is still alive
+    fn get_running_process(
         &self,
         name_or_id: &str,
-        project: Option<String>,
-        override_wait_for_log: Option<String>,
-        override_wait_timeout: Option<u32>,
-    ) -> Result<(Arc<ProxyInfo>, bool, bool, Vec<String>, Option<String>)> {
-        if let Some(process) = self
+        project: Option<&str>,
+    ) -> Result<Arc<ProxyInfo>> {
+        let process = self
             .registry
-            .get_process_by_name_or_id_with_project(name_or_id, project.as_deref())
+            .get_process_by_name_or_id_with_project(name_or_id, project)
+            .ok_or_else(|| McprocdError::ProcessNotFound {
+                name: name_or_id.to_string(),
+            })?;
+        let status = process.get_status();
+        if process.restored
+            || !matches!(
+                status,
+                ProcessStatus::Starting | ProcessStatus::Running | ProcessStatus::Paused
+            )
         {
-            let name = process.name.clone();
-            let project = process.project.clone();
-            let cmd = process.cmd.clone();
-            let args = process.args.clone();
-            let cwd = process.cwd.clone();
-            let env = process.env.clone();
-
-            // Use override values if provided, otherwise use saved values
-            let wait_for_log = override_wait_for_log.or(process.wait_for_log.clone());
-            let wait_timeout = override_wait_timeout.or(process.wait_timeout);
-            let toolchain = process.toolchain.clone();
-            drop(process);
+            return Err(McprocdError::ProcessNotRunning {
+                name: process.name.clone(),
+                status: format!("{:?}", status),
+            });
+        }
+        Ok(process)
+    }
 
-            // Use graceful shutdown for restart
-            self.stop_process(name_or_id, Some(&project), false).await?;
+    /// Subscribe to the raw output of a running process
+    pub fn attach_process(
+        &self,
+        name_or_id: &str,
+        project: Option<&str>,
+    ) -> Result<(Arc<ProxyInfo>, broadcast::Receiver<Bytes>)> {
+        let process = self.get_running_process(name_or_id, project)?;
+        let output = process.output.subscribe();
+        info!("Client attached to {}", process.name);
 
-            // Wait for process to be completely removed
-            self.wait_for_process_removal(&name, Some(&project)).await;
+        let process_key = ProcessKey::new(process.project.clone(), process.name.clone());
+        let log_msg = format!(
+            "{} {}\n",
+            "[mcproc]".yellow().bold(),
+            "Client attached".yellow()
+        );
+        self.log_hub
+            .publish_log_event(&process_key, &log_msg, false);
 
-            self.start_process_with_log_stream(
-                name,
-                Some(project),
-                cmd,
-                args,
-                cwd,
-                env,
-                wait_for_log,
-                wait_timeout,
-                toolchain,
-            )
-            .await
-        } else {
-            Err(McprocdError::ProcessNotFound {
-                name: name_or_id.to_string(),
-            })
-        }
+        Ok((process, output))
     }
 
-    pub fn get_process_by_name_or_id_with_project(
+    /// Write input to the stdin pipe of a running process
+    pub async fn send_input(
         &self,
         name_or_id: &str,
         project: Option<&str>,
-    ) -> Option<Arc<ProxyInfo>> {
-        self.registry
-            .get_process_by_name_or_id_with_project(name_or_id, project)
-    }
+        input: &[u8],
+    ) -> Result<Arc<ProxyInfo>> {
+        let process = self.get_running_process(name_or_id, project)?;
+        if !process.accepts_input() {
+            return Err(McprocdError::StdinUnavailable {
+                name: process.name.clone(),
+            });
+        }
 
-    pub fn get_all_processes(&self) -> Vec<Arc<ProxyInfo>> {
-        self.registry.get_all_processes()
+        process
+            .write_input(input)
+            .await
+            .map_err(McprocdError::InputError)?;
+        debug!("Sent {} bytes to stdin of {}", input.len(), process.name);
+
+        // Record that input was sent without echoing it, as it may be a secret
+        let process_key = ProcessKey::new(process.project.clone(), process.name.clone());
+        let message = format!("Sent {} bytes to stdin", input.len());
+        let log_msg = format!("{} {}\n", "[mcproc]".yellow().bold(), message.yellow());
+        self.log_hub
+            .publish_log_event(&process_key, &log_msg, false);
+
+        Ok(process)
     }
 
-    pub async fn clean_project(
+    /// Send a signal to a running process group, or to its leader only
+    ///
+    /// SIGSTOP and the other job-control stop signals mark the process as
+    /// paused, and SIGCONT marks a paused process as running again.
+    pub fn signal_process(
         &self,
-        project: &str,
-        force: bool,
-    ) -> Result<(Vec<String>, Vec<PathBuf>)> {
-        let processes = self.registry.get_processes_by_project(project);
-        let mut stopped = Vec::new();
+        name_or_id: &str,
+        project: Option<&str>,
+        signal: Signal,
+        group: bool,
+    ) -> Result<Arc<ProxyInfo>> {
+        let process = self.get_running_process(name_or_id, project)?;
+        let status = process.get_status();
 
-        for process in processes {
-            let name = process.name.clone();
-            if let Err(e) = self.stop_process(&process.id, Some(project), force).await {
-                error!(
-                    "Failed to stop process {} in project {}: {}",
-                    name, project, e
-                );
-            } else {
-                stopped.push(name);
+        process
+            .send_signal(signal, group)
+            .map_err(McprocdError::SignalError)?;
+        let target = if group {
+            "process group".to_string()
+        } else {
+            format!("process leader (PID {})", process.pid)
+        };
+        info!("Sent {} to {} of {}", signal, target, process.name);
+
+        match signal {
+            Signal::SIGSTOP | Signal::SIGTSTP | Signal::SIGTTIN | Signal::SIGTTOU => {
+                process.set_status(ProcessStatus::Paused);
             }
+            Signal::SIGCONT if status == ProcessStatus::Paused => {
+                process.set_status(ProcessStatus::Running);
+            }
+            _ => {}
         }
 
-        let exclude = self
-            .registry
-            .get_processes_by_project(project)
-            .into_iter()
-            .map(|process| {
-                let key = ProcessKey::new(process.project.clone(), process.name.clone());
-                self.log_hub.get_log_file_path_for_key(&key)
-            })
-            .collect::<HashSet<_>>();
-        let project_log_dir = self.config.paths.log_dir.join(project);
-        let deleted_logs = cleaner::delete_project_logs(&project_log_dir, &exclude);
+        let process_key = ProcessKey::new(process.project.clone(), process.name.clone());
+        let message = format!("Sent {} to {}", signal, target);
+        let log_msg = format!("{} {}\n", "[mcproc]".yellow().bold(), message.yellow());
+        self.log_hub
+            .publish_log_event(&process_key, &log_msg, false);
 
-        Ok((stopped, deleted_logs))
+        Ok(process)
     }
 
-    pub async fn clean_all_projects(
-        &self,
-        force: bool,
-    ) -> Result<HashMap<String, (Vec<String>, Vec<PathBuf>)>> {
-        let mut projects = self
+    /// Find the processes listening on a port and the managed processes they belong to
+    pub async fn lookup_port(&self, port: u16) -> Vec<PortOwner> {
+        let running = self
             .registry
-            .get_all_projects()
+            .get_all_processes()
             .into_iter()
-            .collect::<HashSet<_>>();
+            .filter(|process| {
+                !process.restored
+                    && matches!(
+                        process.get_status(),
+                        ProcessStatus::Starting | ProcessStatus::Running | ProcessStatus::Paused
+                    )
+            })
+            .collect::<Vec<_>>();
 
-        if let Ok(entries) = std::fs::read_dir(&self.config.paths.log_dir) {
-            for entry in entries.flatten() {
-                if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
-                    projects.insert(entry.file_name().to_string_lossy().into_owned());
-                }
+        port_detector::find_port_holders(port)
+            .await
+            .into_iter()
+            .map(|holder| {
+                let process = running
+                    .iter()
+                    .find(|process| Self::holds_port(process, &holder))
+                    .cloned();
+                PortOwner { holder, process }
+            })
+            .collect()
+    }
+
+    /// Whether a socket belongs to a managed process, its process group or its cgroup
+    fn holds_port(process: &ProxyInfo, holder: &PortHolder) -> bool {
+        if let Some(pid) = holder.pid {
+            let group = getpgid(Some(Pid::from_raw(pid as i32))).ok();
+            if pid == process.pid
+                || group == Some(Pid::from_raw(process.pid as i32))
+                || process
+                    .cgroup
+                    .as_ref()
+                    .is_some_and(|cgroup| cgroup.contains(pid))
+            {
+                return true;
             }
         }
+        process
+            .listeners
+            .lock()
+            .map(|listeners| listeners.contains(&holder.listener))
+            .unwrap_or(false)
+    }
 
-        let mut projects = projects.into_iter().collect::<Vec<_>>();
-        projects.sort();
-        let mut results = HashMap::new();
+    /// The managed process the daemon allocated a port to, if any
+    pub fn port_allocated_to(&self, port: u16) -> Option<Arc<ProxyInfo>> {
+        self.registry
+            .get_all_processes()
+            .into_iter()
+            .find(|process| {
+                process
+                    .allocated_ports
+                    .iter()
+                    .any(|(_, allocated)| *allocated == port)
+            })
+    }
 
-        for project in projects {
-            match self.clean_project(&project, force).await {
-                Ok(result) => {
-                    results.insert(project, result);
-                }
-                Err(e) => {
-                    error!("Failed to clean project {}: {}", project, e);
-                }
+    /// Stop the managed processes among a port's holders, returning their names
+    pub async fn stop_port_holders(&self, owners: &[PortOwner]) -> Result<Vec<String>> {
+        let mut stopped = Vec::<String>::new();
+        for process in owners.iter().filter_map(|owner| owner.process.as_ref()) {
+            let name = format!("{}/{}", process.project, process.name);
+            if stopped.contains(&name) {
+                continue;
+            }
+            self.stop_process(&process.id, Some(&process.project), false)
+                .await?;
+            info!("Stopped {} to free its port", name);
+            stopped.push(name);
+        }
+        Ok(stopped)
+    }
+
+    /// Explain who holds the port a failed process could not bind, judging by its output
+    pub async fn diagnose_port_conflict(
+        &self,
+        process: &ProxyInfo,
+        output: &str,
+    ) -> Option<String> {
+        let candidates = process
+            .port
+            .into_iter()
+            .chain(process.allocated_ports.iter().map(|(_, port)| *port))
+            .collect::<Vec<_>>();
+        let port = port_conflict::conflicting_port(output, &candidates)?;
+        let owners = self
+            .lookup_port(port)
+            .await
+            .into_iter()
+            .filter(|owner| owner.holder.pid != Some(process.pid))
+            .collect::<Vec<_>>();
+        Some(port_conflict::describe_owners(port, &owners))
+    }
+
+    pub async fn restart_process_with_log_stream(
+        &self,
+        name_or_id: &str,
+        project: Option<String>,
+        override_wait_for_log: Option<String>,
+        override_wait_timeout: Option<u32>,
+        started_by: Option<String>,
+    ) -> Result<RestartOutcome> {
+        if let Some(process) = self
+            .registry
+            .get_process_by_name_or_id_with_project(name_or_id, project.as_deref())
+        {
+            let mut params = StartProcessParams::from_process(&process);
+
+            // Use override values if provided, otherwise use saved values
+            if override_wait_for_log.is_some() {
+                params.wait_for_log = override_wait_for_log;
+            }
+            if override_wait_timeout.is_some() {
+                params.wait_timeout = override_wait_timeout;
+            }
+            params.started_by = started_by;
+            let name = process.name.clone();
+            let project = process.project.clone();
+            drop(process);
+
+            // Use graceful shutdown for restart
+            self.stop_process(name_or_id, Some(&project), false).await?;
+
+            // Wait for process to be completely removed
+            self.wait_for_process_removal(&name, Some(&project)).await;
+
+            self.start_process_with_log_stream(params).await
+        } else {
+            Err(McprocdError::ProcessNotFound {
+                name: name_or_id.to_string(),
+            })
+        }
+    }
+
+    /// Check that every dependency of a process is running and ready
+    pub fn check_dependencies(
+        &self,
+        project: &str,
+        name: &str,
+        depends_on: &[String],
+    ) -> Result<()> {
+        for dependency in depends_on {
+            if dependency == name {
+                return Err(McprocdError::InvalidRequest(format!(
+                    "Process {name} cannot depend on itself"
+                )));
+            }
+            let reason = match self
+                .registry
+                .get_process_by_name_with_project(dependency, project)
+            {
+                None => "is not running".to_string(),
+                Some(process) => match process.get_status() {
+                    ProcessStatus::Running if process.is_ready() => continue,
+                    ProcessStatus::Running | ProcessStatus::Starting => "is not ready".to_string(),
+                    status => format!("has status {:?}", status),
+                },
+            };
+            return Err(McprocdError::DependencyNotReady {
+                name: name.to_string(),
+                dependency: dependency.clone(),
+                reason,
+            });
+        }
+        Ok(())
+    }
+
+    /// Reconcile the processes of a project against a desired set of definitions
+    ///
+    /// Processes are handled in dependency order: missing processes are started
+    /// and processes whose definition changed are restarted, each only once its
+    /// dependencies are ready. A process whose dependency is not ready is
+    /// reported as blocked. When `remove_orphans` is set, project processes
+    /// that are not listed are stopped first.
+    pub async fn reconcile_project(
+        &self,
+        project: &str,
+        definitions: Vec<StartProcessParams>,
+        remove_orphans: bool,
+    ) -> Result<Vec<ReconcileResult>> {
+        let order = {
+            let nodes = definitions
+                .iter()
+                .map(|definition| (definition.name.as_str(), definition.depends_on.as_slice()))
+                .collect::<Vec<_>>();
+            dependency_order(&nodes)
+                .map_err(McprocdError::InvalidRequest)?
+                .into_iter()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+        let mut definitions = definitions
+            .into_iter()
+            .map(|definition| (definition.name.clone(), definition))
+            .collect::<HashMap<_, _>>();
+        let mut results = Vec::new();
+
+        if remove_orphans {
+            let orphans = self
+                .registry
+                .get_processes_by_project(project)
+                .into_iter()
+                .filter(|process| !definitions.contains_key(&process.name))
+                .collect();
+            for process in shutdown_order(orphans) {
+                let result = self
+                    .stop_process(&process.id, Some(project), false)
+                    .await
+                    .map(|()| None);
+                results.push(ReconcileResult {
+                    name: process.name.clone(),
+                    action: ReconcileAction::Stopped,
+                    result,
+                });
+            }
+        }
+
+        for name in order {
+            let Some(mut params) = definitions.remove(&name) else {
+                continue;
+            };
+            params.project = Some(project.to_string());
+            let active = self
+                .registry
+                .get_process_by_name_with_project(&name, project)
+                .filter(|process| {
+                    // A schedule stays active between its runs
+                    process.schedule.is_some()
+                        || matches!(
+                            process.get_status(),
+                            ProcessStatus::Starting
+                                | ProcessStatus::Running
+                                | ProcessStatus::Paused
+                        )
+                });
+
+            let (action, result) = match active {
+                Some(process)
+                    if params.same_definition(&StartProcessParams::from_process(&process)) =>
+                {
+                    (ReconcileAction::Unchanged, Ok(Some(process)))
+                }
+                _ => {
+                    if let Err(e) = self.check_dependencies(project, &name, &params.depends_on) {
+                        warn!("Not launching {}: {}", name, e);
+                        results.push(ReconcileResult {
+                            name,
+                            action: ReconcileAction::Blocked,
+                            result: Err(e),
+                        });
+                        continue;
+                    }
+                    match active {
+                        Some(process) => {
+                            info!("Definition of process {} changed, restarting", name);
+                            let result =
+                                match self.stop_process(&process.id, Some(project), false).await {
+                                    Ok(()) => {
+                                        self.wait_for_process_removal(&name, Some(project)).await;
+                                        self.start_process_with_log_stream(params)
+                                            .await
+                                            .map(|(process, ..)| Some(process))
+                                    }
+                                    Err(e) => Err(e),
+                                };
+                            (ReconcileAction::Restarted, result)
+                        }
+                        None => {
+                            let result = self
+                                .start_process_with_log_stream(params)
+                                .await
+                                .map(|(process, ..)| Some(process));
+                            (ReconcileAction::Started, result)
+                        }
+                    }
+                }
+            };
+            results.push(ReconcileResult {
+                name,
+                action,
+                result,
+            });
+        }
+
+        Ok(results)
+    }
+
+    pub fn get_process_by_name_or_id_with_project(
+        &self,
+        name_or_id: &str,
+        project: Option<&str>,
+    ) -> Option<Arc<ProxyInfo>> {
+        self.registry
+            .get_process_by_name_or_id_with_project(name_or_id, project)
+    }
+
+    pub fn get_all_processes(&self) -> Vec<Arc<ProxyInfo>> {
+        self.registry.get_all_processes()
+    }
+
+    /// The most recent runs, newest first, with runs still in the registry as they are now
+    pub fn list_runs(
+        &self,
+        project: Option<&str>,
+        name: Option<&str>,
+        limit: usize,
+    ) -> Vec<RunRecord> {
+        let live = self
+            .registry
+            .get_all_processes()
+            .into_iter()
+            .map(|process| (process.id.clone(), process))
+            .collect::<HashMap<_, _>>();
+        let mut runs = self.history.list(project, name, limit);
+        for run in &mut runs {
+            if let Some(process) = live.get(&run.id) {
+                run.update(RunRecord::from_process(process));
+            }
+        }
+        runs
+    }
+
+    /// Stop the processes of a project and delete their logs
+    ///
+    /// With a label selector, only the matching processes are stopped and
+    /// only their logs deleted.
+    pub async fn clean_project(
+        &self,
+        project: &str,
+        force: bool,
+        selector: &LabelSelector,
+    ) -> Result<(Vec<String>, Vec<PathBuf>)> {
+        // Stop dependents before the processes they depend on
+        let processes = shutdown_order(
+            self.registry
+                .get_processes_by_project(project)
+                .into_iter()
+                .filter(|process| selector.matches(&process.labels))
+                .collect(),
+        );
+        let selected_logs = processes
+            .iter()
+            .map(|process| {
+                let key = ProcessKey::new(process.project.clone(), process.name.clone());
+                self.log_hub.get_log_file_path_for_key(&key)
+            })
+            .collect::<Vec<_>>();
+        let mut stopped = Vec::new();
+
+        for process in processes {
+            let name = process.name.clone();
+            if let Err(e) = self.stop_process(&process.id, Some(project), force).await {
+                error!(
+                    "Failed to stop process {} in project {}: {}",
+                    name, project, e
+                );
+            } else {
+                stopped.push(name);
+            }
+        }
+
+        let exclude = self
+            .registry
+            .get_processes_by_project(project)
+            .into_iter()
+            .map(|process| {
+                let key = ProcessKey::new(process.project.clone(), process.name.clone());
+                self.log_hub.get_log_file_path_for_key(&key)
+            })
+            .collect::<HashSet<_>>();
+        let deleted_logs = if selector.is_empty() {
+            let project_log_dir = self.config.paths.log_dir.join(project);
+            cleaner::delete_project_logs(&project_log_dir, &exclude)
+        } else {
+            cleaner::delete_log_files(
+                selected_logs
+                    .into_iter()
+                    .filter(|path| !exclude.contains(path))
+                    .collect(),
+            )
+        };
+
+        Ok((stopped, deleted_logs))
+    }
+
+    pub async fn clean_all_projects(
+        &self,
+        force: bool,
+        selector: &LabelSelector,
+    ) -> Result<HashMap<String, (Vec<String>, Vec<PathBuf>)>> {
+        let mut projects = self
+            .registry
+            .get_all_projects()
+            .into_iter()
+            .collect::<HashSet<_>>();
+
+        if let Ok(entries) = std::fs::read_dir(&self.config.paths.log_dir) {
+            for entry in entries.flatten() {
+                if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                    projects.insert(entry.file_name().to_string_lossy().into_owned());
+                }
+            }
+        }
+
+        let mut projects = projects.into_iter().collect::<Vec<_>>();
+        projects.sort();
+        let mut results = HashMap::new();
+
+        for project in projects {
+            match self.clean_project(&project, force, selector).await {
+                Ok((stopped, deleted)) => {
+                    // Projects with nothing selected are left out of a selective clean
+                    if selector.is_empty() || !stopped.is_empty() || !deleted.is_empty() {
+                        results.insert(project, (stopped, deleted));
+                    }
+                }
+                Err(e) => {
+                    error!("Failed to clean project {}: {}", project, e);
+                }
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Stop the given processes one at a time, stopping dependents before the
+    /// processes they depend on within each project
+    ///
+    /// Results are sorted by project and name rather than in stopping order.
+    pub async fn stop_processes(
+        &self,
+        processes: Vec<Arc<ProxyInfo>>,
+        force: bool,
+    ) -> Vec<(Arc<ProxyInfo>, Result<()>)> {
+        let mut projects = BTreeMap::<String, Vec<Arc<ProxyInfo>>>::new();
+        for process in processes {
+            projects
+                .entry(process.project.clone())
+                .or_default()
+                .push(process);
+        }
+
+        let mut results = Vec::new();
+        for (project, processes) in projects {
+            for process in shutdown_order(processes) {
+                let result = self.stop_process(&process.id, Some(&project), force).await;
+                if let Err(e) = &result {
+                    error!(
+                        "Failed to stop process {} in project {}: {}",
+                        process.name, project, e
+                    );
+                }
+                results.push((process, result));
+            }
+        }
+        results.sort_by(|(a, _), (b, _)| (&a.project, &a.name).cmp(&(&b.project, &b.name)));
+        results
+    }
+
+    /// Restart the given processes, at most `parallelism` at a time
+    ///
+    /// A process is restarted once the selected processes it depends on have
+    /// been. The outcome of each restart is sent as soon as it is known.
+    pub async fn restart_processes(
+        self: &Arc<Self>,
+        processes: Vec<Arc<ProxyInfo>>,
+        parallelism: usize,
+        started_by: Option<String>,
+        results: mpsc::UnboundedSender<(Arc<ProxyInfo>, Result<RestartOutcome>)>,
+    ) {
+        let permits = Arc::new(tokio::sync::Semaphore::new(parallelism.max(1)));
+
+        for wave in restart_waves(processes) {
+            let mut tasks = tokio::task::JoinSet::new();
+            for process in wave {
+                let Ok(permit) = permits.clone().acquire_owned().await else {
+                    return;
+                };
+                let manager = self.clone();
+                let started_by = started_by.clone();
+                let results = results.clone();
+                tasks.spawn(async move {
+                    let result = manager
+                        .restart_process_with_log_stream(
+                            &process.id,
+                            Some(process.project.clone()),
+                            None,
+                            None,
+                            started_by,
+                        )
+                        .await;
+                    drop(permit);
+                    let _ = results.send((process, result));
+                });
+            }
+
+            while let Some(result) = tasks.join_next().await {
+                if let Err(error) = result {
+                    warn!("Restart task failed: {error}");
+                }
             }
         }
-
-        Ok(results)
     }
 
     /// Synchronize process status with actual process state
@@ -678,14 +1681,10 @@ impl ProcessManager {
                 let current_status = proxy.get_status();
                 if matches!(
                     current_status,
-                    ProcessStatus::Running | ProcessStatus::Starting
+                    ProcessStatus::Running | ProcessStatus::Starting | ProcessStatus::Paused
                 ) {
                     // Update status based on exit code
-                    let new_status = if code == 0 {
-                        ProcessStatus::Stopped
-                    } else {
-                        ProcessStatus::Failed
-                    };
+                    let new_status = proxy.exit_status(Some(code));
                     proxy.set_status(new_status);
                     debug!(
                         "Synchronized status for process {} from {:?} to {:?} (exit_code: {})",
@@ -715,7 +1714,10 @@ impl ProcessManager {
                             "Process {} (PID {}) died during startup, updating status to Failed",
                             name, pid
                         );
-                    } else if matches!(current_status, ProcessStatus::Running) {
+                    } else if matches!(
+                        current_status,
+                        ProcessStatus::Running | ProcessStatus::Paused
+                    ) {
                         // Process was running but is now dead
                         proxy.set_status(ProcessStatus::Failed);
                         debug!(
@@ -739,13 +1741,31 @@ impl ProcessManager {
     }
 
     /// Start a background task that periodically checks and synchronizes process states
-    pub fn start_periodic_sync(&self) {
+    ///
+    /// The same task samples the resource usage of running processes, usually
+    /// more often than it checks their states, and evaluates their watchdog
+    /// rules against every sample.
+    pub fn start_periodic_sync(self: &Arc<Self>) {
         let registry = self.registry.clone();
+        let metrics_config = self.config.process.metrics.clone();
+        let manager = Arc::downgrade(self);
 
         tokio::spawn(async move {
             let mut interval = tokio::time::interval(Duration::from_secs(10));
+            let mut metrics_interval =
+                tokio::time::interval(Duration::from_secs(metrics_config.interval_secs.max(1)));
             loop {
-                interval.tick().await;
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = metrics_interval.tick(), if metrics_config.enabled => {
+                        Self::sample_metrics(registry.get_all_processes(), metrics_config.history)
+                            .await;
+                        if let Some(manager) = manager.upgrade() {
+                            manager.check_watchdogs();
+                        }
+                        continue;
+                    }
+                }
 
                 // Get all processes that should be checked
                 let processes = registry.get_all_processes();
@@ -754,7 +1774,9 @@ impl ProcessManager {
                     .filter(|p| {
                         matches!(
                             p.get_status(),
-                            ProcessStatus::Running | ProcessStatus::Starting
+                            ProcessStatus::Running
+                                | ProcessStatus::Starting
+                                | ProcessStatus::Paused
                         )
                     })
                     .cloned()
@@ -779,62 +1801,832 @@ impl ProcessManager {
         info!("Started periodic process state synchronization (interval: 10s)");
     }
 
-    async fn refresh_detected_ports(processes: &[Arc<ProxyInfo>]) {
-        let mut tasks = tokio::task::JoinSet::new();
+    /// Start a background task that relaunches exited processes according to their restart policy
+    pub fn start_restart_supervisor(self: &Arc<Self>) {
+        let Some(mut restart_rx) = self.restart_rx.lock().ok().and_then(|mut rx| rx.take()) else {
+            warn!("Restart supervisor is already running");
+            return;
+        };
+        let manager = Arc::downgrade(self);
 
-        for process in processes
-            .iter()
-            .filter(|process| matches!(process.get_status(), ProcessStatus::Running))
-            .cloned()
-        {
-            tasks.spawn(async move {
-                let ports = port_detector::detect_ports(process.pid).await;
-                if !ports.is_empty() {
-                    debug!("Detected ports for process {}: {:?}", process.name, ports);
-                }
-                process.update_detected_port(&ports);
-            });
-        }
+        tokio::spawn(async move {
+            while let Some(process) = restart_rx.recv().await {
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
+                tokio::spawn(async move {
+                    manager.restart_after_exit(process).await;
+                });
+            }
+        });
 
-        while let Some(result) = tasks.join_next().await {
-            if let Err(error) = result {
-                warn!("Port detection task failed: {error}");
+        info!("Started automatic restart supervisor");
+    }
+
+    /// Start a background task that restarts processes when their watched files change
+    pub fn start_file_watcher(self: &Arc<Self>) {
+        let Some(mut watch_rx) = self.watch_rx.lock().ok().and_then(|mut rx| rx.take()) else {
+            warn!("File watcher is already running");
+            return;
+        };
+        let manager = Arc::downgrade(self);
+
+        tokio::spawn(async move {
+            while let Some(process) = watch_rx.recv().await {
+                tokio::spawn(Self::watch_files(manager.clone(), process));
             }
-        }
+        });
+
+        info!("Started file watcher");
     }
 
-    /// Static version of sync_process_status for use in background tasks
-    async fn sync_process_status_static(proxy: &Arc<ProxyInfo>) {
-        let name = &proxy.name;
-        debug!(
-            "sync_process_status: checking process {} (PID: {})",
-            name, proxy.pid
-        );
+    /// Restart a process whenever its watched files change, until it is stopped or replaced
+    ///
+    /// A process that exited on its own stays watched, so fixing the file that
+    /// made it crash brings it back.
+    async fn watch_files(manager: Weak<Self>, process: Arc<ProxyInfo>) {
+        let Some(watch) = &process.watch else {
+            return;
+        };
+        let process_key = ProcessKey::new(process.project.clone(), process.name.clone());
+        let cwd = process
+            .cwd
+            .clone()
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_default();
+        let is_current = |manager: &Self| {
+            !process.is_stop_requested()
+                && manager
+                    .registry
+                    .get_process_by_name_with_project(&process.name, &process.project)
+                    .is_some_and(|current| current.id == process.id)
+        };
 
-        // Check if process monitor has already detected exit
-        if let Ok(exit_code) = proxy.exit_code.lock() {
-            if let Some(code) = *exit_code {
-                // Process has exited - ensure status reflects this
-                let current_status = proxy.get_status();
-                if matches!(
-                    current_status,
-                    ProcessStatus::Running | ProcessStatus::Starting
-                ) {
-                    // Update status based on exit code
-                    let new_status = if code == 0 {
-                        ProcessStatus::Stopped
-                    } else {
-                        ProcessStatus::Failed
-                    };
-                    proxy.set_status(new_status);
-                    info!(
-                        "Periodic sync: updated process {} from {:?} to {:?} (exit_code: {})",
-                        name, current_status, new_status, code
+        let mut watcher = match FileWatcher::new(watch, &cwd) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                if let Some(manager) = manager.upgrade() {
+                    warn!("Not watching files of process {}: {}", process.name, e);
+                    let log_msg = format!(
+                        "{} {}\n",
+                        "[mcproc]".red().bold(),
+                        format!("Not watching files: {}", e).red()
                     );
+                    manager
+                        .log_hub
+                        .publish_log_event(&process_key, &log_msg, true);
                 }
                 return;
             }
-        }
+        };
+        debug!("Watching files of process {}", process.name);
+
+        let replaced = async {
+            let mut interval = tokio::time::interval(Duration::from_secs(1));
+            loop {
+                interval.tick().await;
+                if !manager
+                    .upgrade()
+                    .is_some_and(|manager| is_current(&manager))
+                {
+                    break;
+                }
+            }
+        };
+        tokio::pin!(replaced);
+
+        loop {
+            let changed = tokio::select! {
+                _ = &mut replaced => break,
+                changed = watcher.next_change() => match changed {
+                    Some(changed) => changed,
+                    None => break,
+                },
+            };
+            let Some(manager) = manager.upgrade().filter(|manager| is_current(manager)) else {
+                break;
+            };
+
+            let message = format!(
+                "Restarting process {}: {}",
+                process.name,
+                file_watch::describe_change(&changed, &cwd)
+            );
+            info!("{}", message);
+            let log_msg = format!("{} {}\n", "[mcproc]".yellow().bold(), message.yellow());
+            manager
+                .log_hub
+                .publish_log_event(&process_key, &log_msg, false);
+
+            // The new run is watched by its own task once it started
+            if let Err(e) = manager
+                .restart_process_with_log_stream(
+                    &process.id,
+                    Some(process.project.clone()),
+                    None,
+                    None,
+                    Some("file watch".to_string()),
+                )
+                .await
+            {
+                error!("Failed to restart process {}: {}", process.name, e);
+                let log_msg = format!(
+                    "{} {}\n",
+                    "[mcproc]".red().bold(),
+                    format!("Failed to restart process {}: {}", process.name, e).red()
+                );
+                manager
+                    .log_hub
+                    .publish_log_event(&process_key, &log_msg, true);
+            }
+        }
+        debug!("Stopped watching files of process {}", process.name);
+    }
+
+    /// Start a background task that launches the runs of scheduled processes
+    pub fn start_scheduler(self: &Arc<Self>) {
+        let Some(mut schedule_rx) = self.schedule_rx.lock().ok().and_then(|mut rx| rx.take())
+        else {
+            warn!("Scheduler is already running");
+            return;
+        };
+        let manager = Arc::downgrade(self);
+
+        tokio::spawn(async move {
+            while let Some(process) = schedule_rx.recv().await {
+                tokio::spawn(Self::run_schedule(manager.clone(), process));
+            }
+        });
+
+        info!("Started scheduler");
+    }
+
+    /// Launch a run of a scheduled process whenever one is due, until it is stopped or replaced
+    ///
+    /// A run that is due while the previous one is still going, or while a
+    /// dependency is not ready, is skipped rather than queued.
+    async fn run_schedule(manager: Weak<Self>, process: Arc<ProxyInfo>) {
+        let Some(schedule) = &process.schedule else {
+            return;
+        };
+        let state = &process.schedule_state;
+        let process_key = ProcessKey::new(process.project.clone(), process.name.clone());
+        // Every run of the schedule shares its state; a new schedule brings a new one
+        let current = |manager: &Self| {
+            manager
+                .registry
+                .get_process_by_name_with_project(&process.name, &process.project)
+                .filter(|current| {
+                    !current.is_stop_requested() && Arc::ptr_eq(&current.schedule_state, state)
+                })
+        };
+        let log = |manager: &Self, message: String| {
+            let log_msg = format!("{} {}\n", "[mcproc]".yellow().bold(), message.yellow());
+            manager
+                .log_hub
+                .publish_log_event(&process_key, &log_msg, false);
+        };
+        debug!("Scheduling runs of process {} {}", process.name, schedule);
+
+        let mut previous = None;
+        loop {
+            let now = chrono::Utc::now();
+            let planned = state.lock().ok().and_then(|mut state| {
+                // The first run was planned when the process was registered
+                let next_run = match (previous, state.next_run) {
+                    (None, Some(next_run)) => Some(next_run),
+                    _ => schedule.next_run(previous, now),
+                };
+                state.next_run = next_run;
+                next_run
+            });
+            let Some(planned) = planned else {
+                warn!("Schedule of process {} never runs again", process.name);
+                return;
+            };
+
+            // Wake up at least once a minute to notice a stopped schedule
+            loop {
+                let remaining = (planned - chrono::Utc::now()).to_std().unwrap_or_default();
+                if remaining.is_zero() {
+                    break;
+                }
+                tokio::time::sleep(remaining.min(Duration::from_secs(60))).await;
+                if !manager
+                    .upgrade()
+                    .is_some_and(|manager| current(&manager).is_some())
+                {
+                    debug!("Stopped scheduling runs of process {}", process.name);
+                    return;
+                }
+            }
+            previous = Some(planned);
+
+            let Some(manager) = manager.upgrade() else {
+                return;
+            };
+            let Some(entry) = current(&manager) else {
+                debug!("Stopped scheduling runs of process {}", process.name);
+                return;
+            };
+
+            let skipped = if matches!(
+                entry.get_status(),
+                ProcessStatus::Starting
+                    | ProcessStatus::Running
+                    | ProcessStatus::Paused
+                    | ProcessStatus::Stopping
+            ) {
+                Some("the previous run is still running".to_string())
+            } else {
+                manager
+                    .check_dependencies(&entry.project, &entry.name, &entry.depends_on)
+                    .err()
+                    .map(|e| e.to_string())
+            };
+            if let Some(reason) = skipped {
+                let message = format!("Skipping scheduled run of {}: {}", process.name, reason);
+                info!("{}", message);
+                log(&manager, message);
+                if let Ok(mut state) = state.lock() {
+                    state.skipped += 1;
+                }
+                continue;
+            }
+
+            let message = format!("Starting scheduled run of {}", process.name);
+            info!("{}", message);
+            log(&manager, message);
+            let mut params = StartProcessParams::from_process(&entry);
+            params.schedule_state = state.clone();
+            params.started_by = Some("schedule".to_string());
+            let state = state.clone();
+            let process_key = process_key.clone();
+            tokio::spawn(async move {
+                let start_time = chrono::Utc::now();
+                let run = match manager
+                    .run_task(params, TaskOptions::default(), 0, None)
+                    .await
+                {
+                    Ok(result) => ScheduledRun {
+                        start_time: result.process.start_time,
+                        exit_code: result.exit_code,
+                        succeeded: result.succeeded,
+                        duration: result.duration,
+                    },
+                    Err(e) => {
+                        let message =
+                            format!("Scheduled run of {} failed to start: {}", entry.name, e);
+                        error!("{}", message);
+                        let log_msg = format!("{} {}\n", "[mcproc]".red().bold(), message.red());
+                        manager
+                            .log_hub
+                            .publish_log_event(&process_key, &log_msg, true);
+                        ScheduledRun {
+                            start_time,
+                            exit_code: None,
+                            succeeded: false,
+                            duration: Duration::ZERO,
+                        }
+                    }
+                };
+                if let Ok(mut state) = state.lock() {
+                    state.record(run);
+                }
+            });
+        }
+    }
+
+    /// Relaunch an exited process after its backoff delay, or mark it as crash looping
+    async fn restart_after_exit(&self, process: Arc<ProxyInfo>) {
+        let restart_config = &self.config.process.restart;
+        let process_key = ProcessKey::new(process.project.clone(), process.name.clone());
+
+        // A run that stayed up long enough is not part of a crash loop
+        let exit_time = process
+            .exit_time
+            .lock()
+            .ok()
+            .and_then(|exit_time| *exit_time)
+            .unwrap_or_else(chrono::Utc::now);
+        let stable_after = chrono::Duration::seconds(restart_config.stable_after_secs as i64);
+        let consecutive = if exit_time - process.start_time >= stable_after {
+            0
+        } else {
+            process.restart_state.consecutive
+        };
+
+        if consecutive >= restart_config.max_attempts {
+            process.set_status(ProcessStatus::CrashLoop);
+            let message = format!(
+                "Process {} is crash looping after {} consecutive restarts, giving up",
+                process.name, consecutive
+            );
+            warn!("{}", message);
+            let log_msg = format!("{} {}\n", "[mcproc]".red().bold(), message.red());
+            self.log_hub.publish_log_event(&process_key, &log_msg, true);
+            self.publish_process_event(crate::daemon::process::event::ProcessEvent::Failed {
+                process_id: process.id.clone(),
+                name: process.name.clone(),
+                project: process.project.clone(),
+                error: message,
+            });
+            return;
+        }
+
+        let delay = restart::backoff_delay(restart_config, consecutive);
+        let message = format!(
+            "Restarting process {} in {:.1}s (attempt {}/{})",
+            process.name,
+            delay.as_secs_f64(),
+            consecutive + 1,
+            restart_config.max_attempts
+        );
+        info!("{}", message);
+        let log_msg = format!("{} {}\n", "[mcproc]".yellow().bold(), message.yellow());
+        self.log_hub
+            .publish_log_event(&process_key, &log_msg, false);
+
+        tokio::time::sleep(delay).await;
+
+        // A manual start, stop or restart during the backoff takes precedence
+        let still_current = self
+            .registry
+            .get_process_by_name_with_project(&process.name, &process.project)
+            .is_some_and(|current| current.id == process.id);
+        if !still_current || process.is_stop_requested() {
+            debug!(
+                "Skipping automatic restart of {}: process was replaced or stopped",
+                process.name
+            );
+            return;
+        }
+
+        let mut params = StartProcessParams::from_process(&process);
+        params.restart_state = RestartState {
+            count: process.restart_state.count + 1,
+            consecutive: consecutive + 1,
+            last_restart_time: Some(chrono::Utc::now()),
+        };
+        params.started_by = Some("restart policy".to_string());
+        if let Err(e) = self.start_process_with_log_stream(params).await {
+            error!("Failed to restart process {}: {}", process.name, e);
+            let log_msg = format!(
+                "{} {}\n",
+                "[mcproc]".red().bold(),
+                format!("Failed to restart process {}: {}", process.name, e).red()
+            );
+            self.log_hub.publish_log_event(&process_key, &log_msg, true);
+        }
+    }
+
+    /// Start a background task that runs the liveness checks of running processes
+    pub fn start_health_monitor(self: &Arc<Self>) {
+        let manager = Arc::downgrade(self);
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(HEALTH_MONITOR_TICK);
+            loop {
+                interval.tick().await;
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
+
+                let now = tokio::time::Instant::now();
+                for process in manager.registry.get_all_processes() {
+                    let Some(check) = &process.health_check else {
+                        continue;
+                    };
+                    if !matches!(process.get_status(), ProcessStatus::Running) {
+                        continue;
+                    }
+                    let due = process
+                        .health
+                        .lock()
+                        .is_ok_and(|mut health| health.begin_check(check.interval, now));
+                    if due {
+                        let manager = manager.clone();
+                        tokio::spawn(async move {
+                            manager.run_health_check(process).await;
+                        });
+                    }
+                }
+            }
+        });
+
+        info!("Started health monitor");
+    }
+
+    /// Run one liveness check, publish health changes and restart after repeated failures
+    async fn run_health_check(&self, process: Arc<ProxyInfo>) {
+        let Some(check) = &process.health_check else {
+            return;
+        };
+        let result = check.run(&process).await;
+        let (changed, failures, error) = {
+            let Ok(mut health) = process.health.lock() else {
+                return;
+            };
+            let changed = health.finish_check(result, check.interval, tokio::time::Instant::now());
+            (
+                changed,
+                health.consecutive_failures,
+                health.last_error.clone(),
+            )
+        };
+
+        // A stop requested while the check was in flight makes its result moot
+        if process.is_stop_requested() {
+            return;
+        }
+
+        let process_key = ProcessKey::new(process.project.clone(), process.name.clone());
+        if let Some(health) = changed {
+            let log_msg = match (health, &error) {
+                (HealthStatus::Unhealthy, Some(error)) => {
+                    let message = format!("Process {} is unhealthy: {}", process.name, error);
+                    warn!("{}", message);
+                    format!("{} {}\n", "[mcproc]".yellow().bold(), message.yellow())
+                }
+                _ => {
+                    let message = format!("Process {} is healthy", process.name);
+                    info!("{}", message);
+                    format!("{} {}\n", "[mcproc]".green().bold(), message.green())
+                }
+            };
+            self.log_hub.publish_log_event(
+                &process_key,
+                &log_msg,
+                health == HealthStatus::Unhealthy,
+            );
+            self.publish_process_event(
+                crate::daemon::process::event::ProcessEvent::HealthChanged {
+                    process_id: process.id.clone(),
+                    name: process.name.clone(),
+                    project: process.project.clone(),
+                    health,
+                    error: error.clone(),
+                },
+            );
+        }
+
+        if check
+            .restart_after_failures
+            .is_some_and(|threshold| failures >= threshold)
+        {
+            let message = format!(
+                "Restarting process {} after {} consecutive failed health checks",
+                process.name, failures
+            );
+            self.restart_in_place(process, message, "health check")
+                .await;
+        }
+    }
+
+    /// Replace a running process with a fresh run of the same definition, logging why
+    async fn restart_in_place(&self, process: Arc<ProxyInfo>, message: String, started_by: &str) {
+        let still_current = self
+            .registry
+            .get_process_by_name_with_project(&process.name, &process.project)
+            .is_some_and(|current| current.id == process.id);
+        if !still_current {
+            return;
+        }
+
+        let process_key = ProcessKey::new(process.project.clone(), process.name.clone());
+        warn!("{}", message);
+        let log_msg = format!("{} {}\n", "[mcproc]".yellow().bold(), message.yellow());
+        self.log_hub
+            .publish_log_event(&process_key, &log_msg, false);
+
+        let mut params = StartProcessParams::from_process(&process);
+        params.restart_state = RestartState {
+            count: process.restart_state.count + 1,
+            consecutive: process.restart_state.consecutive,
+            last_restart_time: Some(chrono::Utc::now()),
+        };
+        params.started_by = Some(started_by.to_string());
+        let result = async {
+            self.stop_process(&process.id, Some(&process.project), false)
+                .await?;
+            self.wait_for_process_removal(&process.name, Some(&process.project))
+                .await;
+            self.start_process_with_log_stream(params).await
+        }
+        .await;
+        if let Err(e) = result {
+            error!("Failed to restart process {}: {}", process.name, e);
+            let log_msg = format!(
+                "{} {}\n",
+                "[mcproc]".red().bold(),
+                format!("Failed to restart process {}: {}", process.name, e).red()
+            );
+            self.log_hub.publish_log_event(&process_key, &log_msg, true);
+        }
+    }
+
+    /// Evaluate the watchdog rules of running processes against their latest sample
+    fn check_watchdogs(self: &Arc<Self>) {
+        let now = std::time::Instant::now();
+        for process in self.registry.get_all_processes() {
+            if process.watchdog.is_empty()
+                || process.is_stop_requested()
+                || !matches!(process.get_status(), ProcessStatus::Running)
+            {
+                continue;
+            }
+            let Some(sample) = process
+                .metrics
+                .lock()
+                .ok()
+                .and_then(|metrics| metrics.latest().copied())
+            else {
+                continue;
+            };
+            let last_output = process.last_output.lock().map_or(now, |at| *at);
+            let transitions = match process.watchdog_state.lock() {
+                Ok(mut state) => state.evaluate(&process.watchdog, &sample, last_output, now),
+                Err(_) => continue,
+            };
+            for transition in transitions {
+                let manager = self.clone();
+                let process = process.clone();
+                tokio::spawn(async move {
+                    manager.apply_watchdog(process, transition).await;
+                });
+            }
+        }
+    }
+
+    /// Take the action of a watchdog rule that fired, or log that a flag cleared
+    async fn apply_watchdog(&self, process: Arc<ProxyInfo>, transition: WatchdogTransition) {
+        let process_key = ProcessKey::new(process.project.clone(), process.name.clone());
+        let (rule, sample) = match transition {
+            WatchdogTransition::Fired { rule, sample } => (rule, sample),
+            WatchdogTransition::Cleared { rule } => {
+                if let WatchdogAction::Flag(flag) = &rule.action {
+                    let message = format!(
+                        "Process {} is no longer {}: watchdog rule `{}` stopped holding",
+                        process.name, flag, rule
+                    );
+                    info!("{}", message);
+                    let log_msg = format!("{} {}\n", "[mcproc]".green().bold(), message.green());
+                    self.log_hub
+                        .publish_log_event(&process_key, &log_msg, false);
+                }
+                return;
+            }
+        };
+
+        let reason = format!(
+            "watchdog rule `{}` fired ({})",
+            rule,
+            watchdog::describe_usage(&sample)
+        );
+        self.publish_process_event(crate::daemon::process::event::ProcessEvent::Watchdog {
+            process_id: process.id.clone(),
+            name: process.name.clone(),
+            project: process.project.clone(),
+            reason: reason.clone(),
+        });
+
+        match &rule.action {
+            WatchdogAction::Restart => {
+                let message = format!("Restarting process {}: {}", process.name, reason);
+                self.restart_in_place(process, message, "watchdog").await;
+            }
+            WatchdogAction::Stop => {
+                let message = format!("Stopping process {}: {}", process.name, reason);
+                warn!("{}", message);
+                let log_msg = format!("{} {}\n", "[mcproc]".yellow().bold(), message.yellow());
+                self.log_hub
+                    .publish_log_event(&process_key, &log_msg, false);
+                if let Err(e) = self
+                    .stop_process(&process.id, Some(&process.project), false)
+                    .await
+                {
+                    error!("Failed to stop process {}: {}", process.name, e);
+                }
+            }
+            WatchdogAction::Flag(flag) => {
+                let message = format!("Process {} flagged as {}: {}", process.name, flag, reason);
+                warn!("{}", message);
+                let log_msg = format!("{} {}\n", "[mcproc]".yellow().bold(), message.yellow());
+                self.log_hub
+                    .publish_log_event(&process_key, &log_msg, false);
+            }
+        }
+    }
+
+    /// Write the current registry to the data directory
+    fn persist_processes(&self) {
+        self.store.save(&self.registry.get_all_processes());
+    }
+
+    /// Write the registry one last time, before the daemon stops its processes
+    pub fn persist_for_shutdown(&self) {
+        self.store.save_final(&self.registry.get_all_processes());
+    }
+
+    /// Start a background task that saves the registry whenever a process changes state
+    pub fn start_state_persistence(self: &Arc<Self>) {
+        let Some(mut events) = self.event_hub.as_ref().map(|hub| hub.subscribe()) else {
+            return;
+        };
+        let manager = Arc::downgrade(self);
+
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    // Missed events may have been process events, so save anyway
+                    Ok(StreamEvent::Process(_)) | Err(RecvError::Lagged(_)) => {}
+                    Ok(_) => continue,
+                    Err(RecvError::Closed) => break,
+                }
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
+                manager.persist_processes();
+            }
+        });
+
+        info!("Started process state persistence");
+    }
+
+    /// Load the processes persisted by the previous daemon into the registry
+    ///
+    /// Processes that were still running are restored as stopped because the
+    /// daemon restarted, with their logs still available. They are returned so
+    /// the caller can relaunch them.
+    pub fn restore_processes(&self) -> Vec<Arc<ProxyInfo>> {
+        let mut interrupted = Vec::new();
+        let mut restored = 0;
+
+        for record in self.store.load() {
+            let process_key = ProcessKey::new(record.project.clone(), record.name.clone());
+            if !self.registry.try_reserve_name(process_key.clone()) {
+                warn!(
+                    "Skipping duplicate persisted process {}/{}",
+                    record.project, record.name
+                );
+                continue;
+            }
+            let was_active = record.was_active();
+            let process = Arc::new(record.into_process());
+            self.ports.claim(&process_key, &process.allocated_ports);
+            self.registry.add_process(process.clone());
+            restored += 1;
+
+            if was_active {
+                self.history.record(&process);
+                let log_msg = format!(
+                    "{} {}\n",
+                    "[mcproc]".yellow().bold(),
+                    format!("Process {} stopped: daemon restarted", process.name).yellow()
+                );
+                self.log_hub
+                    .publish_log_event(&process_key, &log_msg, false);
+            }
+            // A schedule resumes instead of relaunching its interrupted run
+            if process.schedule.is_some() {
+                let _ = self.schedule_tx.send(process);
+            } else if was_active {
+                interrupted.push(process);
+            }
+        }
+
+        if restored > 0 {
+            info!(
+                "Restored {} process(es) from the previous daemon, {} of them were running",
+                restored,
+                interrupted.len()
+            );
+        }
+        self.persist_processes();
+        interrupted
+    }
+
+    /// Relaunch restored processes, project by project in dependency order
+    pub async fn relaunch_processes(&self, processes: Vec<Arc<ProxyInfo>>) {
+        let mut projects = BTreeMap::<String, Vec<StartProcessParams>>::new();
+        for process in processes {
+            let mut params = StartProcessParams::from_process(&process);
+            params.restart_state = RestartState {
+                count: process.restart_state.count,
+                consecutive: 0,
+                last_restart_time: process.restart_state.last_restart_time,
+            };
+            params.started_by = Some("daemon restart".to_string());
+            projects
+                .entry(process.project.clone())
+                .or_default()
+                .push(params);
+        }
+
+        for (project, definitions) in projects {
+            let results = match self.reconcile_project(&project, definitions, false).await {
+                Ok(results) => results,
+                Err(e) => {
+                    error!("Failed to relaunch processes of project {}: {}", project, e);
+                    continue;
+                }
+            };
+            for result in results {
+                match result.result {
+                    Ok(_) => info!("Relaunched process {}/{}", project, result.name),
+                    Err(e) => warn!(
+                        "Failed to relaunch process {}/{}: {}",
+                        project, result.name, e
+                    ),
+                }
+            }
+        }
+    }
+
+    /// Record a resource usage sample for every running process
+    async fn sample_metrics(processes: Vec<Arc<ProxyInfo>>, history: usize) {
+        let sampled = tokio::task::spawn_blocking(move || {
+            for process in processes.iter().filter(|process| {
+                !process.restored
+                    && matches!(
+                        process.get_status(),
+                        ProcessStatus::Running | ProcessStatus::Paused
+                    )
+            }) {
+                let Some(usage) = crate::daemon::process::metrics::sample_tree(
+                    process.pid,
+                    process.cgroup.as_ref(),
+                ) else {
+                    continue;
+                };
+                if let Ok(mut metrics) = process.metrics.lock() {
+                    metrics.record(usage, std::time::Instant::now(), history);
+                }
+            }
+        })
+        .await;
+        if let Err(error) = sampled {
+            warn!("Metrics sampling task failed: {error}");
+        }
+    }
+
+    async fn refresh_detected_ports(processes: &[Arc<ProxyInfo>]) {
+        let mut tasks = tokio::task::JoinSet::new();
+
+        for process in processes
+            .iter()
+            .filter(|process| matches!(process.get_status(), ProcessStatus::Running))
+            .cloned()
+        {
+            tasks.spawn(async move {
+                let listeners =
+                    port_detector::detect_process_listeners(process.pid, process.cgroup.as_ref())
+                        .await;
+                if !listeners.is_empty() {
+                    debug!(
+                        "Detected listeners for process {}: {:?}",
+                        process.name, listeners
+                    );
+                }
+                process.update_listeners(listeners);
+            });
+        }
+
+        while let Some(result) = tasks.join_next().await {
+            if let Err(error) = result {
+                warn!("Port detection task failed: {error}");
+            }
+        }
+    }
+
+    /// Static version of sync_process_status for use in background tasks
+    async fn sync_process_status_static(proxy: &Arc<ProxyInfo>) {
+        let name = &proxy.name;
+        debug!(
+            "sync_process_status: checking process {} (PID: {})",
+            name, proxy.pid
+        );
+
+        // Check if process monitor has already detected exit
+        if let Ok(exit_code) = proxy.exit_code.lock() {
+            if let Some(code) = *exit_code {
+                // Process has exited - ensure status reflects this
+                let current_status = proxy.get_status();
+                if matches!(
+                    current_status,
+                    ProcessStatus::Running | ProcessStatus::Starting | ProcessStatus::Paused
+                ) {
+                    // Update status based on exit code
+                    let new_status = proxy.exit_status(Some(code));
+                    proxy.set_status(new_status);
+                    info!(
+                        "Periodic sync: updated process {} from {:?} to {:?} (exit_code: {})",
+                        name, current_status, new_status, code
+                    );
+                }
+                return;
+            }
+        }
 
         // Double-check process is actually running using kill -0
         let pid = proxy.pid;
@@ -855,7 +2647,10 @@ impl ProcessManager {
                             "Periodic sync: process {} (PID {}) died during startup, marked as Failed",
                             name, pid
                         );
-                    } else if matches!(current_status, ProcessStatus::Running) {
+                    } else if matches!(
+                        current_status,
+                        ProcessStatus::Running | ProcessStatus::Paused
+                    ) {
                         // Process was running but is now dead
                         proxy.set_status(ProcessStatus::Failed);
                         info!(
@@ -880,213 +2675,1508 @@ impl ProcessManager {
             }
         }
     }
-}
+}
+
+/// Order processes so that dependents come before the processes they depend on
+fn shutdown_order(processes: Vec<Arc<ProxyInfo>>) -> Vec<Arc<ProxyInfo>> {
+    let nodes = processes
+        .iter()
+        .map(|process| (process.name.as_str(), process.depends_on.as_slice()))
+        .collect::<Vec<_>>();
+    let Ok(order) = dependency_order(&nodes) else {
+        return processes;
+    };
+    let position = order
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name.to_string(), i))
+        .collect::<HashMap<_, _>>();
+    let mut processes = processes;
+    processes.sort_by_key(|process| std::cmp::Reverse(position.get(&process.name).copied()));
+    processes
+}
+
+/// Group processes into waves restarted one after the other, each process
+/// coming after the processes it depends on
+///
+/// Dependencies on processes outside the group are ignored. Processes caught
+/// in a dependency cycle are restarted together in the last wave.
+fn restart_waves(processes: Vec<Arc<ProxyInfo>>) -> Vec<Vec<Arc<ProxyInfo>>> {
+    let mut pending = processes;
+    let mut waves = Vec::new();
+
+    while !pending.is_empty() {
+        let waiting = pending
+            .iter()
+            .map(|process| (process.project.clone(), process.name.clone()))
+            .collect::<HashSet<_>>();
+        let (ready, blocked): (Vec<_>, Vec<_>) = pending.into_iter().partition(|process| {
+            !process.depends_on.iter().any(|dependency| {
+                dependency != &process.name
+                    && waiting.contains(&(process.project.clone(), dependency.clone()))
+            })
+        });
+        if ready.is_empty() {
+            waves.push(blocked);
+            break;
+        }
+        waves.push(ready);
+        pending = blocked;
+    }
+    waves
+}
+
+/// Output of a running task: forwarded as it arrives, with the last lines kept
+struct TaskOutput<'a> {
+    process: &'a ProxyInfo,
+    tail: VecDeque<String>,
+    tail_lines: usize,
+    log_tx: Option<mpsc::UnboundedSender<proto::LogEntry>>,
+}
+
+impl TaskOutput<'_> {
+    fn collect(&mut self, event: StreamEvent) {
+        let StreamEvent::Log {
+            process_name,
+            project,
+            mut entry,
+        } = event
+        else {
+            return;
+        };
+        if process_name != self.process.name || project != self.process.project {
+            return;
+        }
+
+        // Daemon messages are streamed but are not part of the task's output
+        let line = String::from_utf8_lossy(&strip_ansi_escapes::strip(&entry.content)).to_string();
+        if !line.starts_with("[mcproc]") && self.tail_lines > 0 {
+            if self.tail.len() == self.tail_lines {
+                self.tail.pop_front();
+            }
+            self.tail.push_back(line);
+        }
+
+        if let Some(log_tx) = &self.log_tx {
+            entry.process_name = Some(process_name);
+            if log_tx.send(entry).is_err() {
+                // The client went away; keep running the task without streaming
+                self.log_tx = None;
+            }
+        }
+    }
+}
+
+/// Next event from an optional subscription; never resolves without one
+async fn next_event(
+    events: &mut Option<broadcast::Receiver<StreamEvent>>,
+) -> std::result::Result<StreamEvent, RecvError> {
+    match events {
+        Some(events) => events.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::daemon::process::health::{HealthCheck, HealthCheckKind};
+    use crate::daemon::process::limits::ResourceLimits;
+    use crate::daemon::process::pty::PtySize;
+    use crate::daemon::process::readiness::ReadinessProbe;
+    use crate::daemon::process::restart::RestartPolicy;
+    use crate::daemon::process::stop::StopPolicy;
+    use crate::test_support::ProcessTestFixture;
+
+    fn test_manager() -> ProcessTestFixture {
+        ProcessTestFixture::new("mcproc-manager", 500)
+    }
+
+    async fn start_sleep(
+        manager: &ProcessManager,
+        name: &str,
+        project: &str,
+    ) -> Result<Arc<ProxyInfo>> {
+        manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: name.to_string(),
+                project: Some(project.to_string()),
+                args: vec!["sleep".to_string(), "5".to_string()],
+                ..Default::default()
+            })
+            .await
+            .map(|result| result.0)
+    }
+
+    #[tokio::test]
+    async fn permits_same_process_name_in_different_projects() {
+        let fixture = test_manager();
+        let manager = &fixture.process_manager;
+        let root = fixture.root.clone();
+        let first = start_sleep(manager, "shared-name", "a").await.unwrap();
+        let second = start_sleep(manager, "shared-name", "b").await.unwrap();
+        assert_eq!(manager.registry.get_all_processes().len(), 2);
+        manager
+            .stop_process(&first.id, Some("a"), true)
+            .await
+            .unwrap();
+        manager
+            .stop_process(&second.id, Some("b"), true)
+            .await
+            .unwrap();
+        tokio::fs::remove_dir_all(root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn concurrent_starts_reserve_name_atomically() {
+        for _ in 0..10 {
+            let fixture = test_manager();
+            let manager = &fixture.process_manager;
+            let root = fixture.root.clone();
+            let (first, second) = tokio::join!(
+                start_sleep(manager, "same-name", "same-project"),
+                start_sleep(manager, "same-name", "same-project")
+            );
+            assert_eq!(usize::from(first.is_ok()) + usize::from(second.is_ok()), 1);
+            let error = match (first, second) {
+                (Err(error), _) | (_, Err(error)) => error,
+                _ => unreachable!(),
+            };
+            assert!(
+                matches!(error, McprocdError::ProcessAlreadyExists(_)),
+                "{error:?}"
+            );
+            let processes = manager.registry.get_processes_by_project("same-project");
+            assert_eq!(processes.len(), 1);
+            manager
+                .stop_process(&processes[0].id, Some("same-project"), true)
+                .await
+                .unwrap();
+            tokio::fs::remove_dir_all(root).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn wait_for_log_returns_matching_line_and_running_process() {
+        let fixture = test_manager();
+        let manager = &fixture.process_manager;
+        let root = fixture.root.clone();
+        let result = manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "wait-match".to_string(),
+                project: Some("wait-for-log".to_string()),
+                cmd: Some("echo BOOT; echo READY line; sleep 30".to_string()),
+                wait_for_log: Some("READY".to_string()),
+                ..Default::default()
+            })
+            .await;
+        let (process, timeout_occurred, pattern_matched, _, matched_line) = match result {
+            Ok(result) => result,
+            Err(error) => {
+                tokio::fs::remove_dir_all(root).await.unwrap();
+                panic!("failed to start process: {error:?}");
+            }
+        };
+        let status = process.get_status();
+
+        let stop_result = manager
+            .stop_process(&process.id, Some("wait-for-log"), true)
+            .await;
+        let remove_result = tokio::fs::remove_dir_all(root).await;
+
+        stop_result.unwrap();
+        remove_result.unwrap();
+        assert!(pattern_matched);
+        assert!(!timeout_occurred);
+        assert!(matched_line.is_some_and(|line| line.contains("READY")));
+        assert_eq!(status, ProcessStatus::Running);
+    }
+
+    #[tokio::test]
+    async fn wait_for_log_timeout_returns_running_process() {
+        let fixture = test_manager();
+        let manager = &fixture.process_manager;
+        let root = fixture.root.clone();
+        let result = manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "wait-timeout".to_string(),
+                project: Some("wait-for-log".to_string()),
+                args: vec!["sleep".to_string(), "30".to_string()],
+                wait_for_log: Some("NEVER_APPEARS".to_string()),
+                wait_timeout: Some(1),
+                ..Default::default()
+            })
+            .await;
+        let (process, timeout_occurred, pattern_matched, _, matched_line) = match result {
+            Ok(result) => result,
+            Err(error) => {
+                tokio::fs::remove_dir_all(root).await.unwrap();
+                panic!("failed to start process: {error:?}");
+            }
+        };
+        let status = process.get_status();
+
+        let stop_result = manager
+            .stop_process(&process.id, Some("wait-for-log"), true)
+            .await;
+        let remove_result = tokio::fs::remove_dir_all(root).await;
+
+        stop_result.unwrap();
+        remove_result.unwrap();
+        assert!(timeout_occurred);
+        assert!(!pattern_matched);
+        assert!(matched_line.is_none());
+        assert_eq!(status, ProcessStatus::Running);
+    }
+
+    #[tokio::test]
+    async fn wait_for_log_invalid_regex_releases_process_name() {
+        let fixture = test_manager();
+        let manager = &fixture.process_manager;
+        let root = fixture.root.clone();
+        let invalid_result = manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "invalid-wait-pattern".to_string(),
+                project: Some("wait-for-log".to_string()),
+                args: vec!["sleep".to_string(), "30".to_string()],
+                wait_for_log: Some("[unclosed".to_string()),
+                ..Default::default()
+            })
+            .await;
+        let invalid_error = match invalid_result {
+            Err(error) => error,
+            Ok((process, _, _, _, _)) => {
+                manager
+                    .stop_process(&process.id, Some("wait-for-log"), true)
+                    .await
+                    .unwrap();
+                tokio::fs::remove_dir_all(root).await.unwrap();
+                panic!("invalid regex unexpectedly started a process");
+            }
+        };
+        let registry_was_empty = manager.registry.get_all_processes().is_empty();
+
+        let restart_result = start_sleep(manager, "invalid-wait-pattern", "wait-for-log").await;
+        let restarted_process = match restart_result {
+            Ok(process) => process,
+            Err(error) => {
+                tokio::fs::remove_dir_all(root).await.unwrap();
+                panic!("process name reservation was not released: {error:?}");
+            }
+        };
+        let stop_result = manager
+            .stop_process(&restarted_process.id, Some("wait-for-log"), true)
+            .await;
+        let remove_result = tokio::fs::remove_dir_all(root).await;
+
+        stop_result.unwrap();
+        remove_result.unwrap();
+        assert!(matches!(invalid_error, McprocdError::InvalidRegex { .. }));
+        assert!(registry_was_empty);
+    }
+
+    #[tokio::test]
+    async fn on_failure_policy_restarts_until_crash_loop() {
+        let fixture = ProcessTestFixture::with_config("mcproc-manager", |config| {
+            config.process.restart.process_stop_timeout_ms = 500;
+            config.process.restart.max_attempts = 2;
+            config.process.restart.backoff_initial_ms = 50;
+        });
+        let manager = &fixture.process_manager;
+        manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "crasher".to_string(),
+                project: Some("restart".to_string()),
+                cmd: Some("echo crashing; exit 3".to_string()),
+                restart_policy: RestartPolicy::OnFailure,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let process = tokio::time::timeout(Duration::from_secs(15), async {
+            loop {
+                if let Some(process) = manager
+                    .registry
+                    .get_process_by_name_with_project("crasher", "restart")
+                    .filter(|process| process.get_status() == ProcessStatus::CrashLoop)
+                {
+                    return process;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("process never reached CrashLoop");
+
+        assert_eq!(process.restart_state.count, 2);
+        assert!(process.restart_state.last_restart_time.is_some());
+        assert_eq!(*process.exit_code.lock().unwrap(), Some(3));
+    }
+
+    #[tokio::test]
+    async fn stopping_process_does_not_trigger_restart_policy() {
+        let fixture = ProcessTestFixture::with_config("mcproc-manager", |config| {
+            config.process.restart.process_stop_timeout_ms = 500;
+            config.process.restart.backoff_initial_ms = 10;
+        });
+        let manager = &fixture.process_manager;
+        let (process, ..) = manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "always".to_string(),
+                project: Some("restart".to_string()),
+                args: vec!["sleep".to_string(), "30".to_string()],
+                restart_policy: RestartPolicy::Always,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        manager
+            .stop_process(&process.id, Some("restart"), false)
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        assert!(manager.registry.get_all_processes().is_empty());
+    }
+
+    #[tokio::test]
+    async fn stop_policy_replaces_sigterm() {
+        let fixture = ProcessTestFixture::with_config("mcproc-manager", |config| {
+            config.process.restart.process_stop_timeout_ms = 200;
+        });
+        let manager = &fixture.process_manager;
+        let interrupted = fixture.root.join("interrupted");
+        let released = fixture.root.join("released");
+
+        // Ignores SIGTERM and only exits cleanly on SIGINT
+        let (by_signal, ..) = manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "by-signal".to_string(),
+                project: Some("stop".to_string()),
+                cmd: Some(format!(
+                    "trap '' TERM; trap 'touch {}; exit 0' INT; while true; do sleep 0.1; done",
+                    interrupted.display()
+                )),
+                stop_policy: StopPolicy::from_request(Some("INT".to_string()), None, Some(5))
+                    .unwrap(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        // Exits once the stop command has run
+        let (by_command, ..) = manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "by-command".to_string(),
+                project: Some("stop".to_string()),
+                cmd: Some(format!(
+                    "trap '' TERM; while [ ! -f {} ]; do sleep 0.1; done",
+                    released.display()
+                )),
+                stop_policy: StopPolicy::from_request(
+                    None,
+                    Some(format!("touch {}", released.display())),
+                    Some(5),
+                )
+                .unwrap(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        manager
+            .stop_process(&by_signal.id, Some("stop"), false)
+            .await
+            .unwrap();
+        manager
+            .stop_process(&by_command.id, Some("stop"), false)
+            .await
+            .unwrap();
+
+        assert!(interrupted.exists());
+        assert!(released.exists());
+        assert_eq!(*by_signal.exit_code.lock().unwrap(), Some(0));
+        assert_eq!(*by_command.exit_code.lock().unwrap(), Some(0));
+    }
+
+    #[tokio::test]
+    async fn paused_process_is_resumed_to_handle_stop_signal() {
+        let fixture = ProcessTestFixture::with_config("mcproc-manager", |config| {
+            config.process.restart.process_stop_timeout_ms = 5_000;
+        });
+        let manager = &fixture.process_manager;
+        let terminated = fixture.root.join("terminated");
+        let (process, ..) = manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "paused".to_string(),
+                project: Some("signal".to_string()),
+                cmd: Some(format!(
+                    "trap 'touch {}; exit 0' TERM; while true; do sleep 0.1; done",
+                    terminated.display()
+                )),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let paused = manager
+            .signal_process("paused", Some("signal"), Signal::SIGSTOP, true)
+            .unwrap();
+        assert_eq!(paused.get_status(), ProcessStatus::Paused);
+        manager
+            .stop_process(&process.id, Some("signal"), false)
+            .await
+            .unwrap();
+
+        assert!(terminated.exists());
+        assert_eq!(*process.exit_code.lock().unwrap(), Some(0));
+        assert!(matches!(
+            manager.signal_process("paused", Some("signal"), Signal::SIGCONT, true),
+            Err(McprocdError::ProcessNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn pty_process_runs_on_a_terminal_and_reads_input_from_it() {
+        let fixture = test_manager();
+        let manager = &fixture.process_manager;
+        let received = fixture.root.join("received");
+        let (process, timeout_occurred, pattern_matched, _, matched_line) = manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "terminal".to_string(),
+                project: Some("pty".to_string()),
+                cmd: Some(format!(
+                    "test -t 0 && test -t 1 && test -t 2 && echo \"tty $(stty size) $TERM\"; read line; echo \"$line\" > {}; sleep 30",
+                    received.display()
+                )),
+                wait_for_log: Some("^tty ".to_string()),
+                wait_timeout: Some(5),
+                pty: Some(PtySize {
+                    cols: 132,
+                    rows: 50,
+                }),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(pattern_matched && !timeout_occurred);
+        assert_eq!(matched_line.as_deref(), Some("tty 50 132 xterm-256color"));
+
+        manager
+            .send_input("terminal", Some("pty"), b"hello\n")
+            .await
+            .unwrap();
+        for _ in 0..50 {
+            if received.exists() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert_eq!(std::fs::read_to_string(&received).unwrap(), "hello\n");
+
+        manager
+            .stop_process(&process.id, Some("pty"), false)
+            .await
+            .unwrap();
+        assert_eq!(process.get_status(), ProcessStatus::Stopped);
+    }
+
+    #[tokio::test]
+    async fn requested_ports_are_allocated_injected_and_kept_across_restarts() {
+        // Hold the first port of the range so the allocator has to skip it
+        let listener = std::net::TcpListener::bind(("0.0.0.0", 0)).unwrap();
+        let held = listener.local_addr().unwrap().port();
+        let fixture = ProcessTestFixture::with_config("mcproc-manager", |config| {
+            config.process.restart.process_stop_timeout_ms = 500;
+            config.process.ports.range_start = held;
+            config.process.ports.range_end = held.saturating_add(20);
+        });
+        let manager = &fixture.process_manager;
+        let start = |name: &str, ports: &[&str]| {
+            manager.start_process_with_log_stream(StartProcessParams {
+                name: name.to_string(),
+                project: Some("ports".to_string()),
+                cmd: Some(
+                    "echo \"ports $PORT $MCPROC_PORT_HTTP ${MCPROC_PORT_HMR:-none}\"; sleep 30"
+                        .to_string(),
+                ),
+                wait_for_log: Some("^ports ".to_string()),
+                wait_timeout: Some(5),
+                ports: ports.iter().map(|port| port.to_string()).collect(),
+                ..Default::default()
+            })
+        };
+
+        let (web, _, _, _, matched_line) = start("web", &["http", "hmr"]).await.unwrap();
+        let [(_, http), (_, hmr)] = web.allocated_ports.as_slice() else {
+            panic!("expected two ports, got {:?}", web.allocated_ports);
+        };
+        assert_eq!(
+            matched_line.as_deref(),
+            Some(format!("ports {http} {http} {hmr}").as_str())
+        );
+        assert_eq!(web.port, Some(*http));
+        assert!(![held, *hmr].contains(http) && *hmr != held);
+
+        let (api, _, _, _, _) = start("api", &["http"]).await.unwrap();
+        let api_port = api.allocated_ports[0].1;
+        assert!(![held, *http, *hmr].contains(&api_port));
+
+        manager
+            .stop_process(&web.id, Some("ports"), false)
+            .await
+            .unwrap();
+        let (restarted, _, _, _, _) = start("web", &["http", "hmr"]).await.unwrap();
+        assert_eq!(restarted.allocated_ports, web.allocated_ports);
+
+        assert!(matches!(
+            start("bad", &["http", "HTTP"]).await,
+            Err(McprocdError::InvalidRequest(_))
+        ));
+
+        for process in [restarted, api] {
+            manager
+                .stop_process(&process.id, Some("ports"), false)
+                .await
+                .unwrap();
+        }
+        drop(listener);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn cgroup_tracks_double_forked_children_through_port_detection_and_stop() {
+        let fixture = test_manager();
+        let manager = &fixture.process_manager;
+        let escape = "import os, socket, time\n\
+                      s = socket.socket()\n\
+                      s.bind(('127.0.0.1', 0))\n\
+                      s.listen()\n\
+                      print('escaped', os.getpid(), s.getsockname()[1], flush=True)\n\
+                      time.sleep(60)";
+        let (process, _, _, _, matched_line) = manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "forking".to_string(),
+                project: Some("cgroups".to_string()),
+                cmd: Some(format!("(setsid python3 -c \"{escape}\" &); sleep 60")),
+                wait_for_log: Some("^escaped ".to_string()),
+                wait_timeout: Some(10),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let Some(cgroup) = process.cgroup.clone() else {
+            eprintln!("skipping: no writable cgroup v2 hierarchy");
+            manager
+                .stop_process(&process.id, Some("cgroups"), true)
+                .await
+                .unwrap();
+            return;
+        };
+        let matched_line = matched_line.unwrap();
+        let [_, escaped, port] = matched_line.split_whitespace().collect::<Vec<_>>()[..] else {
+            panic!("unexpected line {matched_line}");
+        };
+        let escaped = escaped.parse::<u32>().unwrap();
+        let port = port.parse::<u16>().unwrap();
+
+        // The listener has left both the process group and the process tree
+        assert!(!crate::daemon::process::procfs::process_tree(process.pid).contains(&escaped));
+        assert!(cgroup.pids().contains(&escaped));
+        assert_eq!(*process.detected_port.lock().unwrap(), Some(port));
+        let owners = manager.lookup_port(port).await;
+        assert_eq!(owners[0].process.as_ref().unwrap().id, process.id);
+
+        manager
+            .stop_process(&process.id, Some("cgroups"), false)
+            .await
+            .unwrap();
+        assert!(!cgroup.is_populated());
+        let state = std::fs::read_to_string(format!("/proc/{escaped}/stat")).unwrap_or_default();
+        assert!(state.is_empty() || state.contains(") Z "), "{state}");
+        drop(process);
+        drop(owners);
+        assert!(!cgroup.path().exists());
+    }
+
+    #[tokio::test]
+    async fn resource_limits_use_cgroup_controllers_or_fall_back_to_setrlimit() {
+        let fixture = test_manager();
+        let manager = &fixture.process_manager;
+        let limits = ResourceLimits {
+            memory: Some(1 << 30),
+            cpus: Some(0.5),
+            open_files: Some(64),
+            ..Default::default()
+        };
+        let (process, _, _, _, matched_line) = manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "limited".to_string(),
+                project: Some("limits".to_string()),
+                cmd: Some("echo \"nofile $(ulimit -n) as $(ulimit -v)\"; sleep 30".to_string()),
+                wait_for_log: Some("^nofile ".to_string()),
+                wait_timeout: Some(10),
+                limits,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(process.limits, limits);
+        assert_eq!(StartProcessParams::from_process(&process).limits, limits);
+
+        let matched_line = matched_line.unwrap();
+        let memory_max = process
+            .cgroup
+            .as_ref()
+            .and_then(|cgroup| std::fs::read_to_string(cgroup.path().join("memory.max")).ok());
+        match memory_max {
+            Some(memory_max) => {
+                assert_eq!(memory_max.trim(), (1u64 << 30).to_string());
+                assert_eq!(matched_line, "nofile 64 as unlimited");
+            }
+            // Without a memory controller each process gets an address space limit
+            None => assert_eq!(matched_line, format!("nofile 64 as {}", 1 << 20)),
+        }
+
+        manager
+            .stop_process(&process.id, Some("limits"), false)
+            .await
+            .unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn timed_out_tasks_are_recorded_as_timed_out() {
+        let fixture = test_manager();
+        let manager = &fixture.process_manager;
+        let result = manager
+            .run_task(
+                StartProcessParams {
+                    name: "slow".to_string(),
+                    project: Some("tasks".to_string()),
+                    cmd: Some("sleep 30".to_string()),
+                    ..Default::default()
+                },
+                TaskOptions {
+                    timeout: Duration::from_secs(1),
+                    ..Default::default()
+                },
+                0,
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(result.timed_out);
+        assert!(result.process.is_timed_out());
+
+        let runs = manager.history.list(Some("tasks"), Some("slow"), 10);
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, ProcessStatus::Failed);
+        assert_eq!(runs[0].exit_reason.as_deref(), Some("Timed out after 1s"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn memory_limit_kills_are_reported_as_the_exit_reason() {
+        let fixture = test_manager();
+        let manager = &fixture.process_manager;
+        let (process, _, _, _, _) = manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "hog".to_string(),
+                project: Some("limits".to_string()),
+                cmd: Some(
+                    "exec python3 -c \"import time; print('ready', flush=True); time.sleep(1); b = bytearray(256 << 20); time.sleep(30)\""
+                        .to_string(),
+                ),
+                wait_for_log: Some("^ready".to_string()),
+                wait_timeout: Some(10),
+                limits: ResourceLimits {
+                    memory: Some(64 << 20),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        if !process
+            .cgroup
+            .as_ref()
+            .is_some_and(|cgroup| cgroup.path().join("memory.max").exists())
+        {
+            eprintln!("skipping: no cgroup memory controller");
+            let _ = manager
+                .stop_process(&process.id, Some("limits"), true)
+                .await;
+            return;
+        }
+
+        for _ in 0..100 {
+            if process.get_status() == ProcessStatus::Failed {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert_eq!(process.get_status(), ProcessStatus::Failed);
+        assert_eq!(
+            process.exit_reason.lock().unwrap().as_deref(),
+            Some("OOM-killed by memory limit 64M")
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn sampled_metrics_are_reported_with_a_bounded_history() {
+        let fixture = test_manager();
+        let manager = &fixture.process_manager;
+        let process = start_sleep(manager, "sampled", "metrics").await.unwrap();
+
+        for _ in 0..4 {
+            ProcessManager::sample_metrics(vec![process.clone()], 3).await;
+        }
+        let info = crate::daemon::api::grpc::helpers::create_process_info(
+            &process,
+            &fixture.root,
+            None,
+            Vec::new(),
+            None,
+        );
+        let metrics = info.metrics.unwrap();
+        assert_eq!(metrics.processes, 1);
+        assert!(metrics.rss_bytes > 0);
+        assert!(metrics.threads >= 1);
+        assert!(metrics.cpu_percent.is_some());
+        assert_eq!(info.metrics_history.len(), 3);
+        assert_eq!(info.metrics_history.last(), Some(&metrics));
+
+        manager
+            .stop_process(&process.id, Some("metrics"), true)
+            .await
+            .unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn watchdog_rules_flag_and_stop_processes_with_a_logged_reason() {
+        use crate::daemon::process::event::ProcessEvent;
+        use crate::daemon::process::watchdog::WatchdogRule;
+
+        let fixture = test_manager();
+        let manager = &fixture.process_manager;
+        let mut events = manager.event_hub.as_ref().unwrap().subscribe();
+        let rule = |rule: &str| {
+            WatchdogRule::try_from(crate::common::watchdog::parse_watchdog_rule(rule).unwrap())
+                .unwrap()
+        };
+        let (process, ..) = manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "idle".to_string(),
+                project: Some("watchdog".to_string()),
+                args: vec!["sleep".to_string(), "30".to_string()],
+                watchdog: vec![rule("flag stalled if no-output"), rule("stop if cpu < 50")],
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        // The first sample has no CPU use yet, so only the flag fires
+        ProcessManager::sample_metrics(vec![process.clone()], 10).await;
+        manager.check_watchdogs();
+        let info = |process: &ProxyInfo| {
+            crate::daemon::api::grpc::helpers::create_process_info(
+                process,
+                &fixture.root,
+                None,
+                Vec::new(),
+                None,
+            )
+        };
+        assert_eq!(info(&process).watchdog_flags, vec!["stalled"]);
+        assert_eq!(info(&process).watchdog.len(), 2);
+        assert_eq!(process.get_status(), ProcessStatus::Running);
+
+        ProcessManager::sample_metrics(vec![process.clone()], 10).await;
+        manager.check_watchdogs();
+        let (reasons, logs) = tokio::time::timeout(Duration::from_secs(10), async {
+            let (mut reasons, mut logs) = (Vec::new(), Vec::new());
+            while reasons.len() < 2 || !logs.iter().any(|log: &String| log.contains("Stopping")) {
+                match events.recv().await.unwrap() {
+                    StreamEvent::Process(ProcessEvent::Watchdog { reason, .. }) => {
+                        reasons.push(reason)
+                    }
+                    StreamEvent::Log { entry, .. } if entry.content.contains("[mcproc]") => {
+                        logs.push(entry.content)
+                    }
+                    _ => {}
+                }
+            }
+            (reasons, logs)
+        })
+        .await
+        .expect("watchdog actions were not reported");
+
+        assert!(reasons[0].starts_with("watchdog rule `flag stalled if no-output` fired (rss "));
+        assert!(reasons[1].starts_with("watchdog rule `stop if cpu < 50%` fired ("));
+        assert!(logs
+            .iter()
+            .any(|log| log.contains("Process idle flagged as stalled: watchdog rule")));
+        assert!(logs
+            .iter()
+            .any(|log| log.contains("Stopping process idle: watchdog rule `stop if cpu < 50%`")));
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while matches!(
+                process.get_status(),
+                ProcessStatus::Running | ProcessStatus::Stopping
+            ) {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("watchdog never stopped the process");
+        assert!(process.is_stop_requested());
+    }
+
+    #[tokio::test]
+    async fn watched_file_changes_restart_the_process_once_per_burst() {
+        use crate::daemon::process::file_watch::FileWatch;
+
+        let fixture = test_manager();
+        let manager = &fixture.process_manager;
+        let app = fixture.root.join("app");
+        std::fs::create_dir_all(app.join("src")).unwrap();
+        std::fs::write(app.join(".gitignore"), "tmp/\n").unwrap();
+        std::fs::create_dir_all(app.join("tmp")).unwrap();
+        let mut logs = manager.event_hub.as_ref().unwrap().subscribe();
+
+        let (process, ..) = manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "server".to_string(),
+                project: Some("watch".to_string()),
+                args: vec!["sleep".to_string(), "30".to_string()],
+                cwd: Some(app.clone()),
+                watch: Some(
+                    FileWatch::try_from(proto::FileWatch {
+                        debounce_ms: Some(200),
+                        ..Default::default()
+                    })
+                    .unwrap(),
+                ),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        // Give the watcher time to start, then change an ignored file and a burst of sources
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        std::fs::write(app.join("tmp/cache"), "").unwrap();
+        for file in ["main.rs", "lib.rs", "util.rs"] {
+            std::fs::write(app.join("src").join(file), "").unwrap();
+        }
+
+        let restart_log = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                if let StreamEvent::Log { entry, .. } = logs.recv().await.unwrap() {
+                    if entry.content.contains("Restarting process server") {
+                        return entry.content;
+                    }
+                }
+            }
+        })
+        .await
+        .expect("file change did not restart the process");
+        assert!(restart_log.contains("src/main.rs and 2 other files changed"));
+
+        let restarted = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                if let Some(current) = manager
+                    .registry
+                    .get_process_by_name_with_project("server", "watch")
+                    .filter(|current| {
+                        current.id != process.id && current.get_status() == ProcessStatus::Running
+                    })
+                {
+                    return current;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("process was not restarted");
+        assert_eq!(restarted.watch, process.watch);
+
+        // The burst led to a single restart
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        let current = manager
+            .registry
+            .get_process_by_name_with_project("server", "watch")
+            .unwrap();
+        assert_eq!(current.id, restarted.id);
+
+        // The new run is watched again
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        std::fs::write(app.join("src/main.rs"), "fn main() {}").unwrap();
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while manager
+                .registry
+                .get_process_by_name_with_project("server", "watch")
+                .is_some_and(|current| current.id == restarted.id)
+            {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("the restarted process was not watched");
+
+        fixture.stop_all().await;
+    }
+
+    #[tokio::test]
+    async fn scheduled_processes_run_on_schedule_and_skip_overlapping_runs() {
+        let fixture = test_manager();
+        let manager = &fixture.process_manager;
+        let every_second = || Some("every 1s".parse().unwrap());
+
+        let (tick, ..) = manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "tick".to_string(),
+                project: Some("cron".to_string()),
+                cmd: Some("echo tick".to_string()),
+                schedule: every_second(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(tick.get_status(), ProcessStatus::Scheduled);
+        assert_eq!(tick.pid, 0);
+        let (slow, ..) = manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "slow".to_string(),
+                project: Some("cron".to_string()),
+                args: vec!["sleep".to_string(), "3".to_string()],
+                schedule: every_second(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                let runs = tick.schedule_state.lock().unwrap().runs().count();
+                let skipped = slow.schedule_state.lock().unwrap().skipped;
+                if runs >= 2 && skipped >= 1 {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("scheduled runs did not happen");
+
+        {
+            let state = tick.schedule_state.lock().unwrap();
+            assert!(state
+                .runs()
+                .all(|run| run.exit_code == Some(0) && run.succeeded));
+            assert!(state.next_run.is_some());
+        }
+        let current = manager
+            .registry
+            .get_process_by_name_with_project("tick", "cron")
+            .unwrap();
+        assert_ne!(current.id, tick.id);
+        assert_eq!(current.schedule, tick.schedule);
+
+        // Stopping the schedule ends its runs
+        manager
+            .stop_process("tick", Some("cron"), false)
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(manager
+            .registry
+            .get_process_by_name_with_project("tick", "cron")
+            .is_none());
+
+        fixture.stop_all().await;
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::test_support::ProcessTestFixture;
+    #[tokio::test]
+    async fn reconciling_a_schedule_after_a_run_leaves_it_unchanged() {
+        let fixture = test_manager();
+        let manager = &fixture.process_manager;
+        let definition = || StartProcessParams {
+            name: "tick".to_string(),
+            cmd: Some("echo tick".to_string()),
+            schedule: Some("every 1s".parse().unwrap()),
+            ..Default::default()
+        };
 
-    fn test_manager() -> ProcessTestFixture {
-        ProcessTestFixture::new("mcproc-manager", 500)
-    }
+        let results = manager
+            .reconcile_project("cron", vec![definition()], false)
+            .await
+            .unwrap();
+        let scheduled = results[0].result.as_ref().unwrap().clone().unwrap();
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while scheduled.schedule_state.lock().unwrap().runs().count() == 0 {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("the scheduled run did not finish");
 
-    async fn start_sleep(
-        manager: &ProcessManager,
-        name: &str,
-        project: &str,
-    ) -> Result<Arc<ProxyInfo>> {
-        manager
-            .start_process_with_log_stream(
-                name.to_string(),
-                Some(project.to_string()),
-                None,
-                vec!["sleep".to_string(), "5".to_string()],
-                None,
-                None,
-                None,
-                None,
-                None,
-            )
+        let results = manager
+            .reconcile_project("cron", vec![definition()], false)
             .await
-            .map(|result| result.0)
+            .unwrap();
+        assert_eq!(results[0].action, ReconcileAction::Unchanged);
+        assert!(scheduled.schedule_state.lock().unwrap().runs().count() >= 1);
+
+        fixture.stop_all().await;
     }
 
     #[tokio::test]
-    async fn permits_same_process_name_in_different_projects() {
+    async fn attached_clients_receive_raw_output_including_prompts() {
         let fixture = test_manager();
         let manager = &fixture.process_manager;
-        let root = fixture.root.clone();
-        let first = start_sleep(manager, "shared-name", "a").await.unwrap();
-        let second = start_sleep(manager, "shared-name", "b").await.unwrap();
-        assert_eq!(manager.registry.get_all_processes().len(), 2);
         manager
-            .stop_process(&first.id, Some("a"), true)
+            .start_process_with_log_stream(StartProcessParams {
+                name: "prompt".to_string(),
+                project: Some("attach".to_string()),
+                cmd: Some(
+                    "read go; printf 'name> '; read name; echo \"hi $name\"; sleep 30".to_string(),
+                ),
+                stdin: true,
+                ..Default::default()
+            })
             .await
             .unwrap();
+
+        let (process, mut output) = manager.attach_process("prompt", Some("attach")).unwrap();
+        async fn next_output(output: &mut broadcast::Receiver<Bytes>) -> Bytes {
+            tokio::time::timeout(Duration::from_secs(5), output.recv())
+                .await
+                .expect("no output from attached process")
+                .unwrap()
+        }
+        process.write_input(b"go\n").await.unwrap();
+        assert_eq!(&next_output(&mut output).await[..], b"name> ");
+        process.write_input(b"bob\n").await.unwrap();
+        assert_eq!(&next_output(&mut output).await[..], b"hi bob\n");
+
         manager
-            .stop_process(&second.id, Some("b"), true)
+            .stop_process(&process.id, Some("attach"), false)
             .await
             .unwrap();
-        tokio::fs::remove_dir_all(root).await.unwrap();
+        assert!(matches!(
+            manager.attach_process("prompt", Some("attach")),
+            Err(McprocdError::ProcessNotRunning { .. } | McprocdError::ProcessNotFound { .. })
+        ));
     }
 
     #[tokio::test]
-    async fn concurrent_starts_reserve_name_atomically() {
-        for _ in 0..10 {
-            let fixture = test_manager();
-            let manager = &fixture.process_manager;
-            let root = fixture.root.clone();
-            let (first, second) = tokio::join!(
-                start_sleep(manager, "same-name", "same-project"),
-                start_sleep(manager, "same-name", "same-project")
-            );
-            assert_eq!(usize::from(first.is_ok()) + usize::from(second.is_ok()), 1);
-            let error = match (first, second) {
-                (Err(error), _) | (_, Err(error)) => error,
-                _ => unreachable!(),
-            };
-            assert!(
-                matches!(error, McprocdError::ProcessAlreadyExists(_)),
-                "{error:?}"
-            );
-            let processes = manager.registry.get_processes_by_project("same-project");
-            assert_eq!(processes.len(), 1);
-            manager
-                .stop_process(&processes[0].id, Some("same-project"), true)
-                .await
-                .unwrap();
-            tokio::fs::remove_dir_all(root).await.unwrap();
-        }
+    async fn tcp_readiness_probe_marks_process_ready() {
+        let fixture = test_manager();
+        let manager = &fixture.process_manager;
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let (process, timeout_occurred, ..) = manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "probed".to_string(),
+                project: Some("probes".to_string()),
+                args: vec!["sleep".to_string(), "30".to_string()],
+                readiness_probes: vec![
+                    ReadinessProbe::PortDetected,
+                    ReadinessProbe::Tcp {
+                        host: "127.0.0.1".to_string(),
+                        port,
+                    },
+                ],
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert!(!timeout_occurred);
+        assert!(process.is_ready());
+        assert_eq!(
+            process.ready_by.lock().unwrap().as_deref(),
+            Some(format!("tcp 127.0.0.1:{port}").as_str())
+        );
+        fixture.stop_all().await;
     }
 
     #[tokio::test]
-    async fn wait_for_log_returns_matching_line_and_running_process() {
+    async fn failing_readiness_probe_times_out_without_marking_ready() {
         let fixture = test_manager();
         let manager = &fixture.process_manager;
-        let root = fixture.root.clone();
-        let result = manager
-            .start_process_with_log_stream(
-                "wait-match".to_string(),
-                Some("wait-for-log".to_string()),
-                Some("echo BOOT; echo READY line; sleep 30".to_string()),
-                Vec::new(),
-                None,
-                None,
-                Some("READY".to_string()),
-                None,
-                None,
-            )
-            .await;
-        let (process, timeout_occurred, pattern_matched, _, matched_line) = match result {
-            Ok(result) => result,
-            Err(error) => {
-                tokio::fs::remove_dir_all(root).await.unwrap();
-                panic!("failed to start process: {error:?}");
-            }
+        let port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().port()
         };
-        let status = process.get_status();
 
-        let stop_result = manager
-            .stop_process(&process.id, Some("wait-for-log"), true)
-            .await;
-        let remove_result = tokio::fs::remove_dir_all(root).await;
+        let (process, timeout_occurred, ..) = manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "unreachable".to_string(),
+                project: Some("probes".to_string()),
+                args: vec!["sleep".to_string(), "30".to_string()],
+                wait_timeout: Some(1),
+                readiness_probes: vec![ReadinessProbe::Http {
+                    url: format!("http://127.0.0.1:{port}/health"),
+                    min_status: 200,
+                    max_status: 399,
+                }],
+                ..Default::default()
+            })
+            .await
+            .unwrap();
 
-        stop_result.unwrap();
-        remove_result.unwrap();
-        assert!(pattern_matched);
-        assert!(!timeout_occurred);
-        assert!(matched_line.is_some_and(|line| line.contains("READY")));
-        assert_eq!(status, ProcessStatus::Running);
+        assert!(timeout_occurred);
+        assert_eq!(process.get_status(), ProcessStatus::Running);
+        assert!(!process.is_ready());
+        assert!(process.ready_by.lock().unwrap().is_none());
+        fixture.stop_all().await;
+    }
+
+    fn command_health_check(cmd: String, restart_after_failures: Option<u32>) -> HealthCheck {
+        HealthCheck {
+            kind: HealthCheckKind::Command(cmd),
+            interval: Duration::from_secs(1),
+            timeout: Duration::from_secs(5),
+            restart_after_failures,
+        }
+    }
+
+    async fn wait_for_health(process: &ProxyInfo, status: HealthStatus) {
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while process.health.lock().unwrap().status != status {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .unwrap_or_else(|_| panic!("process never became {status:?}"));
     }
 
     #[tokio::test]
-    async fn wait_for_log_timeout_returns_running_process() {
+    async fn health_checks_track_liveness_transitions() {
         let fixture = test_manager();
         let manager = &fixture.process_manager;
-        let root = fixture.root.clone();
-        let result = manager
-            .start_process_with_log_stream(
-                "wait-timeout".to_string(),
-                Some("wait-for-log".to_string()),
-                None,
-                vec!["sleep".to_string(), "30".to_string()],
-                None,
-                None,
-                Some("NEVER_APPEARS".to_string()),
-                Some(1),
-                None,
-            )
-            .await;
-        let (process, timeout_occurred, pattern_matched, _, matched_line) = match result {
-            Ok(result) => result,
-            Err(error) => {
-                tokio::fs::remove_dir_all(root).await.unwrap();
-                panic!("failed to start process: {error:?}");
+        let marker = fixture.root.join("healthy");
+
+        let (process, ..) = manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "checked".to_string(),
+                project: Some("health".to_string()),
+                args: vec!["sleep".to_string(), "30".to_string()],
+                health_check: Some(command_health_check(
+                    format!("test -f {}", marker.display()),
+                    None,
+                )),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        wait_for_health(&process, HealthStatus::Unhealthy).await;
+        assert!(process
+            .health
+            .lock()
+            .unwrap()
+            .last_error
+            .as_deref()
+            .is_some_and(|error| error.contains("exited with code 1")));
+
+        std::fs::write(&marker, "").unwrap();
+        wait_for_health(&process, HealthStatus::Healthy).await;
+        assert_eq!(process.health.lock().unwrap().consecutive_failures, 0);
+        assert_eq!(process.get_status(), ProcessStatus::Running);
+        fixture.stop_all().await;
+    }
+
+    #[tokio::test]
+    async fn repeated_health_check_failures_restart_the_process() {
+        let fixture = test_manager();
+        let manager = &fixture.process_manager;
+
+        let (process, ..) = manager
+            .start_process_with_log_stream(StartProcessParams {
+                name: "wedged".to_string(),
+                project: Some("health".to_string()),
+                args: vec!["sleep".to_string(), "30".to_string()],
+                health_check: Some(command_health_check("exit 1".to_string(), Some(2))),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let restarted = tokio::time::timeout(Duration::from_secs(15), async {
+            loop {
+                if let Some(current) = manager
+                    .registry
+                    .get_process_by_name_with_project("wedged", "health")
+                    .filter(|current| current.id != process.id)
+                {
+                    return current;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
             }
+        })
+        .await
+        .expect("unhealthy process was never restarted");
+
+        assert_eq!(process.health.lock().unwrap().consecutive_failures, 2);
+        assert_eq!(restarted.restart_state.count, 1);
+        assert_eq!(restarted.health_check, process.health_check);
+        assert_eq!(
+            restarted.health.lock().unwrap().status,
+            HealthStatus::Unknown
+        );
+        fixture.stop_all().await;
+    }
+
+    #[tokio::test]
+    async fn restored_processes_are_stopped_until_relaunched() {
+        let fixture = test_manager();
+        let process = start_sleep(&fixture.process_manager, "api", "persist")
+            .await
+            .unwrap();
+        fixture.process_manager.persist_for_shutdown();
+        fixture.stop_all().await;
+
+        let manager = fixture.restarted_process_manager();
+        let interrupted = manager.restore_processes();
+        assert_eq!(interrupted.len(), 1);
+        let restored = manager
+            .get_process_by_name_or_id_with_project("api", Some("persist"))
+            .unwrap();
+        assert_eq!(restored.id, process.id);
+        assert_eq!(restored.get_status(), ProcessStatus::Stopped);
+        assert_eq!(
+            restored.status_reason.as_deref(),
+            Some(crate::daemon::process::persistence::DAEMON_RESTARTED)
+        );
+
+        manager.relaunch_processes(interrupted).await;
+        let relaunched = manager
+            .get_process_by_name_or_id_with_project("api", Some("persist"))
+            .unwrap();
+        assert_ne!(relaunched.id, process.id);
+        assert_eq!(relaunched.get_status(), ProcessStatus::Running);
+        assert_eq!(relaunched.args, process.args);
+
+        manager
+            .stop_process(&relaunched.id, Some("persist"), true)
+            .await
+            .unwrap();
+        assert!(manager.store.load().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reconcile_project_starts_restarts_and_removes_orphans() {
+        let fixture = test_manager();
+        let manager = &fixture.process_manager;
+        let sleeper = |name: &str, seconds: &str| StartProcessParams {
+            name: name.to_string(),
+            args: vec!["sleep".to_string(), seconds.to_string()],
+            ..Default::default()
         };
-        let status = process.get_status();
+        let orphan = start_sleep(manager, "orphan", "manifest").await.unwrap();
 
-        let stop_result = manager
-            .stop_process(&process.id, Some("wait-for-log"), true)
+        let first = manager
+            .reconcile_project(
+                "manifest",
+                vec![sleeper("web", "30"), sleeper("worker", "30")],
+                false,
+            )
+            .await
+            .unwrap();
+        assert!(first
+            .iter()
+            .all(|result| result.action == ReconcileAction::Started && result.result.is_ok()));
+        let web = first[0].result.as_ref().unwrap().clone().unwrap();
+
+        let second = manager
+            .reconcile_project(
+                "manifest",
+                vec![sleeper("web", "30"), sleeper("worker", "31")],
+                true,
+            )
+            .await
+            .unwrap();
+        let actions = second
+            .iter()
+            .map(|result| (result.name.as_str(), result.action))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            actions,
+            vec![
+                ("orphan", ReconcileAction::Stopped),
+                ("web", ReconcileAction::Unchanged),
+                ("worker", ReconcileAction::Restarted),
+            ]
+        );
+        assert_eq!(
+            second[1].result.as_ref().unwrap().as_ref().unwrap().id,
+            web.id
+        );
+        manager
+            .wait_for_process_removal("orphan", Some("manifest"))
             .await;
-        let remove_result = tokio::fs::remove_dir_all(root).await;
+        assert!(manager.registry.get_process_by_id(&orphan.id).is_none());
 
-        stop_result.unwrap();
-        remove_result.unwrap();
-        assert!(timeout_occurred);
-        assert!(!pattern_matched);
-        assert!(matched_line.is_none());
-        assert_eq!(status, ProcessStatus::Running);
+        fixture.stop_all().await;
     }
 
     #[tokio::test]
-    async fn wait_for_log_invalid_regex_releases_process_name() {
+    async fn reconcile_project_waits_for_dependencies_and_blocks_on_failure() {
         let fixture = test_manager();
         let manager = &fixture.process_manager;
-        let root = fixture.root.clone();
-        let invalid_result = manager
-            .start_process_with_log_stream(
-                "invalid-wait-pattern".to_string(),
-                Some("wait-for-log".to_string()),
-                None,
-                vec!["sleep".to_string(), "30".to_string()],
-                None,
-                None,
-                Some("[unclosed".to_string()),
-                None,
-                None,
+        let definition = |name: &str, cmd: &str, depends_on: &[&str]| StartProcessParams {
+            name: name.to_string(),
+            cmd: Some(cmd.to_string()),
+            depends_on: depends_on.iter().map(|name| name.to_string()).collect(),
+            ..Default::default()
+        };
+
+        let results = manager
+            .reconcile_project(
+                "deps",
+                vec![
+                    definition("api", "sleep 30", &["db"]),
+                    StartProcessParams {
+                        wait_for_log: Some("accepting connections".to_string()),
+                        ..definition("db", "sleep 0.3; echo accepting connections; sleep 30", &[])
+                    },
+                    definition("migrate", "echo migration failed; exit 1", &[]),
+                    definition("worker", "sleep 30", &["migrate"]),
+                ],
+                false,
+            )
+            .await
+            .unwrap();
+
+        let actions = results
+            .iter()
+            .map(|result| (result.name.as_str(), result.action))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            actions,
+            vec![
+                ("db", ReconcileAction::Started),
+                ("api", ReconcileAction::Started),
+                ("migrate", ReconcileAction::Started),
+                ("worker", ReconcileAction::Blocked),
+            ]
+        );
+        assert!(results[1]
+            .result
+            .as_ref()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .is_ready());
+        assert!(matches!(
+            results[3].result,
+            Err(McprocdError::DependencyNotReady { ref dependency, .. }) if dependency == "migrate"
+        ));
+        assert!(manager
+            .registry
+            .get_process_by_name_with_project("worker", "deps")
+            .is_none());
+
+        fixture.stop_all().await;
+    }
+
+    #[tokio::test]
+    async fn reconcile_project_rejects_dependency_cycles() {
+        let fixture = test_manager();
+        let definition = |name: &str, depends_on: &str| StartProcessParams {
+            name: name.to_string(),
+            cmd: Some("sleep 30".to_string()),
+            depends_on: vec![depends_on.to_string()],
+            ..Default::default()
+        };
+
+        let result = fixture
+            .process_manager
+            .reconcile_project(
+                "deps",
+                vec![definition("a", "b"), definition("b", "a")],
+                false,
             )
             .await;
-        let invalid_error = match invalid_result {
-            Err(error) => error,
-            Ok((process, _, _, _, _)) => {
-                manager
-                    .stop_process(&process.id, Some("wait-for-log"), true)
-                    .await
-                    .unwrap();
-                tokio::fs::remove_dir_all(root).await.unwrap();
-                panic!("invalid regex unexpectedly started a process");
-            }
+
+        assert!(
+            matches!(result, Err(McprocdError::InvalidRequest(message)) if message.contains("a -> b -> a"))
+        );
+        assert!(fixture.process_manager.get_all_processes().is_empty());
+    }
+
+    #[test]
+    fn shutdown_order_stops_dependents_first() {
+        let process = |name: &str, depends_on: &[&str]| {
+            Arc::new(ProxyInfo::new(
+                crate::daemon::process::types::ProxyInfoParams {
+                    name: name.to_string(),
+                    project: "deps".to_string(),
+                    depends_on: depends_on.iter().map(|name| name.to_string()).collect(),
+                    ..Default::default()
+                },
+            ))
         };
-        let registry_was_empty = manager.registry.get_all_processes().is_empty();
 
-        let restart_result = start_sleep(manager, "invalid-wait-pattern", "wait-for-log").await;
-        let restarted_process = match restart_result {
-            Ok(process) => process,
-            Err(error) => {
-                tokio::fs::remove_dir_all(root).await.unwrap();
-                panic!("process name reservation was not released: {error:?}");
-            }
+        let order = shutdown_order(vec![
+            process("db", &[]),
+            process("api", &["db", "cache"]),
+            process("cache", &[]),
+            process("web", &["api"]),
+        ])
+        .into_iter()
+        .map(|process| process.name.clone())
+        .collect::<Vec<_>>();
+
+        assert_eq!(order, vec!["web", "api", "cache", "db"]);
+    }
+
+    #[test]
+    fn restart_waves_follow_dependencies_within_each_project() {
+        let process = |project: &str, name: &str, depends_on: &[&str]| {
+            Arc::new(ProxyInfo::new(
+                crate::daemon::process::types::ProxyInfoParams {
+                    name: name.to_string(),
+                    project: project.to_string(),
+                    depends_on: depends_on.iter().map(|name| name.to_string()).collect(),
+                    ..Default::default()
+                },
+            ))
         };
-        let stop_result = manager
-            .stop_process(&restarted_process.id, Some("wait-for-log"), true)
-            .await;
-        let remove_result = tokio::fs::remove_dir_all(root).await;
 
-        stop_result.unwrap();
-        remove_result.unwrap();
-        assert!(matches!(invalid_error, McprocdError::InvalidRegex { .. }));
-        assert!(registry_was_empty);
+        let waves = restart_waves(vec![
+            process("shop", "web", &["api"]),
+            process("shop", "api", &["db"]),
+            process("shop", "db", &[]),
+            process("blog", "api", &["db"]),
+            process("blog", "a", &["b"]),
+            process("blog", "b", &["a"]),
+        ])
+        .into_iter()
+        .map(|wave| {
+            wave.iter()
+                .map(|process| format!("{}/{}", process.project, process.name))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            waves,
+            vec![
+                vec!["shop/db", "blog/api"],
+                vec!["shop/api"],
+                vec!["shop/web"],
+                vec!["blog/a", "blog/b"],
+            ]
+        );
     }
 }
@@ -0,0 +1,152 @@
+//! Resource usage sampling of managed process trees
+//!
+//! The sync loop samples every running process and keeps a short history per
+//! process. CPU use is the CPU time the tree consumed since the previous
+//! sample, relative to the time in between.
+
+use crate::daemon::process::cgroup::Cgroup;
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Resource usage summed over the processes of a tree
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TreeUsage {
+    pub processes: u32,
+    /// CPU time consumed by the live processes, user and system
+    pub cpu_time: Duration,
+    pub rss_bytes: u64,
+    pub threads: u32,
+    pub open_fds: u32,
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+}
+
+/// One resource usage sample of a process tree
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetricsSample {
+    pub sampled_at: DateTime<Utc>,
+    pub processes: u32,
+    pub rss_bytes: u64,
+    /// CPU use since the previous sample, where 100 is one full core
+    pub cpu_percent: Option<f64>,
+    pub threads: u32,
+    pub open_fds: u32,
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+}
+
+/// Recent samples of one process run, oldest first
+#[derive(Debug, Default)]
+pub struct MetricsHistory {
+    samples: VecDeque<MetricsSample>,
+    /// CPU time and time of the previous sample
+    previous: Option<(Duration, Instant)>,
+}
+
+impl MetricsHistory {
+    /// Add a sample, dropping the oldest ones beyond `capacity`
+    pub fn record(&mut self, usage: TreeUsage, now: Instant, capacity: usize) {
+        let cpu_percent = self.previous.and_then(|(cpu_time, at)| {
+            let elapsed = now.checked_duration_since(at)?.as_secs_f64();
+            // Children that exit take their CPU time with them
+            let used = usage.cpu_time.saturating_sub(cpu_time).as_secs_f64();
+            (elapsed > 0.0).then(|| used / elapsed * 100.0)
+        });
+        self.previous = Some((usage.cpu_time, now));
+
+        self.samples.push_back(MetricsSample {
+            sampled_at: Utc::now(),
+            processes: usage.processes,
+            rss_bytes: usage.rss_bytes,
+            cpu_percent,
+            threads: usage.threads,
+            open_fds: usage.open_fds,
+            read_bytes: usage.read_bytes,
+            write_bytes: usage.write_bytes,
+        });
+        while self.samples.len() > capacity.max(1) {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn latest(&self) -> Option<&MetricsSample> {
+        self.samples.back()
+    }
+
+    pub fn samples(&self) -> impl Iterator<Item = &MetricsSample> {
+        self.samples.iter()
+    }
+}
+
+/// Resource usage of a process and its descendants, where the platform exposes it
+///
+/// The tree is the process's cgroup when it has one, so descendants that left
+/// the process tree are counted as well.
+pub fn sample_tree(pid: u32, cgroup: Option<&Cgroup>) -> Option<TreeUsage> {
+    #[cfg(target_os = "linux")]
+    {
+        use crate::daemon::process::procfs;
+
+        let pids = match cgroup {
+            Some(cgroup) => cgroup.pids(),
+            None => procfs::process_tree(pid),
+        };
+        let usage = procfs::tree_usage(&pids);
+        (usage.processes > 0).then_some(usage)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (pid, cgroup);
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(cpu_millis: u64, rss_bytes: u64) -> TreeUsage {
+        TreeUsage {
+            processes: 1,
+            cpu_time: Duration::from_millis(cpu_millis),
+            rss_bytes,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn history_computes_cpu_from_consecutive_samples_and_keeps_the_newest() {
+        let start = Instant::now();
+        let mut history = MetricsHistory::default();
+        history.record(usage(1_000, 10), start, 3);
+        assert_eq!(history.latest().unwrap().cpu_percent, None);
+
+        history.record(usage(2_500, 20), start + Duration::from_secs(1), 3);
+        assert_eq!(history.latest().unwrap().cpu_percent, Some(150.0));
+
+        // A child exiting lowers the tree's CPU time
+        history.record(usage(500, 30), start + Duration::from_secs(2), 3);
+        assert_eq!(history.latest().unwrap().cpu_percent, Some(0.0));
+
+        history.record(usage(1_000, 40), start + Duration::from_secs(3), 3);
+        assert_eq!(
+            history
+                .samples()
+                .map(|sample| sample.rss_bytes)
+                .collect::<Vec<_>>(),
+            vec![20, 30, 40]
+        );
+        assert_eq!(history.latest().unwrap().cpu_percent, Some(50.0));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn sampling_this_process_reports_memory_threads_and_descriptors() {
+        let usage = sample_tree(std::process::id(), None).unwrap();
+        assert!(usage.processes >= 1);
+        assert!(usage.rss_bytes > 0);
+        assert!(usage.threads >= 1);
+        assert!(usage.open_fds >= 3);
+    }
+}
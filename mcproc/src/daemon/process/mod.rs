@@ -1,14 +1,32 @@
+pub mod cgroup;
 pub mod event;
 pub mod exit_handler;
+pub mod file_watch;
+pub mod health;
+pub mod history;
 pub mod hyperlog;
 pub mod launcher;
+pub mod limits;
 pub mod log_stream;
 pub mod manager;
+pub mod metrics;
+pub mod persistence;
+pub mod port_allocator;
+pub mod port_conflict;
 pub mod port_detector;
+#[cfg(target_os = "linux")]
+pub mod procfs;
 pub mod proxy;
+pub mod pty;
+pub mod readiness;
 pub mod registry;
+pub mod restart;
+pub mod schedule;
+pub mod stop;
+pub mod task;
 pub mod toolchain;
 pub mod types;
+pub mod watchdog;
 
 pub use manager::ProcessManager;
 pub use proxy::ProcessStatus;
@@ -0,0 +1,376 @@
+use crate::common::schedule::Schedule;
+use crate::daemon::process::file_watch::FileWatch;
+use crate::daemon::process::health::HealthCheck;
+use crate::daemon::process::limits::ResourceLimits;
+use crate::daemon::process::port_allocator::AllocatedPort;
+use crate::daemon::process::proxy::{ProcessStatus, ProxyInfo};
+use crate::daemon::process::pty::PtySize;
+use crate::daemon::process::readiness::ReadinessProbe;
+use crate::daemon::process::restart::{RestartPolicy, RestartState};
+use crate::daemon::process::stop::StopPolicy;
+use crate::daemon::process::task::TaskOptions;
+use crate::daemon::process::types::ProxyInfoParams;
+use crate::daemon::process::watchdog::WatchdogRule;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, warn};
+
+/// File in the data directory holding the persisted registry
+const STATE_FILE_NAME: &str = "processes.json";
+
+/// Status reason of processes that were running when the daemon went away
+pub const DAEMON_RESTARTED: &str = "daemon restarted";
+
+/// Definition and last-known state of a process, as written to disk
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PersistedProcess {
+    pub id: String,
+    pub name: String,
+    pub project: String,
+    pub cmd: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    pub cwd: Option<PathBuf>,
+    pub env: Option<HashMap<String, String>>,
+    pub wait_for_log: Option<String>,
+    pub wait_timeout: Option<u32>,
+    pub toolchain: Option<String>,
+    #[serde(default)]
+    pub restart_policy: RestartPolicy,
+    #[serde(default)]
+    pub restart_count: u32,
+    pub last_restart_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    #[serde(default)]
+    pub readiness_probes: Vec<ReadinessProbe>,
+    pub health_check: Option<HealthCheck>,
+    #[serde(default)]
+    pub stop_policy: StopPolicy,
+    #[serde(default)]
+    pub stdin: bool,
+    #[serde(default)]
+    pub pty: Option<PtySize>,
+    #[serde(default)]
+    pub allocated_ports: Vec<AllocatedPort>,
+    #[serde(default)]
+    pub limits: ResourceLimits,
+    #[serde(default)]
+    pub watchdog: Vec<WatchdogRule>,
+    #[serde(default)]
+    pub watch: Option<FileWatch>,
+    #[serde(default)]
+    pub task: Option<TaskOptions>,
+    #[serde(default)]
+    pub schedule: Option<Schedule>,
+    #[serde(default)]
+    pub started_by: Option<String>,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub status: ProcessStatus,
+    pub status_reason: Option<String>,
+    pub pid: u32,
+    pub start_time: DateTime<Utc>,
+    pub exit_code: Option<i32>,
+    #[serde(default)]
+    pub exit_reason: Option<String>,
+    pub exit_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StateFile {
+    processes: Vec<PersistedProcess>,
+}
+
+impl PersistedProcess {
+    pub fn from_process(process: &ProxyInfo) -> Self {
+        Self {
+            id: process.id.clone(),
+            name: process.name.clone(),
+            project: process.project.clone(),
+            cmd: process.cmd.clone(),
+            args: process.args.clone(),
+            cwd: process.cwd.clone(),
+            env: process.env.clone(),
+            wait_for_log: process.wait_for_log.clone(),
+            wait_timeout: process.wait_timeout,
+            toolchain: process.toolchain.clone(),
+            restart_policy: process.restart_policy,
+            restart_count: process.restart_state.count,
+            last_restart_time: process.restart_state.last_restart_time,
+            depends_on: process.depends_on.clone(),
+            readiness_probes: process.readiness_probes.clone(),
+            health_check: process.health_check.clone(),
+            stop_policy: process.stop_policy.clone(),
+            stdin: process.stdin,
+            pty: process.pty,
+            allocated_ports: process.allocated_ports.clone(),
+            limits: process.limits,
+            watchdog: process.watchdog.clone(),
+            watch: process.watch.clone(),
+            task: process.task.clone(),
+            schedule: process.schedule.clone(),
+            started_by: process.started_by.clone(),
+            labels: process.labels.clone(),
+            description: process.description.clone(),
+            status: process.get_status(),
+            status_reason: process.status_reason.clone(),
+            pid: process.pid,
+            start_time: process.start_time,
+            exit_code: process.exit_code.lock().ok().and_then(|code| *code),
+            exit_reason: process
+                .exit_reason
+                .lock()
+                .ok()
+                .and_then(|reason| reason.clone()),
+            exit_time: process.exit_time.lock().ok().and_then(|time| *time),
+        }
+    }
+
+    /// Whether the process was alive when this state was written
+    pub fn was_active(&self) -> bool {
+        matches!(
+            self.status,
+            ProcessStatus::Starting
+                | ProcessStatus::Running
+                | ProcessStatus::Paused
+                | ProcessStatus::Stopping
+        )
+    }
+
+    /// Rebuild the registry entry of a process that no longer runs
+    ///
+    /// Processes that were still alive are reported as stopped because the
+    /// daemon restarted; the outcome of finished processes is kept as is.
+    pub fn into_process(self) -> ProxyInfo {
+        let was_active = self.was_active();
+        let mut process = ProxyInfo::new(ProxyInfoParams {
+            id: self.id,
+            name: self.name,
+            project: self.project,
+            cmd: self.cmd,
+            args: self.args,
+            cwd: self.cwd,
+            env: self.env,
+            wait_for_log: self.wait_for_log,
+            wait_timeout: self.wait_timeout,
+            toolchain: self.toolchain,
+            restart_policy: self.restart_policy,
+            restart_state: RestartState {
+                count: self.restart_count,
+                consecutive: 0,
+                last_restart_time: self.last_restart_time,
+            },
+            depends_on: self.depends_on,
+            readiness_probes: self.readiness_probes,
+            health_check: self.health_check,
+            stop_policy: self.stop_policy,
+            stdin: self.stdin,
+            pty: self.pty,
+            allocated_ports: self.allocated_ports,
+            limits: self.limits,
+            watchdog: self.watchdog,
+            watch: self.watch,
+            task: self.task,
+            schedule: self.schedule,
+            // The run history is not kept across daemon restarts
+            schedule_state: Default::default(),
+            started_by: self.started_by,
+            labels: self.labels,
+            description: self.description,
+            // Like the PID, the cgroup belonged to the previous daemon's run
+            cgroup: None,
+            pid: self.pid,
+        });
+        process.start_time = self.start_time;
+        process.restored = true;
+        if was_active {
+            process.status_reason = Some(DAEMON_RESTARTED.to_string());
+            process.set_status(ProcessStatus::Stopped);
+        } else {
+            process.status_reason = self.status_reason;
+            process.set_status(self.status);
+            if let Ok(mut exit_code) = process.exit_code.lock() {
+                *exit_code = self.exit_code;
+            }
+            if let Ok(mut exit_reason) = process.exit_reason.lock() {
+                *exit_reason = self.exit_reason;
+            }
+        }
+        if let Ok(mut exit_time) = process.exit_time.lock() {
+            *exit_time = self.exit_time;
+        }
+        process
+    }
+}
+
+/// Keeps the process registry in the data directory across daemon restarts
+pub struct ProcessStore {
+    path: PathBuf,
+    /// Serializes writers so concurrent saves cannot interleave
+    write_lock: Mutex<()>,
+    /// Set during shutdown so stopping processes does not overwrite the final snapshot
+    frozen: AtomicBool,
+}
+
+impl ProcessStore {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            path: data_dir.join(STATE_FILE_NAME),
+            write_lock: Mutex::new(()),
+            frozen: AtomicBool::new(false),
+        }
+    }
+
+    /// Read the persisted processes, or nothing when no usable state exists
+    pub fn load(&self) -> Vec<PersistedProcess> {
+        let contents = match std::fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
+            Err(e) => {
+                warn!("Failed to read process state {:?}: {}", self.path, e);
+                return Vec::new();
+            }
+        };
+        match serde_json::from_str::<StateFile>(&contents) {
+            Ok(state) => state.processes,
+            Err(e) => {
+                warn!("Ignoring unreadable process state {:?}: {}", self.path, e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Write a snapshot of the given processes, replacing the previous one
+    pub fn save(&self, processes: &[Arc<ProxyInfo>]) {
+        if self.frozen.load(Ordering::Relaxed) {
+            return;
+        }
+        self.write(processes);
+    }
+
+    /// Write a final snapshot and ignore later saves
+    pub fn save_final(&self, processes: &[Arc<ProxyInfo>]) {
+        self.frozen.store(true, Ordering::Relaxed);
+        self.write(processes);
+    }
+
+    fn write(&self, processes: &[Arc<ProxyInfo>]) {
+        let mut processes = processes
+            .iter()
+            .map(|process| PersistedProcess::from_process(process))
+            .collect::<Vec<_>>();
+        processes.sort_by(|a, b| (&a.project, &a.name).cmp(&(&b.project, &b.name)));
+
+        let Ok(_guard) = self.write_lock.lock() else {
+            return;
+        };
+        let result = serde_json::to_vec_pretty(&StateFile { processes })
+            .map_err(std::io::Error::other)
+            .and_then(|contents| {
+                // Write to a temporary file first so a crash never leaves a truncated state
+                let tmp_path = self.path.with_extension("json.tmp");
+                std::fs::write(&tmp_path, contents)?;
+                std::fs::rename(&tmp_path, &self.path)
+            });
+        match result {
+            Ok(()) => debug!("Saved process state to {:?}", self.path),
+            Err(e) => warn!("Failed to save process state {:?}: {}", self.path, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn running_process() -> Arc<ProxyInfo> {
+        let process = ProxyInfo::new(ProxyInfoParams {
+            id: "id-1".to_string(),
+            name: "api".to_string(),
+            project: "shop".to_string(),
+            args: vec!["cargo".to_string(), "run".to_string()],
+            cwd: Some(PathBuf::from("/work/shop")),
+            restart_policy: RestartPolicy::OnFailure,
+            restart_state: RestartState {
+                count: 2,
+                consecutive: 1,
+                last_restart_time: Some(Utc::now()),
+            },
+            depends_on: vec!["db".to_string()],
+            readiness_probes: vec![ReadinessProbe::Tcp {
+                host: "127.0.0.1".to_string(),
+                port: 8080,
+            }],
+            health_check: Some(HealthCheck {
+                kind: crate::daemon::process::health::HealthCheckKind::Command("true".to_string()),
+                interval: Duration::from_secs(10),
+                timeout: Duration::from_secs(5),
+                restart_after_failures: Some(3),
+            }),
+            allocated_ports: vec![("http".to_string(), 20000)],
+            pid: 4242,
+            ..Default::default()
+        });
+        process.set_status(ProcessStatus::Running);
+        Arc::new(process)
+    }
+
+    #[test]
+    fn saved_processes_are_restored_as_stopped_by_daemon_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ProcessStore::new(dir.path());
+        let process = running_process();
+        store.save(std::slice::from_ref(&process));
+
+        let loaded = store.load();
+        assert_eq!(loaded, vec![PersistedProcess::from_process(&process)]);
+        assert!(loaded[0].was_active());
+
+        let restored = loaded[0].clone().into_process();
+        assert_eq!(restored.id, process.id);
+        assert!(restored.restored);
+        assert_eq!(restored.start_time, process.start_time);
+        assert_eq!(restored.get_status(), ProcessStatus::Stopped);
+        assert_eq!(restored.status_reason.as_deref(), Some(DAEMON_RESTARTED));
+        assert_eq!(restored.restart_state.count, 2);
+        assert_eq!(restored.readiness_probes, process.readiness_probes);
+        assert_eq!(restored.health_check, process.health_check);
+    }
+
+    #[test]
+    fn finished_processes_keep_their_outcome() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ProcessStore::new(dir.path());
+        let process = running_process();
+        process.set_status(ProcessStatus::Failed);
+        *process.exit_code.lock().unwrap() = Some(3);
+        store.save(&[process]);
+
+        let restored = store.load().remove(0).into_process();
+        assert_eq!(restored.get_status(), ProcessStatus::Failed);
+        assert_eq!(*restored.exit_code.lock().unwrap(), Some(3));
+        assert_eq!(restored.status_reason, None);
+    }
+
+    #[test]
+    fn final_snapshot_is_not_overwritten() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ProcessStore::new(dir.path());
+        assert!(store.load().is_empty());
+
+        store.save_final(&[running_process()]);
+        store.save(&[]);
+        assert_eq!(store.load().len(), 1);
+
+        std::fs::write(dir.path().join(STATE_FILE_NAME), "not json").unwrap();
+        assert!(store.load().is_empty());
+    }
+}
@@ -0,0 +1,247 @@
+//! Allocation of free ports for processes that ask for them by name
+
+use crate::common::process_key::ProcessKey;
+use std::collections::{HashMap, HashSet};
+use std::net::{Ipv4Addr, Ipv6Addr, TcpListener};
+use std::ops::RangeInclusive;
+use std::sync::Mutex;
+
+/// A port allocated to a process under a name such as "http"
+pub type AllocatedPort = (String, u16);
+
+/// Environment variable that carries a named port, e.g. `MCPROC_PORT_HMR`
+pub fn port_env_var(name: &str) -> String {
+    let name = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    format!("MCPROC_PORT_{name}")
+}
+
+/// Environment variables injected for allocated ports
+///
+/// Every port is exported as `MCPROC_PORT_<NAME>` and the first one also as `PORT`.
+pub fn port_env(ports: &[AllocatedPort]) -> Vec<(String, String)> {
+    let mut env = Vec::with_capacity(ports.len() + 1);
+    if let Some((_, port)) = ports.first() {
+        env.push(("PORT".to_string(), port.to_string()));
+    }
+    for (name, port) in ports {
+        env.push((port_env_var(name), port.to_string()));
+    }
+    env
+}
+
+/// Whether nothing on this machine listens on the port
+fn is_port_free(port: u16) -> bool {
+    if TcpListener::bind((Ipv4Addr::UNSPECIFIED, port)).is_err() {
+        return false;
+    }
+    match TcpListener::bind((Ipv6Addr::UNSPECIFIED, port)) {
+        Ok(_) => true,
+        // Hosts without IPv6 cannot have anything listening there
+        Err(e) => e.kind() != std::io::ErrorKind::AddrInUse,
+    }
+}
+
+/// Hands out ports from a configured range
+///
+/// Ports stay claimed by a process name while it is registered, so a restart
+/// gets the same ports back when they are still free.
+pub struct PortAllocator {
+    range: RangeInclusive<u16>,
+    claims: Mutex<HashMap<ProcessKey, Vec<AllocatedPort>>>,
+}
+
+impl PortAllocator {
+    pub fn new(range: RangeInclusive<u16>) -> Self {
+        Self {
+            range,
+            claims: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record ports a process already holds, e.g. one restored from disk
+    pub fn claim(&self, key: &ProcessKey, ports: &[AllocatedPort]) {
+        if ports.is_empty() {
+            return;
+        }
+        if let Ok(mut claims) = self.claims.lock() {
+            claims.insert(key.clone(), ports.to_vec());
+        }
+    }
+
+    /// Allocate a free port for each name
+    ///
+    /// Claims of names that are no longer registered are dropped first.
+    /// `busy` holds ports other managed processes use without having
+    /// claimed them, such as ports they were detected listening on.
+    pub fn allocate(
+        &self,
+        key: &ProcessKey,
+        names: &[String],
+        is_registered: impl Fn(&ProcessKey) -> bool,
+        busy: &HashSet<u16>,
+    ) -> Result<Vec<AllocatedPort>, String> {
+        self.allocate_with(key, names, is_registered, busy, is_port_free)
+    }
+
+    fn allocate_with(
+        &self,
+        key: &ProcessKey,
+        names: &[String],
+        is_registered: impl Fn(&ProcessKey) -> bool,
+        busy: &HashSet<u16>,
+        is_free: impl Fn(u16) -> bool,
+    ) -> Result<Vec<AllocatedPort>, String> {
+        let mut claims = self
+            .claims
+            .lock()
+            .map_err(|_| "Port allocator lock poisoned".to_string())?;
+        claims.retain(|claimed_by, _| claimed_by == key || is_registered(claimed_by));
+        if names.is_empty() {
+            claims.remove(key);
+            return Ok(Vec::new());
+        }
+
+        let previous = claims.remove(key).unwrap_or_default();
+        let mut taken = claims
+            .values()
+            .flatten()
+            .map(|(_, port)| *port)
+            .chain(busy.iter().copied())
+            .collect::<HashSet<_>>();
+
+        let mut allocated = Vec::with_capacity(names.len());
+        for name in names {
+            let reused = previous
+                .iter()
+                .find(|(previous_name, port)| {
+                    previous_name == name && !taken.contains(port) && is_free(*port)
+                })
+                .map(|(_, port)| *port);
+            let port = match reused {
+                Some(port) => port,
+                None => self
+                    .range
+                    .clone()
+                    .find(|port| !taken.contains(port) && is_free(*port))
+                    .ok_or_else(|| {
+                        format!(
+                            "No free port left in {}-{} for '{}'",
+                            self.range.start(),
+                            self.range.end(),
+                            name
+                        )
+                    })?,
+            };
+            taken.insert(port);
+            allocated.push((name.clone(), port));
+        }
+
+        claims.insert(key.clone(), allocated.clone());
+        Ok(allocated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn ports_are_exported_as_port_and_named_variables() {
+        let ports = vec![("http".to_string(), 20000), ("hmr-ws".to_string(), 20001)];
+        assert_eq!(
+            port_env(&ports),
+            vec![
+                ("PORT".to_string(), "20000".to_string()),
+                ("MCPROC_PORT_HTTP".to_string(), "20000".to_string()),
+                ("MCPROC_PORT_HMR_WS".to_string(), "20001".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn allocations_avoid_claimed_busy_and_listening_ports() {
+        let allocator = PortAllocator::new(20000..=20005);
+        let web = ProcessKey::new("shop", "web");
+        let api = ProcessKey::new("shop", "api");
+        let registered = |_: &ProcessKey| true;
+        let listening = |port: u16| port != 20001;
+
+        let web_ports = allocator
+            .allocate_with(
+                &web,
+                &names(&["http", "hmr"]),
+                registered,
+                &HashSet::new(),
+                listening,
+            )
+            .unwrap();
+        assert_eq!(
+            web_ports,
+            vec![("http".to_string(), 20000), ("hmr".to_string(), 20002)]
+        );
+
+        let busy = HashSet::from([20003]);
+        let api_ports = allocator
+            .allocate_with(&api, &names(&["http"]), registered, &busy, listening)
+            .unwrap();
+        assert_eq!(api_ports, vec![("http".to_string(), 20004)]);
+
+        // A restart gets its ports back
+        let again = allocator
+            .allocate_with(
+                &web,
+                &names(&["http", "hmr"]),
+                registered,
+                &HashSet::new(),
+                listening,
+            )
+            .unwrap();
+        assert_eq!(again, web_ports);
+
+        assert!(allocator
+            .allocate_with(
+                &ProcessKey::new("shop", "worker"),
+                &names(&["a", "b"]),
+                registered,
+                &busy,
+                listening
+            )
+            .unwrap_err()
+            .contains("No free port"));
+    }
+
+    #[test]
+    fn claims_of_unregistered_processes_are_released() {
+        let allocator = PortAllocator::new(20000..=20000);
+        let web = ProcessKey::new("shop", "web");
+        let api = ProcessKey::new("shop", "api");
+        allocator.claim(&web, &[("http".to_string(), 20000)]);
+
+        assert!(allocator
+            .allocate_with(&api, &names(&["http"]), |_| true, &HashSet::new(), |_| true)
+            .is_err());
+        let ports = allocator
+            .allocate_with(
+                &api,
+                &names(&["http"]),
+                |key| key == &api,
+                &HashSet::new(),
+                |_| true,
+            )
+            .unwrap();
+        assert_eq!(ports, vec![("http".to_string(), 20000)]);
+    }
+}
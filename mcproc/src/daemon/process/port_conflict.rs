@@ -0,0 +1,163 @@
+//! Diagnosis of processes that fail because their port is already taken
+
+use crate::daemon::process::port_detector::PortHolder;
+use crate::daemon::process::proxy::ProxyInfo;
+use regex::Regex;
+use std::sync::{Arc, LazyLock};
+
+static ADDRESS_IN_USE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)address already in use|EADDRINUSE|port \d+ is already in use")
+        .expect("valid regex")
+});
+
+/// A port number as printed in bind errors: after the word `port`, or after a host or
+/// address such as `localhost:3000`, `127.0.0.1:3000`, `[::]:3000`, `:::3000` or `*:3000`
+static PORT_IN_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\bport\s+(\d{1,5})\b|(?:\[[0-9a-f:.]*\]|::|\*|\b\d{1,3}(?:\.\d{1,3}){3}|\b[a-z][\w.-]*):(\d{1,5})\b",
+    )
+    .expect("valid regex")
+});
+
+/// A time of day such as `12:34:56` or `12:34:56.789`, which is no address
+static TIME_OF_DAY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b\d{1,2}:\d{2}:\d{2}(?:[.,]\d+)?\b").expect("valid regex"));
+
+/// A process listening on a port, and the managed process it belongs to
+#[derive(Clone)]
+pub struct PortOwner {
+    pub holder: PortHolder,
+    pub process: Option<Arc<ProxyInfo>>,
+}
+
+/// The port a failed process could not bind, if its output says so
+///
+/// Uses the port named next to the error when there is one, preferring one
+/// of the candidates, such as the process's PORT, when several are named.
+/// Otherwise falls back to the first candidate. Lines may carry the log
+/// file's `TIMESTAMP [ERROR] ` prefix or timestamps printed by the process.
+pub fn conflicting_port(output: &str, candidates: &[u16]) -> Option<u16> {
+    let line = output
+        .lines()
+        .rev()
+        .find(|line| ADDRESS_IN_USE.is_match(line))?;
+    let line = line
+        .split_once(" [ERROR] ")
+        .map_or(line, |(_, message)| message);
+    let line = TIME_OF_DAY.replace_all(line, " ");
+    let ports = PORT_IN_LINE
+        .captures_iter(&line)
+        .filter_map(|captures| captures.get(1).or_else(|| captures.get(2)))
+        .filter_map(|port| port.as_str().parse::<u16>().ok())
+        .filter(|port| *port > 0)
+        .collect::<Vec<_>>();
+    ports
+        .iter()
+        .rev()
+        .find(|port| candidates.contains(port))
+        .or_else(|| ports.last())
+        .copied()
+        .or_else(|| candidates.first().copied())
+}
+
+/// Describe who holds a port, e.g. "port 3000 is held by PID 42 (python3 -m http.server 3000)"
+pub fn describe_owners(port: u16, owners: &[PortOwner]) -> String {
+    let mut holders = Vec::<String>::new();
+    for owner in owners {
+        let holder = match (&owner.process, owner.holder.pid) {
+            (Some(process), _) => format!(
+                "mcproc process {}/{} (PID {})",
+                process.project, process.name, process.pid
+            ),
+            (None, Some(pid)) => match &owner.holder.command {
+                Some(command) => format!("PID {pid} ({command})"),
+                None => format!("PID {pid}"),
+            },
+            (None, None) => "a process mcproc cannot inspect".to_string(),
+        };
+        if !holders.contains(&holder) {
+            holders.push(holder);
+        }
+    }
+
+    if holders.is_empty() {
+        return format!("port {port} is no longer in use");
+    }
+    let mut description = format!("port {port} is held by {}", holders.join(", "));
+    if owners.iter().any(|owner| owner.process.is_some()) {
+        description.push_str(&format!(
+            "; stop it with `mcproc port {port} --kill-holder`"
+        ));
+    }
+    description
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::daemon::process::port_detector::{Listener, Protocol};
+    use crate::daemon::process::types::ProxyInfoParams;
+
+    #[test]
+    fn conflicting_ports_are_read_from_common_bind_errors() {
+        let node = "Server starting\nError: listen EADDRINUSE: address already in use :::3000\n    at Server.setupListenHandle";
+        assert_eq!(conflicting_port(node, &[]), Some(3000));
+        let go = "listen tcp 127.0.0.1:8080: bind: address already in use";
+        assert_eq!(conflicting_port(go, &[]), Some(8080));
+        let vite = "Error: Port 5173 is already in use";
+        assert_eq!(conflicting_port(vite, &[]), Some(5173));
+        let python = "OSError: [Errno 98] Address already in use";
+        assert_eq!(conflicting_port(python, &[4000]), Some(4000));
+        assert_eq!(conflicting_port(python, &[]), None);
+        let logged = format!("2026-01-02 12:34:56.789 +00:00 [ERROR] {python}");
+        assert_eq!(conflicting_port(&logged, &[4000]), Some(4000));
+        assert_eq!(conflicting_port("connection refused :3000", &[4000]), None);
+        let bracketed = "listen tcp [::]:3000: bind: address already in use";
+        assert_eq!(conflicting_port(bracketed, &[]), Some(3000));
+    }
+
+    #[test]
+    fn timestamps_are_not_mistaken_for_ports() {
+        let timestamped = "12:34:56 Error: address already in use";
+        assert_eq!(conflicting_port(timestamped, &[4000]), Some(4000));
+        let iso =
+            "2026-01-02T12:34:56.789Z listen tcp 127.0.0.1:8080: bind: address already in use";
+        assert_eq!(conflicting_port(iso, &[4000]), Some(8080));
+        let proxied = "proxy localhost:3000 -> localhost:9229: address already in use";
+        assert_eq!(conflicting_port(proxied, &[3000]), Some(3000));
+        assert_eq!(conflicting_port(proxied, &[]), Some(9229));
+    }
+
+    #[test]
+    fn owners_are_described_by_managed_name_or_command_line() {
+        let listener = Listener {
+            protocol: Protocol::Tcp,
+            address: "0.0.0.0".parse().unwrap(),
+            port: 3000,
+        };
+        let managed = Arc::new(ProxyInfo::new(ProxyInfoParams {
+            name: "web".to_string(),
+            project: "shop".to_string(),
+            pid: 42,
+            ..Default::default()
+        }));
+        let owner = |pid, command: Option<&str>, process: Option<Arc<ProxyInfo>>| PortOwner {
+            holder: PortHolder {
+                listener,
+                pid,
+                command: command.map(str::to_string),
+            },
+            process,
+        };
+
+        assert_eq!(
+            describe_owners(3000, &[owner(Some(42), Some("node"), Some(managed.clone())), owner(Some(42), Some("node"), Some(managed))]),
+            "port 3000 is held by mcproc process shop/web (PID 42); stop it with `mcproc port 3000 --kill-holder`"
+        );
+        assert_eq!(
+            describe_owners(3000, &[owner(Some(7), Some("python3 -m http.server 3000"), None), owner(None, None, None)]),
+            "port 3000 is held by PID 7 (python3 -m http.server 3000), a process mcproc cannot inspect"
+        );
+        assert_eq!(describe_owners(3000, &[]), "port 3000 is no longer in use");
+    }
+}
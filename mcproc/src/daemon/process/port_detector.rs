@@ -1,13 +1,172 @@
+use crate::daemon::process::cgroup::Cgroup;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
 use tokio::process::Command;
 use tracing::{debug, warn};
 
-/// Detect listening ports for a given PID using lsof
-pub async fn detect_ports(pid: u32) -> Vec<u32> {
+/// Transport protocol of a listening socket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Protocol {
+    Tcp,
+    Udp,
+}
+
+impl Protocol {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Protocol::Tcp => "tcp",
+            Protocol::Udp => "udp",
+        }
+    }
+}
+
+/// A socket a process listens on: a TCP listener or an unconnected UDP socket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Listener {
+    pub protocol: Protocol,
+    pub address: IpAddr,
+    pub port: u16,
+}
+
+/// A process holding a listening socket on a port
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortHolder {
+    pub listener: Listener,
+    /// None when the socket belongs to a process that cannot be inspected
+    pub pid: Option<u32>,
+    pub command: Option<String>,
+}
+
+/// Find the processes listening on a port anywhere on the machine
+#[cfg(target_os = "linux")]
+pub async fn find_port_holders(port: u16) -> Vec<PortHolder> {
+    use crate::daemon::process::procfs;
+
+    tokio::task::spawn_blocking(move || procfs::port_holders(port))
+        .await
+        .unwrap_or_default()
+}
+
+/// Find the processes listening on a TCP port anywhere on the machine, using lsof
+#[cfg(not(target_os = "linux"))]
+pub async fn find_port_holders(port: u16) -> Vec<PortHolder> {
+    let output = match Command::new("lsof")
+        .args(["-Pan", &format!("-iTCP:{port}"), "-sTCP:LISTEN"])
+        .output()
+        .await
+    {
+        Ok(output) if output.status.success() => output,
+        Ok(_) => return Vec::new(),
+        Err(e) => {
+            debug!("Failed to run lsof for port {}: {}", port, e);
+            return Vec::new();
+        }
+    };
+
+    let mut holders = Vec::new();
+    // Format: COMMAND PID USER FD TYPE DEVICE SIZE/OFF NODE NAME
+    for line in String::from_utf8_lossy(&output.stdout).lines().skip(1) {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 9 {
+            continue;
+        }
+        let (Ok(pid), Some(listener)) = (parts[1].parse::<u32>(), parse_lsof_name(parts[8])) else {
+            continue;
+        };
+        let command = Command::new("ps")
+            .args(["-o", "command=", "-p", &pid.to_string()])
+            .output()
+            .await
+            .ok()
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|command| !command.is_empty());
+        holders.push(PortHolder {
+            listener,
+            pid: Some(pid),
+            command,
+        });
+    }
+    holders
+}
+
+/// Distinct TCP ports among the listeners, in detection order
+pub fn tcp_ports(listeners: &[Listener]) -> Vec<u32> {
+    let mut ports = Vec::new();
+    for listener in listeners {
+        let port = u32::from(listener.port);
+        if listener.protocol == Protocol::Tcp && !ports.contains(&port) {
+            ports.push(port);
+        }
+    }
+    ports
+}
+
+/// Detect the sockets a managed process listens on
+///
+/// With a cgroup every process in it counts, including descendants that left
+/// the process tree by double-forking; otherwise the process tree is walked.
+pub async fn detect_process_listeners(pid: u32, cgroup: Option<&Cgroup>) -> Vec<Listener> {
+    match cgroup {
+        Some(cgroup) => detect_cgroup_listeners(cgroup.clone()).await,
+        None => detect_listeners(pid).await,
+    }
+}
+
+/// Detect the sockets held by the processes in a cgroup
+#[cfg(target_os = "linux")]
+async fn detect_cgroup_listeners(cgroup: Cgroup) -> Vec<Listener> {
+    use crate::daemon::process::procfs;
+
+    tokio::task::spawn_blocking(move || procfs::listeners(&cgroup.pids()))
+        .await
+        .unwrap_or_default()
+}
+
+/// Detect the TCP sockets held by the processes in a cgroup, using lsof
+#[cfg(not(target_os = "linux"))]
+async fn detect_cgroup_listeners(cgroup: Cgroup) -> Vec<Listener> {
+    lsof_listeners(cgroup.pids()).await
+}
+
+/// Detect the sockets a given PID and its descendants listen on
+#[cfg(target_os = "linux")]
+pub async fn detect_listeners(pid: u32) -> Vec<Listener> {
+    use crate::daemon::process::procfs;
+
+    let listeners = tokio::task::spawn_blocking(move || {
+        let pids = procfs::process_tree(pid);
+        debug!("Process tree for PID {}: {:?}", pid, pids);
+        procfs::listeners(&pids)
+    })
+    .await
+    .unwrap_or_default();
+    debug!(
+        "Detected listeners for PID {} and children: {:?}",
+        pid, listeners
+    );
+    listeners
+}
+
+/// Detect the TCP sockets a given PID and its descendants listen on, using lsof
+#[cfg(not(target_os = "linux"))]
+pub async fn detect_listeners(pid: u32) -> Vec<Listener> {
     // First, get all child PIDs
     let all_pids = get_process_tree(pid).await;
     debug!("Process tree for PID {}: {:?}", pid, all_pids);
 
-    let mut all_ports = Vec::new();
+    let listeners = lsof_listeners(all_pids).await;
+    debug!(
+        "Detected listeners for PID {} and children: {:?}",
+        pid, listeners
+    );
+    listeners
+}
+
+/// Listening TCP sockets of the given processes, using lsof
+#[cfg(not(target_os = "linux"))]
+async fn lsof_listeners(all_pids: Vec<u32>) -> Vec<Listener> {
+    let mut listeners = Vec::new();
 
     // Check ports for each PID in the process tree
     for check_pid in all_pids {
@@ -47,25 +206,19 @@ pub async fn detect_ports(pid: u32) -> Vec<u32> {
             }
 
             // NAME field contains address:port
-            let name = parts[8];
-            if let Some(port_str) = extract_port(name) {
-                if let Ok(port) = port_str.parse::<u32>() {
-                    if !all_ports.contains(&port) {
-                        all_ports.push(port);
-                    }
+            if let Some(listener) = parse_lsof_name(parts[8]) {
+                if !listeners.contains(&listener) {
+                    listeners.push(listener);
                 }
             }
         }
     }
 
-    debug!(
-        "Detected ports for PID {} and children: {:?}",
-        pid, all_ports
-    );
-    all_ports
+    listeners
 }
 
 /// Get process tree - parent PID and all its children
+#[cfg(not(target_os = "linux"))]
 async fn get_process_tree(pid: u32) -> Vec<u32> {
     let mut pids = vec![pid];
     let mut next_index = 0;
@@ -96,6 +249,22 @@ async fn get_process_tree(pid: u32) -> Vec<u32> {
     pids
 }
 
+/// Parse an lsof NAME field such as `*:3000` or `[::1]:8080` into a TCP listener
+#[cfg_attr(target_os = "linux", allow(dead_code))]
+fn parse_lsof_name(name: &str) -> Option<Listener> {
+    let port = extract_port(name)?.parse().ok()?;
+    let host = name.rsplit_once(':')?.0;
+    let address = match host.trim_start_matches('[').trim_end_matches(']') {
+        "*" => IpAddr::from([0, 0, 0, 0]),
+        host => host.parse().ok()?,
+    };
+    Some(Listener {
+        protocol: Protocol::Tcp,
+        address,
+        port,
+    })
+}
+
 /// Extract port from lsof NAME field
 /// Examples:
 /// - *:3000 -> 3000
@@ -170,6 +339,34 @@ pub async fn detect_ports_netstat(pid: u32) -> Vec<u32> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn lsof_names_parse_into_tcp_listeners() {
+        let listener = |address: &str, port| Listener {
+            protocol: Protocol::Tcp,
+            address: address.parse().unwrap(),
+            port,
+        };
+        assert_eq!(parse_lsof_name("*:3000"), Some(listener("0.0.0.0", 3000)));
+        assert_eq!(
+            parse_lsof_name("127.0.0.1:8080"),
+            Some(listener("127.0.0.1", 8080))
+        );
+        assert_eq!(parse_lsof_name("[::1]:8080"), Some(listener("::1", 8080)));
+        assert_eq!(parse_lsof_name("localhost"), None);
+        assert_eq!(
+            tcp_ports(&[
+                listener("0.0.0.0", 3000),
+                listener("::", 3000),
+                Listener {
+                    protocol: Protocol::Udp,
+                    ..listener("0.0.0.0", 5353)
+                },
+                listener("127.0.0.1", 3001),
+            ]),
+            vec![3000, 3001]
+        );
+    }
+
     #[test]
     fn test_extract_port() {
         assert_eq!(extract_port("*:3000"), Some("3000"));
@@ -180,7 +377,21 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn detect_ports_is_awaitable() {
-        let _ = detect_ports(u32::MAX).await;
+    async fn detecting_listeners_of_a_missing_process_finds_none() {
+        assert!(detect_listeners(u32::MAX).await.is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn udp_listeners_are_detected_with_their_address() {
+        let udp = std::net::UdpSocket::bind(("127.0.0.1", 0)).unwrap();
+        let expected = Listener {
+            protocol: Protocol::Udp,
+            address: "127.0.0.1".parse().unwrap(),
+            port: udp.local_addr().unwrap().port(),
+        };
+        let listeners = detect_listeners(std::process::id()).await;
+        assert!(listeners.contains(&expected), "{listeners:?}");
+        assert!(!tcp_ports(&listeners).contains(&u32::from(expected.port)));
     }
 }
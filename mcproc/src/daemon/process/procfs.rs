@@ -0,0 +1,417 @@
+//! Process tree and socket inspection through Linux's /proc
+
+use crate::daemon::process::metrics::TreeUsage;
+use crate::daemon::process::port_detector::{Listener, PortHolder, Protocol};
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::path::Path;
+use std::time::Duration;
+
+/// TCP state of a listening socket in /proc/net/tcp
+const TCP_LISTEN: &str = "0A";
+/// State of an unconnected UDP socket in /proc/net/udp
+const UDP_UNCONNECTED: &str = "07";
+
+/// The process and all of its descendants, parents before children
+pub fn process_tree(pid: u32) -> Vec<u32> {
+    let mut pids = vec![pid];
+    let mut next_index = 0;
+    while next_index < pids.len() {
+        let parent = pids[next_index];
+        next_index += 1;
+        for child in children(parent) {
+            if !pids.contains(&child) {
+                pids.push(child);
+            }
+        }
+    }
+    pids
+}
+
+/// Direct children of a process
+///
+/// Reads the per-thread `children` lists, falling back to scanning every
+/// process's parent when the kernel does not provide them.
+fn children(pid: u32) -> Vec<u32> {
+    let Ok(tasks) = std::fs::read_dir(format!("/proc/{pid}/task")) else {
+        return Vec::new();
+    };
+    let mut found_list = false;
+    let mut children = Vec::new();
+    for task in tasks.flatten() {
+        let Ok(list) = std::fs::read_to_string(task.path().join("children")) else {
+            continue;
+        };
+        found_list = true;
+        children.extend(
+            list.split_whitespace()
+                .filter_map(|pid| pid.parse::<u32>().ok()),
+        );
+    }
+    if found_list {
+        children
+    } else {
+        children_from_stat(pid)
+    }
+}
+
+fn children_from_stat(pid: u32) -> Vec<u32> {
+    all_pids()
+        .into_iter()
+        .filter(|candidate| parent_pid(*candidate) == Some(pid))
+        .collect()
+}
+
+/// Parent PID from /proc/<pid>/stat
+fn parent_pid(pid: u32) -> Option<u32> {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // The command name is in parentheses and may itself contain spaces or ')'
+    let after_name = &stat[stat.rfind(')')? + 1..];
+    after_name.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// Resource usage summed over the processes, from their stat, status, io and fd entries
+///
+/// Processes that exit while being read are left out. I/O counters of
+/// processes owned by other users cannot be read and count as zero.
+pub fn tree_usage(pids: &[u32]) -> TreeUsage {
+    // SAFETY: sysconf only reads a system constant
+    let ticks_per_sec = unsafe { libc::sysconf(libc::_SC_CLK_TCK) }.max(1) as u64;
+    let mut usage = TreeUsage::default();
+    for pid in pids {
+        let Ok(stat) = std::fs::read_to_string(format!("/proc/{pid}/stat")) else {
+            continue;
+        };
+        // Fields after the command name start at field 3 (state)
+        let Some(fields) = stat
+            .rfind(')')
+            .map(|end| stat[end + 1..].split_whitespace().collect::<Vec<_>>())
+        else {
+            continue;
+        };
+        let field = |number: usize| {
+            fields
+                .get(number - 3)
+                .and_then(|value| value.parse::<u64>().ok())
+                .unwrap_or(0)
+        };
+        let ticks = field(14) + field(15);
+
+        usage.processes += 1;
+        usage.cpu_time += Duration::from_millis(ticks * 1000 / ticks_per_sec);
+        usage.threads += field(20) as u32;
+        if let Ok(status) = std::fs::read_to_string(format!("/proc/{pid}/status")) {
+            // Reported in kB
+            usage.rss_bytes += status_value(&status, "VmRSS:") * 1024;
+        }
+        if let Ok(io) = std::fs::read_to_string(format!("/proc/{pid}/io")) {
+            usage.read_bytes += status_value(&io, "read_bytes:");
+            usage.write_bytes += status_value(&io, "write_bytes:");
+        }
+        if let Ok(fds) = std::fs::read_dir(format!("/proc/{pid}/fd")) {
+            usage.open_fds += fds.count() as u32;
+        }
+    }
+    usage
+}
+
+/// First number on the line starting with `key` in a /proc key-value file
+fn status_value(contents: &str, key: &str) -> u64 {
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix(key))
+        .and_then(|value| value.split_whitespace().next()?.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Inodes of the sockets a process has open
+pub fn socket_inodes(pid: u32) -> HashSet<u64> {
+    let Ok(fds) = std::fs::read_dir(format!("/proc/{pid}/fd")) else {
+        return HashSet::new();
+    };
+    fds.flatten()
+        .filter_map(|fd| std::fs::read_link(fd.path()).ok())
+        .filter_map(|target| {
+            target
+                .to_str()?
+                .strip_prefix("socket:[")?
+                .strip_suffix(']')?
+                .parse()
+                .ok()
+        })
+        .collect()
+}
+
+/// Listening sockets held by any of the processes
+///
+/// Socket tables are read from the first process's network namespace.
+pub fn listeners(pids: &[u32]) -> Vec<Listener> {
+    let Some(first) = pids.first() else {
+        return Vec::new();
+    };
+    let inodes = pids
+        .iter()
+        .flat_map(|pid| socket_inodes(*pid))
+        .collect::<HashSet<_>>();
+    if inodes.is_empty() {
+        return Vec::new();
+    }
+
+    let mut listeners = Vec::new();
+    for (listener, inode) in socket_table(&Path::new("/proc").join(first.to_string()).join("net")) {
+        if inodes.contains(&inode) && !listeners.contains(&listener) {
+            listeners.push(listener);
+        }
+    }
+    listeners
+}
+
+/// Sockets listening on the port in the daemon's network namespace, with their processes
+///
+/// Sockets of processes whose descriptors cannot be read, such as those of
+/// other users, are reported without a PID.
+pub fn port_holders(port: u16) -> Vec<PortHolder> {
+    let sockets = socket_table(Path::new("/proc/self/net"))
+        .into_iter()
+        .filter(|(listener, _)| listener.port == port)
+        .collect::<Vec<_>>();
+    if sockets.is_empty() {
+        return Vec::new();
+    }
+
+    let wanted = sockets
+        .iter()
+        .map(|(_, inode)| *inode)
+        .collect::<HashSet<_>>();
+    let mut pids_by_inode = HashMap::<u64, Vec<u32>>::new();
+    for pid in all_pids() {
+        for inode in socket_inodes(pid) {
+            if wanted.contains(&inode) {
+                pids_by_inode.entry(inode).or_default().push(pid);
+            }
+        }
+    }
+
+    sockets
+        .into_iter()
+        .flat_map(|(listener, inode)| match pids_by_inode.remove(&inode) {
+            Some(pids) => pids
+                .into_iter()
+                .map(|pid| PortHolder {
+                    listener,
+                    pid: Some(pid),
+                    command: command_line(pid),
+                })
+                .collect(),
+            None => vec![PortHolder {
+                listener,
+                pid: None,
+                command: None,
+            }],
+        })
+        .collect()
+}
+
+fn all_pids() -> Vec<u32> {
+    std::fs::read_dir("/proc")
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Command line of a process, with arguments separated by spaces
+pub fn command_line(pid: u32) -> Option<String> {
+    let cmdline = std::fs::read(format!("/proc/{pid}/cmdline")).ok()?;
+    let command = cmdline
+        .split(|byte| *byte == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(String::from_utf8_lossy)
+        .collect::<Vec<_>>()
+        .join(" ");
+    if command.is_empty() {
+        // Kernel threads and zombies have no command line
+        let comm = std::fs::read_to_string(format!("/proc/{pid}/comm")).ok()?;
+        Some(format!("[{}]", comm.trim()))
+    } else {
+        Some(command)
+    }
+}
+
+/// Every listening TCP and unconnected UDP socket in a net directory, with its inode
+pub fn socket_table(net_dir: &Path) -> Vec<(Listener, u64)> {
+    [
+        ("tcp", Protocol::Tcp),
+        ("tcp6", Protocol::Tcp),
+        ("udp", Protocol::Udp),
+        ("udp6", Protocol::Udp),
+    ]
+    .into_iter()
+    .filter_map(|(file, protocol)| {
+        let contents = std::fs::read_to_string(net_dir.join(file)).ok()?;
+        Some(parse_socket_table(&contents, protocol))
+    })
+    .flatten()
+    .collect()
+}
+
+/// Parse a /proc/net/{tcp,tcp6,udp,udp6} table into listeners and their inodes
+fn parse_socket_table(contents: &str, protocol: Protocol) -> Vec<(Listener, u64)> {
+    let listening_state = match protocol {
+        Protocol::Tcp => TCP_LISTEN,
+        Protocol::Udp => UDP_UNCONNECTED,
+    };
+    contents
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            // sl local_address rem_address st tx_queue:rx_queue tr:tm->when retrnsmt uid timeout inode
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            if fields.len() < 10 || fields[3] != listening_state {
+                return None;
+            }
+            let (address, port) = fields[1].split_once(':')?;
+            let listener = Listener {
+                protocol,
+                address: parse_address(address)?,
+                port: u16::from_str_radix(port, 16).ok()?,
+            };
+            let inode = fields[9].parse::<u64>().ok().filter(|inode| *inode != 0)?;
+            Some((listener, inode))
+        })
+        .collect()
+}
+
+/// Parse an address as the kernel prints it: 32-bit words in host byte order
+fn parse_address(hex: &str) -> Option<IpAddr> {
+    let mut bytes = Vec::with_capacity(16);
+    for word in hex.as_bytes().chunks(8) {
+        let word = u32::from_str_radix(std::str::from_utf8(word).ok()?, 16).ok()?;
+        bytes.extend_from_slice(&word.to_ne_bytes());
+    }
+    match bytes.len() {
+        4 => Some(IpAddr::V4(Ipv4Addr::new(
+            bytes[0], bytes[1], bytes[2], bytes[3],
+        ))),
+        16 => {
+            let ip = Ipv6Addr::from(<[u8; 16]>::try_from(bytes).ok()?);
+            // Show IPv4-mapped addresses of dual-stack sockets in their usual form
+            Some(ip.to_ipv4_mapped().map_or(IpAddr::V6(ip), IpAddr::V4))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{TcpListener, UdpSocket};
+
+    #[test]
+    fn socket_tables_are_parsed_into_listeners() {
+        let loopback = u32::from_ne_bytes([127, 0, 0, 1]);
+        let tcp = format!(
+            "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n\
+             \x20  0: {loopback:08X}:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 4242 1 0 100 0 0 10 0\n\
+             \x20  1: {loopback:08X}:9C40 0100007F:1F90 01 00000000:00000000 00:00000000 00000000  1000        0 4343 1 0 20 4 30 10 -1\n"
+        );
+        assert_eq!(
+            parse_socket_table(&tcp, Protocol::Tcp),
+            vec![(
+                Listener {
+                    protocol: Protocol::Tcp,
+                    address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+                    port: 8080
+                },
+                4242
+            )]
+        );
+
+        let udp6 = "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops\n\
+             \x20  0: 00000000000000000000000000000000:14E9 00000000000000000000000000000000:0000 07 00000000:00000000 00:00000000 00000000  1000        0 5151 2 0 0\n";
+        assert_eq!(
+            parse_socket_table(udp6, Protocol::Udp),
+            vec![(
+                Listener {
+                    protocol: Protocol::Udp,
+                    address: IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+                    port: 5353
+                },
+                5151
+            )]
+        );
+    }
+
+    #[test]
+    fn listeners_of_this_process_include_tcp_and_udp_sockets() {
+        let tcp = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let udp = UdpSocket::bind(("0.0.0.0", 0)).unwrap();
+        let found = listeners(&[std::process::id()]);
+
+        assert!(found.contains(&Listener {
+            protocol: Protocol::Tcp,
+            address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            port: tcp.local_addr().unwrap().port(),
+        }));
+        assert!(found.contains(&Listener {
+            protocol: Protocol::Udp,
+            address: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            port: udp.local_addr().unwrap().port(),
+        }));
+    }
+
+    #[test]
+    fn port_holders_name_the_process_and_its_command_line() {
+        let tcp = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = tcp.local_addr().unwrap().port();
+        let holders = port_holders(port);
+
+        assert_eq!(holders.len(), 1, "{holders:?}");
+        assert_eq!(holders[0].pid, Some(std::process::id()));
+        assert_eq!(holders[0].listener.protocol, Protocol::Tcp);
+        let command = holders[0].command.as_deref().unwrap();
+        let exe = std::env::current_exe().unwrap();
+        let exe_name = exe.file_name().unwrap().to_str().unwrap();
+        assert!(command.contains(exe_name), "{command}");
+
+        drop(tcp);
+        assert!(port_holders(port).is_empty());
+    }
+
+    #[test]
+    fn status_values_are_read_by_key() {
+        let status = "Name:\tnode\nVmRSS:\t  123456 kB\nThreads:\t11\n";
+        assert_eq!(status_value(status, "VmRSS:"), 123456);
+        assert_eq!(status_value(status, "VmSwap:"), 0);
+        let io = "rchar: 10\nread_bytes: 4096\nwrite_bytes: 0\n";
+        assert_eq!(status_value(io, "read_bytes:"), 4096);
+    }
+
+    #[tokio::test]
+    async fn process_tree_follows_children() {
+        let mut child = tokio::process::Command::new("sh")
+            .args(["-c", "sleep 5 & sleep 5 & wait"])
+            .spawn()
+            .unwrap();
+        let pid = child.id().unwrap();
+        let mut tree = Vec::new();
+        for _ in 0..50 {
+            tree = process_tree(pid);
+            if tree.len() >= 3 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert_eq!(tree[0], pid);
+        assert_eq!(tree.len(), 3);
+        assert!(tree[1..]
+            .iter()
+            .all(|child| parent_pid(*child) == Some(pid)));
+        assert_eq!(children_from_stat(pid).len(), 2);
+
+        child.kill().await.unwrap();
+    }
+}
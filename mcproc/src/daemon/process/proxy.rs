@@ -1,11 +1,31 @@
 use crate::common::process_key::ProcessKey;
+use crate::common::schedule::Schedule;
+use crate::daemon::process::cgroup::Cgroup;
+use crate::daemon::process::file_watch::FileWatch;
+use crate::daemon::process::health::{HealthCheck, HealthState};
+use crate::daemon::process::limits::ResourceLimits;
+use crate::daemon::process::metrics::MetricsHistory;
+use crate::daemon::process::port_allocator::AllocatedPort;
+use crate::daemon::process::port_detector::{self, Listener};
+use crate::daemon::process::pty::PtySize;
+use crate::daemon::process::readiness::ReadinessProbe;
+use crate::daemon::process::restart::{RestartPolicy, RestartState};
+use crate::daemon::process::schedule::ScheduleState;
+use crate::daemon::process::stop::StopPolicy;
+use crate::daemon::process::task::TaskOptions;
+use crate::daemon::process::watchdog::{WatchdogRule, WatchdogState};
+use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicU8, Ordering};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::broadcast;
 use tokio::task::JoinHandle;
-use tracing::info;
+use tracing::{debug, info};
 
 #[cfg(unix)]
 use nix::{
@@ -13,6 +33,9 @@ use nix::{
     unistd::Pid,
 };
 
+/// Output chunks buffered for an attached client that falls behind
+const OUTPUT_CHANNEL_CAPACITY: usize = 256;
+
 /// Process lifecycle states
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[repr(u8)]
@@ -27,6 +50,14 @@ pub enum ProcessStatus {
     Stopped = 4,
     /// Process exited with error
     Failed = 5,
+    /// Automatic restarts were abandoned after repeated quick exits
+    CrashLoop = 6,
+    /// Suspended by a stop signal such as SIGSTOP until it receives SIGCONT
+    Paused = 7,
+    /// Task exited with one of its success exit codes
+    Succeeded = 8,
+    /// Scheduled process waiting for its first run
+    Scheduled = 9,
 }
 
 impl From<u8> for ProcessStatus {
@@ -37,6 +68,10 @@ impl From<u8> for ProcessStatus {
             3 => ProcessStatus::Stopping,
             4 => ProcessStatus::Stopped,
             5 => ProcessStatus::Failed,
+            6 => ProcessStatus::CrashLoop,
+            7 => ProcessStatus::Paused,
+            8 => ProcessStatus::Succeeded,
+            9 => ProcessStatus::Scheduled,
             _ => ProcessStatus::Stopped,
         }
     }
@@ -51,6 +86,10 @@ impl From<ProcessStatus> for proto::ProcessStatus {
             ProcessStatus::Stopping => proto::ProcessStatus::Stopping,
             ProcessStatus::Stopped => proto::ProcessStatus::Stopped,
             ProcessStatus::Failed => proto::ProcessStatus::Failed,
+            ProcessStatus::CrashLoop => proto::ProcessStatus::CrashLoop,
+            ProcessStatus::Paused => proto::ProcessStatus::Paused,
+            ProcessStatus::Succeeded => proto::ProcessStatus::Succeeded,
+            ProcessStatus::Scheduled => proto::ProcessStatus::Scheduled,
         }
     }
 }
@@ -83,22 +122,88 @@ pub struct ProxyInfo {
     pub wait_timeout: Option<u32>,
     /// Version management tool (mise, asdf, nvm, etc.)
     pub toolchain: Option<String>,
+    /// Automatic restart policy
+    pub restart_policy: RestartPolicy,
+    /// Automatic restart bookkeeping inherited from previous runs
+    pub restart_state: RestartState,
+    /// Processes in the same project that must be ready before this one starts
+    pub depends_on: Vec<String>,
+    /// Probes that decide readiness alongside wait_for_log
+    pub readiness_probes: Vec<ReadinessProbe>,
+    /// Liveness check run periodically while the process is running
+    pub health_check: Option<HealthCheck>,
+    /// How the process is asked to stop
+    pub stop_policy: StopPolicy,
+    /// Whether the process was started with a stdin pipe
+    pub stdin: bool,
+    /// Size of the pseudo-terminal the process runs on, if any
+    pub pty: Option<PtySize>,
+    /// Ports the daemon allocated for the process, in request order
+    pub allocated_ports: Vec<AllocatedPort>,
+    /// Memory, CPU, process and open file limits
+    pub limits: ResourceLimits,
+    /// Rules checked against the resource usage samples
+    pub watchdog: Vec<WatchdogRule>,
+    /// Files whose changes restart the process
+    pub watch: Option<FileWatch>,
+    /// Set for one-shot tasks, which are judged by their exit code
+    pub task: Option<TaskOptions>,
+    /// Schedule the command runs on, for scheduled processes
+    pub schedule: Option<Schedule>,
+    /// Next run and run history, shared by the registry entries of all runs of a schedule
+    pub schedule_state: Arc<Mutex<ScheduleState>>,
+    /// Who started this run, e.g. "cli" or "restart policy"
+    pub started_by: Option<String>,
+    /// Free-form labels that selectors match
+    pub labels: BTreeMap<String, String>,
+    /// Note on why the process runs, e.g. "started to reproduce issue 42"
+    pub description: Option<String>,
+    /// cgroup holding the process and every descendant, when cgroups are available
+    pub cgroup: Option<Cgroup>,
+    /// Write end of the stdin pipe, or the terminal, while the process runs
+    pub stdin_pipe: tokio::sync::Mutex<Option<Box<dyn AsyncWrite + Send + Unpin>>>,
+    /// Raw output chunks for attached clients
+    pub output: broadcast::Sender<Bytes>,
     /// Process start time
     pub start_time: DateTime<Utc>,
     /// Current process status (atomic for thread-safe updates)
     pub status: Arc<AtomicU8>,
+    /// Why the process has its status when that is not evident, e.g. "daemon restarted"
+    pub status_reason: Option<String>,
+    /// Restored from the state of a previous daemon, so there is no child to signal
+    pub restored: bool,
     /// Process ID
     pub pid: u32,
     /// Configured port (if any)
     pub port: Option<u16>,
     /// Detected port from process output
     pub detected_port: Arc<Mutex<Option<u16>>>,
+    /// Sockets the process tree was last seen listening on
+    pub listeners: Mutex<Vec<Listener>>,
     /// Exit code when process terminates
     pub exit_code: Arc<Mutex<Option<i32>>>,
+    /// Why the process exited when the exit code does not tell, e.g. an OOM kill
+    pub exit_reason: Mutex<Option<String>>,
     /// Time when process exited
     pub exit_time: Arc<Mutex<Option<DateTime<Utc>>>>,
     /// Hyperlog task handles for cleanup
     pub hyperlog_handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
+    /// Set once a stop is requested so the exit is not treated as a crash
+    pub stop_requested: AtomicBool,
+    /// Set when the daemon stops a task for exceeding its timeout
+    pub timed_out: AtomicBool,
+    /// Set once the process is running and its wait_for_log pattern (if any) matched
+    pub ready: AtomicBool,
+    /// Readiness check that succeeded (wait_for_log or a probe)
+    pub ready_by: Mutex<Option<String>>,
+    /// Results of the liveness checks for this run
+    pub health: Mutex<HealthState>,
+    /// Recent resource usage samples of the process tree
+    pub metrics: Mutex<MetricsHistory>,
+    /// When the process last wrote output, or started if it has not yet
+    pub last_output: Arc<Mutex<std::time::Instant>>,
+    /// Which watchdog rules hold and fired during this run
+    pub watchdog_state: Mutex<WatchdogState>,
 }
 
 impl ProxyInfo {
@@ -116,14 +221,47 @@ impl ProxyInfo {
             wait_for_log: params.wait_for_log,
             wait_timeout: params.wait_timeout,
             toolchain: params.toolchain,
+            restart_policy: params.restart_policy,
+            restart_state: params.restart_state,
+            depends_on: params.depends_on,
+            readiness_probes: params.readiness_probes,
+            health_check: params.health_check,
+            stop_policy: params.stop_policy,
+            stdin: params.stdin,
+            pty: params.pty,
+            allocated_ports: params.allocated_ports,
+            limits: params.limits,
+            watchdog: params.watchdog,
+            watch: params.watch,
+            task: params.task,
+            schedule: params.schedule,
+            schedule_state: params.schedule_state,
+            started_by: params.started_by,
+            labels: params.labels,
+            description: params.description,
+            cgroup: params.cgroup,
+            stdin_pipe: tokio::sync::Mutex::new(None),
+            output: broadcast::channel(OUTPUT_CHANNEL_CAPACITY).0,
             start_time: Utc::now(),
             status: Arc::new(AtomicU8::new(ProcessStatus::Starting as u8)),
+            status_reason: None,
+            restored: false,
             pid: params.pid,
             port: None,
             detected_port: Arc::new(Mutex::new(None)),
+            listeners: Mutex::new(Vec::new()),
             exit_code: Arc::new(Mutex::new(None)),
+            exit_reason: Mutex::new(None),
             exit_time: Arc::new(Mutex::new(None)),
             hyperlog_handles: Arc::new(Mutex::new(Vec::new())),
+            stop_requested: AtomicBool::new(false),
+            timed_out: AtomicBool::new(false),
+            ready: AtomicBool::new(false),
+            ready_by: Mutex::new(None),
+            health: Mutex::new(HealthState::default()),
+            metrics: Mutex::new(MetricsHistory::default()),
+            last_output: Arc::new(Mutex::new(std::time::Instant::now())),
+            watchdog_state: Mutex::new(WatchdogState::default()),
         }
     }
 
@@ -135,39 +273,151 @@ impl ProxyInfo {
         self.status.store(status as u8, Ordering::Relaxed);
     }
 
+    /// Status after exiting with this code; `None` means terminated by a signal
+    pub fn exit_status(&self, exit_code: Option<i32>) -> ProcessStatus {
+        match (&self.task, exit_code) {
+            (Some(task), _) => task.exit_status(exit_code),
+            (None, Some(0)) => ProcessStatus::Stopped,
+            (None, _) => ProcessStatus::Failed,
+        }
+    }
+
+    pub fn is_stop_requested(&self) -> bool {
+        self.stop_requested.load(Ordering::Relaxed)
+    }
+
+    pub fn is_timed_out(&self) -> bool {
+        self.timed_out.load(Ordering::Relaxed)
+    }
+
+    pub fn mark_timed_out(&self) {
+        self.timed_out.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Relaxed)
+    }
+
+    pub fn mark_ready(&self) {
+        self.ready.store(true, Ordering::Relaxed);
+    }
+
     #[allow(dead_code)]
     pub fn get_key(&self) -> &ProcessKey {
         &self.key
     }
 
+    /// Whether input can be sent to the process, through a pipe or its terminal
+    pub fn accepts_input(&self) -> bool {
+        self.stdin || self.pty.is_some()
+    }
+
+    /// Write input to the process's stdin pipe
+    pub async fn write_input(&self, input: &[u8]) -> Result<(), String> {
+        let mut pipe = self.stdin_pipe.lock().await;
+        let Some(stdin) = pipe.as_mut() else {
+            return Err("stdin is not open".to_string());
+        };
+        let result = async {
+            stdin.write_all(input).await?;
+            stdin.flush().await
+        }
+        .await;
+        if let Err(e) = result {
+            // The process closed its end, so later writes would fail as well
+            *pipe = None;
+            return Err(format!("Failed to write to stdin: {}", e));
+        }
+        Ok(())
+    }
+
+    /// Run a shell command in the process's working directory and environment
+    pub async fn run_shell_command(&self, cmd: &str) -> Result<(), String> {
+        let mut command = tokio::process::Command::new("sh");
+        command
+            .arg("-c")
+            .arg(cmd)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true);
+        if let Some(cwd) = &self.cwd {
+            command.current_dir(cwd);
+        }
+        if let Some(env) = &self.env {
+            command.envs(env);
+        }
+
+        let status = command
+            .status()
+            .await
+            .map_err(|e| format!("command `{cmd}` could not be run: {e}"))?;
+        match status.code() {
+            Some(0) => Ok(()),
+            Some(code) => Err(format!("command `{cmd}` exited with code {code}")),
+            None => Err(format!("command `{cmd}` was terminated by a signal")),
+        }
+    }
+
     pub async fn stop(&self, force: bool, process_stop_timeout_ms: u64) -> Result<(), String> {
         info!(
             "Stopping process {} (PID: {}, force: {})",
             self.name, self.pid, force
         );
+        self.stop_requested.store(true, Ordering::Relaxed);
+        // A restored process has no child, and its PID may since have been reused;
+        // a scheduled process waiting for its first run has none yet
+        if self.restored || self.pid == 0 {
+            return Ok(());
+        }
+        let was_paused = self.get_status() == ProcessStatus::Paused;
         self.set_status(ProcessStatus::Stopping);
 
+        // A suspended group cannot handle the stop request until it is resumed
+        #[cfg(unix)]
+        if was_paused && !force {
+            if let Err(e) = Self::send_signal_to_group(self.pid, Signal::SIGCONT) {
+                info!("Failed to resume paused PGID {}: {}", self.pid, e);
+            }
+        }
+
         // NOTE: We do NOT cancel tasks here. Cancelling the monitor task
         // would drop the Child object, closing stdout/stderr pipes and causing
         // group members to receive SIGPIPE instead of our group signal.
 
-        // First attempt: Send SIGTERM (unless force is specified)
+        // First attempt: run the stop command or send the stop signal (unless force is specified)
         if !force {
-            info!(
-                "Sending SIGTERM to process group {} (PGID: {})",
-                self.name, self.pid
-            );
+            let timeout = self.stop_policy.timeout(process_stop_timeout_ms);
+            let start = tokio::time::Instant::now();
+
+            let mut stopped_by_command = false;
+            if let Some(command) = &self.stop_policy.command {
+                info!("Running stop command for {}: {}", self.name, command);
+                match tokio::time::timeout(timeout, self.run_shell_command(command)).await {
+                    Ok(Ok(())) => stopped_by_command = true,
+                    Ok(Err(e)) => info!("Stop command for {} failed: {}", self.name, e),
+                    Err(_) => info!("Stop command for {} timed out", self.name),
+                }
+            }
 
             #[cfg(unix)]
-            {
-                match Self::send_signal_to_group(self.pid, Signal::SIGTERM) {
+            if !stopped_by_command {
+                let signal = self.stop_policy.signal();
+                info!(
+                    "Sending {} to process group {} (PGID: {})",
+                    signal, self.name, self.pid
+                );
+                if let Some(cgroup) = &self.cgroup {
+                    cgroup.signal_outside_group(self.pid, signal);
+                }
+                match Self::send_signal_to_group(self.pid, signal) {
                     Ok(()) => {
-                        info!("SIGTERM sent successfully to PGID {}", self.pid);
+                        info!("{} sent successfully to PGID {}", signal, self.pid);
                     }
                     Err(e) => {
-                        info!("Failed to send SIGTERM to PGID {}: {}", self.pid, e);
-                        // If the process group doesn't exist, mark as stopped
-                        if !Self::is_process_group_alive(self.pid) {
+                        info!("Failed to send {} to PGID {}: {}", signal, self.pid, e);
+                        // If nothing of the process is left, mark as stopped
+                        if !self.is_alive() {
                             self.set_status(ProcessStatus::Stopped);
                             if let Ok(mut exit_time) = self.exit_time.lock() {
                                 *exit_time = Some(Utc::now());
@@ -179,14 +429,11 @@ impl ProxyInfo {
             }
 
             // Wait for graceful shutdown
-            let timeout = tokio::time::Duration::from_millis(process_stop_timeout_ms);
-            let start = tokio::time::Instant::now();
-
             #[cfg(unix)]
             {
                 while start.elapsed() < timeout {
-                    // The group is stopped only after every member has exited.
-                    if !Self::is_process_group_alive(self.pid) {
+                    // The process is stopped only after every member has exited.
+                    if !self.is_alive() {
                         info!("Process group {} has stopped", self.name);
                         self.set_status(ProcessStatus::Stopped);
                         if let Ok(mut exit_time) = self.exit_time.lock() {
@@ -219,6 +466,12 @@ impl ProxyInfo {
                     }
                 }
             }
+            // Descendants that left the process group are still in the cgroup
+            if let Some(cgroup) = &self.cgroup {
+                if let Err(e) = cgroup.kill() {
+                    info!("Failed to kill cgroup {}: {}", cgroup.path().display(), e);
+                }
+            }
 
             self.confirm_stopped_after_force_kill().await?;
         }
@@ -249,12 +502,12 @@ impl ProxyInfo {
     async fn confirm_stopped_within(&self, timeout: tokio::time::Duration) -> Result<(), String> {
         let start = tokio::time::Instant::now();
         while start.elapsed() < timeout {
-            if !Self::is_process_group_alive(self.pid) {
+            if !self.is_alive() {
                 return Ok(());
             }
             // killpg(pgid, 0) keeps succeeding while unreaped zombies remain in
             // the group, so only report failure if a non-zombie member survives.
-            if !Self::group_has_live_non_zombie_member(self.pid).await {
+            if self.cgroup.is_none() && !Self::group_has_live_non_zombie_member(self.pid).await {
                 return Ok(());
             }
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
@@ -293,6 +546,14 @@ impl ProxyInfo {
         }
     }
 
+    /// Record the sockets the process listens on, and its first TCP port
+    pub fn update_listeners(&self, listeners: Vec<Listener>) {
+        self.update_detected_port(&port_detector::tcp_ports(&listeners));
+        if let Ok(mut current) = self.listeners.lock() {
+            *current = listeners;
+        }
+    }
+
     /// Send a signal to every process in the managed process group.
     #[cfg(unix)]
     fn send_signal_to_group(pgid: u32, sig: Signal) -> Result<(), String> {
@@ -306,6 +567,27 @@ impl ProxyInfo {
         }
     }
 
+    /// Send a signal to the whole process group, or to the group leader only
+    #[cfg(unix)]
+    pub fn send_signal(&self, sig: Signal, group: bool) -> Result<(), String> {
+        if group {
+            Self::send_signal_to_group(self.pid, sig)
+        } else {
+            nix::sys::signal::kill(Pid::from_raw(self.pid as i32), sig)
+                .map_err(|e| format!("Failed to send process signal: {}", e))
+        }
+    }
+
+    /// Check whether any process of this run remains: in its cgroup when it
+    /// has one, which also covers descendants that left the process group
+    #[cfg(unix)]
+    pub fn is_alive(&self) -> bool {
+        match &self.cgroup {
+            Some(cgroup) => cgroup.is_populated(),
+            None => Self::is_process_group_alive(self.pid),
+        }
+    }
+
     /// Check whether any process remains in the managed process group.
     #[cfg(unix)]
     fn is_process_group_alive(pgid: u32) -> bool {
@@ -317,6 +599,17 @@ impl ProxyInfo {
     }
 }
 
+impl Drop for ProxyInfo {
+    fn drop(&mut self) {
+        // Only an empty cgroup can be removed; a populated one is left in place
+        if let Some(cgroup) = &self.cgroup {
+            if let Err(e) = cgroup.remove() {
+                debug!("Keeping cgroup {}: {}", cgroup.path().display(), e);
+            }
+        }
+    }
+}
+
 #[cfg(all(test, unix))]
 mod tests {
     use super::*;
@@ -328,14 +621,8 @@ mod tests {
             id: Uuid::new_v4().to_string(),
             name: "stop-test".into(),
             project: "test".into(),
-            cmd: None,
-            args: Vec::new(),
-            cwd: None,
-            env: None,
-            wait_for_log: None,
-            wait_timeout: None,
-            toolchain: None,
             pid,
+            ..Default::default()
         })
     }
 
@@ -350,6 +637,25 @@ mod tests {
         assert_eq!(*proxy.detected_port.lock().unwrap(), None);
     }
 
+    #[test]
+    fn update_listeners_keeps_udp_sockets_but_detects_tcp_port() {
+        let proxy = proxy_for_pid(1234);
+        let dns = Listener {
+            protocol: port_detector::Protocol::Udp,
+            address: "0.0.0.0".parse().unwrap(),
+            port: 5353,
+        };
+        let http = Listener {
+            protocol: port_detector::Protocol::Tcp,
+            address: "::".parse().unwrap(),
+            port: 3000,
+        };
+
+        proxy.update_listeners(vec![dns, http]);
+        assert_eq!(*proxy.detected_port.lock().unwrap(), Some(3000));
+        assert_eq!(*proxy.listeners.lock().unwrap(), vec![dns, http]);
+    }
+
     #[tokio::test]
     async fn force_stop_errors_when_process_survives_sigkill() {
         let mut child = tokio::process::Command::new("sleep")
@@ -0,0 +1,196 @@
+//! Pseudo-terminal support for processes that need a TTY
+
+use nix::pty::{openpty, Winsize};
+use nix::sys::termios::{tcgetattr, tcsetattr, OutputFlags, SetArg};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::os::fd::{AsRawFd, OwnedFd};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use tokio::io::unix::AsyncFd;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+pub const DEFAULT_COLS: u16 = 80;
+pub const DEFAULT_ROWS: u16 = 24;
+
+/// Window size of a process's pseudo-terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PtySize {
+    pub cols: u16,
+    pub rows: u16,
+}
+
+impl Default for PtySize {
+    fn default() -> Self {
+        Self {
+            cols: DEFAULT_COLS,
+            rows: DEFAULT_ROWS,
+        }
+    }
+}
+
+impl PtySize {
+    /// Build a size from request fields, falling back to the defaults
+    pub fn from_request(cols: Option<u32>, rows: Option<u32>) -> Result<Self, String> {
+        let dimension = |value: Option<u32>, default: u16, field: &str| match value {
+            None => Ok(default),
+            Some(value) => u16::try_from(value)
+                .ok()
+                .filter(|value| *value > 0)
+                .ok_or_else(|| format!("{field} must be between 1 and {}", u16::MAX)),
+        };
+        Ok(Self {
+            cols: dimension(cols, DEFAULT_COLS, "pty_cols")?,
+            rows: dimension(rows, DEFAULT_ROWS, "pty_rows")?,
+        })
+    }
+}
+
+/// Open a pseudo-terminal pair, returning the master and the slave for the child
+pub fn open(size: PtySize) -> io::Result<(PtyMaster, OwnedFd)> {
+    let winsize = Winsize {
+        ws_row: size.rows,
+        ws_col: size.cols,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    let pty = openpty(&winsize, None)?;
+
+    // Keep "\n" line endings so the log matches what the process printed
+    let mut termios = tcgetattr(&pty.slave)?;
+    termios.output_flags.remove(OutputFlags::ONLCR);
+    tcsetattr(&pty.slave, SetArg::TCSANOW, &termios)?;
+
+    Ok((PtyMaster::new(pty.master)?, pty.slave))
+}
+
+/// Non-blocking master side of a pseudo-terminal
+pub struct PtyMaster {
+    fd: AsyncFd<OwnedFd>,
+}
+
+impl PtyMaster {
+    fn new(fd: OwnedFd) -> io::Result<Self> {
+        let raw = fd.as_raw_fd();
+        // SAFETY: fcntl on a descriptor we own
+        let flags = unsafe { libc::fcntl(raw, libc::F_GETFL) };
+        if flags == -1 || unsafe { libc::fcntl(raw, libc::F_SETFL, flags | libc::O_NONBLOCK) } == -1
+        {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self {
+            fd: AsyncFd::new(fd)?,
+        })
+    }
+
+    /// Duplicate the master so output can be read while input is written
+    pub fn try_clone(&self) -> io::Result<Self> {
+        Self::new(self.fd.get_ref().try_clone()?)
+    }
+}
+
+impl AsyncRead for PtyMaster {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        loop {
+            let mut guard = ready!(self.fd.poll_read_ready(cx))?;
+            let unfilled = buf.initialize_unfilled();
+            let result = guard.try_io(|fd| {
+                // SAFETY: reading into an initialized buffer of the given length
+                let n = unsafe {
+                    libc::read(fd.as_raw_fd(), unfilled.as_mut_ptr().cast(), unfilled.len())
+                };
+                if n < 0 {
+                    Err(io::Error::last_os_error())
+                } else {
+                    Ok(n as usize)
+                }
+            });
+            match result {
+                Ok(Ok(n)) => {
+                    buf.advance(n);
+                    return Poll::Ready(Ok(()));
+                }
+                // Linux reports EIO once every slave descriptor is closed
+                Ok(Err(e)) if e.raw_os_error() == Some(libc::EIO) => return Poll::Ready(Ok(())),
+                Ok(Err(e)) => return Poll::Ready(Err(e)),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+}
+
+impl AsyncWrite for PtyMaster {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        loop {
+            let mut guard = ready!(self.fd.poll_write_ready(cx))?;
+            let result = guard.try_io(|fd| {
+                // SAFETY: writing from a buffer of the given length
+                let n = unsafe { libc::write(fd.as_raw_fd(), buf.as_ptr().cast(), buf.len()) };
+                if n < 0 {
+                    Err(io::Error::last_os_error())
+                } else {
+                    Ok(n as usize)
+                }
+            });
+            match result {
+                Ok(result) => return Poll::Ready(result),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn pty_size_defaults_and_validates_request_fields() {
+        assert_eq!(PtySize::from_request(None, None), Ok(PtySize::default()));
+        assert_eq!(
+            PtySize::from_request(Some(132), Some(50)),
+            Ok(PtySize {
+                cols: 132,
+                rows: 50
+            })
+        );
+        assert!(PtySize::from_request(Some(0), None).is_err());
+        assert!(PtySize::from_request(None, Some(70_000)).is_err());
+    }
+
+    #[tokio::test]
+    async fn master_and_slave_exchange_data_until_the_slave_closes() {
+        let (mut master, slave) = open(PtySize::default()).unwrap();
+        let mut slave = std::fs::File::from(slave);
+
+        let mut writer = master.try_clone().unwrap();
+        writer.write_all(b"ping\n").await.unwrap();
+        let mut line = [0u8; 5];
+        std::io::Read::read_exact(&mut slave, &mut line).unwrap();
+        assert_eq!(&line, b"ping\n");
+
+        std::io::Write::write_all(&mut slave, b"hello\n").unwrap();
+        drop(slave);
+        let mut output = Vec::new();
+        master.read_to_end(&mut output).await.unwrap();
+        // The terminal echoes the input before the process output
+        assert_eq!(output, b"ping\nhello\n");
+    }
+}
@@ -0,0 +1,298 @@
+use crate::daemon::process::port_detector;
+use crate::daemon::process::proxy::{ProcessStatus, ProxyInfo};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tracing::debug;
+
+/// Delay between two rounds of readiness probes
+const PROBE_INTERVAL: Duration = Duration::from_millis(250);
+/// Upper bound for a single probe attempt
+const PROBE_ATTEMPT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Check polled while a process starts to decide when it is ready
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReadinessProbe {
+    /// A TCP connection to the address succeeds
+    Tcp { host: String, port: u16 },
+    /// An HTTP GET returns a status within `min_status..=max_status`
+    Http {
+        url: String,
+        min_status: u16,
+        max_status: u16,
+    },
+    /// The process or one of its children listens on any TCP port
+    PortDetected,
+}
+
+impl fmt::Display for ReadinessProbe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadinessProbe::Tcp { host, port } => write!(f, "tcp {host}:{port}"),
+            ReadinessProbe::Http {
+                url,
+                min_status,
+                max_status,
+            } => write!(f, "http {url} ({min_status}-{max_status})"),
+            ReadinessProbe::PortDetected => write!(f, "port detected"),
+        }
+    }
+}
+
+impl TryFrom<proto::ReadinessProbe> for ReadinessProbe {
+    type Error = String;
+
+    fn try_from(probe: proto::ReadinessProbe) -> Result<Self, Self::Error> {
+        match probe.probe {
+            Some(proto::readiness_probe::Probe::Tcp(tcp)) => Ok(ReadinessProbe::Tcp {
+                host: tcp
+                    .host
+                    .filter(|host| !host.is_empty())
+                    .unwrap_or_else(|| "127.0.0.1".to_string()),
+                port: u16::try_from(tcp.port)
+                    .ok()
+                    .filter(|port| *port != 0)
+                    .ok_or_else(|| format!("Invalid TCP probe port: {}", tcp.port))?,
+            }),
+            Some(proto::readiness_probe::Probe::Http(http)) => {
+                parse_http_url(&http.url)?;
+                let status = |value: Option<u32>, default: u16| {
+                    value.map_or(Ok(default), |value| {
+                        u16::try_from(value)
+                            .ok()
+                            .filter(|status| (100..=599).contains(status))
+                            .ok_or_else(|| format!("Invalid HTTP probe status: {value}"))
+                    })
+                };
+                let min_status = status(http.min_status, 200)?;
+                let max_status = status(http.max_status, 399)?;
+                if min_status > max_status {
+                    return Err(format!(
+                        "Invalid HTTP probe status range: {min_status}-{max_status}"
+                    ));
+                }
+                Ok(ReadinessProbe::Http {
+                    url: http.url,
+                    min_status,
+                    max_status,
+                })
+            }
+            Some(proto::readiness_probe::Probe::PortDetected(_)) => {
+                Ok(ReadinessProbe::PortDetected)
+            }
+            None => Err("Readiness probe must specify tcp, http or port_detected".to_string()),
+        }
+    }
+}
+
+impl From<&ReadinessProbe> for proto::ReadinessProbe {
+    fn from(probe: &ReadinessProbe) -> Self {
+        let probe = match probe {
+            ReadinessProbe::Tcp { host, port } => {
+                proto::readiness_probe::Probe::Tcp(proto::TcpProbe {
+                    host: Some(host.clone()),
+                    port: u32::from(*port),
+                })
+            }
+            ReadinessProbe::Http {
+                url,
+                min_status,
+                max_status,
+            } => proto::readiness_probe::Probe::Http(proto::HttpProbe {
+                url: url.clone(),
+                min_status: Some(u32::from(*min_status)),
+                max_status: Some(u32::from(*max_status)),
+            }),
+            ReadinessProbe::PortDetected => {
+                proto::readiness_probe::Probe::PortDetected(proto::PortDetectedProbe {})
+            }
+        };
+        proto::ReadinessProbe { probe: Some(probe) }
+    }
+}
+
+impl ReadinessProbe {
+    /// Run the probe once, returning a description of the success
+    pub(super) async fn check(&self, process: &ProxyInfo) -> Option<String> {
+        match self {
+            ReadinessProbe::Tcp { host, port } => TcpStream::connect((host.as_str(), *port))
+                .await
+                .ok()
+                .map(|_| self.to_string()),
+            ReadinessProbe::Http {
+                url,
+                min_status,
+                max_status,
+            } => {
+                let status = http_get_status(url).await?;
+                (*min_status..=*max_status)
+                    .contains(&status)
+                    .then(|| format!("http {url} ({status})"))
+            }
+            // The cgroup also covers listeners that left the process tree
+            ReadinessProbe::PortDetected => port_detector::tcp_ports(
+                &port_detector::detect_process_listeners(process.pid, process.cgroup.as_ref())
+                    .await,
+            )
+            .first()
+            .map(|port| format!("port {port} detected")),
+        }
+    }
+}
+
+/// Poll the probes until one succeeds, returning its description
+///
+/// Returns None as soon as the process is no longer starting or running.
+pub async fn wait_for_probes(probes: &[ReadinessProbe], process: &ProxyInfo) -> Option<String> {
+    loop {
+        if !matches!(
+            process.get_status(),
+            ProcessStatus::Starting | ProcessStatus::Running
+        ) {
+            return None;
+        }
+        for probe in probes {
+            if let Ok(Some(ready_by)) =
+                tokio::time::timeout(PROBE_ATTEMPT_TIMEOUT, probe.check(process)).await
+            {
+                debug!(
+                    "Readiness probe succeeded for {}: {}",
+                    process.name, ready_by
+                );
+                return Some(ready_by);
+            }
+        }
+        tokio::time::sleep(PROBE_INTERVAL).await;
+    }
+}
+
+/// Split an http:// URL into host, port and request path
+fn parse_http_url(url: &str) -> Result<(String, u16, String), String> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| format!("HTTP probe URL must start with http://: {url}"))?;
+    let (authority, path) = match rest.find('/') {
+        Some(index) => (&rest[..index], &rest[index..]),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) if !port.contains(']') => (
+            host,
+            port.parse::<u16>()
+                .map_err(|_| format!("Invalid port in HTTP probe URL: {url}"))?,
+        ),
+        _ => (authority, 80),
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if host.is_empty() {
+        return Err(format!("Missing host in HTTP probe URL: {url}"));
+    }
+    Ok((host.to_string(), port, path.to_string()))
+}
+
+/// Perform a minimal HTTP/1.1 GET and return the response status code
+async fn http_get_status(url: &str) -> Option<u16> {
+    let (host, port, path) = parse_http_url(url).ok()?;
+    let mut stream = TcpStream::connect((host.as_str(), port)).await.ok()?;
+    let request = format!(
+        "GET {path} HTTP/1.1\r\nHost: {host}\r\nUser-Agent: mcproc\r\nConnection: close\r\n\r\n"
+    );
+    stream.write_all(request.as_bytes()).await.ok()?;
+
+    let mut response = Vec::new();
+    let mut buf = [0u8; 512];
+    while !response.contains(&b'\n') {
+        let read = stream.read(&mut buf).await.ok()?;
+        if read == 0 {
+            break;
+        }
+        response.extend_from_slice(&buf[..read]);
+    }
+    let status_line = String::from_utf8_lossy(&response);
+    status_line.split_whitespace().nth(1)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn http_urls_are_split_into_host_port_and_path() {
+        assert_eq!(
+            parse_http_url("http://localhost:3000/health?full=1").unwrap(),
+            ("localhost".to_string(), 3000, "/health?full=1".to_string())
+        );
+        assert_eq!(
+            parse_http_url("http://[::1]").unwrap(),
+            ("::1".to_string(), 80, "/".to_string())
+        );
+        assert!(parse_http_url("https://localhost/").is_err());
+        assert!(parse_http_url("http://localhost:http/").is_err());
+    }
+
+    #[test]
+    fn proto_probes_are_validated_and_defaulted() {
+        let http = ReadinessProbe::try_from(proto::ReadinessProbe {
+            probe: Some(proto::readiness_probe::Probe::Http(proto::HttpProbe {
+                url: "http://localhost:3000/".to_string(),
+                min_status: None,
+                max_status: None,
+            })),
+        })
+        .unwrap();
+        assert_eq!(http.to_string(), "http http://localhost:3000/ (200-399)");
+
+        let tcp = ReadinessProbe::try_from(proto::ReadinessProbe {
+            probe: Some(proto::readiness_probe::Probe::Tcp(proto::TcpProbe {
+                host: None,
+                port: 5432,
+            })),
+        })
+        .unwrap();
+        assert_eq!(tcp.to_string(), "tcp 127.0.0.1:5432");
+        assert_eq!(
+            ReadinessProbe::try_from(proto::ReadinessProbe::from(&tcp)).unwrap(),
+            tcp
+        );
+
+        assert!(ReadinessProbe::try_from(proto::ReadinessProbe {
+            probe: Some(proto::readiness_probe::Probe::Tcp(proto::TcpProbe {
+                host: None,
+                port: 70000,
+            })),
+        })
+        .is_err());
+        assert!(ReadinessProbe::try_from(proto::ReadinessProbe { probe: None }).is_err());
+    }
+
+    #[tokio::test]
+    async fn http_probe_checks_the_status_range() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            for status in ["503 Service Unavailable", "204 No Content"] {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 512];
+                let _ = socket.read(&mut buf).await;
+                let response = format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\n\r\n");
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        let probe = ReadinessProbe::Http {
+            url: format!("http://127.0.0.1:{port}/health"),
+            min_status: 200,
+            max_status: 299,
+        };
+
+        let process = ProxyInfo::new(Default::default());
+        assert_eq!(probe.check(&process).await, None);
+        assert_eq!(
+            probe.check(&process).await,
+            Some(format!("http http://127.0.0.1:{port}/health (204)"))
+        );
+    }
+}
@@ -46,6 +46,11 @@ impl ProcessRegistry {
         self.reserved_names.remove(key);
     }
 
+    /// Whether a process is registered, or being started, under the key
+    pub fn is_reserved(&self, key: &ProcessKey) -> bool {
+        self.reserved_names.contains_key(key)
+    }
+
     pub fn get_process_by_name_with_project(
         &self,
         name: &str,
@@ -152,14 +157,7 @@ mod tests {
             id: id.to_string(),
             name: name.to_string(),
             project: project.to_string(),
-            cmd: None,
-            args: Vec::new(),
-            cwd: None,
-            env: None,
-            wait_for_log: None,
-            wait_timeout: None,
-            toolchain: None,
-            pid: 0,
+            ..Default::default()
         }))
     }
 
@@ -0,0 +1,99 @@
+use crate::common::config::ProcessRestartConfig;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// When a process is restarted after it exits on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    /// Never restart automatically
+    #[default]
+    Never,
+    /// Restart after a non-zero exit code or termination by signal
+    OnFailure,
+    /// Restart after every exit
+    Always,
+}
+
+impl RestartPolicy {
+    /// Whether an exit with the given code should trigger an automatic restart
+    pub fn should_restart(self, exit_code: Option<i32>) -> bool {
+        match self {
+            RestartPolicy::Never => false,
+            RestartPolicy::OnFailure => exit_code != Some(0),
+            RestartPolicy::Always => true,
+        }
+    }
+}
+
+impl From<proto::RestartPolicy> for RestartPolicy {
+    fn from(policy: proto::RestartPolicy) -> Self {
+        match policy {
+            proto::RestartPolicy::Never => RestartPolicy::Never,
+            proto::RestartPolicy::OnFailure => RestartPolicy::OnFailure,
+            proto::RestartPolicy::Always => RestartPolicy::Always,
+        }
+    }
+}
+
+impl From<RestartPolicy> for proto::RestartPolicy {
+    fn from(policy: RestartPolicy) -> Self {
+        match policy {
+            RestartPolicy::Never => proto::RestartPolicy::Never,
+            RestartPolicy::OnFailure => proto::RestartPolicy::OnFailure,
+            RestartPolicy::Always => proto::RestartPolicy::Always,
+        }
+    }
+}
+
+/// Restart bookkeeping carried over from one run of a process to the next
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RestartState {
+    /// Total number of automatic restarts
+    pub count: u32,
+    /// Automatic restarts since the process last stayed up for `stable_after_secs`
+    pub consecutive: u32,
+    /// Time of the most recent automatic restart
+    pub last_restart_time: Option<DateTime<Utc>>,
+}
+
+/// Delay before an automatic restart, doubling with each consecutive attempt
+pub fn backoff_delay(config: &ProcessRestartConfig, consecutive: u32) -> Duration {
+    let factor = 1u64.checked_shl(consecutive).unwrap_or(u64::MAX);
+    Duration::from_millis(
+        config
+            .backoff_initial_ms
+            .saturating_mul(factor)
+            .min(config.backoff_max_ms),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::Config;
+
+    #[test]
+    fn policy_decides_restart_from_exit_code() {
+        assert!(!RestartPolicy::Never.should_restart(Some(1)));
+        assert!(!RestartPolicy::OnFailure.should_restart(Some(0)));
+        assert!(RestartPolicy::OnFailure.should_restart(Some(1)));
+        assert!(RestartPolicy::OnFailure.should_restart(None));
+        assert!(RestartPolicy::Always.should_restart(Some(0)));
+    }
+
+    #[test]
+    fn backoff_doubles_and_is_capped() {
+        let mut config = Config::default().process.restart;
+        config.backoff_initial_ms = 500;
+        config.backoff_max_ms = 3000;
+
+        let delays = (0..5)
+            .map(|attempt| backoff_delay(&config, attempt).as_millis())
+            .collect::<Vec<_>>();
+
+        assert_eq!(delays, vec![500, 1000, 2000, 3000, 3000]);
+        assert_eq!(backoff_delay(&config, 200), Duration::from_millis(3000));
+    }
+}
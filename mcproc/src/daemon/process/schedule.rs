@@ -0,0 +1,69 @@
+//! Run history of scheduled processes
+
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Scheduled runs remembered per process
+const MAX_RUNS: usize = 10;
+
+/// Outcome of one scheduled run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledRun {
+    pub start_time: DateTime<Utc>,
+    /// Unset when the run was terminated by a signal or failed to start
+    pub exit_code: Option<i32>,
+    pub succeeded: bool,
+    pub duration: Duration,
+}
+
+/// Where a schedule stands, shared by the registry entries of all its runs
+#[derive(Debug, Default)]
+pub struct ScheduleState {
+    /// When the next run is due
+    pub next_run: Option<DateTime<Utc>>,
+    /// Recent runs, oldest first
+    runs: VecDeque<ScheduledRun>,
+    /// Runs skipped because the previous run was still going or dependencies were not ready
+    pub skipped: u32,
+}
+
+impl ScheduleState {
+    /// Remember a finished run, forgetting the oldest beyond the limit
+    pub fn record(&mut self, run: ScheduledRun) {
+        if self.runs.len() == MAX_RUNS {
+            self.runs.pop_front();
+        }
+        self.runs.push_back(run);
+    }
+
+    pub fn runs(&self) -> impl Iterator<Item = &ScheduledRun> {
+        self.runs.iter()
+    }
+
+    pub fn last_run(&self) -> Option<&ScheduledRun> {
+        self.runs.back()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_recent_runs_are_kept() {
+        let mut state = ScheduleState::default();
+        let start = Utc::now();
+        for code in 0..12 {
+            state.record(ScheduledRun {
+                start_time: start + chrono::Duration::minutes(i64::from(code)),
+                exit_code: Some(code),
+                succeeded: code == 0,
+                duration: Duration::from_millis(1500),
+            });
+        }
+        let codes: Vec<_> = state.runs().map(|run| run.exit_code.unwrap()).collect();
+        assert_eq!(codes, (2..12).collect::<Vec<_>>());
+        assert_eq!(state.last_run().unwrap().exit_code, Some(11));
+    }
+}
@@ -0,0 +1,76 @@
+use crate::common::signal::{normalize_signal, parse_signal};
+use nix::sys::signal::Signal;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How a process is asked to stop before its process group is killed
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StopPolicy {
+    /// Signal sent to the process group instead of SIGTERM, e.g. "SIGINT"
+    pub signal: Option<String>,
+    /// Shell command run first; the signal is only sent if it fails
+    pub command: Option<String>,
+    /// Grace period before SIGKILL, instead of `process_stop_timeout_ms`
+    pub timeout: Option<Duration>,
+}
+
+impl StopPolicy {
+    /// Build a policy from request fields, normalizing the signal name
+    pub fn from_request(
+        signal: Option<String>,
+        command: Option<String>,
+        timeout_secs: Option<u32>,
+    ) -> Result<Self, String> {
+        let signal = signal
+            .filter(|signal| !signal.trim().is_empty())
+            .map(|signal| normalize_signal(&signal))
+            .transpose()?;
+        let command = command.filter(|command| !command.trim().is_empty());
+        let timeout = match timeout_secs {
+            Some(0) => return Err("Stop timeout must be at least 1 second".to_string()),
+            Some(secs) => Some(Duration::from_secs(u64::from(secs))),
+            None => None,
+        };
+        Ok(Self {
+            signal,
+            command,
+            timeout,
+        })
+    }
+
+    /// Signal sent to request a graceful stop
+    pub fn signal(&self) -> Signal {
+        self.signal
+            .as_deref()
+            .and_then(|signal| parse_signal(signal).ok())
+            .unwrap_or(Signal::SIGTERM)
+    }
+
+    /// Grace period before the process group is killed
+    pub fn timeout(&self, default_ms: u64) -> Duration {
+        self.timeout
+            .unwrap_or_else(|| Duration::from_millis(default_ms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_fields_are_validated_and_defaulted() {
+        let policy = StopPolicy::from_request(Some("int".to_string()), None, Some(120)).unwrap();
+        assert_eq!(policy.signal.as_deref(), Some("SIGINT"));
+        assert_eq!(policy.signal(), Signal::SIGINT);
+        assert_eq!(policy.timeout(30_000), Duration::from_secs(120));
+
+        let default =
+            StopPolicy::from_request(Some(" ".to_string()), Some(String::new()), None).unwrap();
+        assert_eq!(default, StopPolicy::default());
+        assert_eq!(default.signal(), Signal::SIGTERM);
+        assert_eq!(default.timeout(30_000), Duration::from_secs(30));
+
+        assert!(StopPolicy::from_request(Some("SIGNOPE".to_string()), None, None).is_err());
+        assert!(StopPolicy::from_request(None, None, Some(0)).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+//! One-shot tasks: processes that run to completion and are judged by their exit code
+
+use crate::common::exit_code::format_exit_reason;
+use crate::daemon::process::proxy::{ProcessStatus, ProxyInfo};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long a task may run when no timeout is configured
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Output lines returned with a task result when none is configured
+pub const DEFAULT_TAIL_LINES: usize = 20;
+
+/// Largest number of output lines returned with a task result
+pub const MAX_TAIL_LINES: usize = 1000;
+
+/// How a task is judged and bounded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskOptions {
+    /// Exit codes that count as success
+    pub success_exit_codes: Vec<i32>,
+    /// Stop the task and report a timeout after this long
+    pub timeout: Duration,
+}
+
+impl Default for TaskOptions {
+    fn default() -> Self {
+        Self {
+            success_exit_codes: vec![0],
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+}
+
+impl TryFrom<proto::TaskOptions> for TaskOptions {
+    type Error = String;
+
+    fn try_from(options: proto::TaskOptions) -> Result<Self, Self::Error> {
+        if options.timeout_secs == Some(0) {
+            return Err("Task timeout must be at least 1 second".to_string());
+        }
+        let mut success_exit_codes = options.success_exit_codes;
+        if success_exit_codes.is_empty() {
+            success_exit_codes.push(0);
+        }
+        success_exit_codes.sort_unstable();
+        success_exit_codes.dedup();
+        Ok(Self {
+            success_exit_codes,
+            timeout: options
+                .timeout_secs
+                .map_or(DEFAULT_TIMEOUT, |secs| Duration::from_secs(u64::from(secs))),
+        })
+    }
+}
+
+impl From<&TaskOptions> for proto::TaskOptions {
+    fn from(options: &TaskOptions) -> Self {
+        Self {
+            success_exit_codes: options.success_exit_codes.clone(),
+            timeout_secs: Some(u32::try_from(options.timeout.as_secs()).unwrap_or(u32::MAX)),
+        }
+    }
+}
+
+impl TaskOptions {
+    /// Status of a task that exited with this code; a signal is always a failure
+    pub fn exit_status(&self, exit_code: Option<i32>) -> ProcessStatus {
+        match exit_code {
+            Some(code) if self.success_exit_codes.contains(&code) => ProcessStatus::Succeeded,
+            _ => ProcessStatus::Failed,
+        }
+    }
+}
+
+/// Describe how a task exited, e.g. "Task migrate succeeded in 3.2s (exit code 0)"
+pub fn format_task_exit(
+    name: &str,
+    status: ProcessStatus,
+    exit_code: Option<i32>,
+    duration: Duration,
+) -> String {
+    let secs = duration.as_secs_f64();
+    match (status, exit_code) {
+        (ProcessStatus::Succeeded, Some(code)) => {
+            format!("Task {name} succeeded in {secs:.1}s (exit code {code})")
+        }
+        (_, Some(code)) => format!(
+            "Task {name} failed in {secs:.1}s with exit code {code} ({})",
+            format_exit_reason(code)
+        ),
+        (_, None) => format!("Task {name} failed in {secs:.1}s: terminated by a signal"),
+    }
+}
+
+/// Outcome of a task run to completion
+pub struct TaskResult {
+    pub process: Arc<ProxyInfo>,
+    pub exit_code: Option<i32>,
+    pub succeeded: bool,
+    pub timed_out: bool,
+    pub duration: Duration,
+    /// Last lines of output, without daemon messages
+    pub tail: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_default_to_exit_code_zero_and_judge_exits() {
+        let options = TaskOptions::try_from(proto::TaskOptions::default()).unwrap();
+        assert_eq!(options, TaskOptions::default());
+        assert_eq!(options.exit_status(Some(0)), ProcessStatus::Succeeded);
+        assert_eq!(options.exit_status(Some(1)), ProcessStatus::Failed);
+        assert_eq!(options.exit_status(None), ProcessStatus::Failed);
+
+        let options = TaskOptions::try_from(proto::TaskOptions {
+            success_exit_codes: vec![3, 0, 3],
+            timeout_secs: Some(30),
+        })
+        .unwrap();
+        assert_eq!(options.success_exit_codes, vec![0, 3]);
+        assert_eq!(options.timeout, Duration::from_secs(30));
+        assert_eq!(options.exit_status(Some(3)), ProcessStatus::Succeeded);
+        assert_eq!(
+            TaskOptions::try_from(proto::TaskOptions::from(&options)),
+            Ok(options)
+        );
+
+        assert!(TaskOptions::try_from(proto::TaskOptions {
+            timeout_secs: Some(0),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn exits_are_described_with_duration_and_code() {
+        let duration = Duration::from_millis(3240);
+        assert_eq!(
+            format_task_exit("migrate", ProcessStatus::Succeeded, Some(0), duration),
+            "Task migrate succeeded in 3.2s (exit code 0)"
+        );
+        assert_eq!(
+            format_task_exit("migrate", ProcessStatus::Failed, Some(127), duration),
+            "Task migrate failed in 3.2s with exit code 127 (Command not found)"
+        );
+        assert_eq!(
+            format_task_exit("migrate", ProcessStatus::Failed, None, duration),
+            "Task migrate failed in 3.2s: terminated by a signal"
+        );
+    }
+}
@@ -1,7 +1,24 @@
-use std::collections::HashMap;
+use crate::common::schedule::Schedule;
+use crate::daemon::error::Result;
+use crate::daemon::process::cgroup::Cgroup;
+use crate::daemon::process::file_watch::FileWatch;
+use crate::daemon::process::health::HealthCheck;
+use crate::daemon::process::limits::ResourceLimits;
+use crate::daemon::process::port_allocator::AllocatedPort;
+use crate::daemon::process::proxy::ProxyInfo;
+use crate::daemon::process::pty::PtySize;
+use crate::daemon::process::readiness::ReadinessProbe;
+use crate::daemon::process::restart::{RestartPolicy, RestartState};
+use crate::daemon::process::schedule::ScheduleState;
+use crate::daemon::process::stop::StopPolicy;
+use crate::daemon::process::task::TaskOptions;
+use crate::daemon::process::watchdog::WatchdogRule;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 
 /// Parameters for creating a ProxyInfo
+#[derive(Default)]
 pub struct ProxyInfoParams {
     pub id: String,
     pub name: String,
@@ -13,5 +30,161 @@ pub struct ProxyInfoParams {
     pub wait_for_log: Option<String>,
     pub wait_timeout: Option<u32>,
     pub toolchain: Option<String>,
+    pub restart_policy: RestartPolicy,
+    pub restart_state: RestartState,
+    pub depends_on: Vec<String>,
+    pub readiness_probes: Vec<ReadinessProbe>,
+    pub health_check: Option<HealthCheck>,
+    pub stop_policy: StopPolicy,
+    pub stdin: bool,
+    pub pty: Option<PtySize>,
+    pub allocated_ports: Vec<AllocatedPort>,
+    pub limits: ResourceLimits,
+    pub watchdog: Vec<WatchdogRule>,
+    pub watch: Option<FileWatch>,
+    pub task: Option<TaskOptions>,
+    pub schedule: Option<Schedule>,
+    pub schedule_state: Arc<Mutex<ScheduleState>>,
+    pub started_by: Option<String>,
+    pub labels: BTreeMap<String, String>,
+    pub description: Option<String>,
+    pub cgroup: Option<Cgroup>,
     pub pid: u32,
 }
+
+/// Parameters for starting a managed process
+#[derive(Debug, Clone, Default)]
+pub struct StartProcessParams {
+    pub name: String,
+    pub project: Option<String>,
+    pub cmd: Option<String>,
+    pub args: Vec<String>,
+    pub cwd: Option<PathBuf>,
+    pub env: Option<HashMap<String, String>>,
+    pub wait_for_log: Option<String>,
+    pub wait_timeout: Option<u32>,
+    pub toolchain: Option<String>,
+    pub restart_policy: RestartPolicy,
+    /// Restart bookkeeping carried over by automatic restarts
+    pub restart_state: RestartState,
+    /// Processes in the same project that must be ready first
+    pub depends_on: Vec<String>,
+    /// Probes that decide readiness alongside wait_for_log
+    pub readiness_probes: Vec<ReadinessProbe>,
+    /// Liveness check run periodically while the process is running
+    pub health_check: Option<HealthCheck>,
+    /// How the process is asked to stop
+    pub stop_policy: StopPolicy,
+    /// Keep a stdin pipe open so input can be sent to the process
+    pub stdin: bool,
+    /// Run the process on a pseudo-terminal of this size
+    pub pty: Option<PtySize>,
+    /// Names of ports to allocate and inject into the environment
+    pub ports: Vec<String>,
+    /// Memory, CPU, process and open file limits
+    pub limits: ResourceLimits,
+    /// Rules that restart, stop or flag the process based on its resource usage
+    pub watchdog: Vec<WatchdogRule>,
+    /// Files whose changes restart the process
+    pub watch: Option<FileWatch>,
+    /// Run as a one-shot task judged by its exit code
+    pub task: Option<TaskOptions>,
+    /// Run the command on this schedule instead of keeping it running
+    pub schedule: Option<Schedule>,
+    /// Run history shared by the runs of one schedule
+    pub schedule_state: Arc<Mutex<ScheduleState>>,
+    /// Who asks for the run, e.g. "cli" or "restart policy"
+    pub started_by: Option<String>,
+    /// Free-form labels that selectors match
+    pub labels: BTreeMap<String, String>,
+    /// Note on why the process runs
+    pub description: Option<String>,
+}
+
+impl StartProcessParams {
+    /// Rebuild the start parameters of an existing process
+    pub fn from_process(process: &ProxyInfo) -> Self {
+        Self {
+            name: process.name.clone(),
+            project: Some(process.project.clone()),
+            cmd: process.cmd.clone(),
+            args: process.args.clone(),
+            cwd: process.cwd.clone(),
+            env: process.env.clone(),
+            wait_for_log: process.wait_for_log.clone(),
+            wait_timeout: process.wait_timeout,
+            toolchain: process.toolchain.clone(),
+            restart_policy: process.restart_policy,
+            restart_state: RestartState::default(),
+            depends_on: process.depends_on.clone(),
+            readiness_probes: process.readiness_probes.clone(),
+            health_check: process.health_check.clone(),
+            stop_policy: process.stop_policy.clone(),
+            stdin: process.stdin,
+            pty: process.pty,
+            ports: process
+                .allocated_ports
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect(),
+            limits: process.limits,
+            watchdog: process.watchdog.clone(),
+            watch: process.watch.clone(),
+            task: process.task.clone(),
+            schedule: process.schedule.clone(),
+            schedule_state: Arc::default(),
+            started_by: None,
+            labels: process.labels.clone(),
+            description: process.description.clone(),
+        }
+    }
+
+    /// Whether two sets of parameters describe the same process definition
+    ///
+    /// Task options are ignored for schedules: every scheduled run is started
+    /// as a task, so they describe the latest run rather than the definition.
+    pub fn same_definition(&self, other: &Self) -> bool {
+        self.cmd == other.cmd
+            && self.args == other.args
+            && self.cwd == other.cwd
+            && self.env.as_ref().filter(|env| !env.is_empty())
+                == other.env.as_ref().filter(|env| !env.is_empty())
+            && self.wait_for_log == other.wait_for_log
+            && self.wait_timeout == other.wait_timeout
+            && self.toolchain == other.toolchain
+            && self.restart_policy == other.restart_policy
+            && self.depends_on == other.depends_on
+            && self.readiness_probes == other.readiness_probes
+            && self.health_check == other.health_check
+            && self.stop_policy == other.stop_policy
+            && self.stdin == other.stdin
+            && self.pty == other.pty
+            && self.ports == other.ports
+            && self.limits == other.limits
+            && self.watchdog == other.watchdog
+            && self.watch == other.watch
+            && (self.schedule.is_some() || self.task == other.task)
+            && self.schedule == other.schedule
+            && self.labels == other.labels
+            && self.description == other.description
+    }
+}
+
+/// Action taken for one process while reconciling a project manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconcileAction {
+    Unchanged,
+    Started,
+    Restarted,
+    Stopped,
+    /// Not launched because a dependency is not ready
+    Blocked,
+}
+
+/// Outcome of reconciling one process against a project manifest
+pub struct ReconcileResult {
+    pub name: String,
+    pub action: ReconcileAction,
+    /// The resulting process, or None when it was stopped
+    pub result: Result<Option<Arc<ProxyInfo>>>,
+}
@@ -0,0 +1,337 @@
+//! Watchdog rules evaluated against the resource usage samples of running processes
+//!
+//! A rule fires once all of its conditions have held for its duration. Restart
+//! and stop rules fire at most once per run; a flag stays set while its rule
+//! keeps holding and is cleared when it stops.
+
+use crate::common::metrics::format_bytes;
+use crate::common::watchdog::{format_watchdog_rule, DEFAULT_FLAG};
+use crate::daemon::process::metrics::MetricsSample;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Conditions on a process's resource usage and output, and what to do when they hold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchdogRule {
+    /// Resident memory of the process tree above this many bytes
+    pub rss_above: Option<u64>,
+    /// CPU use above this percentage of one core
+    pub cpu_above: Option<f64>,
+    /// CPU use below this percentage of one core
+    pub cpu_below: Option<f64>,
+    /// Nothing written to stdout or stderr
+    pub no_output: bool,
+    /// How long the conditions must hold before the action is taken
+    pub duration: Duration,
+    pub action: WatchdogAction,
+}
+
+/// What the watchdog does once a rule fires
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchdogAction {
+    Restart,
+    Stop,
+    /// Label the process while the rule holds
+    Flag(String),
+}
+
+impl TryFrom<proto::WatchdogRule> for WatchdogRule {
+    type Error = String;
+
+    fn try_from(rule: proto::WatchdogRule) -> Result<Self, Self::Error> {
+        if rule.rss_above_bytes.is_none()
+            && rule.cpu_above_percent.is_none()
+            && rule.cpu_below_percent.is_none()
+            && !rule.no_output
+        {
+            return Err("Watchdog rule needs at least one condition".to_string());
+        }
+        for percent in [rule.cpu_above_percent, rule.cpu_below_percent]
+            .into_iter()
+            .flatten()
+        {
+            if !percent.is_finite() || percent < 0.0 {
+                return Err(format!("Invalid watchdog CPU percentage: {percent}"));
+            }
+        }
+        let action = match proto::WatchdogAction::try_from(rule.action) {
+            Ok(proto::WatchdogAction::Restart) => WatchdogAction::Restart,
+            Ok(proto::WatchdogAction::Stop) => WatchdogAction::Stop,
+            Ok(proto::WatchdogAction::Flag) => {
+                let flag = rule.flag.unwrap_or_else(|| DEFAULT_FLAG.to_string());
+                if flag.trim().is_empty() {
+                    return Err("Watchdog flag label must not be empty".to_string());
+                }
+                WatchdogAction::Flag(flag)
+            }
+            Err(_) => return Err(format!("Unknown watchdog action: {}", rule.action)),
+        };
+        Ok(Self {
+            rss_above: rule.rss_above_bytes,
+            cpu_above: rule.cpu_above_percent,
+            cpu_below: rule.cpu_below_percent,
+            no_output: rule.no_output,
+            duration: Duration::from_secs(u64::from(rule.for_secs)),
+            action,
+        })
+    }
+}
+
+impl From<&WatchdogRule> for proto::WatchdogRule {
+    fn from(rule: &WatchdogRule) -> Self {
+        let (action, flag) = match &rule.action {
+            WatchdogAction::Restart => (proto::WatchdogAction::Restart, None),
+            WatchdogAction::Stop => (proto::WatchdogAction::Stop, None),
+            WatchdogAction::Flag(flag) => (proto::WatchdogAction::Flag, Some(flag.clone())),
+        };
+        Self {
+            rss_above_bytes: rule.rss_above,
+            cpu_above_percent: rule.cpu_above,
+            cpu_below_percent: rule.cpu_below,
+            no_output: rule.no_output,
+            for_secs: u32::try_from(rule.duration.as_secs()).unwrap_or(u32::MAX),
+            action: action as i32,
+            flag,
+        }
+    }
+}
+
+impl fmt::Display for WatchdogRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_watchdog_rule(&self.into()))
+    }
+}
+
+impl WatchdogRule {
+    /// Whether the sampled usage meets the rule's resource conditions
+    ///
+    /// CPU conditions never hold on the first sample of a run, which has no
+    /// CPU use yet.
+    fn usage_holds(&self, sample: &MetricsSample) -> bool {
+        let cpu_holds = |threshold: Option<f64>, holds: fn(f64, f64) -> bool| {
+            threshold.map_or(true, |threshold| {
+                sample
+                    .cpu_percent
+                    .is_some_and(|percent| holds(percent, threshold))
+            })
+        };
+        self.rss_above
+            .map_or(true, |limit| sample.rss_bytes > limit)
+            && cpu_holds(self.cpu_above, |percent, threshold| percent > threshold)
+            && cpu_holds(self.cpu_below, |percent, threshold| percent < threshold)
+    }
+}
+
+/// Describe the usage a rule fired on, e.g. "rss 3.2G, cpu 97.5%"
+pub fn describe_usage(sample: &MetricsSample) -> String {
+    match sample.cpu_percent {
+        Some(percent) => format!("rss {}, cpu {percent:.1}%", format_bytes(sample.rss_bytes)),
+        None => format!("rss {}", format_bytes(sample.rss_bytes)),
+    }
+}
+
+/// A rule that fired or, for flags, stopped holding
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchdogTransition {
+    Fired {
+        rule: WatchdogRule,
+        sample: MetricsSample,
+    },
+    Cleared {
+        rule: WatchdogRule,
+    },
+}
+
+/// Watchdog bookkeeping for one run of a process
+#[derive(Debug, Default)]
+pub struct WatchdogState {
+    /// Per rule, since when it has held and whether it fired
+    rules: Vec<(Option<Instant>, bool)>,
+    /// Time of the sample evaluated last, so a sample is not counted twice
+    last_sample: Option<DateTime<Utc>>,
+}
+
+impl WatchdogState {
+    /// Check the rules against a new sample, returning the rules that fired or cleared
+    ///
+    /// A rule that requires no output holds since the later of when its other
+    /// conditions started holding and the last output.
+    pub fn evaluate(
+        &mut self,
+        rules: &[WatchdogRule],
+        sample: &MetricsSample,
+        last_output: Instant,
+        now: Instant,
+    ) -> Vec<WatchdogTransition> {
+        if self.last_sample == Some(sample.sampled_at) {
+            return Vec::new();
+        }
+        self.last_sample = Some(sample.sampled_at);
+        self.rules.resize(rules.len(), (None, false));
+
+        let mut transitions = Vec::new();
+        for (rule, (since, fired)) in rules.iter().zip(self.rules.iter_mut()) {
+            if !rule.usage_holds(sample) {
+                *since = None;
+                if std::mem::take(fired) && matches!(rule.action, WatchdogAction::Flag(_)) {
+                    transitions.push(WatchdogTransition::Cleared { rule: rule.clone() });
+                }
+                continue;
+            }
+
+            let mut held_since = *since.get_or_insert(now);
+            if rule.no_output && last_output > held_since {
+                held_since = last_output;
+                if *fired && matches!(rule.action, WatchdogAction::Flag(_)) {
+                    *fired = false;
+                    transitions.push(WatchdogTransition::Cleared { rule: rule.clone() });
+                }
+            }
+            if !*fired && now.saturating_duration_since(held_since) >= rule.duration {
+                *fired = true;
+                transitions.push(WatchdogTransition::Fired {
+                    rule: rule.clone(),
+                    sample: *sample,
+                });
+            }
+        }
+        transitions
+    }
+
+    /// Labels of the flag rules that currently hold
+    pub fn flags(&self, rules: &[WatchdogRule]) -> Vec<String> {
+        rules
+            .iter()
+            .zip(&self.rules)
+            .filter_map(|(rule, (_, fired))| match &rule.action {
+                WatchdogAction::Flag(flag) if *fired => Some(flag.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(action: &str) -> WatchdogRule {
+        WatchdogRule::try_from(crate::common::watchdog::parse_watchdog_rule(action).unwrap())
+            .unwrap()
+    }
+
+    fn sample(rss_bytes: u64, cpu_percent: Option<f64>) -> MetricsSample {
+        MetricsSample {
+            sampled_at: Utc::now(),
+            processes: 1,
+            rss_bytes,
+            cpu_percent,
+            threads: 1,
+            open_fds: 3,
+            read_bytes: 0,
+            write_bytes: 0,
+        }
+    }
+
+    #[test]
+    fn proto_rules_are_validated_and_round_trip() {
+        let restart = rule("restart if rss > 3G for 60s");
+        assert_eq!(restart.rss_above, Some(3 << 30));
+        assert_eq!(restart.duration, Duration::from_secs(60));
+        assert_eq!(restart.to_string(), "restart if rss > 3G for 1m");
+        assert_eq!(
+            WatchdogRule::try_from(proto::WatchdogRule::from(&restart)),
+            Ok(restart)
+        );
+        assert_eq!(
+            rule("flag if no-output").action,
+            WatchdogAction::Flag(DEFAULT_FLAG.to_string())
+        );
+
+        assert!(WatchdogRule::try_from(proto::WatchdogRule::default()).is_err());
+        assert!(WatchdogRule::try_from(proto::WatchdogRule {
+            cpu_above_percent: Some(f64::NAN),
+            ..Default::default()
+        })
+        .is_err());
+        assert!(WatchdogRule::try_from(proto::WatchdogRule {
+            no_output: true,
+            action: 7,
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn rules_fire_once_conditions_held_for_their_duration() {
+        let rules = [
+            rule("restart if rss > 1K for 60s"),
+            rule("stop if cpu > 90"),
+        ];
+        let start = Instant::now();
+        let mut state = WatchdogState::default();
+
+        // CPU conditions wait for a sample with CPU use
+        assert!(state
+            .evaluate(&rules, &sample(2048, None), start, start)
+            .is_empty());
+
+        let at = |secs| start + Duration::from_secs(secs);
+        let fired = state.evaluate(&rules, &sample(2048, Some(95.0)), start, at(30));
+        assert!(matches!(
+            &fired[..],
+            [WatchdogTransition::Fired { rule, .. }] if rule.action == WatchdogAction::Stop
+        ));
+
+        // Memory dropping below the threshold restarts the clock
+        assert!(state
+            .evaluate(&rules, &sample(512, Some(95.0)), start, at(40))
+            .is_empty());
+        assert!(state
+            .evaluate(&rules, &sample(2048, Some(95.0)), start, at(50))
+            .is_empty());
+        let fired = state.evaluate(&rules, &sample(2048, Some(95.0)), start, at(110));
+        assert!(matches!(
+            &fired[..],
+            [WatchdogTransition::Fired { rule, .. }] if rule.action == WatchdogAction::Restart
+        ));
+
+        // Fired actions are not repeated
+        assert!(state
+            .evaluate(&rules, &sample(2048, Some(95.0)), start, at(200))
+            .is_empty());
+
+        // Nor is the same sample evaluated twice
+        let sample = sample(2048, Some(10.0));
+        state.evaluate(&rules, &sample, start, at(210));
+        assert!(state.evaluate(&rules, &sample, start, at(300)).is_empty());
+    }
+
+    #[test]
+    fn flags_wait_for_quiet_output_and_clear_when_output_resumes() {
+        let rules = [rule("flag stalled if no-output and cpu < 1 for 15m")];
+        let start = Instant::now();
+        let at = |mins: u64| start + Duration::from_secs(mins * 60);
+        let mut state = WatchdogState::default();
+        let idle = || sample(1024, Some(0.5));
+
+        assert!(state.evaluate(&rules, &idle(), start, at(1)).is_empty());
+        // Output at minute 10 restarts the quiet period
+        assert!(state.evaluate(&rules, &idle(), at(10), at(15)).is_empty());
+        assert!(state.evaluate(&rules, &idle(), at(10), at(24)).is_empty());
+        assert!(matches!(
+            &state.evaluate(&rules, &idle(), at(10), at(25))[..],
+            [WatchdogTransition::Fired { .. }]
+        ));
+        assert_eq!(state.flags(&rules), vec!["stalled"]);
+
+        assert!(matches!(
+            &state.evaluate(&rules, &idle(), at(26), at(27))[..],
+            [WatchdogTransition::Cleared { .. }]
+        ));
+        assert!(state.flags(&rules).is_empty());
+    }
+}
@@ -17,6 +17,12 @@ pub(crate) struct ProcessTestFixture {
 
 impl ProcessTestFixture {
     pub fn new(prefix: &str, process_stop_timeout_ms: u64) -> Self {
+        Self::with_config(prefix, |config| {
+            config.process.restart.process_stop_timeout_ms = process_stop_timeout_ms;
+        })
+    }
+
+    pub fn with_config(prefix: &str, configure: impl FnOnce(&mut Config)) -> Self {
         let root = PathBuf::from("/tmp").join(format!("{prefix}-{}", uuid::Uuid::new_v4()));
         let mut config = Config::default();
         config.paths.data_dir = root.join("data");
@@ -25,7 +31,7 @@ impl ProcessTestFixture {
         config.paths.pid_file = root.join("runtime/mcprocd.pid");
         config.paths.daemon_log_file = root.join("state/mcprocd.log");
         config.process.restart.delay_ms = 0;
-        config.process.restart.process_stop_timeout_ms = process_stop_timeout_ms;
+        configure(&mut config);
 
         create_test_directories(&config);
 
@@ -37,6 +43,11 @@ impl ProcessTestFixture {
             log_hub.clone(),
             event_hub.clone(),
         ));
+        process_manager.start_restart_supervisor();
+        process_manager.start_health_monitor();
+        process_manager.start_file_watcher();
+        process_manager.start_scheduler();
+        process_manager.start_state_persistence();
 
         Self {
             config,
@@ -56,6 +67,15 @@ impl ProcessTestFixture {
         )
     }
 
+    /// A fresh process manager on the same directories, as after a daemon restart
+    pub fn restarted_process_manager(&self) -> Arc<ProcessManager> {
+        Arc::new(ProcessManager::with_event_hub(
+            self.config.clone(),
+            self.log_hub.clone(),
+            self.event_hub.clone(),
+        ))
+    }
+
     pub fn socket_path(&self) -> PathBuf {
         self.config.paths.socket_path.clone()
     }
@@ -45,6 +45,11 @@ fn launch_params(name: &str, project: &str, cmd: &str) -> LaunchProcessParams {
         cwd: None,
         env: None,
         toolchain: None,
+        stdin: false,
+        pty: None,
+        allocated_ports: Vec::new(),
+        cgroup: None,
+        rlimits: vec![],
     }
 }
 
@@ -57,7 +62,7 @@ fn child_pid(child: &tokio::process::Child) -> Pid {
 async fn test_spawned_process_gets_own_process_group() {
     let launcher = ProcessLauncher::new();
     let test_id = std::process::id();
-    let (mut child, _) = launcher
+    let (mut child, _, _) = launcher
         .launch_process(launch_params(
             &format!("process-group-leader-{test_id}"),
             &format!("process-group-test-{test_id}"),
@@ -95,7 +100,7 @@ async fn test_stop_kills_orphaned_grandchild() {
     let test_id = std::process::id();
     let name = format!("orphan-grandchild-{test_id}");
     let project = format!("orphan-grandchild-test-{test_id}");
-    let (mut child, _) = launcher
+    let (mut child, _, _) = launcher
         .launch_process(launch_params(&name, &project, COMMAND))
         .await
         .expect("failed to launch orphan-grandchild command");
@@ -136,13 +141,8 @@ async fn test_stop_kills_orphaned_grandchild() {
         name,
         project,
         cmd: Some(COMMAND.to_string()),
-        args: Vec::new(),
-        cwd: None,
-        env: None,
-        wait_for_log: None,
-        wait_timeout: None,
-        toolchain: None,
         pid: u32::try_from(pid.as_raw()).expect("child PID should be positive"),
+        ..Default::default()
     });
 
     proxy